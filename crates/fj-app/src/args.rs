@@ -1,6 +1,8 @@
-use std::{path::PathBuf, str::FromStr as _};
+use std::{path::PathBuf, str::FromStr as _, time::Duration};
 
 use anyhow::anyhow;
+use fj::models::LengthUnit;
+use fj_export::Format;
 use fj_host::Parameters;
 use fj_kernel::algorithms::approx::Tolerance;
 use fj_math::Scalar;
@@ -13,16 +15,113 @@ pub struct Args {
     pub model: Option<PathBuf>,
 
     /// Export model to this path
+    ///
+    /// The file type is determined by the extension. 3MF and STL files contain
+    /// a triangle mesh. glTF and GLB files contain a triangle mesh per group
+    /// member, with colors. DXF files contain the outline of a 2D model. SVG
+    /// and PDF files contain a technical drawing.
     #[arg(short, long)]
     pub export: Option<PathBuf>,
 
+    /// Export a triangle mesh in this format, regardless of the extension
+    ///
+    /// One of `3mf`, `stl`, `gltf`, or `glb`.
+    #[arg(long, requires = "export", value_parser = parse_format)]
+    pub format: Option<Format>,
+
+    /// The unit of length that exported files use: `mm`, `cm`, `m`, or `in`
+    ///
+    /// Defaults to the unit that the model is measured in. Drawings are always
+    /// measured in millimeters, and scaled according to the model's unit.
+    #[arg(long)]
+    pub units: Option<LengthUnit>,
+
+    /// Write STL files as text, instead of in binary
+    #[arg(long)]
+    pub stl_ascii: bool,
+
+    /// Text for the header of exported STL files
+    #[arg(long)]
+    pub header: Option<String>,
+
+    /// Write coordinates of text-based mesh files with this many decimals
+    #[arg(long)]
+    pub precision: Option<usize>,
+
+    /// Merge vertices of exported meshes that are closer than this distance
+    #[arg(long, value_parser = parse_weld)]
+    pub weld: Option<f64>,
+
+    /// Check exported STL and 3MF meshes, and fail, if they are invalid
+    ///
+    /// Reports open or non-manifold edges, inconsistent winding, degenerate
+    /// and duplicate triangles, and self-intersections.
+    #[arg(long)]
+    pub check_mesh: bool,
+
     /// Parameters for the model, each in the form `key=value`
+    ///
+    /// Vectors are given in brackets, like `offset=[1, 2, 3]`.
     #[arg(short, long, value_parser = parse_parameters)]
     pub parameters: Option<Parameters>,
 
+    /// Read parameters for the model from this TOML or JSON file
+    ///
+    /// The file contains a table of parameter names and values. Parameters
+    /// that are passed with `--parameters` take precedence.
+    #[arg(long)]
+    pub params_file: Option<PathBuf>,
+
+    /// Use this preset of the model, for parameters that aren't set otherwise
+    ///
+    /// Presets are named sets of parameters, which are declared by the model.
+    #[arg(long)]
+    pub preset: Option<String>,
+
+    /// List the presets that the model declares, and exit
+    #[arg(long)]
+    pub list_presets: bool,
+
     /// Model deviation tolerance
     #[arg(short, long, value_parser = parse_tolerance)]
     pub tolerance: Option<Tolerance>,
+
+    /// Export this many slices through the model, instead of the model itself
+    ///
+    /// The slices are evenly spaced along the axis given by `--slice-axis`.
+    /// They are exported to the SVG or DXF file given by `--export`, each on
+    /// its own layer.
+    #[arg(
+        long,
+        requires = "export",
+        value_parser = clap::value_parser!(u32).range(1..)
+    )]
+    pub slices: Option<u32>,
+
+    /// The axis along which the slices are stacked
+    #[arg(long, value_enum, default_value_t = SliceAxis::Z)]
+    pub slice_axis: SliceAxis,
+
+    /// Validate the model, and report all issues found
+    ///
+    /// Each issue is reported as an error or a warning. In export mode, the
+    /// model is not exported, if any errors are found.
+    #[arg(long)]
+    pub validate: bool,
+
+    /// Evaluate the model in a separate process
+    ///
+    /// If the model panics or crashes, an error is reported, instead of
+    /// Fornjot crashing along with it. Implied by `--timeout`.
+    #[arg(long)]
+    pub sandbox: bool,
+
+    /// Stop compiling and evaluating the model after this many seconds
+    ///
+    /// Implies `--sandbox`, as the evaluation of a model can only be stopped,
+    /// if it runs in a separate process.
+    #[arg(long, value_parser = parse_timeout)]
+    pub timeout: Option<Duration>,
 }
 
 impl Args {
@@ -35,10 +134,23 @@ impl Args {
     }
 }
 
+/// The axis along which slices through a model are stacked
+#[derive(Clone, Copy, Debug, Eq, PartialEq, clap::ValueEnum)]
+pub enum SliceAxis {
+    /// Slices in the yz-plane, stacked along the x-axis
+    X,
+
+    /// Slices in the xz-plane, stacked along the y-axis
+    Y,
+
+    /// Slices in the xy-plane, stacked along the z-axis
+    Z,
+}
+
 fn parse_parameters(input: &str) -> anyhow::Result<Parameters> {
     let mut parameters = Parameters::empty();
 
-    for parameter in input.split(',') {
+    for parameter in split_outside_brackets(input) {
         let mut parameter = parameter.splitn(2, '=');
 
         let key = parameter
@@ -58,6 +170,39 @@ fn parse_parameters(input: &str) -> anyhow::Result<Parameters> {
     Ok(parameters)
 }
 
+/// Split at commas, except for those within brackets
+fn split_outside_brackets(input: &str) -> impl Iterator<Item = &str> {
+    let mut depth = 0_u32;
+
+    input.split(move |c| {
+        match c {
+            '[' => depth += 1,
+            ']' => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+
+        c == ',' && depth == 0
+    })
+}
+
+fn parse_timeout(input: &str) -> anyhow::Result<Duration> {
+    let seconds = f64::from_str(input)?;
+    if !(seconds > 0. && seconds < u64::MAX as f64) {
+        return Err(anyhow!("Timeout must be a positive number of seconds"));
+    }
+
+    Ok(Duration::from_secs_f64(seconds))
+}
+
+fn parse_weld(input: &str) -> anyhow::Result<f64> {
+    let distance = f64::from_str(input)?;
+    if !(distance > 0. && distance.is_finite()) {
+        return Err(anyhow!("Weld distance must be a positive number"));
+    }
+
+    Ok(distance)
+}
+
 fn parse_tolerance(input: &str) -> anyhow::Result<Tolerance> {
     let tolerance = f64::from_str(input)?;
     let tolerance = Scalar::from_f64(tolerance);
@@ -65,3 +210,8 @@ fn parse_tolerance(input: &str) -> anyhow::Result<Tolerance> {
 
     Ok(tolerance)
 }
+
+fn parse_format(input: &str) -> anyhow::Result<Format> {
+    Format::from_extension(input)
+        .ok_or_else(|| anyhow!("Unknown export format `{input}`"))
+}
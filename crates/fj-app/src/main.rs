@@ -14,20 +14,34 @@
 
 mod args;
 mod config;
+mod params_file;
 mod path;
 
-use anyhow::{anyhow, Context};
-use fj_export::export;
+use anyhow::{anyhow, bail, Context};
+use fj_export::{
+    drawing::DrawingOptions, export_drawing, export_sketch, export_slices,
+    export_with_options, is_drawing_format, is_sketch_format, ExportOptions,
+    StlEncoding,
+};
 use fj_host::Parameters;
-use fj_operations::shape_processor::ShapeProcessor;
+use fj_kernel::objects::Surface;
+use fj_math::{Aabb, Point, Vector};
+use fj_operations::{shape_processor::ShapeProcessor, Shape as _};
 use fj_window::run::run;
 use path::ModelPath;
 use tracing_subscriber::fmt::format;
 use tracing_subscriber::EnvFilter;
 
-use crate::{args::Args, config::Config};
+use crate::{
+    args::{Args, SliceAxis},
+    config::Config,
+};
 
 fn main() -> anyhow::Result<()> {
+    // If this is the process that evaluates a model for another instance of
+    // the application (see `--sandbox`), this doesn't return.
+    fj_host::run_sandbox();
+
     // Respect `RUST_LOG`. If that's not defined or erroneous, log warnings and
     // above.
     //
@@ -44,20 +58,131 @@ fn main() -> anyhow::Result<()> {
     let args = Args::parse();
     let config = Config::load()?;
     let model_path = ModelPath::from_args_and_config(&args, &config);
-    let parameters = args.parameters.unwrap_or_else(Parameters::empty);
-    let shape_processor = ShapeProcessor {
-        tolerance: args.tolerance,
-    };
+    let mut parameters = args
+        .params_file
+        .as_deref()
+        .map(params_file::load)
+        .transpose()?
+        .unwrap_or_else(Parameters::empty);
+    if let Some(args_parameters) = args.parameters {
+        parameters.extend(args_parameters.0);
+    }
+    let mut shape_processor = ShapeProcessor::new(args.tolerance);
+    shape_processor.validate = args.validate;
 
-    let model = model_path.map(|m| m.load_model(parameters)).transpose()?;
+    let model = model_path
+        .map(|m| m.load_model(parameters))
+        .transpose()?
+        .map(|model| {
+            // Only a model that is evaluated in a separate process can be
+            // stopped, so a timeout implies the sandbox. `--timeout` documents
+            // that.
+            model
+                .with_preset(args.preset)
+                .with_sandbox(args.sandbox || args.timeout.is_some())
+                .with_timeout(args.timeout)
+        });
+
+    if args.list_presets {
+        let evaluation = model.with_context(no_model_error)?.evaluate()?;
+        print_presets(&evaluation.model_metadata);
+
+        return Ok(());
+    }
 
     if let Some(export_path) = args.export {
         // export only mode. just load model, process, export and exit
 
         let evaluation = model.with_context(no_model_error)?.evaluate()?;
-        let shape = shape_processor.process(&evaluation.shape)?;
 
-        export(&shape.mesh, &export_path)?;
+        if args.validate {
+            let report =
+                shape_processor.validation_report(&evaluation.shape)?;
+            eprintln!("{report}");
+
+            // Warnings are reported, but don't prevent the export.
+            if report.has_errors() {
+                bail!("Model is invalid; not exporting");
+            }
+        }
+
+        let is_2d = matches!(evaluation.shape, fj::Shape::Shape2d(_));
+
+        if let Some(num_slices) = args.slices {
+            let planes = slice_planes(
+                evaluation.shape.bounding_volume()?,
+                args.slice_axis,
+                num_slices,
+            );
+            let sections =
+                shape_processor.sections(&evaluation.shape, planes)?;
+
+            let slices = sections
+                .iter()
+                .enumerate()
+                .map(|(i, section)| (format!("slice-{}", i + 1), &**section))
+                .collect::<Vec<_>>();
+            export_slices(
+                &slices,
+                evaluation.metadata.units,
+                args.units,
+                &export_path,
+            )?;
+        } else if args.format.is_none()
+            && (is_sketch_format(&export_path)
+                || (is_2d && is_drawing_format(&export_path)))
+        {
+            // 2D models are exported as they are. That includes SVG files,
+            // which otherwise contain drawings of 3D models.
+            let shape = match &evaluation.shape {
+                fj::Shape::Shape2d(shape) => shape,
+                _ => bail!(
+                    "Only 2D models can be exported to `{}`",
+                    export_path.display()
+                ),
+            };
+
+            let sketch = shape_processor.sketch(shape)?;
+            export_sketch(
+                &sketch,
+                evaluation.metadata.units,
+                args.units,
+                &export_path,
+            )?;
+        } else if args.format.is_none() && is_drawing_format(&export_path) {
+            let solid = shape_processor.solid(&evaluation.shape)?;
+            let tolerance = shape_processor.tolerance(&evaluation.shape)?;
+
+            export_drawing(
+                &solid,
+                &evaluation.metadata,
+                tolerance,
+                &DrawingOptions::default(),
+                &export_path,
+            )?;
+        } else {
+            let options = ExportOptions {
+                format: args.format,
+                units: args.units,
+                stl_encoding: if args.stl_ascii {
+                    StlEncoding::Ascii
+                } else {
+                    StlEncoding::Binary
+                },
+                header: args.header,
+                precision: args.precision,
+                weld_distance: args.weld,
+                check: args.check_mesh,
+            };
+
+            let tree = shape_processor.mesh_tree(&evaluation.shape)?;
+            export_with_options(
+                &tree,
+                Some(&evaluation.metadata),
+                &options,
+                &export_path,
+            )?;
+        }
 
         return Ok(());
     }
@@ -68,6 +193,53 @@ fn main() -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Print the presets of a model, with the parameter values they set
+fn print_presets(model_metadata: &fj::models::ModelMetadata) {
+    if model_metadata.presets.is_empty() {
+        println!("Model `{}` has no presets", model_metadata.name);
+        return;
+    }
+
+    for preset in &model_metadata.presets {
+        let values = preset
+            .values
+            .iter()
+            .map(|(name, value)| format!("{name} = {value}"))
+            .collect::<Vec<_>>();
+        println!("{}: {}", preset.name, values.join(", "));
+    }
+}
+
+/// Compute evenly spaced planes through the bounding volume of a model
+///
+/// The planes are in the middle of equally thick layers of the model, so none
+/// of them just touches the model's top or bottom.
+fn slice_planes(
+    aabb: Aabb<3>,
+    axis: SliceAxis,
+    num_slices: u32,
+) -> Vec<Surface> {
+    // The axes of each plane are chosen, so its normal points along the
+    // positive axis. Otherwise, the slices would be mirrored.
+    let [u, v, normal] = match axis {
+        SliceAxis::X => [Vector::unit_y(), Vector::unit_z(), Vector::unit_x()],
+        SliceAxis::Y => [Vector::unit_z(), Vector::unit_x(), Vector::unit_y()],
+        SliceAxis::Z => [Vector::unit_x(), Vector::unit_y(), Vector::unit_z()],
+    };
+
+    let min = aabb.min.coords.dot(&normal);
+    let size = (aabb.max - aabb.min).dot(&normal);
+
+    (0..num_slices)
+        .map(|i| {
+            let t = (f64::from(i) + 0.5) / f64::from(num_slices);
+            let origin = Point::origin() + normal * (min + size * t);
+
+            Surface::plane_from_points([origin, origin + u, origin + v])
+        })
+        .collect()
+}
+
 fn no_model_error() -> anyhow::Error {
     anyhow!(
         "You must specify a model to start Fornjot in export only mode.\n\
@@ -0,0 +1,80 @@
+use std::{
+    collections::HashMap,
+    fmt::{self, Display},
+    fs,
+    path::Path,
+};
+
+use anyhow::Context as _;
+use figment::{
+    providers::{Format as _, Json, Toml},
+    Figment,
+};
+use fj_host::Parameters;
+use serde::Deserialize;
+
+/// Load parameters for a model from a TOML or JSON file
+///
+/// Files with a `.json` extension, in any case, are read as JSON, all others as TOML. The
+/// file contains a table of parameter names and their values.
+pub fn load(path: &Path) -> anyhow::Result<Parameters> {
+    let context =
+        || format!("Error loading parameters from `{}`", path.display());
+
+    let contents = fs::read_to_string(path).with_context(context)?;
+    let is_json = matches!(
+        path.extension(),
+        Some(extension) if extension.eq_ignore_ascii_case("json")
+    );
+    let figment = if is_json {
+        Figment::from(Json::string(&contents))
+    } else {
+        Figment::from(Toml::string(&contents))
+    };
+    let values: HashMap<String, Value> =
+        figment.extract().with_context(context)?;
+
+    let mut parameters = Parameters::empty();
+    for (name, value) in values {
+        parameters.insert(name, value);
+    }
+
+    Ok(parameters)
+}
+
+/// The value of a parameter in a parameters file
+///
+/// Models receive their parameters as text. Values are converted to text that
+/// the model can parse.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum Value {
+    Bool(bool),
+    Integer(i64),
+    // Integers beyond the range of `i64`, which only JSON files can contain
+    Unsigned(u64),
+    Float(f64),
+    Text(String),
+    Vector(Vec<f64>),
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Bool(value) => write!(f, "{value}"),
+            Self::Integer(value) => write!(f, "{value}"),
+            Self::Unsigned(value) => write!(f, "{value}"),
+            Self::Float(value) => write!(f, "{value}"),
+            Self::Text(value) => write!(f, "{value}"),
+            Self::Vector(components) => {
+                for (i, component) in components.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{component}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
@@ -0,0 +1,509 @@
+//! Checks for the validity of triangle meshes
+//!
+//! Files for 3D printing, like 3MF and STL, must contain meshes that describe
+//! closed volumes. See [`MeshReport`] for the issues that are checked.
+
+use std::{collections::HashMap, fmt};
+
+use fj_interop::mesh::Mesh;
+use fj_math::{Point, Scalar, Sign, Vector};
+
+/// The tolerance of the geometric checks, relative to the size of triangles
+///
+/// A triangle is degenerate, if its height is less than this fraction of its
+/// longest edge. Triangles that touch, or come closer than that, don't count as
+/// intersecting.
+const TOLERANCE: f64 = 1e-6;
+
+/// A report of all issues found in a triangle mesh
+///
+/// A mesh without issues is closed, every edge is shared by exactly two
+/// triangles that are wound consistently, and no triangles overlap.
+///
+/// Triangles are referred to by their position in [`Mesh::triangles`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MeshReport {
+    issues: Vec<MeshIssue>,
+}
+
+impl MeshReport {
+    /// Check a mesh, and report all issues found
+    pub fn for_mesh(mesh: &Mesh<Point<3>>) -> Self {
+        let points = mesh
+            .triangles()
+            .map(|triangle| triangle.inner.points())
+            .collect::<Vec<_>>();
+        let indices = mesh
+            .indices()
+            .collect::<Vec<_>>()
+            .chunks(3)
+            .map(|indices| [indices[0], indices[1], indices[2]])
+            .collect::<Vec<_>>();
+
+        let mut report = Self::default();
+
+        report.check_triangles(&points, &indices);
+        report.check_edges(&points, &indices);
+        report.check_intersections(&points, &indices);
+
+        report
+    }
+
+    /// Access the issues in the report
+    pub fn issues(&self) -> impl Iterator<Item = &MeshIssue> {
+        self.issues.iter()
+    }
+
+    /// Indicate whether the report contains no issues
+    pub fn is_empty(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    fn check_triangles(
+        &mut self,
+        points: &[[Point<3>; 3]],
+        indices: &[[u32; 3]],
+    ) {
+        let mut triangles_by_vertices = HashMap::new();
+
+        for (i, (points, indices)) in points.iter().zip(indices).enumerate() {
+            // The magnitude of the normal is the longest edge times the height
+            // of the triangle.
+            let longest = longest_edge(points);
+            if normal(points).magnitude() <= longest * longest * TOLERANCE {
+                self.issues.push(MeshIssue::DegenerateTriangle(i));
+            }
+
+            let mut vertices = *indices;
+            vertices.sort_unstable();
+
+            match triangles_by_vertices.get(&vertices) {
+                Some(&original) => {
+                    self.issues.push(MeshIssue::DuplicateTriangle {
+                        triangle: i,
+                        original,
+                    });
+                }
+                None => {
+                    triangles_by_vertices.insert(vertices, i);
+                }
+            }
+        }
+    }
+
+    fn check_edges(&mut self, points: &[[Point<3>; 3]], indices: &[[u32; 3]]) {
+        // For every edge, the points it connects, and whether each triangle
+        // that shares it runs along it in the direction of those points.
+        let mut edges: HashMap<[u32; 2], ([Point<3>; 2], Vec<bool>)> =
+            HashMap::new();
+        let mut order = Vec::new();
+
+        for (points, indices) in points.iter().zip(indices) {
+            for i in 0..3 {
+                let j = (i + 1) % 3;
+                let [a, b] = [indices[i], indices[j]];
+
+                let (key, edge, forward) = if a < b {
+                    ([a, b], [points[i], points[j]], true)
+                } else {
+                    ([b, a], [points[j], points[i]], false)
+                };
+
+                edges
+                    .entry(key)
+                    .or_insert_with(|| {
+                        order.push(key);
+                        (edge, Vec::new())
+                    })
+                    .1
+                    .push(forward);
+            }
+        }
+
+        for key in order {
+            let (edge, directions) = &edges[&key];
+
+            match directions.as_slice() {
+                [_] => self.issues.push(MeshIssue::OpenEdge(*edge)),
+                [a, b] if a == b => {
+                    self.issues.push(MeshIssue::InconsistentWinding(*edge))
+                }
+                [_, _] => {}
+                _ => self.issues.push(MeshIssue::NonManifoldEdge {
+                    edge: *edge,
+                    num_triangles: directions.len(),
+                }),
+            }
+        }
+    }
+
+    fn check_intersections(
+        &mut self,
+        points: &[[Point<3>; 3]],
+        indices: &[[u32; 3]],
+    ) {
+        // Sort the triangles along the x-axis, so only those whose bounding
+        // boxes overlap along it need to be compared. The boxes are enlarged by
+        // the tolerance, to include triangles that are almost coplanar.
+        let bounds = points
+            .iter()
+            .map(|triangle| {
+                let margin = longest_edge(triangle) * TOLERANCE;
+                let coords = |i: usize| {
+                    triangle.iter().map(move |p| p.coords.components[i])
+                };
+                let min = [0, 1, 2]
+                    .map(|i| coords(i).min().expect("3 points") - margin);
+                let max = [0, 1, 2]
+                    .map(|i| coords(i).max().expect("3 points") + margin);
+                (min, max)
+            })
+            .collect::<Vec<_>>();
+        let mut order = (0..points.len()).collect::<Vec<_>>();
+        order.sort_by_key(|&i| bounds[i].0[0]);
+
+        let mut intersections = Vec::new();
+
+        for (n, &a) in order.iter().enumerate() {
+            for &b in &order[n + 1..] {
+                if bounds[b].0[0] > bounds[a].1[0] {
+                    break;
+                }
+
+                let overlap = (1..3).all(|i| {
+                    bounds[a].0[i] <= bounds[b].1[i]
+                        && bounds[b].0[i] <= bounds[a].1[i]
+                });
+                if !overlap {
+                    continue;
+                }
+
+                // Triangles that share a vertex touch by design. Duplicates
+                // are reported separately.
+                let shares_vertex =
+                    indices[a].iter().any(|index| indices[b].contains(index));
+                if shares_vertex {
+                    continue;
+                }
+
+                if intersect(&points[a], &points[b]) {
+                    intersections.push([a.min(b), a.max(b)]);
+                }
+            }
+        }
+
+        intersections.sort_unstable();
+        self.issues
+            .extend(intersections.into_iter().map(MeshIssue::SelfIntersection));
+    }
+}
+
+impl fmt::Display for MeshReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.issues.is_empty() {
+            return write!(f, "No mesh issues found");
+        }
+
+        for issue in &self.issues {
+            writeln!(f, "{issue}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// An issue found in a triangle mesh
+///
+/// See [`MeshReport`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum MeshIssue {
+    /// An edge that only one triangle has, making the mesh open
+    OpenEdge([Point<3>; 2]),
+
+    /// An edge that more than two triangles share
+    NonManifoldEdge {
+        /// The points that the edge connects
+        edge: [Point<3>; 2],
+
+        /// The number of triangles that share the edge
+        num_triangles: usize,
+    },
+
+    /// An edge that two triangles run along in the same direction
+    ///
+    /// One of the triangles faces the wrong way.
+    InconsistentWinding([Point<3>; 2]),
+
+    /// A triangle that has no area, or almost none
+    DegenerateTriangle(usize),
+
+    /// A triangle that has the same vertices as a previous triangle
+    DuplicateTriangle {
+        /// The duplicate triangle
+        triangle: usize,
+
+        /// The first triangle with the same vertices
+        original: usize,
+    },
+
+    /// Two triangles that intersect, without sharing a vertex
+    SelfIntersection([usize; 2]),
+}
+
+impl fmt::Display for MeshIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OpenEdge([a, b]) => {
+                write!(f, "Open edge from {a:?} to {b:?}")
+            }
+            Self::NonManifoldEdge {
+                edge: [a, b],
+                num_triangles,
+            } => {
+                write!(
+                    f,
+                    "Edge from {a:?} to {b:?} is shared by {num_triangles} \
+                    triangles"
+                )
+            }
+            Self::InconsistentWinding([a, b]) => {
+                write!(f, "Inconsistent winding at edge from {a:?} to {b:?}")
+            }
+            Self::DegenerateTriangle(triangle) => {
+                write!(f, "Triangle {triangle} is degenerate")
+            }
+            Self::DuplicateTriangle { triangle, original } => {
+                write!(f, "Triangle {triangle} duplicates triangle {original}")
+            }
+            Self::SelfIntersection([a, b]) => {
+                write!(f, "Triangles {a} and {b} intersect")
+            }
+        }
+    }
+}
+
+/// Indicate whether two triangles that don't share a vertex intersect
+fn intersect(a: &[Point<3>; 3], b: &[Point<3>; 3]) -> bool {
+    let normal = normal(a);
+    let max_distance = normal.magnitude() * longest_edge(a) * TOLERANCE;
+    let is_coplanar = b
+        .iter()
+        .all(|&point| normal.dot(&(point - a[0])).abs() <= max_distance);
+
+    // Triangles in the same plane overlap, if any of their edges cross, or if
+    // one contains the other.
+    if is_coplanar {
+        let axis = (0..3)
+            .max_by_key(|&i| normal.components[i].abs())
+            .expect("Three axes");
+        let project = |point: Point<3>| {
+            let [u, v] = [(axis + 1) % 3, (axis + 2) % 3];
+            Point::from([
+                point.coords.components[u],
+                point.coords.components[v],
+            ])
+        };
+        let a = a.map(project);
+        let b = b.map(project);
+
+        let edges_cross = (0..3).any(|i| {
+            (0..3).any(|j| {
+                segments_cross([a[i], a[(i + 1) % 3]], [b[j], b[(j + 1) % 3]])
+            })
+        });
+
+        return edges_cross
+            || contains_point(&a, b[0])
+            || contains_point(&b, a[0]);
+    }
+
+    let edges = |t: &[Point<3>; 3]| [0, 1, 2].map(|i| [t[i], t[(i + 1) % 3]]);
+
+    edges(a)
+        .iter()
+        .any(|&segment| segment_intersects_triangle(segment, b))
+        || edges(b)
+            .iter()
+            .any(|&segment| segment_intersects_triangle(segment, a))
+}
+
+/// Indicate whether a segment passes through the inside of a triangle
+///
+/// Segments that only touch the triangle, within the tolerance, don't.
+fn segment_intersects_triangle(
+    [start, end]: [Point<3>; 2],
+    triangle: &[Point<3>; 3],
+) -> bool {
+    // Möller–Trumbore ray-triangle intersection
+    let direction = end - start;
+    let edge_1 = triangle[1] - triangle[0];
+    let edge_2 = triangle[2] - triangle[0];
+
+    // The determinant is the volume spanned by the segment and the edges. If
+    // it's close to zero, the segment is parallel to the triangle.
+    let p = direction.cross(&edge_2);
+    let determinant = edge_1.dot(&p);
+    let max_volume = direction.magnitude()
+        * edge_1.magnitude()
+        * edge_2.magnitude()
+        * TOLERANCE;
+    if determinant.abs() <= max_volume {
+        return false;
+    }
+
+    let inside = |value: Scalar| {
+        value > Scalar::from(TOLERANCE) && value < Scalar::from(1. - TOLERANCE)
+    };
+
+    let s = start - triangle[0];
+    let u = s.dot(&p) / determinant;
+    let q = s.cross(&edge_1);
+    let v = direction.dot(&q) / determinant;
+    let t = edge_2.dot(&q) / determinant;
+
+    inside(u) && inside(v) && inside(u + v) && inside(t)
+}
+
+/// Indicate whether two segments cross, rather than just touch
+fn segments_cross([a, b]: [Point<2>; 2], [c, d]: [Point<2>; 2]) -> bool {
+    let side_of =
+        |[p, q]: [Point<2>; 2], r: Point<2>| side(p, q, r).to_scalar();
+
+    side_of([a, b], c) * side_of([a, b], d) < Scalar::ZERO
+        && side_of([c, d], a) * side_of([c, d], b) < Scalar::ZERO
+}
+
+/// Indicate whether a point is inside a triangle, rather than on its edges
+fn contains_point(triangle: &[Point<2>; 3], point: Point<2>) -> bool {
+    let sides =
+        [0, 1, 2].map(|i| side(triangle[i], triangle[(i + 1) % 3], point));
+
+    sides.iter().all(|&side| side == Sign::Positive)
+        || sides.iter().all(|&side| side == Sign::Negative)
+}
+
+/// Find out on which side of the line through `p` and `q` the point `r` is
+///
+/// Points within the tolerance of the line are on it.
+fn side(p: Point<2>, q: Point<2>, r: Point<2>) -> Sign {
+    let line = q - p;
+    let area = line.cross2d(&(r - p));
+
+    if area.abs() <= line.dot(&line) * TOLERANCE {
+        return Sign::Zero;
+    }
+
+    area.sign()
+}
+
+fn normal(triangle: &[Point<3>; 3]) -> Vector<3> {
+    let [a, b, c] = *triangle;
+    (b - a).cross(&(c - a))
+}
+
+fn longest_edge(triangle: &[Point<3>; 3]) -> Scalar {
+    let [a, b, c] = *triangle;
+    [b - a, c - b, a - c]
+        .iter()
+        .map(|edge| edge.magnitude())
+        .max()
+        .expect("Three edges")
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_interop::mesh::{Color, Mesh};
+
+    use super::{MeshIssue, MeshReport};
+
+    fn tetrahedron(offset: f64) -> [[[f64; 3]; 3]; 4] {
+        let [a, b, c, d] =
+            [[0., 0., 0.], [1., 0., 0.], [0., 1., 0.], [0., 0., 1.]]
+                .map(|point| point.map(|c| c + offset));
+
+        [[a, c, b], [a, b, d], [b, c, d], [c, a, d]]
+    }
+
+    #[test]
+    fn closed_mesh() {
+        let mut mesh = Mesh::new();
+        for triangle in tetrahedron(0.) {
+            mesh.push_triangle(triangle, Color::default());
+        }
+
+        let report = MeshReport::for_mesh(&mesh);
+        assert!(report.is_empty(), "{report}");
+    }
+
+    #[test]
+    fn broken_mesh() {
+        let [a, b, c, d] = tetrahedron(0.);
+        let [_, e, f, g] = tetrahedron(0.25);
+
+        let mut mesh = Mesh::new();
+        let flipped = [b[0], b[2], b[1]];
+        for triangle in [a, flipped, c, d, d, e, f, g] {
+            mesh.push_triangle(triangle, Color::default());
+        }
+
+        let issues = MeshReport::for_mesh(&mesh)
+            .issues()
+            .cloned()
+            .collect::<Vec<_>>();
+
+        let count = |f: fn(&MeshIssue) -> bool| {
+            issues.iter().filter(|issue| f(issue)).count()
+        };
+
+        assert!(issues.contains(&MeshIssue::DuplicateTriangle {
+            triangle: 4,
+            original: 3,
+        }));
+        assert_eq!(
+            count(|i| matches!(i, MeshIssue::InconsistentWinding(_))),
+            2
+        );
+        assert_eq!(
+            count(|i| matches!(i, MeshIssue::NonManifoldEdge { .. })),
+            3
+        );
+        assert_eq!(count(|i| matches!(i, MeshIssue::OpenEdge(_))), 3);
+        assert!(count(|i| matches!(i, MeshIssue::SelfIntersection(_))) > 0);
+        assert_eq!(count(|i| matches!(i, MeshIssue::DegenerateTriangle(_))), 0);
+    }
+
+    #[test]
+    fn touching_triangles_dont_intersect() {
+        // The corner of the second triangle is slightly below the first one,
+        // within the tolerance.
+        let issues = intersections([
+            [[0., 0., 0.], [1., 0., 0.], [0., 1., 0.]],
+            [[0.25, 0.25, -1e-10], [0.25, 0.25, 1.], [0.5, 0.25, 1.]],
+        ]);
+        assert!(issues.is_empty(), "{issues:?}");
+    }
+
+    #[test]
+    fn nearly_coplanar_triangles_intersect() {
+        // The second triangle is slightly above the plane of the first one,
+        // within the tolerance, and overlaps it.
+        let issues = intersections([
+            [[0., 0., 0.], [1., 0., 0.], [0., 1., 0.]],
+            [[0.5, 0., 1e-10], [0.5, 1., 1e-10], [-0.5, 0.5, 1e-10]],
+        ]);
+        assert_eq!(issues, [MeshIssue::SelfIntersection([0, 1])]);
+    }
+
+    fn intersections(triangles: [[[f64; 3]; 3]; 2]) -> Vec<MeshIssue> {
+        let mut mesh = Mesh::new();
+        for triangle in triangles {
+            mesh.push_triangle(triangle, Color::default());
+        }
+
+        MeshReport::for_mesh(&mesh)
+            .issues()
+            .filter(|issue| matches!(issue, MeshIssue::SelfIntersection(_)))
+            .cloned()
+            .collect()
+    }
+}
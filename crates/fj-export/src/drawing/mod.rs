@@ -0,0 +1,497 @@
+//! Technical drawings of solids
+//!
+//! A drawing is a single sheet with front, top, right, and isometric views of a
+//! solid, as well as a title block. The orthographic views are arranged
+//! according to the [`ProjectionMethod`], and share a common scale.
+
+pub mod pdf;
+pub mod svg;
+
+mod projection;
+
+use fj::models::{LengthUnit, Metadata};
+use fj_kernel::{algorithms::approx::Tolerance, objects::Solid};
+
+use self::projection::{Model, Projection, View};
+
+/// Options for creating a drawing
+///
+/// See [`crate::export_drawing`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct DrawingOptions {
+    /// The width of the sheet, in millimeters
+    pub width: f64,
+
+    /// The height of the sheet, in millimeters
+    pub height: f64,
+
+    /// How the orthographic views are arranged on the sheet
+    pub projection: ProjectionMethod,
+}
+
+impl Default for DrawingOptions {
+    /// A4 in landscape orientation, using first-angle projection
+    fn default() -> Self {
+        Self {
+            width: 297.,
+            height: 210.,
+            projection: ProjectionMethod::FirstAngle,
+        }
+    }
+}
+
+/// How the orthographic views are arranged on the sheet
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ProjectionMethod {
+    /// The top view is below the front view, the right view left of it
+    ///
+    /// This is the standard in most of the world.
+    FirstAngle,
+
+    /// The top view is above the front view, the right view right of it
+    ///
+    /// This is the standard in North America.
+    ThirdAngle,
+}
+
+/// A drawing sheet, made up of lines and text
+///
+/// All coordinates are in millimeters. The origin is in the lower-left corner
+/// of the sheet.
+pub struct Sheet {
+    /// The width of the sheet
+    pub width: f64,
+
+    /// The height of the sheet
+    pub height: f64,
+
+    /// The lines on the sheet
+    pub lines: Vec<Line>,
+
+    /// The text on the sheet
+    pub texts: Vec<Text>,
+}
+
+/// A line on a [`Sheet`]
+pub struct Line {
+    /// The points of the line
+    pub points: Vec<[f64; 2]>,
+
+    /// How the line is drawn
+    pub style: LineStyle,
+}
+
+/// How a [`Line`] is drawn
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum LineStyle {
+    /// A visible edge: thick and continuous
+    Visible,
+
+    /// A hidden edge: thin and dashed
+    Hidden,
+
+    /// The border of the sheet's frame
+    Frame,
+
+    /// The lines of the title block
+    Thin,
+}
+
+impl LineStyle {
+    /// The width of the line, in millimeters
+    pub fn width(&self) -> f64 {
+        match self {
+            Self::Visible => 0.5,
+            Self::Hidden => 0.25,
+            Self::Frame => 0.7,
+            Self::Thin => 0.25,
+        }
+    }
+
+    /// The lengths of dashes and gaps, for dashed lines
+    pub fn dashes(&self) -> Option<[f64; 2]> {
+        match self {
+            Self::Hidden => Some([3., 1.5]),
+            _ => None,
+        }
+    }
+}
+
+/// Text on a [`Sheet`]
+pub struct Text {
+    /// The position of the left end of the text's baseline
+    pub position: [f64; 2],
+
+    /// The height of capital letters, in millimeters
+    pub size: f64,
+
+    /// The text
+    pub text: String,
+}
+
+/// Create a drawing of a solid
+///
+/// The solid is measured in the unit that `metadata` declares. Its views are
+/// scaled accordingly, so the scale in the title block is correct.
+pub fn draw(
+    solid: &Solid,
+    metadata: &Metadata,
+    tolerance: Tolerance,
+    options: &DrawingOptions,
+) -> Sheet {
+    let mut sheet = Sheet {
+        width: options.width,
+        height: options.height,
+        lines: Vec::new(),
+        texts: Vec::new(),
+    };
+
+    let margin = 10.;
+    let frame = [
+        [margin, margin],
+        [options.width - margin, options.height - margin],
+    ];
+    sheet.rectangle(frame, LineStyle::Frame);
+
+    let title_block_height = 30.;
+    let title_block_width = (frame[1][0] - frame[0][0]).min(180.);
+    let title_block = [
+        [frame[1][0] - title_block_width, frame[0][1]],
+        [frame[1][0], frame[0][1] + title_block_height],
+    ];
+
+    // The views go into the area above the title block.
+    let padding = 10.;
+    let area = [
+        [frame[0][0] + padding, title_block[1][1] + padding],
+        [frame[1][0] - padding, frame[1][1] - padding],
+    ];
+    let scale =
+        sheet.views(solid, metadata.units, tolerance, options.projection, area);
+
+    sheet.title_block(title_block, metadata, scale, options.projection);
+
+    sheet
+}
+
+impl Sheet {
+    /// Draw the views of the solid into the given area
+    ///
+    /// Returns the scale of the views.
+    fn views(
+        &mut self,
+        solid: &Solid,
+        units: LengthUnit,
+        tolerance: Tolerance,
+        projection: ProjectionMethod,
+        [min, max]: [[f64; 2]; 2],
+    ) -> Scale {
+        let model = Model::new(solid, tolerance);
+
+        let cells = match projection {
+            ProjectionMethod::FirstAngle => {
+                [[View::Right, View::Front], [View::Isometric, View::Top]]
+            }
+            ProjectionMethod::ThirdAngle => {
+                [[View::Top, View::Isometric], [View::Front, View::Right]]
+            }
+        };
+        let projections = cells.map(|row| row.map(|view| model.project(view)));
+
+        // The rows and columns are as large as their largest view. Since the
+        // front view shares a row with the right view and a column with the
+        // top view, centering each view in its cell lines them all up.
+        let size = |projection: &Projection, i: usize| {
+            projection.max[i] - projection.min[i]
+        };
+        let widths = [0, 1].map(|column| {
+            f64::max(
+                size(&projections[0][column], 0),
+                size(&projections[1][column], 0),
+            )
+        });
+        let heights = [0, 1].map(|row| {
+            f64::max(
+                size(&projections[row][0], 1),
+                size(&projections[row][1], 1),
+            )
+        });
+
+        let gap = 15.;
+        let available = [max[0] - min[0] - gap, max[1] - min[1] - gap];
+        let fit = f64::min(
+            available[0] / (widths[0] + widths[1]),
+            available[1] / (heights[0] + heights[1]),
+        );
+        let scale = Scale::fitting(fit / units.in_millimeters());
+
+        // The projections are in the model's unit, the sheet in millimeters.
+        let factor = scale.factor() * units.in_millimeters();
+
+        // Center the views in the available area.
+        let total = [
+            (widths[0] + widths[1]) * factor + gap,
+            (heights[0] + heights[1]) * factor + gap,
+        ];
+        let origin = [
+            (min[0] + max[0] - total[0]) / 2.,
+            (min[1] + max[1] + total[1]) / 2.,
+        ];
+
+        for (row, projections) in projections.iter().enumerate() {
+            for (column, projection) in projections.iter().enumerate() {
+                let left = origin[0]
+                    + widths[..column].iter().sum::<f64>() * factor
+                    + gap * column as f64;
+                let top = origin[1]
+                    - heights[..row].iter().sum::<f64>() * factor
+                    - gap * row as f64;
+
+                let cell_center = [
+                    left + widths[column] * factor / 2.,
+                    top - heights[row] * factor / 2.,
+                ];
+                let view_center = [0, 1]
+                    .map(|i| (projection.min[i] + projection.max[i]) / 2.);
+
+                let to_sheet = |[u, v]: [f64; 2]| {
+                    [
+                        cell_center[0] + (u - view_center[0]) * factor,
+                        cell_center[1] + (v - view_center[1]) * factor,
+                    ]
+                };
+
+                // Hidden lines go first, so visible lines are drawn on top of
+                // them.
+                let polylines = projection
+                    .hidden
+                    .iter()
+                    .map(|polyline| (polyline, LineStyle::Hidden))
+                    .chain(
+                        projection
+                            .visible
+                            .iter()
+                            .map(|polyline| (polyline, LineStyle::Visible)),
+                    );
+
+                for (polyline, style) in polylines {
+                    self.lines.push(Line {
+                        points: polyline
+                            .iter()
+                            .copied()
+                            .map(to_sheet)
+                            .collect(),
+                        style,
+                    });
+                }
+            }
+        }
+
+        scale
+    }
+
+    fn title_block(
+        &mut self,
+        [min, max]: [[f64; 2]; 2],
+        metadata: &Metadata,
+        scale: Scale,
+        projection: ProjectionMethod,
+    ) {
+        self.rectangle([min, max], LineStyle::Frame);
+
+        let width = max[0] - min[0];
+        let rows = [min[1] + 20., min[1] + 10.];
+        for y in rows {
+            self.line([[min[0], y], [max[0], y]], LineStyle::Thin);
+        }
+
+        let description = metadata
+            .short_description
+            .as_ref()
+            .or(metadata.description.as_ref());
+
+        self.text([min[0] + 2., rows[0] + 3.], 5., width - 4., &metadata.name);
+        if let Some(description) = description {
+            self.text(
+                [min[0] + 2., rows[1] + 3.5],
+                3.,
+                width - 4.,
+                description,
+            );
+        }
+
+        let projection = match projection {
+            ProjectionMethod::FirstAngle => "First angle",
+            ProjectionMethod::ThirdAngle => "Third angle",
+        };
+        let scale = scale.to_string();
+        let fields = [
+            ("Version", metadata.version.as_str()),
+            ("License", metadata.license.as_deref().unwrap_or("-")),
+            ("Scale", scale.as_str()),
+            ("Projection", projection),
+        ];
+
+        let field_width = width / fields.len() as f64;
+        for (i, (label, value)) in fields.into_iter().enumerate() {
+            let x = min[0] + field_width * i as f64;
+            if i > 0 {
+                self.line([[x, min[1]], [x, rows[1]]], LineStyle::Thin);
+            }
+
+            self.text([x + 2., min[1] + 6.5], 2., field_width - 4., label);
+            self.text([x + 2., min[1] + 2.], 3., field_width - 4., value);
+        }
+    }
+
+    fn line(&mut self, points: [[f64; 2]; 2], style: LineStyle) {
+        self.lines.push(Line {
+            points: points.to_vec(),
+            style,
+        });
+    }
+
+    fn rectangle(&mut self, [min, max]: [[f64; 2]; 2], style: LineStyle) {
+        self.lines.push(Line {
+            points: vec![min, [max[0], min[1]], max, [min[0], max[1]], min],
+            style,
+        });
+    }
+
+    /// Add text, shortening it to fit into the given width
+    fn text(&mut self, position: [f64; 2], size: f64, width: f64, text: &str) {
+        // A rough estimate of the average character width, which is good
+        // enough for a sans-serif font.
+        let max_chars = (width / (size * 0.65)) as usize;
+
+        let mut text = text.lines().next().unwrap_or_default().to_owned();
+        if text.chars().count() > max_chars {
+            text = text.chars().take(max_chars.saturating_sub(3)).collect();
+            text.push_str("...");
+        }
+
+        self.texts.push(Text {
+            position,
+            size,
+            text,
+        });
+    }
+}
+
+/// The scale of the views on a drawing
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Scale {
+    /// The views are drawn larger than the model, by the given factor
+    Enlarged(u32),
+
+    /// The views are drawn smaller than the model, by the given factor
+    Reduced(u32),
+}
+
+impl Scale {
+    /// Find the largest standard scale that is not larger than `factor`
+    fn fitting(factor: f64) -> Self {
+        let steps = [1, 2, 5];
+
+        for magnitude in (0..4).rev().map(|i| 10u32.pow(i)) {
+            for step in steps.iter().rev() {
+                let enlargement = step * magnitude;
+                if enlargement > 1 && f64::from(enlargement) <= factor {
+                    return Self::Enlarged(enlargement);
+                }
+            }
+        }
+
+        for magnitude in (0..7).map(|i| 10u32.pow(i)) {
+            for step in steps {
+                let reduction = step * magnitude;
+                if factor * f64::from(reduction) >= 1. {
+                    return Self::Reduced(reduction);
+                }
+            }
+        }
+
+        Self::Reduced(10u32.pow(7))
+    }
+
+    fn factor(&self) -> f64 {
+        match self {
+            Self::Enlarged(factor) => f64::from(*factor),
+            Self::Reduced(factor) => 1. / f64::from(*factor),
+        }
+    }
+}
+
+impl std::fmt::Display for Scale {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Enlarged(factor) => write!(f, "{factor}:1"),
+            Self::Reduced(factor) => write!(f, "1:{factor}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fj::models::{LengthUnit, Metadata};
+    use fj_kernel::{
+        algorithms::approx::Tolerance,
+        objects::{Objects, Solid},
+    };
+
+    use super::{DrawingOptions, Scale, Sheet};
+
+    #[test]
+    fn scale_depends_on_units() {
+        let objects = Objects::new();
+        let tolerance = Tolerance::from_scalar(0.001).unwrap();
+
+        // The same cube, once measured in millimeters, once in inches
+        let draw = |edge_length, units| {
+            let solid = Solid::builder(&objects)
+                .with_cube_from_edge_length(edge_length)
+                .build()
+                .unwrap();
+            let metadata = Metadata::new("cube", "0.1.0").with_units(units);
+
+            super::draw(
+                &solid,
+                &metadata,
+                tolerance,
+                &DrawingOptions::default(),
+            )
+        };
+        let millimeters = draw(254., LengthUnit::Millimeter);
+        let inches = draw(10., LengthUnit::Inch);
+
+        let texts = |sheet: &Sheet| {
+            sheet
+                .texts
+                .iter()
+                .map(|text| text.text.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(texts(&millimeters), texts(&inches));
+
+        let points = |sheet: &Sheet| {
+            sheet
+                .lines
+                .iter()
+                .flat_map(|line| line.points.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(points(&millimeters).len(), points(&inches).len());
+        for (a, b) in points(&millimeters).into_iter().zip(points(&inches)) {
+            assert!((a[0] - b[0]).abs() < 1e-6 && (a[1] - b[1]).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn scale_fitting() {
+        assert_eq!(Scale::fitting(1.), Scale::Reduced(1));
+        assert_eq!(Scale::fitting(0.3), Scale::Reduced(5));
+        assert_eq!(Scale::fitting(0.09), Scale::Reduced(20));
+        assert_eq!(Scale::fitting(2.5), Scale::Enlarged(2));
+        assert_eq!(Scale::fitting(60.), Scale::Enlarged(50));
+    }
+}
@@ -0,0 +1,124 @@
+//! Output of drawings as PDF
+
+use std::io::{self, Write};
+
+use super::Sheet;
+
+/// Points per millimeter, PDF's unit of measurement being the point
+const POINTS_PER_MILLIMETER: f64 = 72. / 25.4;
+
+/// Write a drawing sheet as a single-page PDF document
+///
+/// Text is set in Helvetica, which every PDF viewer provides. Characters that
+/// can't be represented in its encoding are replaced with `?`.
+pub fn write(sheet: &Sheet, mut writer: impl Write) -> io::Result<()> {
+    let content = content(sheet)?;
+
+    let [width, height] =
+        [sheet.width, sheet.height].map(|size| size * POINTS_PER_MILLIMETER);
+
+    let mut objects = vec![
+        b"<< /Type /Catalog /Pages 2 0 R >>".to_vec(),
+        b"<< /Type /Pages /Kids [3 0 R] /Count 1 >>".to_vec(),
+        format!(
+            "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {width} {height}] \
+            /Contents 4 0 R /Resources << /Font << /F1 5 0 R >> >> >>"
+        )
+        .into_bytes(),
+    ];
+
+    let mut stream =
+        format!("<< /Length {} >>\nstream\n", content.len()).into_bytes();
+    stream.extend(content);
+    stream.extend(b"\nendstream");
+    objects.push(stream);
+
+    objects.push(
+        b"<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica \
+        /Encoding /WinAnsiEncoding >>"
+            .to_vec(),
+    );
+
+    let mut pdf = b"%PDF-1.4\n".to_vec();
+    let mut offsets = Vec::new();
+
+    for (i, object) in objects.iter().enumerate() {
+        offsets.push(pdf.len());
+
+        pdf.extend(format!("{} 0 obj\n", i + 1).into_bytes());
+        pdf.extend(object);
+        pdf.extend(b"\nendobj\n");
+    }
+
+    let xref = pdf.len();
+    pdf.extend(format!("xref\n0 {}\n", objects.len() + 1).into_bytes());
+    pdf.extend(b"0000000000 65535 f \n");
+    for offset in offsets {
+        pdf.extend(format!("{offset:010} 00000 n \n").into_bytes());
+    }
+    pdf.extend(
+        format!(
+            "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{xref}\n%%EOF\n",
+            objects.len() + 1
+        )
+        .into_bytes(),
+    );
+
+    writer.write_all(&pdf)
+}
+
+/// Create the content stream of the page
+fn content(sheet: &Sheet) -> io::Result<Vec<u8>> {
+    let mut content = Vec::new();
+
+    // Everything else is specified in millimeters.
+    writeln!(
+        content,
+        "{s} 0 0 {s} 0 0 cm 1 J 1 j",
+        s = POINTS_PER_MILLIMETER
+    )?;
+
+    for line in &sheet.lines {
+        let dashes = match line.style.dashes() {
+            Some([dash, gap]) => format!("[{dash} {gap}]"),
+            None => String::from("[]"),
+        };
+        writeln!(content, "{} w {dashes} 0 d", line.style.width())?;
+
+        for (i, [x, y]) in line.points.iter().enumerate() {
+            let operator = if i == 0 { "m" } else { "l" };
+            writeln!(content, "{x} {y} {operator}")?;
+        }
+        writeln!(content, "S")?;
+    }
+
+    for text in &sheet.texts {
+        let [x, y] = text.position;
+
+        // The size of a font is the height of its em square. Capital letters
+        // in Helvetica are about 72% of that.
+        let font_size = text.size / 0.72;
+
+        write!(content, "BT /F1 {font_size} Tf {x} {y} Td (")?;
+        content.extend(encode(&text.text));
+        writeln!(content, ") Tj ET")?;
+    }
+
+    Ok(content)
+}
+
+/// Encode text as a PDF string in the Windows-1252 encoding
+fn encode(text: &str) -> Vec<u8> {
+    let mut encoded = Vec::new();
+
+    for c in text.chars() {
+        match c {
+            '(' | ')' | '\\' => encoded.extend([b'\\', c as u8]),
+            // Windows-1252 matches Unicode in these ranges.
+            ' '..='~' | '\u{a0}'..='\u{ff}' => encoded.push(c as u8),
+            _ => encoded.push(b'?'),
+        }
+    }
+
+    encoded
+}
@@ -0,0 +1,454 @@
+//! Projection of solids into views, with hidden line detection
+
+use std::{collections::HashSet, ops::Deref};
+
+use fj_kernel::{
+    algorithms::{
+        approx::{Approx, Tolerance},
+        triangulate::Triangulate,
+    },
+    objects::Solid,
+};
+use fj_math::{Point, Vector};
+
+/// A direction from which a solid is viewed
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum View {
+    /// Looking at the solid from the front (in the direction of positive y)
+    Front,
+
+    /// Looking at the solid from above (in the direction of negative z)
+    Top,
+
+    /// Looking at the solid from the right (in the direction of negative x)
+    Right,
+
+    /// Looking at the solid from the front, right, and above
+    Isometric,
+}
+
+impl View {
+    /// The axes of the view: right and up on the sheet, and into the sheet
+    fn axes(&self) -> [Vector<3>; 3] {
+        match self {
+            Self::Front => {
+                [Vector::unit_x(), Vector::unit_z(), Vector::unit_y()]
+            }
+            Self::Top => {
+                [Vector::unit_x(), Vector::unit_y(), -Vector::unit_z()]
+            }
+            Self::Right => {
+                [Vector::unit_y(), Vector::unit_z(), -Vector::unit_x()]
+            }
+            Self::Isometric => {
+                let direction = Vector::from([-1., 1., -1.]).normalize();
+                let right = direction.cross(&Vector::unit_z()).normalize();
+                let up = right.cross(&direction);
+
+                [right, up, direction]
+            }
+        }
+    }
+}
+
+/// The edges and faces of a solid, prepared for projection
+pub struct Model {
+    /// The edges, approximated as polylines
+    edges: Vec<Vec<Point<3>>>,
+
+    /// The triangles of the faces, which can hide edges behind them
+    triangles: Vec<[Point<3>; 3]>,
+}
+
+impl Model {
+    pub fn new(solid: &Solid, tolerance: Tolerance) -> Self {
+        let mut edges = Vec::new();
+        let mut global_edges = HashSet::new();
+
+        let half_edges = solid
+            .shells()
+            .flat_map(|shell| shell.faces())
+            .flat_map(|face| face.all_cycles())
+            .flat_map(|cycle| cycle.half_edges());
+
+        for half_edge in half_edges {
+            // Neighboring faces share an edge. It only needs to be drawn once.
+            if !global_edges.insert(half_edge.global_form().id()) {
+                continue;
+            }
+
+            let mut points = half_edge
+                .deref()
+                .approx(tolerance)
+                .points()
+                .into_iter()
+                .map(|point| point.global_form)
+                .collect::<Vec<_>>();
+            points.push(half_edge.front().global_form().position());
+
+            edges.push(points);
+        }
+
+        let triangles = (solid, tolerance)
+            .triangulate()
+            .triangles()
+            .map(|triangle| triangle.inner.points())
+            .collect();
+
+        Self { edges, triangles }
+    }
+
+    /// Project the model into a view
+    ///
+    /// Every edge is split into parts that are visible, and parts that are
+    /// hidden behind a face.
+    pub fn project(&self, view: View) -> Projection {
+        let [right, up, direction] = view.axes();
+        let project = |point: &Point<3>| {
+            [right, up, direction]
+                .map(|axis| point.coords.dot(&axis).into_f64())
+        };
+
+        let edges = self
+            .edges
+            .iter()
+            .map(|edge| edge.iter().map(project).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+
+        let mut min = [f64::INFINITY; 2];
+        let mut max = [f64::NEG_INFINITY; 2];
+        for &[u, v, _] in edges.iter().flatten() {
+            min = [min[0].min(u), min[1].min(v)];
+            max = [max[0].max(u), max[1].max(v)];
+        }
+        if edges.is_empty() {
+            min = [0.; 2];
+            max = [0.; 2];
+        }
+
+        let size = (max[0] - min[0]).max(max[1] - min[1]);
+        let occlusion = Occlusion::new(
+            self.triangles
+                .iter()
+                .map(|triangle| triangle.map(|point| project(&point)))
+                .filter_map(ProjectedTriangle::new)
+                .collect(),
+            size * 1e-6,
+        );
+
+        let mut projection = Projection {
+            min,
+            max,
+            visible: Vec::new(),
+            hidden: Vec::new(),
+        };
+
+        // Whether an edge is hidden is checked at regular intervals. This is
+        // only an approximation of where it vanishes behind a face, but a
+        // close one, at the scale of a drawing.
+        let step = size / 500.;
+
+        for edge in &edges {
+            let mut polyline: Vec<[f64; 2]> = Vec::new();
+            let mut hidden = None;
+
+            for segment in edge.windows(2) {
+                let [a, b] = [segment[0], segment[1]];
+                let length =
+                    ((b[0] - a[0]).powi(2) + (b[1] - a[1]).powi(2)).sqrt();
+
+                // Segments that point into the sheet don't show up.
+                if length <= occlusion.epsilon {
+                    continue;
+                }
+
+                let num_parts = (length / step).ceil().max(1.) as usize;
+
+                for i in 0..num_parts {
+                    let point_at = |t: f64| {
+                        let t = (i as f64 + t) / num_parts as f64;
+                        [0, 1, 2].map(|j| a[j] + (b[j] - a[j]) * t)
+                    };
+                    let [start, middle, end] = [0., 0.5, 1.].map(point_at);
+
+                    let is_hidden = occlusion.hides(middle);
+                    if hidden != Some(is_hidden) {
+                        projection.push(polyline, hidden);
+                        polyline = vec![[start[0], start[1]]];
+                        hidden = Some(is_hidden);
+                    }
+
+                    polyline.push([end[0], end[1]]);
+                }
+            }
+
+            projection.push(polyline, hidden);
+        }
+
+        projection
+    }
+}
+
+/// A solid, projected into a view
+pub struct Projection {
+    /// The minimum coordinates of the projected edges
+    pub min: [f64; 2],
+
+    /// The maximum coordinates of the projected edges
+    pub max: [f64; 2],
+
+    /// The visible parts of the edges
+    pub visible: Vec<Vec<[f64; 2]>>,
+
+    /// The parts of the edges that are hidden behind faces
+    pub hidden: Vec<Vec<[f64; 2]>>,
+}
+
+impl Projection {
+    fn push(&mut self, polyline: Vec<[f64; 2]>, hidden: Option<bool>) {
+        match hidden {
+            Some(true) => self.hidden.push(polyline),
+            Some(false) => self.visible.push(polyline),
+            None => {}
+        }
+    }
+}
+
+/// The projected triangles of a model, which can hide edges behind them
+///
+/// Checking every point of every edge against every triangle gets expensive
+/// quickly, for larger models. To avoid that, the triangles are sorted into a
+/// grid of cells, by their bounding boxes. A point only needs to be checked
+/// against the triangles in its cell.
+struct Occlusion {
+    triangles: Vec<ProjectedTriangle>,
+    epsilon: f64,
+
+    min: [f64; 2],
+    cell_size: [f64; 2],
+    num_cells: [usize; 2],
+    cells: Vec<Vec<usize>>,
+}
+
+impl Occlusion {
+    fn new(triangles: Vec<ProjectedTriangle>, epsilon: f64) -> Self {
+        let mut min = [f64::INFINITY; 2];
+        let mut max = [f64::NEG_INFINITY; 2];
+        for triangle in &triangles {
+            min = [0, 1].map(|i| min[i].min(triangle.min[i]));
+            max = [0, 1].map(|i| max[i].max(triangle.max[i]));
+        }
+
+        // Aim for a number of cells that is in the order of the number of
+        // triangles, so each cell only contains a few of them.
+        let num_cells = if triangles.is_empty() {
+            [1; 2]
+        } else {
+            [(triangles.len() as f64).sqrt().ceil() as usize; 2]
+        };
+        let cell_size = [0, 1].map(|i| {
+            let size = (max[i] - min[i]) / num_cells[i] as f64;
+            if size > 0. {
+                size
+            } else {
+                1.
+            }
+        });
+
+        let mut occlusion = Self {
+            triangles: Vec::new(),
+            epsilon,
+            min,
+            cell_size,
+            num_cells,
+            cells: vec![Vec::new(); num_cells[0] * num_cells[1]],
+        };
+
+        for (index, triangle) in triangles.iter().enumerate() {
+            let [min_u, min_v] = occlusion.cell(triangle.min);
+            let [max_u, max_v] = occlusion.cell(triangle.max);
+
+            for u in min_u..=max_u {
+                for v in min_v..=max_v {
+                    occlusion.cells[v * num_cells[0] + u].push(index);
+                }
+            }
+        }
+        occlusion.triangles = triangles;
+
+        occlusion
+    }
+
+    /// Indicate whether a projected point is hidden behind a triangle
+    fn hides(&self, [u, v, depth]: [f64; 3]) -> bool {
+        let [cell_u, cell_v] = self.cell([u, v]);
+
+        self.cells[cell_v * self.num_cells[0] + cell_u]
+            .iter()
+            .any(|&index| {
+                self.triangles[index]
+                    .depth_at([u, v])
+                    .map_or(false, |d| d < depth - self.epsilon)
+            })
+    }
+
+    /// Compute the cell that contains a point
+    ///
+    /// Points outside of the grid are assigned to the closest cell. There are
+    /// no triangles outside of the grid that could hide them anyway.
+    fn cell(&self, point: [f64; 2]) -> [usize; 2] {
+        [0, 1].map(|i| {
+            let cell = ((point[i] - self.min[i]) / self.cell_size[i]).floor();
+            (cell.max(0.) as usize).min(self.num_cells[i] - 1)
+        })
+    }
+}
+
+struct ProjectedTriangle {
+    points: [[f64; 3]; 3],
+    min: [f64; 2],
+    max: [f64; 2],
+    area: f64,
+}
+
+impl ProjectedTriangle {
+    /// Create a projected triangle, unless it is seen edge-on
+    fn new(points: [[f64; 3]; 3]) -> Option<Self> {
+        let [a, b, c] = points;
+        let area =
+            (b[0] - a[0]) * (c[1] - a[1]) - (c[0] - a[0]) * (b[1] - a[1]);
+
+        let size = points
+            .iter()
+            .flat_map(|p| [p[0], p[1]])
+            .map(f64::abs)
+            .fold(1., f64::max);
+        if area.abs() <= size * size * 1e-12 {
+            return None;
+        }
+
+        let min = [0, 1].map(|i| a[i].min(b[i]).min(c[i]));
+        let max = [0, 1].map(|i| a[i].max(b[i]).max(c[i]));
+
+        Some(Self {
+            points,
+            min,
+            max,
+            area,
+        })
+    }
+
+    /// Compute the depth of the triangle at a point, if the point is within it
+    ///
+    /// Points on the boundary of the triangle are considered to be within.
+    /// The triangles of the faces next to an edge are at the same depth as the
+    /// edge, so they don't hide it anyway.
+    fn depth_at(&self, [u, v]: [f64; 2]) -> Option<f64> {
+        if u < self.min[0]
+            || u > self.max[0]
+            || v < self.min[1]
+            || v > self.max[1]
+        {
+            return None;
+        }
+
+        let [a, b, c] = self.points;
+        let edge_function = |p: [f64; 3], q: [f64; 3]| {
+            ((q[0] - p[0]) * (v - p[1]) - (u - p[0]) * (q[1] - p[1]))
+                / self.area
+        };
+
+        let weights = [
+            edge_function(b, c),
+            edge_function(c, a),
+            edge_function(a, b),
+        ];
+        if weights.iter().any(|&weight| weight < -1e-9) {
+            return None;
+        }
+
+        let depth = weights
+            .iter()
+            .zip(self.points)
+            .map(|(weight, point)| weight * point[2])
+            .sum();
+
+        Some(depth)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_kernel::{
+        algorithms::{approx::Tolerance, transform::TransformObject},
+        objects::{Objects, Solid},
+    };
+
+    use super::{Model, View};
+
+    #[test]
+    fn isometric_view_of_cube_hides_back_edges() {
+        let objects = Objects::new();
+        let solid = Solid::builder(&objects)
+            .with_cube_from_edge_length(1.)
+            .build()
+            .unwrap();
+
+        let tolerance = Tolerance::from_scalar(0.001).unwrap();
+        let projection = Model::new(&solid, tolerance).project(View::Isometric);
+
+        // Each edge of the cube is foreshortened the same way. Three of the
+        // twelve edges meet in the corner in the back, and are hidden.
+        let edge_length = (2f64 / 3.).sqrt();
+        let hidden = length(&projection.hidden);
+        let visible = length(&projection.visible);
+
+        assert!((hidden - 3. * edge_length).abs() < 0.01);
+        assert!((visible - 9. * edge_length).abs() < 0.01);
+    }
+
+    #[test]
+    fn front_view_hides_cube_behind_cube() {
+        let objects = Objects::new();
+
+        // A larger cube in front, with its center at the origin, and a
+        // smaller one behind it, where it's completely hidden.
+        let front = Solid::builder(&objects)
+            .with_cube_from_edge_length(2.)
+            .build()
+            .unwrap();
+        let back = Solid::builder(&objects)
+            .with_cube_from_edge_length(1.)
+            .build()
+            .unwrap()
+            .translate([0., 3., 0.], &objects)
+            .unwrap();
+        let solid = Solid::builder(&objects)
+            .with_shells(front.shells().chain(back.shells()).cloned())
+            .build()
+            .unwrap();
+
+        let tolerance = Tolerance::from_scalar(0.001).unwrap();
+        let projection = Model::new(&solid, tolerance).project(View::Front);
+
+        // The edges that point into the sheet don't show up. Of the others,
+        // only the front face of the larger cube is visible. Its back face,
+        // and all of the smaller cube, are hidden.
+        let visible = length(&projection.visible);
+        let hidden = length(&projection.hidden);
+
+        assert!((visible - 4. * 2.).abs() < 0.01);
+        assert!((hidden - (4. * 2. + 8. * 1.)).abs() < 0.01);
+    }
+
+    fn length(polylines: &[Vec<[f64; 2]>]) -> f64 {
+        polylines
+            .iter()
+            .flat_map(|polyline| polyline.windows(2))
+            .map(|segment| {
+                let [a, b] = [segment[0], segment[1]];
+                ((b[0] - a[0]).powi(2) + (b[1] - a[1]).powi(2)).sqrt()
+            })
+            .sum()
+    }
+}
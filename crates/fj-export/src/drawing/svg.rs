@@ -0,0 +1,70 @@
+//! Output of drawings as SVG
+
+use std::io::{self, Write};
+
+use super::Sheet;
+
+/// Write a drawing sheet as an SVG document
+pub fn write(sheet: &Sheet, mut writer: impl Write) -> io::Result<()> {
+    let Sheet { width, height, .. } = *sheet;
+
+    writeln!(writer, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(
+        writer,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}mm\" \
+        height=\"{height}mm\" viewBox=\"0 0 {width} {height}\">"
+    )?;
+
+    writeln!(
+        writer,
+        "<g fill=\"none\" stroke=\"black\" stroke-linecap=\"round\" \
+        stroke-linejoin=\"round\">"
+    )?;
+    for line in &sheet.lines {
+        // SVG's y-axis points down.
+        let points = line
+            .points
+            .iter()
+            .map(|[x, y]| format!("{x},{}", height - y))
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        write!(
+            writer,
+            r#"<polyline points="{points}" stroke-width="{}""#,
+            line.style.width()
+        )?;
+        if let Some([dash, gap]) = line.style.dashes() {
+            write!(writer, r#" stroke-dasharray="{dash} {gap}""#)?;
+        }
+        writeln!(writer, "/>")?;
+    }
+    writeln!(writer, "</g>")?;
+
+    writeln!(writer, r#"<g font-family="sans-serif" fill="black">"#)?;
+    for text in &sheet.texts {
+        let [x, y] = text.position;
+
+        // The size of a font is the height of its em square. Capital letters
+        // are only about 70% of that.
+        let font_size = text.size / 0.7;
+
+        writeln!(
+            writer,
+            r#"<text x="{x}" y="{}" font-size="{font_size}">{}</text>"#,
+            height - y,
+            escape(&text.text),
+        )?;
+    }
+    writeln!(writer, "</g>")?;
+
+    writeln!(writer, "</svg>")?;
+
+    Ok(())
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
@@ -0,0 +1,211 @@
+//! Export of sketches to DXF files
+
+use std::{
+    fmt,
+    io::{self, Write},
+};
+
+use fj_kernel::{
+    objects::{Cycle, Sketch},
+    path::SurfacePath,
+};
+use fj_math::{Point, Scalar};
+
+/// Write sketches in the DXF format, each on its own layer
+///
+/// Writes an R12 DXF file, which is understood by virtually all applications
+/// that read DXF. Each cycle of each face becomes a closed polyline, with arcs
+/// preserved as arcs. Cycles that consist of a single circle become circles.
+/// The entities of each sketch are put on the layer of the given name.
+///
+/// Coordinates are the surface coordinates of the faces, which are the global
+/// x and y coordinates for sketches in the xy-plane, multiplied by `scale`.
+/// Pass the factor that converts them into the unit of length that the file is
+/// meant to use. R12 files have no way to declare that unit, so it must be
+/// communicated separately.
+pub fn write_layers<'r>(
+    layers: impl IntoIterator<Item = (&'r str, &'r Sketch)>,
+    scale: f64,
+    mut writer: impl Write,
+) -> io::Result<()> {
+    let mut dxf = DxfWriter {
+        writer: &mut writer,
+        layer: "0",
+        scale: Scalar::from(scale),
+    };
+
+    dxf.pair(0, "SECTION")?;
+    dxf.pair(2, "HEADER")?;
+    dxf.pair(9, "$ACADVER")?;
+    dxf.pair(1, "AC1009")?;
+    dxf.pair(0, "ENDSEC")?;
+
+    dxf.pair(0, "SECTION")?;
+    dxf.pair(2, "ENTITIES")?;
+    for (layer, sketch) in layers {
+        dxf.layer = layer;
+
+        for face in sketch.faces() {
+            for cycle in face.all_cycles() {
+                dxf.cycle(cycle)?;
+            }
+        }
+    }
+    dxf.pair(0, "ENDSEC")?;
+
+    dxf.pair(0, "EOF")?;
+
+    Ok(())
+}
+
+struct DxfWriter<'r, W> {
+    writer: W,
+    layer: &'r str,
+    scale: Scalar,
+}
+
+impl<W: Write> DxfWriter<'_, W> {
+    fn pair(&mut self, code: i32, value: impl fmt::Display) -> io::Result<()> {
+        writeln!(self.writer, "{code}")?;
+        writeln!(self.writer, "{value}")
+    }
+
+    fn point(&mut self, point: Point<2>) -> io::Result<()> {
+        self.pair(10, point.u * self.scale)?;
+        self.pair(20, point.v * self.scale)
+    }
+
+    fn cycle(&mut self, cycle: &Cycle) -> io::Result<()> {
+        let half_edges = cycle.half_edges().collect::<Vec<_>>();
+
+        if let [half_edge] = half_edges.as_slice() {
+            if let SurfacePath::Circle(circle) = half_edge.curve().path() {
+                self.pair(0, "CIRCLE")?;
+                self.pair(8, self.layer)?;
+                self.point(circle.center())?;
+                self.pair(40, circle.radius() * self.scale)?;
+
+                return Ok(());
+            }
+        }
+
+        self.pair(0, "POLYLINE")?;
+        self.pair(8, self.layer)?;
+        self.pair(66, 1)?;
+        self.pair(70, 1)?; // closed
+
+        for half_edge in half_edges {
+            let path = half_edge.curve().path();
+            let start = half_edge.back().surface_form().position();
+
+            let circle = match path {
+                SurfacePath::Line(_) => {
+                    self.vertex(start, 0.)?;
+                    continue;
+                }
+                SurfacePath::Circle(circle) => circle,
+            };
+
+            // DXF defines arcs by their angle, which is positive for arcs that
+            // go counter-clockwise.
+            let [a, b] = half_edge.vertices().clone().map(|v| v.position().t);
+            let direction = circle.a().cross2d(&circle.b()).sign().to_scalar();
+            let angle = (b - a) * direction;
+
+            // Arcs that span more than half a circle are split up. The angle of
+            // each part stays small enough to be represented accurately.
+            let num_parts = (angle.abs() / Scalar::PI).ceil().max(Scalar::ONE);
+            let bulge = (angle / num_parts / Scalar::from(4.)).into_f64().tan();
+
+            for i in 0..num_parts.into_u64() {
+                let t = a + (b - a) * Scalar::from_u64(i) / num_parts;
+                let point = path.point_from_path_coords([t]);
+
+                self.vertex(point, bulge)?;
+            }
+        }
+
+        self.pair(0, "SEQEND")?;
+        self.pair(8, self.layer)?;
+
+        Ok(())
+    }
+
+    fn vertex(&mut self, point: Point<2>, bulge: f64) -> io::Result<()> {
+        self.pair(0, "VERTEX")?;
+        self.pair(8, self.layer)?;
+        self.point(point)?;
+
+        if bulge != 0. {
+            self.pair(42, bulge)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_kernel::{
+        builder::{FaceBuilder, HalfEdgeBuilder},
+        insert::Insert,
+        objects::{Cycle, Face, HalfEdge, Objects, Sketch},
+        partial::HasPartial,
+        validate::ValidationError,
+    };
+
+    #[test]
+    fn write_polygons_and_circles() -> Result<(), ValidationError> {
+        let objects = Objects::new();
+        let surface = objects.surfaces.xy_plane();
+
+        let polygon = Face::partial()
+            .with_surface(surface.clone())
+            .with_exterior_polygon_from_points([[0., 0.], [1., 0.], [0., 1.]])
+            .build(&objects)?
+            .insert(&objects)?;
+
+        let half_edge = HalfEdge::partial()
+            .with_surface(surface)
+            .update_as_circle_from_radius(2., &objects)?
+            .build(&objects)?
+            .insert(&objects)?;
+        let circle = Face::partial()
+            .with_exterior(objects.cycles.insert(Cycle::new([half_edge]))?)
+            .build(&objects)?
+            .insert(&objects)?;
+
+        let sketch = Sketch::builder(&objects)
+            .with_faces([polygon, circle])
+            .build();
+
+        let mut dxf = Vec::new();
+        super::write_layers([("0", &*sketch)], 10., &mut dxf).unwrap();
+        let dxf = String::from_utf8(dxf).unwrap();
+        let lines = dxf.lines().collect::<Vec<_>>();
+
+        let count = |value| lines.iter().filter(|&&line| line == value).count();
+        assert_eq!(count("POLYLINE"), 1);
+        assert_eq!(count("VERTEX"), 3);
+        assert_eq!(count("CIRCLE"), 1);
+
+        // Coordinates and radii are scaled.
+        let value_after = |code| {
+            let mut values = lines
+                .chunks(2)
+                .filter(|pair| pair[0] == code)
+                .map(|pair| pair[1].parse::<f64>().unwrap())
+                .collect::<Vec<_>>();
+            values.sort_by(f64::total_cmp);
+            values
+        };
+        assert_eq!(value_after("10"), [0., 0., 0., 10.]);
+        assert_eq!(value_after("40"), [20.]);
+        assert_eq!(lines.last(), Some(&"EOF"));
+
+        // `$INSUNITS` was only introduced after R12.
+        assert_eq!(count("$INSUNITS"), 0);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,484 @@
+//! Export of meshes to glTF files
+
+use std::{
+    collections::HashMap,
+    io::{self, Write},
+};
+
+use fj::models::LengthUnit;
+use fj_interop::mesh::{Color, Mesh};
+use fj_math::{Point, Vector};
+
+use crate::tree::Tree;
+
+/// How a glTF file is encoded
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Encoding {
+    /// A JSON document, with the binary data embedded as base64
+    Json,
+
+    /// A binary GLB file, that contains the JSON document and the binary data
+    Binary,
+}
+
+/// Write a tree of meshes in the glTF 2.0 format
+///
+/// Every shape in the tree becomes a node with its own mesh, and every group a
+/// node with the nodes of its members as children. Triangles of the same color
+/// share a material.
+///
+/// Normals are smooth where neighboring triangles meet at a shallow angle, as
+/// they do on approximated curved surfaces, and flat across sharp edges.
+///
+/// The coordinates are expected to be in `units`, with the z-axis pointing up.
+/// glTF uses meters, with the y-axis pointing up. The root node converts
+/// between them.
+pub fn write(
+    tree: &Tree,
+    encoding: Encoding,
+    units: LengthUnit,
+    mut writer: impl Write,
+) -> io::Result<()> {
+    let mut document = Document::default();
+
+    let root = document.node(tree);
+    let scale = units.in_millimeters() / 1000.;
+    let model = document.push_node(format!(
+        "{{\"name\":\"Model\",\"children\":[{root}],\
+        \"rotation\":[{s},0,0,{c}],\"scale\":[{scale},{scale},{scale}]}}",
+        s = -std::f32::consts::FRAC_1_SQRT_2,
+        c = std::f32::consts::FRAC_1_SQRT_2,
+    ));
+
+    match encoding {
+        Encoding::Json => {
+            let uri = format!(
+                "data:application/octet-stream;base64,{}",
+                base64(&document.buffer)
+            );
+            let json = document.json(model, Some(&uri));
+
+            writer.write_all(json.as_bytes())?;
+        }
+        Encoding::Binary => {
+            let mut json = document.json(model, None).into_bytes();
+            let mut buffer = document.buffer;
+
+            // Chunks must be aligned to 4 bytes.
+            while json.len() % 4 != 0 {
+                json.push(b' ');
+            }
+            while buffer.len() % 4 != 0 {
+                buffer.push(0);
+            }
+
+            let mut length = 12 + 8 + json.len();
+            if !buffer.is_empty() {
+                length += 8 + buffer.len();
+            }
+
+            writer.write_all(b"glTF")?;
+            writer.write_all(&2u32.to_le_bytes())?;
+            writer.write_all(&(length as u32).to_le_bytes())?;
+
+            writer.write_all(&(json.len() as u32).to_le_bytes())?;
+            writer.write_all(b"JSON")?;
+            writer.write_all(&json)?;
+
+            if !buffer.is_empty() {
+                writer.write_all(&(buffer.len() as u32).to_le_bytes())?;
+                writer.write_all(b"BIN\0")?;
+                writer.write_all(&buffer)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// The parts of a glTF document, as they are being assembled
+///
+/// Objects are kept as JSON text, and referred to by their index.
+#[derive(Default)]
+struct Document {
+    buffer: Vec<u8>,
+    buffer_views: Vec<String>,
+    accessors: Vec<String>,
+    materials: Vec<String>,
+    materials_by_color: HashMap<Color, usize>,
+    meshes: Vec<String>,
+    nodes: Vec<String>,
+    num_parts: usize,
+}
+
+impl Document {
+    fn node(&mut self, tree: &Tree) -> usize {
+        match tree {
+            Tree::Mesh(mesh) => {
+                self.num_parts += 1;
+                let name = format!("Part {}", self.num_parts);
+
+                match self.mesh(mesh) {
+                    Some(mesh) => self.push_node(format!(
+                        "{{\"name\":\"{name}\",\"mesh\":{mesh}}}"
+                    )),
+                    None => self.push_node(format!("{{\"name\":\"{name}\"}}")),
+                }
+            }
+            Tree::Group(members) => {
+                let children = members
+                    .iter()
+                    .map(|member| self.node(member).to_string())
+                    .collect::<Vec<_>>()
+                    .join(",");
+
+                self.push_node(format!(
+                    "{{\"name\":\"Group\",\"children\":[{children}]}}"
+                ))
+            }
+        }
+    }
+
+    fn push_node(&mut self, node: String) -> usize {
+        self.nodes.push(node);
+        self.nodes.len() - 1
+    }
+
+    /// Add a mesh with a primitive for each color, unless it is empty
+    fn mesh(&mut self, mesh: &Mesh<Point<3>>) -> Option<usize> {
+        let mut colors = Vec::new();
+        let mut triangles_by_color = HashMap::new();
+
+        let normals = mesh.smooth_normals(CREASE_ANGLE.to_radians());
+
+        for (triangle, normals) in mesh.triangles().zip(normals) {
+            let triangles =
+                triangles_by_color.entry(triangle.color).or_insert_with(|| {
+                    colors.push(triangle.color);
+                    Vec::new()
+                });
+            triangles.push((triangle.inner.points(), normals));
+        }
+
+        let primitives = colors
+            .into_iter()
+            .filter_map(|color| {
+                let triangles = &triangles_by_color[&color];
+                self.primitive(triangles, color)
+            })
+            .collect::<Vec<_>>();
+
+        if primitives.is_empty() {
+            return None;
+        }
+
+        self.meshes
+            .push(format!("{{\"primitives\":[{}]}}", primitives.join(",")));
+        Some(self.meshes.len() - 1)
+    }
+
+    fn primitive(
+        &mut self,
+        triangles: &[([Point<3>; 3], [Vector<3>; 3])],
+        color: Color,
+    ) -> Option<String> {
+        let mut positions = Vec::new();
+        let mut normals_of_vertices = Vec::new();
+        let mut indices = Vec::new();
+        let mut indices_by_vertex = HashMap::new();
+
+        for (points, normals) in triangles {
+            for (point, normal) in points.iter().zip(normals) {
+                let position = point.coords.components.map(|s| s.into_f32());
+                let normal = normal.components.map(|s| s.into_f32());
+                let key = [position, normal].map(|v| v.map(f32::to_bits));
+
+                let index =
+                    *indices_by_vertex.entry(key).or_insert_with(|| {
+                        positions.push(position);
+                        normals_of_vertices.push(normal);
+                        positions.len() as u32 - 1
+                    });
+                indices.push(index);
+            }
+        }
+
+        if indices.is_empty() {
+            return None;
+        }
+
+        let mut min = [f32::INFINITY; 3];
+        let mut max = [f32::NEG_INFINITY; 3];
+        for position in &positions {
+            for i in 0..3 {
+                min[i] = min[i].min(position[i]);
+                max[i] = max[i].max(position[i]);
+            }
+        }
+
+        let position_accessor = {
+            let view = self.push_buffer_view(
+                positions.iter().flatten().flat_map(|c| c.to_le_bytes()),
+                ARRAY_BUFFER,
+            );
+            self.push_accessor(format!(
+                "{{\"bufferView\":{view},\"componentType\":{FLOAT},\
+                \"count\":{},\"type\":\"VEC3\",\"min\":{},\"max\":{}}}",
+                positions.len(),
+                json_array(min),
+                json_array(max),
+            ))
+        };
+        let normal_accessor = {
+            let view = self.push_buffer_view(
+                normals_of_vertices
+                    .iter()
+                    .flatten()
+                    .flat_map(|c| c.to_le_bytes()),
+                ARRAY_BUFFER,
+            );
+            self.push_accessor(format!(
+                "{{\"bufferView\":{view},\"componentType\":{FLOAT},\
+                \"count\":{},\"type\":\"VEC3\"}}",
+                normals_of_vertices.len(),
+            ))
+        };
+        let index_accessor = {
+            let view = self.push_buffer_view(
+                indices.iter().flat_map(|i| i.to_le_bytes()),
+                ELEMENT_ARRAY_BUFFER,
+            );
+            self.push_accessor(format!(
+                "{{\"bufferView\":{view},\"componentType\":{UNSIGNED_INT},\
+                \"count\":{},\"type\":\"SCALAR\"}}",
+                indices.len(),
+            ))
+        };
+
+        let material = self.material(color);
+
+        Some(format!(
+            "{{\"attributes\":{{\"POSITION\":{position_accessor},\
+            \"NORMAL\":{normal_accessor}}},\"indices\":{index_accessor},\
+            \"material\":{material}}}"
+        ))
+    }
+
+    fn material(&mut self, color: Color) -> usize {
+        if let Some(&material) = self.materials_by_color.get(&color) {
+            return material;
+        }
+
+        let [r, g, b, a] = color.0;
+
+        // Colors are in sRGB, but glTF expects linear values. Alpha is linear
+        // already.
+        let factor = [r, g, b]
+            .map(|c| srgb_to_linear(f32::from(c) / 255.))
+            .into_iter()
+            .chain([f32::from(a) / 255.])
+            .collect::<Vec<_>>();
+
+        let mut material = format!(
+            "{{\"name\":\"#{r:02x}{g:02x}{b:02x}\",\"pbrMetallicRoughness\":\
+            {{\"baseColorFactor\":{},\"metallicFactor\":0,\
+            \"roughnessFactor\":0.5}}",
+            json_array(factor),
+        );
+        if a < 255 {
+            material.push_str(",\"alphaMode\":\"BLEND\"");
+        }
+        material.push('}');
+
+        self.materials.push(material);
+        let index = self.materials.len() - 1;
+        self.materials_by_color.insert(color, index);
+
+        index
+    }
+
+    fn push_buffer_view(
+        &mut self,
+        data: impl IntoIterator<Item = u8>,
+        target: u32,
+    ) -> usize {
+        // All data consists of 4-byte values, so every view is aligned.
+        let offset = self.buffer.len();
+        self.buffer.extend(data);
+        let length = self.buffer.len() - offset;
+
+        self.buffer_views.push(format!(
+            "{{\"buffer\":0,\"byteOffset\":{offset},\
+            \"byteLength\":{length},\"target\":{target}}}"
+        ));
+        self.buffer_views.len() - 1
+    }
+
+    fn push_accessor(&mut self, accessor: String) -> usize {
+        self.accessors.push(accessor);
+        self.accessors.len() - 1
+    }
+
+    /// Assemble the JSON document
+    ///
+    /// If the buffer is not embedded as `uri`, it is expected to be stored in
+    /// the binary chunk of a GLB file.
+    fn json(&self, root: usize, uri: Option<&str>) -> String {
+        let mut json = format!(
+            "{{\"asset\":{{\"version\":\"2.0\",\"generator\":\"Fornjot\"}},\
+            \"scene\":0,\"scenes\":[{{\"nodes\":[{root}]}}]"
+        );
+
+        let buffer = match uri {
+            Some(uri) => format!(
+                "{{\"byteLength\":{},\"uri\":\"{uri}\"}}",
+                self.buffer.len()
+            ),
+            None => format!("{{\"byteLength\":{}}}", self.buffer.len()),
+        };
+        let buffers = if self.buffer.is_empty() {
+            Vec::new()
+        } else {
+            vec![buffer]
+        };
+
+        // glTF doesn't allow empty arrays, so those are left out.
+        let arrays = [
+            ("nodes", &self.nodes),
+            ("meshes", &self.meshes),
+            ("materials", &self.materials),
+            ("accessors", &self.accessors),
+            ("bufferViews", &self.buffer_views),
+            ("buffers", &buffers),
+        ];
+        for (name, objects) in arrays {
+            if !objects.is_empty() {
+                json.push_str(&format!(",\"{name}\":[{}]", objects.join(",")));
+            }
+        }
+
+        json.push('}');
+        json
+    }
+}
+
+/// Edges where triangles meet at a larger angle than this, in degrees, are
+/// sharp
+const CREASE_ANGLE: f64 = 30.;
+
+const ARRAY_BUFFER: u32 = 34962;
+const ELEMENT_ARRAY_BUFFER: u32 = 34963;
+const FLOAT: u32 = 5126;
+const UNSIGNED_INT: u32 = 5125;
+
+fn srgb_to_linear(c: f32) -> f32 {
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn json_array(values: impl IntoIterator<Item = f32>) -> String {
+    let values = values
+        .into_iter()
+        .map(|value| value.to_string())
+        .collect::<Vec<_>>();
+    format!("[{}]", values.join(","))
+}
+
+fn base64(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] =
+        b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut encoded = String::new();
+
+    for chunk in data.chunks(3) {
+        let bytes = [0, 1, 2].map(|i| chunk.get(i).copied().unwrap_or(0));
+        let bits = u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]]);
+
+        for i in 0..4 {
+            if i <= chunk.len() {
+                let index = (bits >> (18 - 6 * i)) & 0x3f;
+                encoded.push(char::from(ALPHABET[index as usize]));
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use fj::models::LengthUnit;
+    use fj_interop::mesh::{Color, Mesh, MeshTree};
+
+    use crate::tree::Tree;
+
+    use super::Encoding;
+
+    #[test]
+    fn write_glb() {
+        let red = Color([255, 0, 0, 255]);
+        let green = Color([0, 255, 0, 255]);
+
+        let mut a = Mesh::new();
+        a.push_triangle([[0., 0., 0.], [1., 0., 0.], [0., 1., 0.]], red);
+        a.push_triangle([[1., 0., 0.], [1., 1., 0.], [0., 1., 0.]], red);
+        a.push_triangle([[0., 0., 0.], [0., 1., 0.], [0., 0., 1.]], green);
+
+        let mut b = Mesh::new();
+        b.push_triangle([[2., 0., 0.], [3., 0., 0.], [2., 1., 0.]], red);
+
+        let tree = MeshTree::Group(vec![MeshTree::Mesh(a), MeshTree::Mesh(b)]);
+
+        let mut glb = Vec::new();
+        super::write(
+            &Tree::from(&tree),
+            Encoding::Binary,
+            LengthUnit::Millimeter,
+            &mut glb,
+        )
+        .unwrap();
+
+        let read_u32 = |offset: usize| {
+            u32::from_le_bytes(glb[offset..offset + 4].try_into().unwrap())
+                as usize
+        };
+
+        assert_eq!(&glb[0..4], b"glTF");
+        assert_eq!(read_u32(8), glb.len());
+
+        let json_length = read_u32(12);
+        assert_eq!(&glb[16..20], b"JSON");
+        let json = std::str::from_utf8(&glb[20..20 + json_length]).unwrap();
+
+        let bin_start = 20 + json_length;
+        assert_eq!(&glb[bin_start + 4..bin_start + 8], b"BIN\0");
+        assert_eq!(bin_start + 8 + read_u32(bin_start), glb.len());
+
+        // Two parts in a group, under the root node.
+        assert_eq!(json.matches("\"name\":\"Part").count(), 2);
+        assert_eq!(json.matches("\"name\":\"Group\"").count(), 1);
+
+        // The same materials are shared by both meshes.
+        assert_eq!(json.matches("\"pbrMetallicRoughness\"").count(), 2);
+        assert_eq!(json.matches("\"POSITION\"").count(), 3);
+
+        // The triangles in the xy-plane share their vertices. The ones of the
+        // green triangle have their own normals.
+        assert!(json.contains("\"count\":4,\"type\":\"VEC3\",\"min\""));
+        assert!(json.contains("\"count\":6,\"type\":\"SCALAR\""));
+    }
+
+    #[test]
+    fn base64() {
+        assert_eq!(super::base64(b""), "");
+        assert_eq!(super::base64(b"f"), "Zg==");
+        assert_eq!(super::base64(b"fo"), "Zm8=");
+        assert_eq!(super::base64(b"foo"), "Zm9v");
+        assert_eq!(super::base64(b"foob"), "Zm9vYg==");
+    }
+}
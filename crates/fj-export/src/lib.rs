@@ -14,102 +14,325 @@
 
 #![warn(missing_docs)]
 
-use std::{fs::File, path::Path};
+pub mod check;
+pub mod drawing;
+
+mod dxf;
+mod gltf;
+mod options;
+mod stl;
+mod svg;
+mod threemf;
+mod tree;
+
+use std::{borrow::Cow, fs::File, io::BufWriter, path::Path};
 
 use thiserror::Error;
+use tracing::warn;
 
-use fj_interop::mesh::Mesh;
+use fj::models::{LengthUnit, Metadata};
+use fj_interop::mesh::{Mesh, MeshTree};
+use fj_kernel::{
+    algorithms::approx::Tolerance,
+    objects::{Sketch, Solid},
+};
 use fj_math::{Point, Triangle};
 
+use self::{check::MeshReport, drawing::DrawingOptions, tree::Tree};
+
+pub use self::options::{ExportOptions, Format, StlEncoding};
+
 /// Export the provided mesh to the file at the given path.
 ///
 /// This function will create a file if it does not exist, and will truncate it if it does.
 ///
-/// Currently 3MF & STL file types are supported. The case insensitive file extension of
+/// Currently 3MF, STL, glTF & GLB file types are supported. The case insensitive file extension of
 /// the provided path is used to switch between supported types.
 pub fn export(mesh: &Mesh<Point<3>>, path: &Path) -> Result<(), Error> {
+    write(
+        Tree::Mesh(Cow::Borrowed(mesh)),
+        None,
+        &ExportOptions::default(),
+        path,
+    )
+}
+
+/// Export the provided tree of meshes to the file at the given path.
+///
+/// This function will create a file if it does not exist, and will truncate it if it does.
+///
+/// Supports the same file types as [`export`]. 3MF, glTF & GLB files keep the
+/// meshes of group members separate. STL files contain all meshes merged into
+/// one. 3MF files also contain `metadata`, if available.
+pub fn export_tree(
+    tree: &MeshTree,
+    metadata: Option<&Metadata>,
+    path: &Path,
+) -> Result<(), Error> {
+    export_with_options(tree, metadata, &ExportOptions::default(), path)
+}
+
+/// Export the provided tree of meshes to the file at the given path, as
+/// configured by `options`.
+///
+/// This function will create a file if it does not exist, and will truncate it if it does.
+///
+/// Supports the same file types as [`export_tree`]. Unless a format is set in
+/// the options, it is determined by the extension of the path. The meshes are
+/// expected to be in the unit of length that `metadata` declares, or in
+/// millimeters, if no metadata is available.
+pub fn export_with_options(
+    tree: &MeshTree,
+    metadata: Option<&Metadata>,
+    options: &ExportOptions,
+    path: &Path,
+) -> Result<(), Error> {
+    write(tree.into(), metadata, options, path)
+}
+
+fn write(
+    tree: Tree,
+    metadata: Option<&Metadata>,
+    options: &ExportOptions,
+    path: &Path,
+) -> Result<(), Error> {
+    let format = match options.format {
+        Some(format) => format,
+        None => Format::from_path(path)?,
+    };
+
+    let model_units = match metadata {
+        Some(metadata) => metadata.units,
+        None => LengthUnit::default(),
+    };
+    let units = options.units.unwrap_or(model_units);
+
+    let tree =
+        tree.map(&|mesh| prepare_mesh(mesh, model_units, units, options));
+
+    if options.check {
+        // STL files contain a single mesh, 3MF files one per object.
+        let merged;
+        let meshes = match format {
+            Format::Stl => {
+                merged = tree.merged();
+                vec![&*merged]
+            }
+            Format::ThreeMf => tree.meshes(),
+            Format::Gltf | Format::Glb => Vec::new(),
+        };
+
+        for mesh in meshes {
+            let report = MeshReport::for_mesh(mesh);
+            if !report.is_empty() {
+                return Err(Error::InvalidMesh(report));
+            }
+        }
+    }
+
+    // Check the header before creating the file, so there's no empty file
+    // left behind, if it's invalid.
+    if format == Format::Stl {
+        stl::check_header(options)?;
+    }
+
+    let writer = BufWriter::new(File::create(path)?);
+
+    match format {
+        Format::ThreeMf => {
+            threemf::write(&tree, metadata, units, options, writer)?
+        }
+        Format::Stl => stl::write(&tree.merged(), options, writer)?,
+        Format::Gltf => {
+            gltf::write(&tree, gltf::Encoding::Json, units, writer)?
+        }
+        Format::Glb => {
+            gltf::write(&tree, gltf::Encoding::Binary, units, writer)?
+        }
+    }
+
+    Ok(())
+}
+
+/// Export the provided sketch to the file at the given path.
+///
+/// This function will create a file if it does not exist, and will truncate it if it does.
+///
+/// Currently DXF & SVG file types are supported. Use [`is_sketch_format`] to
+/// find out whether a path must be passed to this function.
+///
+/// The sketch is measured in `model_units`. It is exported in `units`, if
+/// specified, and in `model_units` otherwise.
+pub fn export_sketch(
+    sketch: &Sketch,
+    model_units: LengthUnit,
+    units: Option<LengthUnit>,
+    path: &Path,
+) -> Result<(), Error> {
+    export_slices(&[("0".to_owned(), sketch)], model_units, units, path)
+}
+
+/// Export a stack of sketches, like the slices of a solid, to the file at the
+/// given path.
+///
+/// This function will create a file if it does not exist, and will truncate it if it does.
+///
+/// Currently DXF & SVG file types are supported. Each sketch is put on its own
+/// layer, named as specified.
+///
+/// The sketches are measured in `model_units`. They are exported in `units`,
+/// if specified, and in `model_units` otherwise. DXF files can't record which
+/// unit they use, so coordinates are converted, but the unit isn't stored.
+pub fn export_slices(
+    slices: &[(String, &Sketch)],
+    model_units: LengthUnit,
+    units: Option<LengthUnit>,
+    path: &Path,
+) -> Result<(), Error> {
+    let layers = slices.iter().map(|(name, sketch)| (name.as_str(), *sketch));
+    let units = units.unwrap_or(model_units);
+
     match path.extension() {
-        Some(extension) if extension.to_ascii_uppercase() == "3MF" => {
-            export_3mf(mesh, path)
+        Some(extension) if extension.to_ascii_uppercase() == "DXF" => {
+            let file = File::create(path)?;
+            let scale = model_units.convert(1., units);
+            dxf::write_layers(layers, scale, BufWriter::new(file))?;
         }
-        Some(extension) if extension.to_ascii_uppercase() == "STL" => {
-            export_stl(mesh, path)
+        Some(extension) if extension.to_ascii_uppercase() == "SVG" => {
+            let file = File::create(path)?;
+            svg::write_layers(
+                layers,
+                model_units,
+                units,
+                BufWriter::new(file),
+            )?;
         }
-        Some(extension) => Err(Error::InvalidExtension(
-            extension.to_string_lossy().into_owned(),
-        )),
-        None => Err(Error::NoExtension),
+        Some(extension) => {
+            return Err(Error::InvalidExtension(
+                extension.to_string_lossy().into_owned(),
+            ))
+        }
+        None => return Err(Error::NoExtension),
     }
+
+    Ok(())
 }
 
-fn export_3mf(mesh: &Mesh<Point<3>>, path: &Path) -> Result<(), Error> {
-    let vertices = mesh.vertices().map(|vertex| vertex.into()).collect();
-
-    let indices: Vec<_> = mesh.indices().collect();
-    let triangles = indices
-        .chunks(3)
-        .map(|triangle| {
-            [
-                triangle[0] as usize,
-                triangle[1] as usize,
-                triangle[2] as usize,
-            ]
-        })
-        .collect();
-
-    let mesh = threemf::TriangleMesh {
-        vertices,
-        triangles,
+/// Indicate whether the file type of the given path is exported from a sketch
+///
+/// Returns `true`, if the path must be passed to [`export_sketch`] instead of
+/// [`export`]. SVG files can be exported from sketches too, but are used for
+/// drawings of 3D models. Check [`is_drawing_format`] too, for 2D models.
+pub fn is_sketch_format(path: &Path) -> bool {
+    matches!(
+        path.extension(),
+        Some(extension) if extension.to_ascii_uppercase() == "DXF"
+    )
+}
+
+/// Export a technical drawing of the provided solid to the file at the given
+/// path.
+///
+/// This function will create a file if it does not exist, and will truncate it if it does.
+///
+/// Currently SVG & PDF file types are supported. The title block of the drawing
+/// is filled from `metadata`, and the scale of the views accounts for the unit
+/// that `metadata` declares. The edges of the solid are approximated within
+/// `tolerance`. See [`drawing`] for details.
+pub fn export_drawing(
+    solid: &Solid,
+    metadata: &Metadata,
+    tolerance: Tolerance,
+    options: &DrawingOptions,
+    path: &Path,
+) -> Result<(), Error> {
+    let extension = match path.extension() {
+        Some(extension) => extension.to_ascii_uppercase(),
+        None => return Err(Error::NoExtension),
     };
+    if extension != "SVG" && extension != "PDF" {
+        return Err(Error::InvalidExtension(
+            extension.to_string_lossy().into_owned(),
+        ));
+    }
+
+    let sheet = drawing::draw(solid, metadata, tolerance, options);
+    let writer = BufWriter::new(File::create(path)?);
 
-    threemf::write(path, &mesh)?;
+    if extension == "SVG" {
+        drawing::svg::write(&sheet, writer)?;
+    } else {
+        drawing::pdf::write(&sheet, writer)?;
+    }
 
     Ok(())
 }
 
-fn export_stl(mesh: &Mesh<Point<3>>, path: &Path) -> Result<(), Error> {
-    let points = mesh
-        .triangles()
-        .map(|triangle| triangle.inner.points())
-        .collect::<Vec<_>>();
-
-    let vertices = points.iter().map(|points| {
-        points.map(|point| point.coords.components.map(|s| s.into_f32()))
-    });
-
-    let normals = points
-        .iter()
-        .map(|&points| points.into())
-        .map(|triangle: Triangle<3>| triangle.normal())
-        .map(|vector| vector.components.map(|s| s.into_f32()));
-
-    let triangles = vertices
-        .zip(normals)
-        .map(|([v1, v2, v3], normal)| stl::Triangle {
-            normal,
-            v1,
-            v2,
-            v3,
-            attr_byte_count: 0,
-        })
-        .collect::<Vec<_>>();
-
-    let mut file = File::create(path)?;
-
-    let binary_stl_file = stl::BinaryStlFile {
-        header: stl::BinaryStlHeader {
-            header: [0u8; 80],
-            num_triangles: triangles
-                .len()
-                .try_into()
-                .map_err(|_| Error::InvalidTriangleCount)?,
-        },
-        triangles,
+/// Indicate whether the file type of the given path is used for drawings
+///
+/// Returns `true`, if the path must be passed to [`export_drawing`] instead of
+/// [`export`].
+pub fn is_drawing_format(path: &Path) -> bool {
+    matches!(
+        path.extension(),
+        Some(extension) if ["SVG", "PDF"]
+            .iter()
+            .any(|format| extension.to_ascii_uppercase() == *format)
+    )
+}
+
+/// Weld the vertices of the mesh and convert it to the exported units
+///
+/// The mesh is only copied, if it needs to be changed. Triangles that
+/// degenerate in the process are left out, with a warning.
+fn prepare_mesh<'r>(
+    mesh: Cow<'r, Mesh<Point<3>>>,
+    from: LengthUnit,
+    to: LengthUnit,
+    options: &ExportOptions,
+) -> Cow<'r, Mesh<Point<3>>> {
+    let num_triangles = mesh.triangles().count();
+
+    let mesh = match options.weld_distance {
+        Some(distance) => Cow::Owned(mesh.welded(distance)),
+        None => mesh,
+    };
+    let mesh = if from == to {
+        mesh
+    } else {
+        Cow::Owned(scaled(&mesh, from.convert(1., to)))
     };
 
-    stl::write_stl(&mut file, &binary_stl_file)?;
+    let num_degenerate = num_triangles - mesh.triangles().count();
+    if num_degenerate > 0 {
+        warn!(
+            "Left out {num_degenerate} triangles that degenerated while \
+            preparing the mesh for export"
+        );
+    }
 
-    Ok(())
+    mesh
+}
+
+fn scaled(mesh: &Mesh<Point<3>>, scale: f64) -> Mesh<Point<3>> {
+    let mut scaled = Mesh::new();
+    for triangle in mesh.triangles() {
+        let points = triangle.inner.points().map(|point| point * scale);
+
+        // Very small triangles could degenerate, due to rounding.
+        if let Ok(points) = Triangle::from_points(points) {
+            scaled.push_triangle(points, triangle.color);
+        }
+    }
+
+    scaled
+}
+
+fn format_float(value: f64, precision: Option<usize>) -> String {
+    match precision {
+        Some(precision) => format!("{value:.precision$}"),
+        None => value.to_string(),
+    }
 }
 
 /// An error that can occur while exporting
@@ -131,7 +354,15 @@ pub enum Error {
     #[error("maximum triangle count exceeded")]
     InvalidTriangleCount,
 
-    /// Threemf error whilst exporting to 3MF file
-    #[error("threemf error whilst exporting to 3MF file")]
-    ThreeMF(#[from] threemf::Error),
+    /// The header for a binary STL file is invalid
+    #[error("invalid STL header: {0}")]
+    InvalidStlHeader(String),
+
+    /// The exported mesh is invalid
+    #[error("exported mesh is invalid\n{0}")]
+    InvalidMesh(MeshReport),
+
+    /// Error writing the ZIP package of a 3MF file
+    #[error("error writing the ZIP package of a 3MF file")]
+    Zip(#[from] zip::result::ZipError),
 }
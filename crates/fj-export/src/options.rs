@@ -0,0 +1,106 @@
+//! Options for exporting meshes
+//!
+//! See [`ExportOptions`].
+
+use std::path::Path;
+
+use fj::models::LengthUnit;
+
+/// Options for exporting meshes
+///
+/// See [`crate::export_with_options`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ExportOptions {
+    /// The file format
+    ///
+    /// If this is `None`, the format is determined by the extension of the
+    /// path that is exported to.
+    pub format: Option<Format>,
+
+    /// The unit of length that coordinates are exported in
+    ///
+    /// If this is `None`, the unit that the model declares in its metadata is
+    /// used. Coordinates are converted from the model's unit, where necessary.
+    pub units: Option<LengthUnit>,
+
+    /// Whether STL files are written as text or in binary
+    pub stl_encoding: StlEncoding,
+
+    /// Text for the header of STL files
+    ///
+    /// Binary STL files have a header of 80 bytes. Longer text, or text that
+    /// starts with `solid`, results in an error, as readers would take the
+    /// file for an ASCII STL file. ASCII STL files use the text as the name of
+    /// the solid.
+    pub header: Option<String>,
+
+    /// The number of digits after the decimal point, for coordinates in text
+    ///
+    /// Applies to ASCII STL and 3MF files. If this is `None`, coordinates are
+    /// written with as many digits as needed to represent them exactly.
+    pub precision: Option<usize>,
+
+    /// Merge vertices that are closer to each other than this distance
+    ///
+    /// The distance is in the unit of the model, and must be positive.
+    /// Triangles that degenerate as a result are left out. If this is `None`,
+    /// only identical vertices are merged.
+    pub weld_distance: Option<f64>,
+
+    /// Check meshes for validity, and don't export them, if issues are found
+    ///
+    /// Applies to STL and 3MF files, which are used for 3D printing and must
+    /// describe closed volumes. See [`crate::check::MeshReport`].
+    pub check: bool,
+}
+
+/// A file format that meshes can be exported to
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Format {
+    /// 3D Manufacturing Format
+    ThreeMf,
+
+    /// STL, as text or in binary, depending on [`ExportOptions::stl_encoding`]
+    Stl,
+
+    /// glTF, as a JSON document with embedded binary data
+    Gltf,
+
+    /// glTF, as a binary GLB file
+    Glb,
+}
+
+impl Format {
+    /// Determine the format from a file extension, ignoring case
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension.to_ascii_uppercase().as_str() {
+            "3MF" => Some(Self::ThreeMf),
+            "STL" => Some(Self::Stl),
+            "GLTF" => Some(Self::Gltf),
+            "GLB" => Some(Self::Glb),
+            _ => None,
+        }
+    }
+
+    /// Determine the format from the extension of a path
+    pub fn from_path(path: &Path) -> Result<Self, crate::Error> {
+        let extension = path
+            .extension()
+            .ok_or(crate::Error::NoExtension)?
+            .to_string_lossy();
+
+        Self::from_extension(&extension)
+            .ok_or_else(|| crate::Error::InvalidExtension(extension.into()))
+    }
+}
+
+/// How STL files are encoded
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum StlEncoding {
+    /// Binary STL, which is more compact
+    #[default]
+    Binary,
+
+    /// ASCII STL, which is human-readable
+    Ascii,
+}
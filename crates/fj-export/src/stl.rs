@@ -0,0 +1,184 @@
+//! Export of meshes to STL files
+
+use std::io::Write;
+
+use fj_interop::mesh::Mesh;
+use fj_math::Point;
+
+use crate::{format_float, Error, ExportOptions, StlEncoding};
+
+/// Write a mesh in the STL format
+///
+/// Depending on [`ExportOptions::stl_encoding`], the file is written as text
+/// or in binary.
+pub fn write(
+    mesh: &Mesh<Point<3>>,
+    options: &ExportOptions,
+    mut writer: impl Write,
+) -> Result<(), Error> {
+    check_header(options)?;
+    let header = options.header.as_deref().unwrap_or_default();
+
+    let triangles = mesh.triangles().map(|triangle| {
+        let normal = triangle.inner.normal().components.map(|s| s.into_f64());
+        let points = triangle
+            .inner
+            .points()
+            .map(|point| point.coords.components.map(|s| s.into_f64()));
+
+        (normal, points)
+    });
+
+    match options.stl_encoding {
+        StlEncoding::Binary => {
+            let num_triangles: u32 = mesh
+                .triangles()
+                .count()
+                .try_into()
+                .map_err(|_| Error::InvalidTriangleCount)?;
+
+            let mut header_bytes = [0u8; 80];
+            for (byte, text) in header_bytes.iter_mut().zip(header.bytes()) {
+                *byte = text;
+            }
+
+            writer.write_all(&header_bytes)?;
+            writer.write_all(&num_triangles.to_le_bytes())?;
+
+            for (normal, points) in triangles {
+                for vector in [normal].iter().chain(&points) {
+                    for component in vector {
+                        writer.write_all(&(*component as f32).to_le_bytes())?;
+                    }
+                }
+
+                // attribute byte count, which is unused
+                writer.write_all(&0u16.to_le_bytes())?;
+            }
+        }
+        StlEncoding::Ascii => {
+            // The name of the solid ends with the line.
+            let name = header.lines().next().unwrap_or_default();
+            let number = |value| format_float(value, options.precision);
+
+            writeln!(writer, "solid {name}")?;
+
+            for (normal, points) in triangles {
+                let [x, y, z] = normal.map(number);
+                writeln!(writer, "  facet normal {x} {y} {z}")?;
+                writeln!(writer, "    outer loop")?;
+
+                for point in points {
+                    let [x, y, z] = point.map(number);
+                    writeln!(writer, "      vertex {x} {y} {z}")?;
+                }
+
+                writeln!(writer, "    endloop")?;
+                writeln!(writer, "  endfacet")?;
+            }
+
+            writeln!(writer, "endsolid {name}")?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Check that the header can be written into a binary STL file
+///
+/// The header of a binary STL file has room for 80 bytes. It must not start
+/// with `solid`, as readers would take the file for an ASCII STL file.
+pub fn check_header(options: &ExportOptions) -> Result<(), Error> {
+    let header = match (&options.header, options.stl_encoding) {
+        (Some(header), StlEncoding::Binary) => header,
+        _ => return Ok(()),
+    };
+
+    if header.len() > 80 {
+        return Err(Error::InvalidStlHeader(format!(
+            "header is {} bytes long; must not be longer than 80 bytes",
+            header.len()
+        )));
+    }
+
+    let starts_with_solid = header
+        .trim_start()
+        .get(..5)
+        .map_or(false, |start| start.eq_ignore_ascii_case("solid"));
+    if starts_with_solid {
+        return Err(Error::InvalidStlHeader(
+            "header of binary STL file must not start with `solid`".to_owned(),
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_interop::mesh::{Color, Mesh};
+
+    use crate::{Error, ExportOptions, StlEncoding};
+
+    #[test]
+    fn write_binary_and_ascii() {
+        let mut mesh = Mesh::new();
+        mesh.push_triangle(
+            [[0., 0., 0.], [1., 0., 0.], [0., 1., 0.]],
+            Color::default(),
+        );
+
+        let mut options = ExportOptions {
+            header: Some("Part".to_owned()),
+            precision: Some(2),
+            ..ExportOptions::default()
+        };
+
+        let mut binary = Vec::new();
+        super::write(&mesh, &options, &mut binary).unwrap();
+
+        assert_eq!(binary.len(), 80 + 4 + 50);
+        assert!(binary.starts_with(b"Part\0"));
+        assert_eq!(binary[80..84], 1u32.to_le_bytes());
+
+        options.stl_encoding = StlEncoding::Ascii;
+
+        let mut ascii = Vec::new();
+        super::write(&mesh, &options, &mut ascii).unwrap();
+        let ascii = String::from_utf8(ascii).unwrap();
+
+        assert!(ascii.starts_with("solid Part\n"));
+        assert!(ascii.contains("facet normal 0.00 0.00 1.00\n"));
+        assert!(ascii.contains("vertex 1.00 0.00 0.00\n"));
+        assert!(ascii.ends_with("endsolid Part\n"));
+    }
+
+    #[test]
+    fn reject_invalid_binary_headers() {
+        let mesh = Mesh::new();
+        let write = |header: &str, stl_encoding| {
+            let options = ExportOptions {
+                header: Some(header.to_owned()),
+                stl_encoding,
+                ..ExportOptions::default()
+            };
+            super::write(&mesh, &options, Vec::new())
+        };
+
+        let too_long = "a".repeat(81);
+        assert!(matches!(
+            write(&too_long, StlEncoding::Binary),
+            Err(Error::InvalidStlHeader(_))
+        ));
+        assert!(matches!(
+            write("Solid part", StlEncoding::Binary),
+            Err(Error::InvalidStlHeader(_))
+        ));
+
+        assert!(write(&"a".repeat(80), StlEncoding::Binary).is_ok());
+
+        // ASCII files start with `solid` anyway, and have no length limit.
+        assert!(write("solid part", StlEncoding::Ascii).is_ok());
+        assert!(write(&too_long, StlEncoding::Ascii).is_ok());
+    }
+}
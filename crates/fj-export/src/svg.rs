@@ -0,0 +1,246 @@
+//! Export of sketches to SVG files
+
+use std::io::{self, Write};
+
+use fj::models::LengthUnit;
+use fj_kernel::{
+    objects::{Cycle, Sketch},
+    path::SurfacePath,
+};
+use fj_math::{Point, Scalar};
+
+/// Write multiple sketches as an SVG document, each on its own layer
+///
+/// Each face becomes a path that is filled with the face's color, with its
+/// interior cycles cut out. Arcs are preserved as arcs. Layers are groups that
+/// Inkscape recognizes as layers, labeled with the given names.
+///
+/// Coordinates are the surface coordinates of the faces, which are the global
+/// x and y coordinates for sketches in the xy-plane. They are measured in
+/// `model_units`. The size of the document is given in `units`, so
+/// applications display and print it at its actual size.
+pub fn write_layers<'r>(
+    layers: impl IntoIterator<Item = (&'r str, &'r Sketch)>,
+    model_units: LengthUnit,
+    units: LengthUnit,
+    mut writer: impl Write,
+) -> io::Result<()> {
+    let layers = layers.into_iter().collect::<Vec<_>>();
+
+    let [min, max] = bounds(layers.iter().map(|&(_, sketch)| sketch));
+    let [width, height] = [max[0] - min[0], max[1] - min[1]];
+    let [physical_width, physical_height] =
+        [width, height].map(|length| physical(length, model_units, units));
+
+    writeln!(writer, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(
+        writer,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" \
+        xmlns:inkscape=\"http://www.inkscape.org/namespaces/inkscape\" \
+        width=\"{physical_width}\" height=\"{physical_height}\" \
+        viewBox=\"{} {} {width} {height}\">",
+        min[0], -max[1],
+    )?;
+
+    for (i, (name, sketch)) in layers.into_iter().enumerate() {
+        // SVG's y-axis points down. Flipping it also keeps the direction of
+        // arcs intact.
+        writeln!(
+            writer,
+            "<g id=\"layer{}\" inkscape:label=\"{}\" \
+            inkscape:groupmode=\"layer\" transform=\"scale(1 -1)\">",
+            i + 1,
+            escape(name),
+        )?;
+
+        for face in sketch.faces() {
+            let [r, g, b, a] = face.color().0;
+
+            let data = face
+                .all_cycles()
+                .map(|cycle| path_data(cycle))
+                .collect::<Vec<_>>()
+                .join(" ");
+
+            writeln!(
+                writer,
+                "<path d=\"{data}\" fill=\"#{r:02x}{g:02x}{b:02x}\" \
+                fill-opacity=\"{}\" fill-rule=\"evenodd\" stroke=\"black\" \
+                stroke-width=\"0.1\"/>",
+                f64::from(a) / 255.,
+            )?;
+        }
+
+        writeln!(writer, "</g>")?;
+    }
+
+    writeln!(writer, "</svg>")?;
+
+    Ok(())
+}
+
+fn path_data(cycle: &Cycle) -> String {
+    let mut data = Vec::new();
+
+    for (i, half_edge) in cycle.half_edges().enumerate() {
+        if i == 0 {
+            let start = half_edge.back().surface_form().position();
+            data.push(format!("M {} {}", start.u, start.v));
+        }
+
+        let path = half_edge.curve().path();
+
+        let circle = match path {
+            SurfacePath::Line(_) => {
+                let end = half_edge.front().surface_form().position();
+                data.push(format!("L {} {}", end.u, end.v));
+                continue;
+            }
+            SurfacePath::Circle(circle) => circle,
+        };
+
+        // Since the y-axis is flipped, the sweep flag is set for arcs that go
+        // counter-clockwise.
+        let [a, b] = half_edge.vertices().clone().map(|v| v.position().t);
+        let direction = circle.a().cross2d(&circle.b()).sign().to_scalar();
+        let angle = (b - a) * direction;
+        let sweep = u8::from(angle > Scalar::ZERO);
+
+        // Arcs are split into quarter circles at most. That way, the large arc
+        // flag is never needed, and full circles are represented too.
+        let num_parts = (angle.abs() / (Scalar::PI / Scalar::TWO))
+            .ceil()
+            .max(Scalar::ONE);
+        let radius = circle.radius();
+
+        for i in 1..=num_parts.into_u64() {
+            let t = a + (b - a) * Scalar::from_u64(i) / num_parts;
+            let point = path.point_from_path_coords([t]);
+
+            data.push(format!(
+                "A {radius} {radius} 0 0 {sweep} {} {}",
+                point.u, point.v
+            ));
+        }
+    }
+
+    data.push("Z".to_owned());
+    data.join(" ")
+}
+
+fn bounds<'r>(sketches: impl IntoIterator<Item = &'r Sketch>) -> [[f64; 2]; 2] {
+    let mut min = [f64::INFINITY; 2];
+    let mut max = [f64::NEG_INFINITY; 2];
+
+    let mut extend = |point: Point<2>| {
+        let [u, v] = [point.u.into_f64(), point.v.into_f64()];
+        min = [min[0].min(u), min[1].min(v)];
+        max = [max[0].max(u), max[1].max(v)];
+    };
+
+    let half_edges = sketches
+        .into_iter()
+        .flat_map(|sketch| sketch.faces())
+        .flat_map(|face| face.all_cycles())
+        .flat_map(|cycle| cycle.half_edges());
+
+    for half_edge in half_edges {
+        match half_edge.curve().path() {
+            SurfacePath::Line(_) => {
+                extend(half_edge.back().surface_form().position());
+            }
+            SurfacePath::Circle(circle) => {
+                // The bounding box of the whole circle is good enough, even if
+                // only an arc is drawn.
+                let radius = circle.radius();
+                extend(circle.center() - [radius, radius]);
+                extend(circle.center() + [radius, radius]);
+            }
+        }
+    }
+
+    if min[0] > max[0] {
+        return [[0.; 2]; 2];
+    }
+
+    [min, max]
+}
+
+/// Express a length as an SVG length in the given unit
+///
+/// SVG has no unit for meters, so those are expressed in centimeters.
+fn physical(length: f64, model_units: LengthUnit, units: LengthUnit) -> String {
+    let units = match units {
+        LengthUnit::Meter => LengthUnit::Centimeter,
+        units => units,
+    };
+
+    format!("{}{}", model_units.convert(length, units), units.symbol())
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use fj::models::LengthUnit;
+    use fj_kernel::{
+        builder::{FaceBuilder, HalfEdgeBuilder},
+        insert::Insert,
+        objects::{Cycle, Face, HalfEdge, Objects, Sketch},
+        partial::HasPartial,
+        validate::ValidationError,
+    };
+
+    #[test]
+    fn write_layers() -> Result<(), ValidationError> {
+        let objects = Objects::new();
+        let surface = objects.surfaces.xy_plane();
+
+        let square = Face::partial()
+            .with_surface(surface.clone())
+            .with_exterior_polygon_from_points([
+                [0., 0.],
+                [4., 0.],
+                [4., 4.],
+                [0., 4.],
+            ])
+            .build(&objects)?
+            .insert(&objects)?;
+
+        let half_edge = HalfEdge::partial()
+            .with_surface(surface)
+            .update_as_circle_from_radius(1., &objects)?
+            .build(&objects)?
+            .insert(&objects)?;
+        let circle = Face::partial()
+            .with_exterior(objects.cycles.insert(Cycle::new([half_edge]))?)
+            .build(&objects)?
+            .insert(&objects)?;
+
+        let a = Sketch::builder(&objects).with_faces([square]).build();
+        let b = Sketch::builder(&objects).with_faces([circle]).build();
+
+        let mut svg = Vec::new();
+        super::write_layers(
+            [("a", &*a), ("b", &*b)],
+            LengthUnit::Inch,
+            LengthUnit::Millimeter,
+            &mut svg,
+        )
+        .unwrap();
+        let svg = String::from_utf8(svg).unwrap();
+
+        assert_eq!(svg.matches("inkscape:groupmode=\"layer\"").count(), 2);
+        assert_eq!(svg.matches("<path").count(), 2);
+        assert_eq!(svg.matches(" A ").count(), 4);
+        assert!(svg.contains("viewBox=\"-1 -4 5 5\""));
+        assert!(svg.contains("width=\"127mm\" height=\"127mm\""));
+
+        Ok(())
+    }
+}
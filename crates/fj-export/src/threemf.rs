@@ -0,0 +1,309 @@
+//! Export of meshes to 3MF files
+
+use std::{
+    collections::HashMap,
+    io::{Seek, Write},
+};
+
+use fj::models::{LengthUnit, Metadata};
+use fj_interop::mesh::Color;
+use zip::{result::ZipResult, write::FileOptions, ZipWriter};
+
+use crate::{format_float, tree::Tree, ExportOptions};
+
+/// Write a tree of meshes as a 3MF package
+///
+/// Every shape in the tree becomes an object of its own, which is placed on
+/// the build plate by a separate build item. The colors of the triangles
+/// become base materials. Name, description, and license of the model are
+/// written as metadata, if `metadata` is available.
+///
+/// The coordinates are expected to be in `units` already.
+pub fn write(
+    tree: &Tree,
+    metadata: Option<&Metadata>,
+    units: LengthUnit,
+    options: &ExportOptions,
+    writer: impl Write + Seek,
+) -> ZipResult<()> {
+    let mut archive = ZipWriter::new(writer);
+
+    archive.start_file("[Content_Types].xml", FileOptions::default())?;
+    archive.write_all(CONTENT_TYPES.as_bytes())?;
+
+    archive.start_file("_rels/.rels", FileOptions::default())?;
+    archive.write_all(RELATIONSHIPS.as_bytes())?;
+
+    archive.start_file("3D/3dmodel.model", FileOptions::default())?;
+    write_model(tree, metadata, units, options, &mut archive)?;
+
+    archive.finish()?;
+
+    Ok(())
+}
+
+const CONTENT_TYPES: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types">
+<Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/>
+<Default Extension="model" ContentType="application/vnd.ms-package.3dmanufacturing-3dmodel+xml"/>
+</Types>
+"#;
+
+const RELATIONSHIPS: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+<Relationship Target="/3D/3dmodel.model" Id="rel0" Type="http://schemas.microsoft.com/3dmanufacturing/2013/01/3dmodel"/>
+</Relationships>
+"#;
+
+fn write_model(
+    tree: &Tree,
+    metadata: Option<&Metadata>,
+    units: LengthUnit,
+    options: &ExportOptions,
+    mut writer: impl Write,
+) -> ZipResult<()> {
+    // A 3MF object must have at least one triangle. Shapes without any don't
+    // contribute anything to the model anyway, so they are left out.
+    let meshes = tree
+        .meshes()
+        .into_iter()
+        .filter(|mesh| mesh.triangles().next().is_some())
+        .collect::<Vec<_>>();
+
+    // All colors go into a single group of base materials. It's the first
+    // resource, and the objects follow it.
+    let mut colors = Vec::new();
+    let mut color_indices = HashMap::new();
+    for triangle in meshes.iter().flat_map(|mesh| mesh.triangles()) {
+        color_indices.entry(triangle.color).or_insert_with(|| {
+            colors.push(triangle.color);
+            colors.len() - 1
+        });
+    }
+    let materials_id = 1;
+
+    let unit = match units {
+        LengthUnit::Millimeter => "millimeter",
+        LengthUnit::Centimeter => "centimeter",
+        LengthUnit::Meter => "meter",
+        LengthUnit::Inch => "inch",
+    };
+
+    writeln!(writer, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(
+        writer,
+        "<model unit=\"{unit}\" xml:lang=\"en-US\" \
+        xmlns=\"http://schemas.microsoft.com/3dmanufacturing/core/2015/02\">"
+    )?;
+
+    writeln!(writer, r#"<metadata name="Application">Fornjot</metadata>"#)?;
+    if let Some(metadata) = metadata {
+        let description = metadata
+            .description
+            .as_ref()
+            .or(metadata.short_description.as_ref());
+
+        let entries = [
+            ("Title", Some(&metadata.name)),
+            ("Description", description),
+            ("LicenseTerms", metadata.license.as_ref()),
+        ];
+        for (name, value) in entries {
+            if let Some(value) = value {
+                writeln!(
+                    writer,
+                    r#"<metadata name="{name}">{}</metadata>"#,
+                    escape(value)
+                )?;
+            }
+        }
+    }
+
+    writeln!(writer, "<resources>")?;
+
+    if !colors.is_empty() {
+        writeln!(writer, r#"<basematerials id="{materials_id}">"#)?;
+        for Color([r, g, b, a]) in &colors {
+            writeln!(
+                writer,
+                "<base name=\"#{r:02x}{g:02x}{b:02x}\" \
+                displaycolor=\"#{r:02X}{g:02X}{b:02X}{a:02X}\"/>"
+            )?;
+        }
+        writeln!(writer, "</basematerials>")?;
+    }
+
+    let mut object_ids = Vec::new();
+    for (i, mesh) in meshes.iter().enumerate() {
+        let id = materials_id + 1 + i;
+        object_ids.push(id);
+
+        let name = match metadata {
+            Some(metadata) if meshes.len() == 1 => metadata.name.clone(),
+            Some(metadata) => format!("{} {}", metadata.name, i + 1),
+            None => format!("Part {}", i + 1),
+        };
+
+        write!(
+            writer,
+            r#"<object id="{id}" type="model" name="{}""#,
+            escape(&name)
+        )?;
+        // Objects with colored triangles need a default color too.
+        if let Some(triangle) = mesh.triangles().next() {
+            write!(
+                writer,
+                r#" pid="{materials_id}" pindex="{}""#,
+                color_indices[&triangle.color]
+            )?;
+        }
+        writeln!(writer, ">")?;
+
+        writeln!(writer, "<mesh>")?;
+
+        writeln!(writer, "<vertices>")?;
+        for vertex in mesh.vertices() {
+            let [x, y, z] = vertex
+                .coords
+                .components
+                .map(|c| format_float(c.into_f64(), options.precision));
+            writeln!(writer, r#"<vertex x="{x}" y="{y}" z="{z}"/>"#)?;
+        }
+        writeln!(writer, "</vertices>")?;
+
+        // Each triangle of the mesh corresponds to three indices.
+        let indices = mesh.indices().collect::<Vec<_>>();
+
+        writeln!(writer, "<triangles>")?;
+        for (triangle, indices) in mesh.triangles().zip(indices.chunks(3)) {
+            writeln!(
+                writer,
+                "<triangle v1=\"{}\" v2=\"{}\" v3=\"{}\" pid=\"{}\" \
+                p1=\"{}\"/>",
+                indices[0],
+                indices[1],
+                indices[2],
+                materials_id,
+                color_indices[&triangle.color],
+            )?;
+        }
+        writeln!(writer, "</triangles>")?;
+
+        writeln!(writer, "</mesh>")?;
+        writeln!(writer, "</object>")?;
+    }
+
+    writeln!(writer, "</resources>")?;
+
+    writeln!(writer, "<build>")?;
+    for id in object_ids {
+        writeln!(writer, r#"<item objectid="{id}"/>"#)?;
+    }
+    writeln!(writer, "</build>")?;
+
+    writeln!(writer, "</model>")?;
+
+    Ok(())
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Cursor, Read};
+
+    use fj::models::{LengthUnit, Metadata};
+    use fj_interop::mesh::{Color, Mesh, MeshTree};
+
+    use crate::{tree::Tree, ExportOptions};
+
+    #[test]
+    fn write_objects_with_colors_and_metadata() {
+        let red = Color([255, 0, 0, 255]);
+        let green = Color([0, 255, 0, 255]);
+
+        let mut a = Mesh::new();
+        a.push_triangle([[0., 0., 0.], [1., 0., 0.], [0., 1., 0.]], red);
+        a.push_triangle([[1., 0., 0.], [1., 1., 0.], [0., 1., 0.]], green);
+
+        let mut b = Mesh::new();
+        b.push_triangle([[2., 0., 0.], [3., 0., 0.], [2., 1., 0.]], red);
+
+        let tree = MeshTree::Group(vec![MeshTree::Mesh(a), MeshTree::Mesh(b)]);
+
+        let mut metadata = Metadata::new("Spacer & Washer", "1.0.0");
+        metadata.license = Some("MIT".to_owned());
+
+        let mut package = Cursor::new(Vec::new());
+        let units = LengthUnit::Millimeter;
+        let options = ExportOptions::default();
+        let tree = Tree::from(&tree);
+        super::write(&tree, Some(&metadata), units, &options, &mut package)
+            .unwrap();
+
+        let mut archive = zip::ZipArchive::new(package).unwrap();
+        let mut model = String::new();
+        archive
+            .by_name("3D/3dmodel.model")
+            .unwrap()
+            .read_to_string(&mut model)
+            .unwrap();
+
+        assert!(model.contains(r#"unit="millimeter""#));
+        assert!(model.contains(
+            r#"<metadata name="Title">Spacer &amp; Washer</metadata>"#
+        ));
+        assert!(model.contains(r#"<metadata name="LicenseTerms">MIT"#));
+
+        assert_eq!(model.matches("<base ").count(), 2);
+        assert!(model.contains(r##"displaycolor="#FF0000FF""##));
+
+        assert_eq!(model.matches("<object ").count(), 2);
+        assert_eq!(model.matches("<item ").count(), 2);
+        assert!(model.contains(r#"name="Spacer &amp; Washer 2""#));
+
+        // The triangles of the first object share two vertices.
+        assert_eq!(model.matches("<vertex ").count(), 4 + 3);
+        assert!(model.contains(r#"v1="1" v2="3" v3="2" pid="1" p1="1""#));
+    }
+
+    #[test]
+    fn leave_out_empty_meshes() {
+        let mut a = Mesh::new();
+        a.push_triangle(
+            [[0., 0., 0.], [1., 0., 0.], [0., 1., 0.]],
+            Color::default(),
+        );
+        let tree = MeshTree::Group(vec![
+            MeshTree::Mesh(Mesh::new()),
+            MeshTree::Mesh(a),
+        ]);
+
+        let mut package = Cursor::new(Vec::new());
+        super::write(
+            &Tree::from(&tree),
+            None,
+            LengthUnit::Millimeter,
+            &ExportOptions::default(),
+            &mut package,
+        )
+        .unwrap();
+
+        let mut archive = zip::ZipArchive::new(package).unwrap();
+        let mut model = String::new();
+        archive
+            .by_name("3D/3dmodel.model")
+            .unwrap()
+            .read_to_string(&mut model)
+            .unwrap();
+
+        assert_eq!(model.matches("<object ").count(), 1);
+        assert_eq!(model.matches("<item ").count(), 1);
+        assert!(model.contains(r#"name="Part 1""#));
+    }
+}
@@ -0,0 +1,75 @@
+//! Trees of meshes, as they are exported
+//!
+//! See [`Tree`].
+
+use std::borrow::Cow;
+
+use fj_interop::mesh::{Mesh, MeshTree};
+use fj_math::Point;
+
+/// A tree of meshes, as it is exported
+///
+/// Like [`MeshTree`], except that meshes are borrowed, until preparing them
+/// for export requires changing them. That way, meshes are only copied, if
+/// there's a reason to.
+pub enum Tree<'r> {
+    /// A single mesh
+    Mesh(Cow<'r, Mesh<Point<3>>>),
+
+    /// A group of trees
+    Group(Vec<Tree<'r>>),
+}
+
+impl<'r> Tree<'r> {
+    /// Access all meshes in the tree
+    pub fn meshes(&self) -> Vec<&Mesh<Point<3>>> {
+        match self {
+            Self::Mesh(mesh) => vec![mesh],
+            Self::Group(members) => {
+                members.iter().flat_map(|member| member.meshes()).collect()
+            }
+        }
+    }
+
+    /// Create a tree of the same shape, with every mesh transformed by `f`
+    pub fn map(
+        self,
+        f: &impl Fn(Cow<'r, Mesh<Point<3>>>) -> Cow<'r, Mesh<Point<3>>>,
+    ) -> Self {
+        match self {
+            Self::Mesh(mesh) => Self::Mesh(f(mesh)),
+            Self::Group(members) => Self::Group(
+                members.into_iter().map(|member| member.map(f)).collect(),
+            ),
+        }
+    }
+
+    /// Merge all meshes in the tree into a single one
+    ///
+    /// If the tree consists of a single mesh, that mesh is borrowed.
+    pub fn merged(&self) -> Cow<Mesh<Point<3>>> {
+        if let Self::Mesh(mesh) = self {
+            return Cow::Borrowed(mesh);
+        }
+
+        let mut merged = Mesh::new();
+        for mesh in self.meshes() {
+            for triangle in mesh.triangles() {
+                merged.push_triangle(triangle.inner, triangle.color);
+            }
+        }
+
+        Cow::Owned(merged)
+    }
+}
+
+impl<'r> From<&'r MeshTree> for Tree<'r> {
+    fn from(tree: &'r MeshTree) -> Self {
+        match tree {
+            MeshTree::Mesh(mesh) => Self::Mesh(Cow::Borrowed(mesh)),
+            MeshTree::Group(members) => {
+                Self::Group(members.iter().map(Self::from).collect())
+            }
+        }
+    }
+}
@@ -1,10 +1,14 @@
-use std::thread;
+use std::{cell::Cell, thread, time::Duration};
 
-use crossbeam_channel::{Receiver, SendError, Sender};
+use crossbeam_channel::{Receiver, RecvError, SendError, Sender};
 
 use crate::{Error, Evaluation, Model};
 
 /// Evaluates a model in a background thread
+///
+/// If the evaluation is triggered again while the model is being evaluated,
+/// the running evaluation is cancelled, and a new one started. See
+/// [`Model::evaluate_cancellable`].
 pub struct Evaluator {
     trigger_tx: Sender<TriggerEvaluation>,
     event_rx: Receiver<ModelEvent>,
@@ -17,28 +21,43 @@ impl Evaluator {
         let (trigger_tx, trigger_rx) = crossbeam_channel::bounded(0);
 
         thread::spawn(move || {
-            while let Ok(TriggerEvaluation) = trigger_rx.recv() {
+            // Set, if a trigger arrived while the model was being evaluated.
+            let superseded = Cell::new(false);
+
+            loop {
+                if !superseded.take() {
+                    if let Err(RecvError) = trigger_rx.recv() {
+                        break;
+                    }
+                }
+
                 if let Err(SendError(_)) =
                     event_tx.send(ModelEvent::ChangeDetected)
                 {
                     break;
                 }
 
-                let evaluation = match model.evaluate() {
-                    Ok(evaluation) => evaluation,
-                    Err(err) => {
-                        if let Err(SendError(_)) =
-                            event_tx.send(ModelEvent::Error(err))
-                        {
-                            break;
-                        }
-                        continue;
+                // A newer trigger supersedes the running evaluation, as its
+                // result would be outdated anyway.
+                let result = model.evaluate_cancellable(&|| {
+                    if let Ok(TriggerEvaluation) = trigger_rx.try_recv() {
+                        superseded.set(true);
+                    }
+                    superseded.get()
+                });
+
+                let event = match result {
+                    Ok(evaluation) => {
+                        ModelEvent::Evaluation(Box::new(evaluation))
                     }
+                    Err(Error::Cancelled) => ModelEvent::Cancelled,
+                    Err(Error::TimedOut { timeout }) => {
+                        ModelEvent::TimedOut(timeout)
+                    }
+                    Err(err) => ModelEvent::Error(err),
                 };
 
-                if let Err(SendError(_)) =
-                    event_tx.send(ModelEvent::Evaluation(evaluation))
-                {
+                if let Err(SendError(_)) = event_tx.send(event) {
                     break;
                 };
             }
@@ -74,7 +93,17 @@ pub enum ModelEvent {
     ChangeDetected,
 
     /// The model has been evaluated
-    Evaluation(Evaluation),
+    Evaluation(Box<Evaluation>),
+
+    /// The evaluation was cancelled, because the model changed again
+    ///
+    /// An evaluation of the changed model follows.
+    Cancelled,
+
+    /// The evaluation was stopped, because it took longer than the timeout
+    ///
+    /// See [`Model::with_timeout`].
+    TimedOut(Duration),
 
     /// An error
     Error(Error),
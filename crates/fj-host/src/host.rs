@@ -14,9 +14,8 @@ impl Host {
     /// This is only useful, if you want to continuously watch the model for
     /// changes. If you don't, just keep using `Model`.
     pub fn from_model(model: Model) -> Result<Self, Error> {
-        let watch_path = model.watch_path();
-        let evaluator = Evaluator::from_model(model);
-        let _watcher = Watcher::watch_model(&watch_path, &evaluator)?;
+        let evaluator = Evaluator::from_model(model.clone());
+        let _watcher = Watcher::watch_model(model, &evaluator)?;
 
         Ok(Self {
             evaluator,
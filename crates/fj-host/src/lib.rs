@@ -20,6 +20,8 @@ mod host;
 mod model;
 mod parameters;
 mod platform;
+mod process;
+mod sandbox;
 mod watcher;
 
 pub use self::{
@@ -27,5 +29,6 @@ pub use self::{
     host::Host,
     model::{Error, Evaluation, Model},
     parameters::Parameters,
+    sandbox::run_sandbox,
     watcher::Watcher,
 };
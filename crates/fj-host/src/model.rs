@@ -1,21 +1,27 @@
 use std::{
+    collections::HashSet,
     io,
     path::{Path, PathBuf},
-    process::Command,
+    process::{Command, ExitStatus},
     str,
+    time::{Duration, Instant},
 };
 
 use fj::{abi, version::RawVersion};
 use tracing::warn;
 
-use crate::{platform::HostPlatform, Parameters};
+use crate::{platform::HostPlatform, process, sandbox, Parameters};
 
 /// Represents a Fornjot model
+#[derive(Clone)]
 pub struct Model {
-    src_path: PathBuf,
+    crate_dir: PathBuf,
     lib_path: PathBuf,
     manifest_path: PathBuf,
     parameters: Parameters,
+    preset: Option<String>,
+    sandbox: bool,
+    timeout: Option<Duration>,
 }
 
 impl Model {
@@ -36,7 +42,6 @@ impl Model {
             .exec()?;
 
         let pkg = package_associated_with_directory(&metadata, &crate_dir)?;
-        let src_path = crate_dir.join("src");
 
         let lib_path = {
             let name = pkg.name.replace('-', "_");
@@ -47,27 +52,107 @@ impl Model {
         };
 
         Ok(Self {
-            src_path,
+            crate_dir,
             lib_path,
             manifest_path: pkg.manifest_path.as_std_path().to_path_buf(),
             parameters,
+            preset: None,
+            sandbox: false,
+            timeout: None,
         })
     }
 
-    /// Access the path that needs to be watched for changes
-    pub fn watch_path(&self) -> PathBuf {
-        self.src_path.clone()
+    /// Evaluate the model in a separate process
+    ///
+    /// By default, the model library is loaded into the process that calls
+    /// [`Model::evaluate`]. A model that panics, aborts, or crashes takes down
+    /// that process with it. With the sandbox enabled, the model is loaded by a
+    /// helper process instead, which sends back the shape it creates. Should
+    /// the helper process crash, [`Model::evaluate`] returns an error.
+    ///
+    /// The helper process is started from the current executable, which must
+    /// call [`crate::run_sandbox`] at the start of its `main` function.
+    pub fn with_sandbox(self, sandbox: bool) -> Self {
+        Self { sandbox, ..self }
+    }
+
+    /// Use one of the model's presets, for parameters that aren't set otherwise
+    ///
+    /// Presets are declared by the model, and listed in its metadata (see
+    /// [`fj::models::ModelMetadata::presets`]). Parameters that are passed to
+    /// the model explicitly take precedence over the values of the preset.
+    pub fn with_preset(self, preset: Option<String>) -> Self {
+        Self { preset, ..self }
+    }
+
+    /// Stop evaluating the model, if it takes longer than `timeout`
+    ///
+    /// The timeout covers compiling and evaluating the model. If it elapses,
+    /// [`Error::TimedOut`] is returned. Code that runs in the current process
+    /// can't be stopped, so the timeout only covers the evaluation of a model
+    /// that runs in a separate process (see [`Model::with_sandbox`]).
+    pub fn with_timeout(self, timeout: Option<Duration>) -> Self {
+        Self { timeout, ..self }
+    }
+
+    /// Access the paths that need to be watched for changes
+    ///
+    /// These are the source directories of the model, and of all local
+    /// packages that it depends on, directly or indirectly. Packages can list
+    /// additional files or directories to watch in their `Cargo.toml`, relative
+    /// to the package:
+    ///
+    /// ```toml
+    /// [package.metadata.fornjot]
+    /// watch = ["assets"]
+    /// ```
+    ///
+    /// The `Cargo.toml` files of those packages are watched too. Since they can
+    /// change while the model is open, the paths are determined anew on every
+    /// call. Additional paths that don't exist are left out.
+    pub fn watch_paths(&self) -> Result<Vec<PathBuf>, Error> {
+        find_watch_paths(&self.crate_dir)
     }
 
     /// Evaluate the model
     pub fn evaluate(&self) -> Result<Evaluation, Error> {
+        self.evaluate_cancellable(&|| false)
+    }
+
+    /// Evaluate the model, unless the evaluation is cancelled
+    ///
+    /// `cancelled` is called regularly while the model is compiled and
+    /// evaluated. Once it returns `true`, compilation or evaluation is stopped,
+    /// and [`Error::Cancelled`] is returned. As with the timeout, a model that
+    /// is evaluated in the current process runs to completion, once its
+    /// compilation has finished (see [`Model::with_timeout`]).
+    pub fn evaluate_cancellable(
+        &self,
+        cancelled: &dyn Fn() -> bool,
+    ) -> Result<Evaluation, Error> {
+        let deadline = self
+            .timeout
+            .map(|timeout| (Instant::now() + timeout, timeout));
+        let interrupt = || {
+            if cancelled() {
+                return Err(Error::Cancelled);
+            }
+            if let Some((deadline, timeout)) = deadline {
+                if Instant::now() >= deadline {
+                    return Err(Error::TimedOut { timeout });
+                }
+            }
+            Ok(())
+        };
+
         let manifest_path = self.manifest_path.display().to_string();
 
-        let cargo_output = Command::new("cargo")
+        let mut command = Command::new("cargo");
+        command
             .arg("rustc")
             .args(["--manifest-path", &manifest_path])
-            .args(["--crate-type", "cdylib"])
-            .output()?;
+            .args(["--crate-type", "cdylib"]);
+        let cargo_output = process::run(command, &[], &interrupt)?;
 
         if !cargo_output.status.success() {
             let output =
@@ -85,81 +170,21 @@ impl Model {
             .1
             .trim();
 
-        // So, strictly speaking this is all unsound:
-        // - `Library::new` requires us to abide by the arbitrary requirements
-        //   of any library initialization or termination routines.
-        // - `Library::get` requires us to specify the correct type for the
-        //   model function.
-        // - The model function itself is `unsafe`, because it is a function
-        //   from across an FFI interface.
-        //
-        // Typical models won't have initialization or termination routines (I
-        // think), should abide by the `ModelFn` signature, and might not do
-        // anything unsafe. But we have no way to know that the library the user
-        // told us to load actually does (I think).
-        //
-        // I don't know of a way to fix this. We should take this as motivation
-        // to switch to a better technique:
-        // https://github.com/hannobraun/Fornjot/issues/71
-        let shape = unsafe {
-            let lib = libloading::Library::new(&self.lib_path)
-                .map_err(Error::LoadingLibrary)?;
-
-            if cfg!(target_os = "windows") {
-                warn!(
-                    "Version check is disabled on Windows (see \
-                    https://github.com/hannobraun/Fornjot/issues/1307)"
-                );
-            } else {
-                let version_pkg: libloading::Symbol<fn() -> RawVersion> =
-                    lib.get(b"version_pkg").map_err(Error::LoadingVersion)?;
-
-                let version_pkg = version_pkg().to_string();
-                if fj::version::VERSION_PKG != version_pkg {
-                    let host = String::from_utf8_lossy(
-                        fj::version::VERSION_PKG.as_bytes(),
-                    )
-                    .into_owned();
-                    let model = version_pkg;
-
-                    return Err(Error::VersionMismatch { host, model });
-                }
-
-                let version_full: libloading::Symbol<fn() -> RawVersion> =
-                    lib.get(b"version_full").map_err(Error::LoadingVersion)?;
-
-                let version_full = version_full().to_string();
-                if fj::version::VERSION_FULL != version_full {
-                    let host = String::from_utf8_lossy(
-                        fj::version::VERSION_FULL.as_bytes(),
-                    )
-                    .into_owned();
-                    let model = version_full;
-
-                    warn!("{}", Error::VersionMismatch { host, model });
-                }
-            }
-
-            let init: libloading::Symbol<abi::InitFunction> = lib
-                .get(abi::INIT_FUNCTION_NAME.as_bytes())
-                .map_err(Error::LoadingInit)?;
-
-            let mut host = Host::new(&self.parameters);
-
-            match init(&mut abi::Host::from(&mut host)) {
-                abi::ffi_safe::Result::Ok(_metadata) => {}
-                abi::ffi_safe::Result::Err(e) => {
-                    return Err(Error::InitializeModel(e.into()));
-                }
-            }
-
-            let model = host.take_model().ok_or(Error::NoModelRegistered)?;
-
-            model.shape(&host).map_err(Error::Shape)?
+        let (shape, metadata, model_metadata) = if self.sandbox {
+            sandbox::evaluate(
+                &self.lib_path,
+                &self.parameters,
+                self.preset.as_deref(),
+                &interrupt,
+            )?
+        } else {
+            load(&self.lib_path, &self.parameters, self.preset.as_deref())?
         };
 
         Ok(Evaluation {
             shape,
+            metadata,
+            model_metadata,
             compile_time: seconds_taken.into(),
         })
     }
@@ -172,6 +197,12 @@ pub struct Evaluation {
     /// The shape
     pub shape: fj::Shape,
 
+    /// The metadata of the model's package
+    pub metadata: fj::models::Metadata,
+
+    /// The metadata of the model itself, like its arguments and presets
+    pub model_metadata: fj::models::ModelMetadata,
+
     /// The time it took to compile the shape, from the Cargo output
     pub compile_time: String,
 }
@@ -206,6 +237,224 @@ impl<'a> fj::models::Context for Host<'a> {
     }
 }
 
+/// The shape of a model, the metadata of its package, and of the model itself
+pub(crate) type Loaded =
+    (fj::Shape, fj::models::Metadata, fj::models::ModelMetadata);
+
+/// Load the model library and evaluate the model in this process
+///
+/// This is what the out-of-process evaluation does too, in a separate process.
+/// See [`Model::with_sandbox`].
+pub(crate) fn load(
+    lib_path: &Path,
+    parameters: &Parameters,
+    preset: Option<&str>,
+) -> Result<Loaded, Error> {
+    // So, strictly speaking this is all unsound:
+    // - `Library::new` requires us to abide by the arbitrary requirements
+    //   of any library initialization or termination routines.
+    // - `Library::get` requires us to specify the correct type for the
+    //   model function.
+    // - The model function itself is `unsafe`, because it is a function
+    //   from across an FFI interface.
+    //
+    // Typical models won't have initialization or termination routines (I
+    // think), should abide by the `ModelFn` signature, and might not do
+    // anything unsafe. But we have no way to know that the library the user
+    // told us to load actually does (I think).
+    //
+    // I don't know of a way to fix this. We should take this as motivation
+    // to switch to a better technique:
+    // https://github.com/hannobraun/Fornjot/issues/71
+    //
+    // Until then, evaluating the model in a separate process (see
+    // `Model::with_sandbox`) at least keeps a misbehaving model from taking
+    // down the host with it.
+    unsafe {
+        let lib = libloading::Library::new(lib_path)
+            .map_err(Error::LoadingLibrary)?;
+
+        if cfg!(target_os = "windows") {
+            warn!(
+                "Version check is disabled on Windows (see \
+                https://github.com/hannobraun/Fornjot/issues/1307)"
+            );
+        } else {
+            let version_pkg: libloading::Symbol<fn() -> RawVersion> =
+                lib.get(b"version_pkg").map_err(Error::LoadingVersion)?;
+
+            let version_pkg = version_pkg().to_string();
+            if fj::version::VERSION_PKG != version_pkg {
+                let host = String::from_utf8_lossy(
+                    fj::version::VERSION_PKG.as_bytes(),
+                )
+                .into_owned();
+                let model = version_pkg;
+
+                return Err(Error::VersionMismatch { host, model });
+            }
+
+            let version_full: libloading::Symbol<fn() -> RawVersion> =
+                lib.get(b"version_full").map_err(Error::LoadingVersion)?;
+
+            let version_full = version_full().to_string();
+            if fj::version::VERSION_FULL != version_full {
+                let host = String::from_utf8_lossy(
+                    fj::version::VERSION_FULL.as_bytes(),
+                )
+                .into_owned();
+                let model = version_full;
+
+                warn!("{}", Error::VersionMismatch { host, model });
+            }
+        }
+
+        let init: libloading::Symbol<abi::InitFunction> = lib
+            .get(abi::INIT_FUNCTION_NAME.as_bytes())
+            .map_err(Error::LoadingInit)?;
+
+        let mut host = Host::new(parameters);
+
+        let metadata = match init(&mut abi::Host::from(&mut host)) {
+            abi::ffi_safe::Result::Ok(metadata) => metadata,
+            abi::ffi_safe::Result::Err(e) => {
+                return Err(model_error(
+                    e.into_error(),
+                    Error::InitializeModel,
+                ));
+            }
+        };
+
+        let model = host.take_model().ok_or(Error::NoModelRegistered)?;
+        let model_metadata = model.metadata();
+
+        let parameters = match preset {
+            Some(preset) => parameters.with_preset(&model_metadata, preset)?,
+            None => parameters.clone(),
+        };
+
+        // Check the parameters up front, so invalid values are reported with
+        // the name of the parameter, before any model code runs.
+        parameters.parse(&model_metadata)?;
+
+        let shape = model
+            .shape(&Host::new(&parameters))
+            .map_err(|err| model_error(err, Error::Shape))?;
+        Ok((shape, metadata.into(), model_metadata))
+    }
+}
+
+/// Convert an error from model code, keeping panics apart from other errors
+fn model_error(
+    err: fj::models::Error,
+    other: fn(fj::models::Error) -> Error,
+) -> Error {
+    match err.downcast::<fj::models::ModelPanic>() {
+        Ok(panic) => Error::ModelPanicked(*panic),
+        Err(err) => other(err),
+    }
+}
+
+/// Find the paths to watch for the package in `crate_dir`
+///
+/// See [`Model::watch_paths`].
+fn find_watch_paths(crate_dir: &Path) -> Result<Vec<PathBuf>, Error> {
+    let metadata = cargo_metadata::MetadataCommand::new()
+        .current_dir(crate_dir)
+        .exec()?;
+    let pkg = package_associated_with_directory(&metadata, crate_dir)?;
+
+    Ok(watch_paths(&metadata, pkg))
+}
+
+fn watch_paths(
+    metadata: &cargo_metadata::Metadata,
+    pkg: &cargo_metadata::Package,
+) -> Vec<PathBuf> {
+    let mut paths = Vec::new();
+
+    let mut visited = HashSet::new();
+    let mut packages = vec![&pkg.id];
+
+    while let Some(id) = packages.pop() {
+        if !visited.insert(id) {
+            continue;
+        }
+
+        let package = &metadata[id];
+
+        // Packages from a registry or a Git repository don't change, while the
+        // model is open.
+        if package.source.is_some() {
+            continue;
+        }
+
+        // Only libraries are compiled into the model. Other targets, build
+        // scripts in particular, might live in the root directory of the
+        // package, which is often where the target directory is too.
+        for target in &package.targets {
+            let is_lib = target
+                .kind
+                .iter()
+                .any(|kind| kind.ends_with("lib") || kind == "proc-macro");
+
+            if let (true, Some(dir)) = (is_lib, target.src_path.parent()) {
+                paths.push(dir.as_std_path().to_path_buf());
+            }
+        }
+
+        // Changes to the manifest can add dependencies, or paths to watch.
+        paths.push(package.manifest_path.as_std_path().to_path_buf());
+
+        let package_dir = package
+            .manifest_path
+            .parent()
+            .expect("A Cargo.toml always has a parent");
+        let assets = package.metadata["fornjot"]["watch"]
+            .as_array()
+            .into_iter()
+            .flatten();
+
+        for asset in assets {
+            let path = match asset.as_str() {
+                Some(asset) => package_dir.join(asset).into_std_path_buf(),
+                None => {
+                    warn!(
+                        "Ignoring non-string entry `{asset}` in \
+                        `package.metadata.fornjot.watch` of `{}`",
+                        package.name
+                    );
+                    continue;
+                }
+            };
+
+            if !path.exists() {
+                warn!(
+                    "Not watching `{}`, listed in `Cargo.toml` of `{}`, as it \
+                    doesn't exist",
+                    path.display(),
+                    package.name
+                );
+                continue;
+            }
+
+            paths.push(path);
+        }
+
+        let node = metadata.resolve.as_ref().and_then(|resolve| {
+            resolve.nodes.iter().find(|node| &node.id == id)
+        });
+        if let Some(node) = node {
+            packages.extend(&node.dependencies);
+        }
+    }
+
+    paths.sort();
+    paths.dedup();
+
+    paths
+}
+
 fn package_associated_with_directory<'m>(
     metadata: &'m cargo_metadata::Metadata,
     dir: &Path,
@@ -306,10 +555,71 @@ pub enum Error {
     #[error("No model was registered")]
     NoModelRegistered,
 
+    /// A parameter value doesn't match the type of the model's argument
+    #[error("Invalid value for parameter `{name}`")]
+    InvalidParameter {
+        /// The name of the parameter
+        name: String,
+
+        /// The error that occurred while parsing the value
+        #[source]
+        source: fj::models::ParameterError,
+    },
+
+    /// The model doesn't have the preset that was asked for
+    #[error("Model has no preset `{name}`{}", available_presets(presets))]
+    UnknownPreset {
+        /// The name of the preset
+        name: String,
+
+        /// The presets that the model has
+        presets: Vec<String>,
+    },
+
     /// An error was returned from [`fj::models::Model::shape()`].
     #[error("Unable to determine the model's geometry")]
     Shape(#[source] fj::models::Error),
 
+    /// The model panicked
+    #[error("{0}\n\nBacktrace:\n{}", .0.backtrace)]
+    ModelPanicked(fj::models::ModelPanic),
+
+    /// The model was evaluated in a separate process, which failed
+    #[error("{message}")]
+    Sandbox {
+        /// The error reported by the separate process
+        message: String,
+    },
+
+    /// The separate process that evaluated the model crashed
+    #[error("Model evaluation crashed ({status})\n{output}")]
+    SandboxCrashed {
+        /// The exit status of the process
+        status: ExitStatus,
+
+        /// The output of the process, first stdout, then stderr
+        output: String,
+    },
+
+    /// The separate process that evaluated the model sent an invalid response
+    #[error("Invalid response from model evaluation process")]
+    SandboxResponse(#[source] serde_json::Error),
+
+    /// The evaluation was cancelled
+    ///
+    /// See [`Model::evaluate_cancellable`].
+    #[error("Evaluation of the model was cancelled")]
+    Cancelled,
+
+    /// The evaluation took longer than the timeout
+    ///
+    /// See [`Model::with_timeout`].
+    #[error("Evaluation of the model timed out after {timeout:?}")]
+    TimedOut {
+        /// The timeout that elapsed
+        timeout: Duration,
+    },
+
     /// Error while watching the model code for changes
     #[error("Error watching model for changes")]
     Notify(#[from] notify::Error),
@@ -336,3 +646,98 @@ pub enum Error {
         possible_paths: Vec<PathBuf>,
     },
 }
+
+fn available_presets(presets: &[String]) -> String {
+    if presets.is_empty() {
+        return String::new();
+    }
+
+    let presets = presets
+        .iter()
+        .map(|preset| format!("`{preset}`"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!(" (available: {presets})")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, io, path::Path};
+
+    use crate::Parameters;
+
+    use super::{Error, Model};
+
+    #[test]
+    fn watch_paths() -> Result<(), Error> {
+        let dir = tempfile::tempdir()?;
+        let dir = dir.path().canonicalize()?;
+
+        package(&dir.join("helper"), "")?;
+        package(&dir.join("other"), "")?;
+        package(
+            &dir.join("model"),
+            r#"
+            [dependencies]
+            helper = { path = "../helper" }
+
+            [package.metadata.fornjot]
+            watch = ["assets", "missing"]
+            "#,
+        )?;
+        fs::create_dir(dir.join("model/assets"))?;
+
+        let model = Model::new(dir.join("model"), Parameters::empty())?;
+
+        // Paths that don't exist are left out.
+        assert_eq!(
+            model.watch_paths()?,
+            [
+                "helper/Cargo.toml",
+                "helper/src",
+                "model/Cargo.toml",
+                "model/assets",
+                "model/src",
+            ]
+            .map(|path| dir.join(path))
+        );
+
+        // Dependencies that are added later are watched too.
+        package(
+            &dir.join("model"),
+            r#"
+            [dependencies]
+            helper = { path = "../helper" }
+            other = { path = "../other" }
+            "#,
+        )?;
+        assert_eq!(
+            model.watch_paths()?,
+            [
+                "helper/Cargo.toml",
+                "helper/src",
+                "model/Cargo.toml",
+                "model/src",
+                "other/Cargo.toml",
+                "other/src",
+            ]
+            .map(|path| dir.join(path))
+        );
+
+        Ok(())
+    }
+
+    fn package(dir: &Path, manifest: &str) -> io::Result<()> {
+        let name = dir.file_name().unwrap().to_string_lossy();
+
+        fs::create_dir_all(dir.join("src"))?;
+        fs::write(dir.join("src/lib.rs"), "")?;
+        fs::write(
+            dir.join("Cargo.toml"),
+            format!(
+                "[package]\nname = \"{name}\"\nversion = \"0.1.0\"\n\
+                edition = \"2021\"\n{manifest}"
+            ),
+        )
+    }
+}
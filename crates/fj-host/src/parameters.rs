@@ -3,6 +3,11 @@ use std::{
     ops::{Deref, DerefMut},
 };
 
+use fj::models::{ModelMetadata, ParameterValue};
+use tracing::warn;
+
+use crate::Error;
+
 /// Parameters that are passed to a model.
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct Parameters(pub HashMap<String, String>);
@@ -23,6 +28,75 @@ impl Parameters {
         self.0.insert(key.into(), value.to_string());
         self
     }
+
+    /// Fill in the values of one of the model's presets
+    ///
+    /// Values that are already set take precedence over those of the preset.
+    pub fn with_preset(
+        &self,
+        metadata: &ModelMetadata,
+        name: &str,
+    ) -> Result<Self, Error> {
+        let preset = metadata
+            .presets
+            .iter()
+            .find(|preset| preset.name == name)
+            .ok_or_else(|| Error::UnknownPreset {
+                name: name.to_owned(),
+                presets: metadata
+                    .presets
+                    .iter()
+                    .map(|preset| preset.name.clone())
+                    .collect(),
+            })?;
+
+        let mut parameters = self.clone();
+        for (argument, value) in &preset.values {
+            parameters
+                .0
+                .entry(argument.clone())
+                .or_insert_with(|| value.clone());
+        }
+
+        Ok(parameters)
+    }
+
+    /// Parse the parameters according to the types of the model's arguments
+    ///
+    /// Parameters that don't match any of the model's arguments are ignored,
+    /// with a warning.
+    pub fn parse(
+        &self,
+        metadata: &ModelMetadata,
+    ) -> Result<HashMap<String, ParameterValue>, Error> {
+        let mut values = HashMap::new();
+
+        for (name, value) in &self.0 {
+            let argument =
+                metadata.arguments.iter().find(|arg| &arg.name == name);
+            let argument = match argument {
+                Some(argument) => argument,
+                None => {
+                    warn!(
+                        "Model `{}` has no parameter `{name}`",
+                        metadata.name
+                    );
+                    continue;
+                }
+            };
+
+            let value =
+                argument.parameter_type.parse(value).map_err(|source| {
+                    Error::InvalidParameter {
+                        name: name.clone(),
+                        source,
+                    }
+                })?;
+            values.insert(name.clone(), value);
+        }
+
+        Ok(values)
+    }
 }
 
 impl Deref for Parameters {
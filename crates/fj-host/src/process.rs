@@ -0,0 +1,221 @@
+//! Running child processes that can be interrupted
+
+use std::{
+    io::{self, Read, Write},
+    process::{Child, Command, Output, Stdio},
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+use crate::Error;
+
+/// How often a running process is checked for interruption
+const POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+/// Run a command to completion, feeding it `input`, and collect its output
+///
+/// `interrupt` is called regularly while the process runs. If it returns an
+/// error, the process is killed, and the error is returned. Any processes that
+/// it started, like the compiler that Cargo runs, are killed along with it.
+///
+/// On Unix, the process runs in its own process group to make that possible.
+/// As a consequence, it doesn't receive signals sent to the terminal's
+/// foreground process group, like the one sent by pressing Ctrl+C.
+pub(crate) fn run(
+    mut command: Command,
+    input: &[u8],
+    interrupt: &dyn Fn() -> Result<(), Error>,
+) -> Result<Output, Error> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt as _;
+        command.process_group(0);
+    }
+
+    let mut child = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    // The process could block on a full pipe, if its output isn't read while
+    // it runs. That includes while it's being fed its input.
+    let stdout = child.stdout.take().map(read_in_background);
+    let stderr = child.stderr.take().map(read_in_background);
+
+    // Dropping stdin closes it, so the process doesn't wait for more input. If
+    // the process exits without reading all of it, its exit status tells why.
+    if let Some(mut stdin) = child.stdin.take() {
+        match stdin.write_all(input) {
+            Err(err) if err.kind() != io::ErrorKind::BrokenPipe => {
+                kill(&mut child)?;
+                return Err(err.into());
+            }
+            _ => {}
+        }
+    }
+
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+
+        if let Err(err) = interrupt() {
+            kill(&mut child)?;
+            return Err(err);
+        }
+
+        thread::sleep(POLL_INTERVAL);
+    };
+
+    Ok(Output {
+        status,
+        stdout: join(stdout),
+        stderr: join(stderr),
+    })
+}
+
+/// Kill the process, and all processes it started
+fn kill(child: &mut Child) -> io::Result<()> {
+    // The process might have exited in the meantime, in which case killing it
+    // fails, and there's nothing left to do.
+
+    #[cfg(unix)]
+    {
+        // A negative ID addresses the process group that the process leads.
+        // See `run`.
+        let group = -(child.id() as libc::pid_t);
+
+        // `kill` has no preconditions that could make calling it unsound.
+        unsafe {
+            libc::kill(group, libc::SIGKILL);
+        }
+    }
+
+    #[cfg(windows)]
+    {
+        let _ = Command::new("taskkill")
+            .args(["/T", "/F", "/PID", &child.id().to_string()])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status();
+    }
+
+    let _ = child.kill();
+    child.wait()?;
+
+    Ok(())
+}
+
+fn read_in_background(
+    mut pipe: impl Read + Send + 'static,
+) -> JoinHandle<Vec<u8>> {
+    thread::spawn(move || {
+        let mut output = Vec::new();
+        // If reading fails, the output is incomplete, which the caller will
+        // notice when processing it.
+        let _ = pipe.read_to_end(&mut output);
+        output
+    })
+}
+
+fn join(output: Option<JoinHandle<Vec<u8>>>) -> Vec<u8> {
+    output
+        .map(|output| output.join().unwrap_or_default())
+        .unwrap_or_default()
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use std::{
+        cell::Cell,
+        env, fs,
+        process::{Command, Stdio},
+        thread,
+        time::{Duration, Instant},
+    };
+
+    use crate::Error;
+
+    use super::run;
+
+    #[test]
+    fn feed_input_larger_than_pipe_buffer() {
+        // `cat` writes its output while reading its input. If the output
+        // weren't read in the meantime, both processes would wait for each
+        // other.
+        let input = vec![b'a'; 1024 * 1024];
+        let output = run(Command::new("cat"), &input, &|| Ok(())).unwrap();
+
+        assert!(output.status.success());
+        assert_eq!(output.stdout, input);
+    }
+
+    #[test]
+    fn cancel() {
+        let calls = Cell::new(0);
+        let interrupt = || {
+            calls.set(calls.get() + 1);
+            if calls.get() > 2 {
+                return Err(Error::Cancelled);
+            }
+            Ok(())
+        };
+
+        let start = Instant::now();
+        let result = run(sleep(10), &[], &interrupt);
+
+        assert!(matches!(result, Err(Error::Cancelled)));
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn time_out_and_kill_started_processes() {
+        // The process starts another one, which would outlive it, if only the
+        // process itself were killed.
+        let pid_file = env::temp_dir()
+            .join(format!("fj-host-process-test-{}", std::process::id()));
+        let mut command = Command::new("sh");
+        command.args([
+            "-c",
+            &format!("sleep 10 & echo $! > {}; wait", pid_file.display()),
+        ]);
+
+        let timeout = Duration::from_millis(200);
+        let deadline = Instant::now() + timeout;
+        let interrupt = || {
+            if Instant::now() >= deadline {
+                return Err(Error::TimedOut { timeout });
+            }
+            Ok(())
+        };
+
+        let result = run(command, &[], &interrupt);
+        assert!(matches!(result, Err(Error::TimedOut { .. })));
+
+        let pid = fs::read_to_string(&pid_file).unwrap();
+        fs::remove_file(&pid_file).unwrap();
+
+        // The killed process is cleaned up by the system, which can take a
+        // moment.
+        let is_running = || {
+            Command::new("kill")
+                .args(["-0", pid.trim()])
+                .stderr(Stdio::null())
+                .status()
+                .unwrap()
+                .success()
+        };
+        let start = Instant::now();
+        while is_running() {
+            assert!(start.elapsed() < Duration::from_secs(5));
+            thread::sleep(Duration::from_millis(20));
+        }
+    }
+
+    fn sleep(seconds: u32) -> Command {
+        let mut command = Command::new("sleep");
+        command.arg(seconds.to_string());
+        command
+    }
+}
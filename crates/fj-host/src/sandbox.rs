@@ -0,0 +1,301 @@
+//! Evaluation of models in a separate process
+//!
+//! See [`crate::Model::with_sandbox`].
+
+use std::{
+    collections::HashMap,
+    env,
+    error::Error as _,
+    fmt::Write as _,
+    io::{self, Write as _},
+    path::{Path, PathBuf},
+    process::{self, Command},
+};
+
+use tracing::{info, warn};
+
+use crate::{
+    model::{self, Loaded},
+    process::run,
+    Error, Parameters,
+};
+
+/// Marks a process as the helper process that evaluates a model
+const SANDBOX_VAR: &str = "FJ_HOST_SANDBOX";
+
+/// Evaluate a model in a helper process, if this process is one
+///
+/// Applications that use [`crate::Model::with_sandbox`] must call this at the
+/// start of their `main` function. Usually, this returns right away. But in a
+/// helper process, it reads a request from stdin, evaluates the model, writes
+/// the result to stdout, and exits the process.
+pub fn run_sandbox() {
+    if env::var_os(SANDBOX_VAR).is_none() {
+        return;
+    }
+
+    let response = serve();
+
+    // Start a new line, in case the model printed something that didn't end
+    // with one.
+    let mut stdout = io::stdout().lock();
+    let written = writeln!(stdout)
+        .and_then(|()| {
+            serde_json::to_writer(&mut stdout, &response)
+                .map_err(io::Error::from)
+        })
+        .and_then(|()| writeln!(stdout))
+        .and_then(|()| stdout.flush());
+
+    let code = match written {
+        Ok(()) => 0,
+        Err(err) => {
+            eprintln!("Failed to send evaluated model: {err}");
+            1
+        }
+    };
+    process::exit(code);
+}
+
+/// Evaluate the model in a helper process
+///
+/// Spawns the current executable as the helper process. See [`run_sandbox`].
+///
+/// `interrupt` is called regularly while the helper process runs. If it
+/// returns an error, the process is killed.
+pub(crate) fn evaluate(
+    lib_path: &Path,
+    parameters: &Parameters,
+    preset: Option<&str>,
+    interrupt: &dyn Fn() -> Result<(), Error>,
+) -> Result<Loaded, Error> {
+    let request = Request {
+        lib_path: lib_path.to_path_buf(),
+        parameters: parameters.0.clone(),
+        preset: preset.map(ToOwned::to_owned),
+    };
+    let mut command = Command::new(env::current_exe()?);
+    command.env(SANDBOX_VAR, "1");
+
+    exchange(command, &request, interrupt)
+}
+
+/// Send the request to the helper process that `command` starts, and receive
+/// the evaluated model
+///
+/// Anything else that the process outputs is logged. If the process fails, it
+/// is part of the error instead.
+fn exchange(
+    command: Command,
+    request: &Request,
+    interrupt: &dyn Fn() -> Result<(), Error>,
+) -> Result<Loaded, Error> {
+    let request = serde_json::to_vec(request).map_err(io::Error::from)?;
+    let output = run(command, &request, interrupt)?;
+
+    // The model might print to stdout too. The response is on the last line.
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let (model_output, response) = match stdout.trim_end().rsplit_once('\n') {
+        Some((model_output, response)) => (model_output, response),
+        None => ("", stdout.trim_end()),
+    };
+
+    if !output.status.success() {
+        return Err(Error::SandboxCrashed {
+            status: output.status,
+            output: format!("{stdout}{stderr}"),
+        });
+    }
+
+    let model_output = model_output.trim_end();
+    if !model_output.is_empty() {
+        info!("{model_output}");
+    }
+    let stderr = stderr.trim_end();
+    if !stderr.is_empty() {
+        warn!("{stderr}");
+    }
+
+    let response: Result<Response, Failure> =
+        serde_json::from_str(response).map_err(Error::SandboxResponse)?;
+
+    match response {
+        Ok(Response {
+            shape,
+            metadata,
+            model_metadata,
+        }) => Ok((shape, metadata, model_metadata)),
+        Err(Failure::Panicked(panic)) => Err(Error::ModelPanicked(panic)),
+        Err(Failure::Error(message)) => Err(Error::Sandbox { message }),
+    }
+}
+
+fn serve() -> Result<Response, Failure> {
+    let request: Request = serde_json::from_reader(io::stdin().lock())
+        .map_err(|err| {
+            Failure::Error(format!(
+                "Failed to receive model to evaluate: {err}"
+            ))
+        })?;
+
+    let Request {
+        lib_path,
+        parameters,
+        preset,
+    } = request;
+
+    let parameters = Parameters(parameters);
+    let load = model::load(&lib_path, &parameters, preset.as_deref());
+    let (shape, metadata, model_metadata) = load.map_err(|err| {
+        if let Error::ModelPanicked(panic) = err {
+            return Failure::Panicked(panic);
+        }
+
+        // The error can't be sent as it is, so include its sources in the
+        // message.
+        let mut message = err.to_string();
+        let mut source = err.source();
+        while let Some(err) = source {
+            let _ = write!(message, "\n- {err}");
+            source = err.source();
+        }
+        Failure::Error(message)
+    })?;
+
+    Ok(Response {
+        shape,
+        metadata,
+        model_metadata,
+    })
+}
+
+#[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+struct Request {
+    lib_path: PathBuf,
+    parameters: HashMap<String, String>,
+    preset: Option<String>,
+}
+
+#[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+struct Response {
+    shape: fj::Shape,
+    metadata: fj::models::Metadata,
+    model_metadata: fj::models::ModelMetadata,
+}
+
+#[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+enum Failure {
+    Panicked(fj::models::ModelPanic),
+    Error(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, path::PathBuf};
+
+    use crate::Error;
+
+    use super::{Failure, Loaded, Request, Response};
+
+    #[test]
+    fn protocol_round_trip() {
+        let request = Request {
+            lib_path: PathBuf::from("target/debug/libmodel.so"),
+            parameters: HashMap::from([("radius".into(), "1.5".into())]),
+            preset: Some("large".into()),
+        };
+        let json = serde_json::to_string(&request).unwrap();
+        assert_eq!(serde_json::from_str::<Request>(&json).unwrap(), request);
+
+        let responses: [Result<Response, Failure>; 2] = [
+            Ok(response()),
+            Err(Failure::Error("Failed to load model".into())),
+        ];
+        for response in responses {
+            let json = serde_json::to_string(&response).unwrap();
+            let received: Result<Response, Failure> =
+                serde_json::from_str(&json).unwrap();
+            assert_eq!(received, response);
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn receive_response_after_model_output() {
+        let response: Result<Response, Failure> = Ok(response());
+        let json = serde_json::to_string(&response).unwrap();
+
+        let (shape, metadata, model_metadata) = exchange(&format!(
+            "cat > /dev/null; echo 'model output'; echo '{json}'"
+        ))
+        .unwrap();
+        let received = Response {
+            shape,
+            metadata,
+            model_metadata,
+        };
+        assert_eq!(Ok(received), response);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn report_error_of_helper_process() {
+        let response: Result<Response, Failure> =
+            Err(Failure::Error("Failed to load model".into()));
+        let json = serde_json::to_string(&response).unwrap();
+
+        let err = exchange(&format!("cat > /dev/null; echo '{json}'"));
+        assert!(matches!(
+            err,
+            Err(Error::Sandbox { message }) if message == "Failed to load model"
+        ));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn report_output_of_crashed_helper_process() {
+        let err = exchange(
+            "cat > /dev/null; echo 'model output'; echo 'crashed' >&2; exit 3",
+        );
+
+        match err {
+            Err(Error::SandboxCrashed { status, output }) => {
+                assert_eq!(status.code(), Some(3));
+                assert_eq!(output, "model output\ncrashed\n");
+            }
+            _ => panic!("Expected crash to be reported"),
+        }
+    }
+
+    /// Exchange a request with a shell script, that acts as the helper process
+    #[cfg(unix)]
+    fn exchange(script: &str) -> Result<Loaded, Error> {
+        let request = Request {
+            lib_path: PathBuf::from("target/debug/libmodel.so"),
+            parameters: HashMap::new(),
+            preset: None,
+        };
+
+        let mut command = std::process::Command::new("sh");
+        command.args(["-c", script]);
+
+        super::exchange(command, &request, &|| Ok(()))
+    }
+
+    fn response() -> Response {
+        let sketch =
+            fj::Sketch::from_points(vec![[0., 0.], [1., 0.], [0., 1.]]);
+
+        Response {
+            shape: sketch.into(),
+            metadata: fj::models::Metadata::new("model", "0.1.0"),
+            model_metadata: fj::models::ModelMetadata::new("model")
+                .with_argument("width")
+                .with_preset(
+                    fj::models::PresetMetadata::new("small")
+                        .with_value("width", "1"),
+                ),
+        }
+    }
+}
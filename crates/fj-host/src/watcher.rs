@@ -1,26 +1,45 @@
-use std::{collections::HashSet, ffi::OsStr, path::Path, thread};
+use std::{
+    collections::HashSet,
+    ffi::OsStr,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+    thread,
+    time::Duration,
+};
 
+use crossbeam_channel::SendError;
 use notify::Watcher as _;
+use tracing::warn;
 
-use crate::{evaluator::TriggerEvaluation, Error, Evaluator};
+use crate::{evaluator::TriggerEvaluation, Error, Evaluator, Model};
+
+/// How long to wait for more changes, before triggering an evaluation
+///
+/// Editors and build tools often write several files at once, or the same file
+/// several times in a row.
+const DEBOUNCE_INTERVAL: Duration = Duration::from_millis(100);
 
 /// Watches a model for changes, reloading it continually
 pub struct Watcher {
-    _watcher: Box<dyn notify::Watcher>,
+    _watched: Arc<Mutex<WatchedPaths>>,
 }
 
 impl Watcher {
-    /// Watch the provided model for changes
+    /// Watch the paths of a model for changes
+    ///
+    /// See [`Model::watch_paths`]. After every change, the paths are determined
+    /// anew, so paths that the model depends on later are watched too. Paths
+    /// that can't be watched are skipped, with a warning.
     pub fn watch_model(
-        watch_path: impl AsRef<Path>,
+        model: Model,
         evaluator: &Evaluator,
     ) -> Result<Self, Error> {
-        let watch_path = watch_path.as_ref();
-
         let watch_tx = evaluator.trigger();
         let watch_tx_2 = evaluator.trigger();
 
-        let mut watcher = notify::recommended_watcher(
+        let (change_tx, change_rx) = crossbeam_channel::unbounded();
+
+        let watcher = notify::recommended_watcher(
             move |event: notify::Result<notify::Event>| {
                 // Unfortunately the `notify` documentation doesn't say when
                 // this might happen, so no idea if it needs to be handled.
@@ -44,7 +63,7 @@ impl Watcher {
                 {
                     let file_ext = event
                         .paths
-                        .get(0)
+                        .first()
                         .expect("File path missing in watch event")
                         .extension();
 
@@ -65,14 +84,49 @@ impl Watcher {
                     // application is being shut down.
                     //
                     // Either way, not much we can do about it here.
-                    watch_tx
-                        .send(TriggerEvaluation)
-                        .expect("Channel is disconnected");
+                    change_tx.send(()).expect("Channel is disconnected");
                 }
             },
         )?;
 
-        watcher.watch(watch_path, notify::RecursiveMode::Recursive)?;
+        let mut watched = WatchedPaths {
+            watcher,
+            paths: Vec::new(),
+        };
+        watched.update(model.watch_paths()?);
+
+        let watched = Arc::new(Mutex::new(watched));
+        let watched_2 = Arc::downgrade(&watched);
+
+        // Changes are collected until none have come in for a while, and only
+        // then is the evaluation triggered.
+        thread::spawn(move || {
+            while let Ok(()) = change_rx.recv() {
+                while let Ok(()) = change_rx.recv_timeout(DEBOUNCE_INTERVAL) {}
+
+                // The change might have added dependencies to the model, and
+                // with them, paths to watch. If the `Watcher` has been
+                // dropped, there's nothing left to do.
+                let watched = match watched_2.upgrade() {
+                    Some(watched) => watched,
+                    None => break,
+                };
+                match model.watch_paths() {
+                    Ok(paths) => watched
+                        .lock()
+                        .expect("Watcher panicked while updating paths")
+                        .update(paths),
+                    Err(err) => {
+                        warn!("Failed to update the paths to watch: {err}")
+                    }
+                }
+                drop(watched);
+
+                if let Err(SendError(_)) = watch_tx.send(TriggerEvaluation) {
+                    break;
+                }
+            }
+        });
 
         // To prevent a race condition between the initial load and the start of
         // watching, we'll trigger the initial load here, after having started
@@ -89,8 +143,57 @@ impl Watcher {
                 .expect("Channel is disconnected")
         });
 
-        Ok(Self {
-            _watcher: Box::new(watcher),
-        })
+        Ok(Self { _watched: watched })
+    }
+}
+
+/// The paths that are currently being watched
+struct WatchedPaths {
+    watcher: notify::RecommendedWatcher,
+    paths: Vec<PathBuf>,
+}
+
+impl WatchedPaths {
+    /// Watch the provided paths, instead of the current ones
+    ///
+    /// All paths are watched anew, as a watch might not survive the path being
+    /// replaced, which is how some editors save files.
+    fn update(&mut self, paths: Vec<PathBuf>) {
+        for path in self.paths.drain(..) {
+            // The path might not exist anymore, in which case it isn't watched
+            // anyway.
+            let _ = self.watcher.unwatch(&path);
+        }
+
+        for path in paths {
+            if let Err(err) =
+                self.watcher.watch(&path, notify::RecursiveMode::Recursive)
+            {
+                warn!("Not watching `{}` for changes: {err}", path.display());
+                continue;
+            }
+
+            self.paths.push(path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::WatchedPaths;
+
+    #[test]
+    fn skip_paths_that_cant_be_watched() {
+        let dir = tempfile::tempdir().unwrap();
+        let existing = dir.path().to_path_buf();
+        let missing = dir.path().join("missing");
+
+        let mut watched = WatchedPaths {
+            watcher: notify::recommended_watcher(|_| {}).unwrap(),
+            paths: Vec::new(),
+        };
+        watched.update(vec![missing, existing.clone()]);
+
+        assert_eq!(watched.paths, [existing]);
     }
 }
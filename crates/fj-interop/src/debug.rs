@@ -11,6 +11,9 @@ use fj_math::{Point, Segment};
 pub struct DebugInfo {
     /// Rays being used during face triangulation
     pub triangle_edge_checks: Vec<TriangleEdgeCheck>,
+
+    /// Issues found while validating the shape
+    pub validation_issues: Vec<ValidationIssue>,
 }
 
 impl DebugInfo {
@@ -26,6 +29,13 @@ impl DebugInfo {
     /// allocations.
     pub fn clear(&mut self) {
         self.triangle_edge_checks.clear();
+        self.validation_issues.clear();
+    }
+
+    /// Add all information from another instance to this one
+    pub fn extend(&mut self, other: Self) {
+        self.triangle_edge_checks.extend(other.triangle_edge_checks);
+        self.validation_issues.extend(other.validation_issues);
     }
 }
 
@@ -48,3 +58,13 @@ impl TriangleEdgeCheck {
         }
     }
 }
+
+/// An issue found while validating a shape
+#[derive(Clone, Debug)]
+pub struct ValidationIssue {
+    /// A one-line description of the issue
+    pub message: String,
+
+    /// Points that can be used to highlight the objects the issue refers to
+    pub points: Vec<Point<3>>,
+}
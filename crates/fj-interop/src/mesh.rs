@@ -1,8 +1,12 @@
 //! A triangle mesh
 
-use std::{collections::HashMap, hash::Hash};
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap},
+    hash::Hash,
+};
 
-use fj_math::Point;
+use fj_math::{Point, Scalar, Vector};
 
 /// A triangle mesh
 #[derive(Clone, Debug)]
@@ -89,6 +93,191 @@ impl Mesh<Point<3>> {
             color,
         });
     }
+
+    /// Create a copy of the mesh, with nearby vertices merged
+    ///
+    /// Every vertex is replaced by the first vertex that is closer to it than
+    /// `distance`. Triangles that degenerate as a result are left out.
+    ///
+    /// No vertices are closer than a `distance` of zero or less, so the mesh is
+    /// returned unchanged in that case.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if `distance` is infinite.
+    pub fn welded(&self, distance: impl Into<Scalar>) -> Self {
+        let distance = distance.into();
+        assert!(
+            distance.into_f64().is_finite(),
+            "Weld distance must be finite"
+        );
+
+        if !distance.is_positive() {
+            return self.clone();
+        }
+
+        // The vertices are sorted into cells of a grid. Vertices that are
+        // close enough to be merged are in the same or in neighboring cells.
+        let cell_of = |point: Point<3>| {
+            point
+                .coords
+                .components
+                .map(|c| (c / distance).floor().into_f64() as i64)
+        };
+        let mut cells: HashMap<[i64; 3], Vec<Point<3>>> = HashMap::new();
+
+        let mut weld = |point: Point<3>| {
+            let [x, y, z] = cell_of(point);
+
+            // The cell coordinates saturate for very small distances, so the
+            // neighbors have to be computed without overflowing.
+            let around = |c: i64| c.saturating_sub(1)..=c.saturating_add(1);
+            for neighbor in around(x).flat_map(|x| {
+                around(y).flat_map(move |y| around(z).map(move |z| [x, y, z]))
+            }) {
+                let merged = cells.get(&neighbor).and_then(|points| {
+                    points
+                        .iter()
+                        .find(|other| other.distance_to(&point) < distance)
+                });
+                if let Some(merged) = merged {
+                    return *merged;
+                }
+            }
+
+            cells.entry([x, y, z]).or_default().push(point);
+            point
+        };
+
+        let mut mesh = Self::new();
+
+        for triangle in self.triangles() {
+            let points = triangle.inner.points().map(&mut weld);
+
+            if let Ok(welded) = fj_math::Triangle::from_points(points) {
+                mesh.push_triangle(welded, triangle.color);
+            }
+        }
+
+        mesh
+    }
+
+    /// Compute smooth normals for the corners of all triangles
+    ///
+    /// Returns the normals of the three corners of each triangle, in the order
+    /// of [`Mesh::triangles`]. The normal at a corner is the average of the
+    /// normals of all triangles that share the corner, weighted by their area.
+    ///
+    /// Only triangles that meet at an angle of up to `crease_angle` (in
+    /// radians) are averaged. This keeps approximated curved surfaces smooth,
+    /// while corners on sharp edges get a separate normal for each side.
+    pub fn smooth_normals(
+        &self,
+        crease_angle: impl Into<Scalar>,
+    ) -> Vec<[Vector<3>; 3]> {
+        let min_cos = crease_angle.into().cos();
+
+        // The length of these normals is twice the area of their triangle.
+        let normals = self
+            .triangles
+            .iter()
+            .map(|triangle| {
+                let [a, b, c] = triangle.inner.points();
+                (b - a).cross(&(c - a))
+            })
+            .collect::<Vec<_>>();
+
+        let mut triangles_by_point: HashMap<Point<3>, Vec<usize>> =
+            HashMap::new();
+        for (i, triangle) in self.triangles.iter().enumerate() {
+            for point in triangle.inner.points() {
+                triangles_by_point.entry(point).or_default().push(i);
+            }
+        }
+
+        self.triangles
+            .iter()
+            .zip(&normals)
+            .map(|(triangle, normal)| {
+                let normal = normal.normalize();
+
+                triangle.inner.points().map(|point| {
+                    let sum = triangles_by_point[&point]
+                        .iter()
+                        .map(|&other| normals[other])
+                        .filter(|other| {
+                            other.normalize().dot(&normal) >= min_cos
+                        })
+                        .fold(Vector::from([0., 0., 0.]), |sum, other| {
+                            sum + other
+                        });
+
+                    if sum.magnitude() == Scalar::ZERO {
+                        normal
+                    } else {
+                        sum.normalize()
+                    }
+                })
+            })
+            .collect()
+    }
+
+    /// Create a simplified copy of the mesh, with `max_triangles` triangles
+    ///
+    /// Edges are collapsed, one after the other, starting with the edge whose
+    /// collapse changes the shape of the mesh the least. Open boundaries and
+    /// borders between colors are kept in place, as far as possible.
+    ///
+    /// Edges are never collapsed, if that would flip a triangle or make the
+    /// mesh non-manifold. The simplified mesh can have more triangles than
+    /// requested, if no more edges can be collapsed.
+    pub fn decimated(&self, max_triangles: usize) -> Self {
+        let mut decimation = Decimation::new(self);
+        decimation.run(max_triangles);
+        decimation.into_mesh()
+    }
+
+    /// Split the mesh into its connected components
+    ///
+    /// Triangles are connected, if they share a vertex. The components are in
+    /// the order of their first triangle, and keep the order of the triangles.
+    pub fn components(&self) -> Vec<Self> {
+        let mut parents = (0..self.vertices.len()).collect::<Vec<_>>();
+
+        fn root(parents: &mut [usize], mut vertex: usize) -> usize {
+            while parents[vertex] != vertex {
+                parents[vertex] = parents[parents[vertex]];
+                vertex = parents[vertex];
+            }
+            vertex
+        }
+
+        for indices in self.indices.chunks(3) {
+            let a = root(&mut parents, indices[0] as usize);
+            for &index in &indices[1..] {
+                let b = root(&mut parents, index as usize);
+                parents[b] = a;
+            }
+        }
+
+        let mut components = Vec::new();
+        let mut components_by_root = HashMap::new();
+
+        for (triangle, indices) in
+            self.triangles.iter().zip(self.indices.chunks(3))
+        {
+            let root = root(&mut parents, indices[0] as usize);
+            let component =
+                *components_by_root.entry(root).or_insert_with(|| {
+                    components.push(Self::new());
+                    components.len() - 1
+                });
+
+            components[component].push_triangle(triangle.inner, triangle.color);
+        }
+
+        components
+    }
 }
 
 // This needs to be a manual implementation. Deriving `Default` would require
@@ -104,6 +293,441 @@ impl<V> Default for Mesh<V> {
     }
 }
 
+/// A tree of triangle meshes
+///
+/// Reflects how a model is made up of groups of shapes. Each member of a group
+/// that isn't a group itself has its own mesh.
+#[derive(Clone, Debug)]
+pub enum MeshTree {
+    /// The mesh of a single shape
+    Mesh(Mesh<Point<3>>),
+
+    /// A group of shapes
+    Group(Vec<MeshTree>),
+}
+
+impl MeshTree {
+    /// Access the meshes of all shapes in the tree, in depth-first order
+    pub fn meshes(&self) -> Vec<&Mesh<Point<3>>> {
+        match self {
+            Self::Mesh(mesh) => vec![mesh],
+            Self::Group(members) => {
+                members.iter().flat_map(|member| member.meshes()).collect()
+            }
+        }
+    }
+
+    /// Create a tree of the same shape, with every mesh transformed by `f`
+    pub fn map(&self, f: &impl Fn(&Mesh<Point<3>>) -> Mesh<Point<3>>) -> Self {
+        match self {
+            Self::Mesh(mesh) => Self::Mesh(f(mesh)),
+            Self::Group(members) => Self::Group(
+                members.iter().map(|member| member.map(f)).collect(),
+            ),
+        }
+    }
+
+    /// Merge all meshes in the tree into a single one
+    pub fn merged(&self) -> Mesh<Point<3>> {
+        let mut merged = Mesh::new();
+
+        for mesh in self.meshes() {
+            for triangle in mesh.triangles() {
+                merged.push_triangle(triangle.inner, triangle.color);
+            }
+        }
+
+        merged
+    }
+}
+
+/// Simplification of a mesh by collapsing edges
+///
+/// Based on "Surface Simplification Using Quadric Error Metrics", by Michael
+/// Garland and Paul S. Heckbert. To keep things simple, an edge collapses into
+/// one of its end points or its midpoint, whichever has the smallest error.
+struct Decimation {
+    positions: Vec<[f64; 3]>,
+    quadrics: Vec<Quadric>,
+
+    /// Incremented whenever a vertex changes, which outdates queued collapses
+    versions: Vec<u32>,
+    removed: Vec<bool>,
+
+    /// The triangles that each vertex is a part of
+    ///
+    /// Can contain triangles that have since been removed.
+    triangles_of_vertex: Vec<Vec<usize>>,
+    triangles: Vec<Option<([usize; 3], Color)>>,
+    num_triangles: usize,
+
+    queue: BinaryHeap<(Reverse<Scalar>, usize, usize, [u32; 2])>,
+}
+
+impl Decimation {
+    /// How much more an open boundary resists collapsing than a surface
+    const BOUNDARY_WEIGHT: f64 = 1000.;
+
+    fn new(mesh: &Mesh<Point<3>>) -> Self {
+        let positions = mesh
+            .vertices
+            .iter()
+            .map(|vertex| vertex.coords.components.map(|s| s.into_f64()))
+            .collect::<Vec<_>>();
+        let triangles = mesh
+            .indices
+            .chunks(3)
+            .zip(&mesh.triangles)
+            .map(|(indices, triangle)| {
+                let indices = [0, 1, 2].map(|i| indices[i] as usize);
+                Some((indices, triangle.color))
+            })
+            .collect::<Vec<_>>();
+
+        let mut quadrics = vec![[0.; 10]; positions.len()];
+        let mut triangles_of_vertex = vec![Vec::new(); positions.len()];
+        let mut triangles_of_edge: HashMap<[usize; 2], Vec<usize>> =
+            HashMap::new();
+
+        for (i, triangle) in triangles.iter().enumerate() {
+            let (indices, _) = triangle.expect("No triangle removed yet");
+            let [a, b, c] = indices.map(|index| positions[index]);
+            let normal = cross(sub(b, a), sub(c, a));
+            let area = dot(normal, normal).sqrt() / 2.;
+
+            if let Some(normal) = normalize(normal) {
+                let quadric = Self::quadric(normal, a, area);
+                for index in indices {
+                    add(&mut quadrics[index], &quadric);
+                }
+            }
+
+            for (j, &index) in indices.iter().enumerate() {
+                triangles_of_vertex[index].push(i);
+
+                let other = indices[(j + 1) % 3];
+                triangles_of_edge
+                    .entry([index.min(other), index.max(other)])
+                    .or_default()
+                    .push(i);
+            }
+        }
+
+        // Edges that are not shared by exactly two triangles of the same
+        // color are constrained by a plane that is perpendicular to their
+        // triangle.
+        for (&[a, b], adjacent) in &triangles_of_edge {
+            let is_border = match adjacent.as_slice() {
+                [t, u] => {
+                    triangles[*t].map(|t| t.1) != triangles[*u].map(|u| u.1)
+                }
+                _ => true,
+            };
+            if !is_border {
+                continue;
+            }
+
+            let (indices, _) = triangles[adjacent[0]].expect("Triangle exists");
+            let [p, q, r] = indices.map(|index| positions[index]);
+            let edge = sub(positions[b], positions[a]);
+
+            if let Some(normal) =
+                normalize(cross(edge, cross(sub(q, p), sub(r, p))))
+            {
+                let weight = Self::BOUNDARY_WEIGHT * dot(edge, edge);
+                let quadric = Self::quadric(normal, positions[a], weight);
+                add(&mut quadrics[a], &quadric);
+                add(&mut quadrics[b], &quadric);
+            }
+        }
+
+        let num_vertices = positions.len();
+        let mut decimation = Self {
+            positions,
+            quadrics,
+            versions: vec![0; num_vertices],
+            removed: vec![false; num_vertices],
+            triangles_of_vertex,
+            num_triangles: triangles.len(),
+            triangles,
+            queue: BinaryHeap::new(),
+        };
+
+        for [a, b] in triangles_of_edge.into_keys() {
+            decimation.enqueue(a, b);
+        }
+
+        decimation
+    }
+
+    fn run(&mut self, max_triangles: usize) {
+        while self.num_triangles > max_triangles {
+            let (a, b, versions) = match self.queue.pop() {
+                Some((_, a, b, versions)) => (a, b, versions),
+                None => break,
+            };
+
+            let is_outdated = self.removed[a]
+                || self.removed[b]
+                || versions != [self.versions[a], self.versions[b]];
+            if is_outdated {
+                continue;
+            }
+
+            let (_, position) = self.collapse_target(a, b);
+            if self.can_collapse(a, b, position) {
+                self.collapse(a, b, position);
+            }
+        }
+    }
+
+    fn into_mesh(self) -> Mesh<Point<3>> {
+        let mut mesh = Mesh::new();
+
+        for (indices, color) in self.triangles.into_iter().flatten() {
+            let points =
+                indices.map(|index| Point::from(self.positions[index]));
+
+            // Rounding can leave triangles without area.
+            if let Ok(triangle) = fj_math::Triangle::from_points(points) {
+                mesh.push_triangle(triangle, color);
+            }
+        }
+
+        mesh
+    }
+
+    fn enqueue(&mut self, a: usize, b: usize) {
+        let (error, _) = self.collapse_target(a, b);
+        let versions = [self.versions[a], self.versions[b]];
+
+        self.queue
+            .push((Reverse(Scalar::from_f64(error)), a, b, versions));
+    }
+
+    /// Find the position that an edge collapses into, and its error
+    fn collapse_target(&self, a: usize, b: usize) -> (f64, [f64; 3]) {
+        let mut quadric = self.quadrics[a];
+        add(&mut quadric, &self.quadrics[b]);
+
+        let [p, q] = [self.positions[a], self.positions[b]];
+        let midpoint = [0, 1, 2].map(|i| (p[i] + q[i]) / 2.);
+
+        [p, q, midpoint]
+            .into_iter()
+            .map(|position| (Self::error(&quadric, position), position))
+            .min_by(|(x, _), (y, _)| x.total_cmp(y))
+            .expect("Candidates are not empty")
+    }
+
+    fn can_collapse(&self, a: usize, b: usize, position: [f64; 3]) -> bool {
+        // The link condition: the vertices that are adjacent to both ends of
+        // the edge must be exactly those opposite the edge. Otherwise the
+        // collapse would make the mesh non-manifold.
+        let neighbors_of_a = self.neighbors(a);
+        let neighbors_of_b = self.neighbors(b);
+        let num_shared = neighbors_of_a
+            .iter()
+            .filter(|vertex| neighbors_of_b.contains(vertex))
+            .count();
+        let opposite = self
+            .live_triangles(a)
+            .filter(|(indices, _)| indices.contains(&b))
+            .flat_map(|(indices, _)| *indices)
+            .filter(|&vertex| vertex != a && vertex != b)
+            .collect::<Vec<_>>();
+        if num_shared != opposite.len() {
+            return false;
+        }
+
+        // An edge that connects two borders would pinch the mesh together.
+        let is_border_vertex = |vertex| {
+            self.neighbors(vertex)
+                .into_iter()
+                .any(|neighbor| self.is_border(vertex, neighbor))
+        };
+        if is_border_vertex(a) && is_border_vertex(b) && !self.is_border(a, b) {
+            return false;
+        }
+
+        // A vertex that is surrounded by three triangles would be left with
+        // two, which would be back to back.
+        for vertex in opposite {
+            let num_triangles = self.live_triangles(vertex).count();
+            let is_surrounded = num_triangles == self.neighbors(vertex).len();
+
+            if is_surrounded && num_triangles <= 3 {
+                return false;
+            }
+        }
+
+        // The triangles that remain must not be flipped.
+        for vertex in [a, b] {
+            for (indices, _) in self.live_triangles(vertex) {
+                if indices.contains(&a) && indices.contains(&b) {
+                    continue;
+                }
+
+                let before = indices.map(|index| self.positions[index]);
+                let after = indices.map(|index| {
+                    if index == a || index == b {
+                        position
+                    } else {
+                        self.positions[index]
+                    }
+                });
+
+                let [before, after] = [before, after]
+                    .map(|[p, q, r]| cross(sub(q, p), sub(r, p)));
+                if dot(before, after) <= 0. {
+                    return false;
+                }
+            }
+        }
+
+        true
+    }
+
+    /// Collapse the edge from `a` to `b`, keeping `a`
+    fn collapse(&mut self, a: usize, b: usize, position: [f64; 3]) {
+        self.positions[a] = position;
+        let quadric = self.quadrics[b];
+        add(&mut self.quadrics[a], &quadric);
+
+        for triangle in std::mem::take(&mut self.triangles_of_vertex[b]) {
+            let (indices, _) = match &mut self.triangles[triangle] {
+                Some(triangle) => triangle,
+                None => continue,
+            };
+
+            if indices.contains(&a) {
+                self.triangles[triangle] = None;
+                self.num_triangles -= 1;
+                continue;
+            }
+
+            for index in indices.iter_mut() {
+                if *index == b {
+                    *index = a;
+                }
+            }
+            self.triangles_of_vertex[a].push(triangle);
+        }
+
+        self.removed[b] = true;
+        self.versions[a] += 1;
+
+        let triangles = &self.triangles;
+        self.triangles_of_vertex[a].retain(|&t| triangles[t].is_some());
+
+        for neighbor in self.neighbors(a) {
+            self.enqueue(a, neighbor);
+        }
+    }
+
+    /// Indicate whether an edge is on an open boundary or a color border
+    fn is_border(&self, a: usize, b: usize) -> bool {
+        let mut colors = self
+            .live_triangles(a)
+            .filter(|(indices, _)| indices.contains(&b))
+            .map(|(_, color)| color);
+
+        match (colors.next(), colors.next(), colors.next()) {
+            (Some(x), Some(y), None) => x != y,
+            _ => true,
+        }
+    }
+
+    fn live_triangles(
+        &self,
+        vertex: usize,
+    ) -> impl Iterator<Item = &([usize; 3], Color)> {
+        self.triangles_of_vertex[vertex]
+            .iter()
+            .filter_map(|&triangle| self.triangles[triangle].as_ref())
+    }
+
+    fn neighbors(&self, vertex: usize) -> Vec<usize> {
+        let mut neighbors = self
+            .live_triangles(vertex)
+            .flat_map(|(indices, _)| *indices)
+            .filter(|&other| other != vertex)
+            .collect::<Vec<_>>();
+
+        neighbors.sort_unstable();
+        neighbors.dedup();
+
+        neighbors
+    }
+
+    /// Compute the quadric of the squared distance to a plane, times `weight`
+    fn quadric(normal: [f64; 3], point: [f64; 3], weight: f64) -> Quadric {
+        let [a, b, c] = normal;
+        let d = -dot(normal, point);
+
+        [
+            a * a,
+            a * b,
+            a * c,
+            a * d,
+            b * b,
+            b * c,
+            b * d,
+            c * c,
+            c * d,
+            d * d,
+        ]
+        .map(|value| value * weight)
+    }
+
+    fn error(q: &Quadric, [x, y, z]: [f64; 3]) -> f64 {
+        q[0] * x * x
+            + 2. * q[1] * x * y
+            + 2. * q[2] * x * z
+            + 2. * q[3] * x
+            + q[4] * y * y
+            + 2. * q[5] * y * z
+            + 2. * q[6] * y
+            + q[7] * z * z
+            + 2. * q[8] * z
+            + q[9]
+    }
+}
+
+/// A symmetric 4x4 matrix, stored as its upper triangle
+type Quadric = [f64; 10];
+
+fn add(quadric: &mut Quadric, other: &Quadric) {
+    for (value, other) in quadric.iter_mut().zip(other) {
+        *value += other;
+    }
+}
+
+fn sub(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn cross(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+fn dot(a: [f64; 3], b: [f64; 3]) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn normalize(v: [f64; 3]) -> Option<[f64; 3]> {
+    let length = dot(v, v).sqrt();
+    if length == 0. {
+        return None;
+    }
+
+    Some(v.map(|c| c / length))
+}
+
 /// An index that refers to a vertex in a mesh
 pub type Index = u32;
 
@@ -129,3 +753,198 @@ impl Default for Color {
         Self([255, 0, 0, 255])
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use fj_math::{Point, Scalar};
+
+    use super::{Color, Mesh};
+
+    #[test]
+    fn welded() {
+        let mut mesh = Mesh::new();
+        mesh.push_triangle([[0., 0., 0.], [1., 0., 0.], [0., 1., 0.]], RED);
+        mesh.push_triangle(
+            [[1. + 1e-9, 0., 0.], [1., 1., 0.], [0., 1. - 1e-9, 0.]],
+            BLUE,
+        );
+        // A triangle that is smaller than the weld distance
+        mesh.push_triangle(
+            [[2., 2., 0.], [2. + 1e-9, 2., 0.], [2., 2. + 1e-9, 0.]],
+            RED,
+        );
+        assert_eq!(mesh.vertices().count(), 9);
+
+        let welded = mesh.welded(1e-6);
+
+        // The coincident vertices are merged, and the tiny triangle is left
+        // out, as it degenerated.
+        assert_eq!(welded.vertices().count(), 4);
+        assert_eq!(welded.triangles().count(), 2);
+        assert!(welded.contains_triangle([
+            [1., 0., 0.],
+            [1., 1., 0.],
+            [0., 1., 0.]
+        ]));
+        assert_eq!(
+            welded.triangles().map(|t| t.color).collect::<Vec<_>>(),
+            [RED, BLUE]
+        );
+    }
+
+    #[test]
+    fn welded_with_zero_or_tiny_distance() {
+        let mesh = cube([0., 0., 0.]);
+
+        let unchanged = mesh.welded(0.);
+        assert_eq!(unchanged.triangles().count(), mesh.triangles().count());
+
+        // The cells of the grid are out of range of `i64` at this distance.
+        let welded = mesh.welded(1e-300);
+        assert_eq!(welded.triangles().count(), mesh.triangles().count());
+    }
+
+    #[test]
+    fn smooth_normals_keep_edges_of_cube_sharp() {
+        let mesh = cube([0., 0., 0.]);
+        let normals = mesh.smooth_normals(PI / 4.);
+
+        for (triangle, normals) in mesh.triangles().zip(normals) {
+            let face_normal = triangle.inner.normal().normalize();
+
+            for normal in normals {
+                assert!(
+                    (normal - face_normal).magnitude() < Scalar::from(1e-9)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn smooth_normals_of_sphere_point_outward() {
+        let mesh = sphere(16);
+        let normals = mesh.smooth_normals(PI / 4.);
+
+        for (triangle, normals) in mesh.triangles().zip(normals) {
+            for (point, normal) in
+                triangle.inner.points().into_iter().zip(normals)
+            {
+                // The sphere is centered at the origin, with a radius of 1, so
+                // its points are its exact normals.
+                let exact = point.coords;
+                assert!(normal.dot(&exact) > Scalar::from(0.99));
+            }
+        }
+    }
+
+    #[test]
+    fn decimated() {
+        let mesh = sphere(8);
+        let decimated = mesh.decimated(100);
+
+        assert!(mesh.triangles().count() > 200);
+        assert_eq!(decimated.triangles().count(), 100);
+
+        // No triangle is flipped. Since the sphere is convex and centered at
+        // the origin, every triangle faces away from it.
+        for triangle in decimated.triangles() {
+            let [a, b, c] = triangle.inner.points();
+            let center = (a.coords + b.coords + c.coords) / 3.;
+            assert!(triangle.inner.normal().dot(&center) > Scalar::ZERO);
+        }
+    }
+
+    #[test]
+    fn components() {
+        let a = cube([0., 0., 0.]);
+        let b = cube([2., 0., 0.]);
+
+        let mut mesh = Mesh::new();
+        for triangle in a.triangles().chain(b.triangles()) {
+            mesh.push_triangle(triangle.inner, triangle.color);
+        }
+
+        let components = mesh.components();
+        assert_eq!(components.len(), 2);
+
+        for (component, cube) in components.iter().zip([a, b]) {
+            assert_eq!(
+                component.triangles().collect::<Vec<_>>(),
+                cube.triangles().collect::<Vec<_>>()
+            );
+        }
+    }
+
+    const RED: Color = Color([255, 0, 0, 255]);
+    const BLUE: Color = Color([0, 0, 255, 255]);
+
+    /// A cube with an edge length of 1, its lowest corner at `offset`
+    fn cube(offset: [f64; 3]) -> Mesh<Point<3>> {
+        // The corners of each face, counter-clockwise when viewed from outside
+        let faces = [
+            [[0, 0, 0], [0, 1, 0], [1, 1, 0], [1, 0, 0]],
+            [[0, 0, 1], [1, 0, 1], [1, 1, 1], [0, 1, 1]],
+            [[0, 0, 0], [1, 0, 0], [1, 0, 1], [0, 0, 1]],
+            [[0, 1, 0], [0, 1, 1], [1, 1, 1], [1, 1, 0]],
+            [[0, 0, 0], [0, 0, 1], [0, 1, 1], [0, 1, 0]],
+            [[1, 0, 0], [1, 1, 0], [1, 1, 1], [1, 0, 1]],
+        ];
+
+        let mut mesh = Mesh::new();
+        for face in faces {
+            let [a, b, c, d] = face.map(|corner| {
+                Point::from([0, 1, 2].map(|i| offset[i] + f64::from(corner[i])))
+            });
+
+            mesh.push_triangle([a, b, c], RED);
+            mesh.push_triangle([a, c, d], RED);
+        }
+
+        mesh
+    }
+
+    /// A sphere with a radius of 1, centered at the origin
+    ///
+    /// The sphere is made up of `n` rings, of `2 * n` segments each.
+    fn sphere(n: usize) -> Mesh<Point<3>> {
+        let point = |ring: usize, segment: usize| {
+            if ring == 0 {
+                return Point::from([0., 0., 1.]);
+            }
+            if ring == n {
+                return Point::from([0., 0., -1.]);
+            }
+
+            let theta = PI * ring as f64 / n as f64;
+            let phi = PI * (segment % (2 * n)) as f64 / n as f64;
+
+            Point::from([
+                theta.sin() * phi.cos(),
+                theta.sin() * phi.sin(),
+                theta.cos(),
+            ])
+        };
+
+        let mut mesh = Mesh::new();
+        for ring in 0..n {
+            for segment in 0..2 * n {
+                let a = point(ring, segment);
+                let b = point(ring + 1, segment);
+                let c = point(ring + 1, segment + 1);
+                let d = point(ring, segment + 1);
+
+                // The triangles at the poles would degenerate.
+                if ring != n - 1 {
+                    mesh.push_triangle([a, b, c], RED);
+                }
+                if ring != 0 {
+                    mesh.push_triangle([a, c, d], RED);
+                }
+            }
+        }
+
+        mesh
+    }
+}
@@ -0,0 +1,75 @@
+//! Heal objects that are almost, but not quite, consistent
+//!
+//! See [`Heal`].
+
+mod shell;
+
+pub(crate) use self::shell::heal_faces;
+
+use std::fmt;
+
+use crate::{
+    objects::Objects,
+    validate::{ValidationConfig, ValidationError},
+};
+
+/// Heal an object
+///
+/// Objects that come from imported or numerically noisy geometry can have
+/// vertices that are meant to be identical, but are represented by different
+/// [`GlobalVertex`] objects. Edges that are meant to be shared by neighboring
+/// faces are then represented by different [`GlobalEdge`]s, and the faces
+/// can't form a closed [`Shell`].
+///
+/// Healing merges all global vertices that are closer to each other than
+/// [`ValidationConfig::distinct_min_distance`], then unifies the global edges
+/// (and their global curves) that connect the merged vertices.
+///
+/// [`GlobalEdge`]: crate::objects::GlobalEdge
+/// [`GlobalVertex`]: crate::objects::GlobalVertex
+/// [`Shell`]: crate::objects::Shell
+pub trait Heal {
+    /// The type of the healed object
+    type Healed;
+
+    /// Heal the object
+    ///
+    /// Returns the healed object, together with a report on what was changed.
+    fn heal(
+        self,
+        config: &ValidationConfig,
+        objects: &Objects,
+    ) -> Result<(Self::Healed, HealingReport), ValidationError>;
+}
+
+/// A report on the changes made by [`Heal::heal`]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct HealingReport {
+    /// The number of global vertices that were merged into other ones
+    pub merged_global_vertices: usize,
+
+    /// The number of global edges that were merged into other ones
+    pub merged_global_edges: usize,
+
+    /// The number of global curves that were merged into other ones
+    pub merged_global_curves: usize,
+}
+
+impl HealingReport {
+    /// Indicate whether healing changed anything
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+impl fmt::Display for HealingReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "merged {} global vertices, {} global edges, and {} global curves",
+            self.merged_global_vertices,
+            self.merged_global_edges,
+            self.merged_global_curves,
+        )
+    }
+}
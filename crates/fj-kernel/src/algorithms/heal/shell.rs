@@ -0,0 +1,804 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use fj_math::{Circle, Line, Point, Scalar};
+
+use crate::{
+    insert::Insert,
+    objects::{
+        Curve, Cycle, Face, FaceSet, GlobalEdge, GlobalVertex, HalfEdge,
+        Objects, Shell, Surface, SurfaceVertex, Vertex,
+    },
+    path::{GlobalPath, SurfacePath},
+    storage::{Handle, ObjectId},
+    validate::{ValidationConfig, ValidationError},
+};
+
+use super::{Heal, HealingReport};
+
+/// Sew the faces into closed shells
+///
+/// Faces that are connected through shared edges, once healed, end up in the
+/// same shell. Faces that belong to separate bodies end up in separate shells,
+/// as a single shell can only bound a single body.
+///
+/// Global vertices that are closer to each other than
+/// [`ValidationConfig::distinct_min_distance`] are merged. The surface forms of
+/// merged vertices, and the curves they lie on, are moved to the position of
+/// the merged vertex.
+impl Heal for FaceSet {
+    type Healed = Vec<Handle<Shell>>;
+
+    fn heal(
+        self,
+        config: &ValidationConfig,
+        objects: &Objects,
+    ) -> Result<(Self::Healed, HealingReport), ValidationError> {
+        let (faces, report) = heal_faces(self, config, objects)?;
+
+        let shells = connected_components(faces)
+            .into_iter()
+            .map(|faces| Shell::new(faces).insert(objects))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok((shells, report))
+    }
+}
+
+/// Sew the faces of the shell back together
+///
+/// Unlike healing a [`FaceSet`], this always results in a single shell.
+impl Heal for Handle<Shell> {
+    type Healed = Handle<Shell>;
+
+    fn heal(
+        self,
+        config: &ValidationConfig,
+        objects: &Objects,
+    ) -> Result<(Self::Healed, HealingReport), ValidationError> {
+        let (faces, report) =
+            heal_faces(self.faces().clone(), config, objects)?;
+        let shell = Shell::new(faces).insert(objects)?;
+
+        Ok((shell, report))
+    }
+}
+
+/// Heal the faces, without sewing them into a shell
+pub(crate) fn heal_faces(
+    faces: FaceSet,
+    config: &ValidationConfig,
+    objects: &Objects,
+) -> Result<(Vec<Handle<Face>>, HealingReport), ValidationError> {
+    let mut healer = Healer::new(&faces, config, objects)?;
+
+    let faces = faces
+        .into_iter()
+        .map(|face| healer.heal_face(face))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok((faces, healer.report))
+}
+
+/// Group faces that are connected through shared global edges
+///
+/// The groups are ordered by their first face, and the faces within each group
+/// keep their order.
+fn connected_components(faces: Vec<Handle<Face>>) -> Vec<Vec<Handle<Face>>> {
+    fn root(parents: &mut [usize], mut i: usize) -> usize {
+        while parents[i] != i {
+            parents[i] = parents[parents[i]];
+            i = parents[i];
+        }
+        i
+    }
+
+    let mut parents = (0..faces.len()).collect::<Vec<_>>();
+    let mut faces_by_global_edge = HashMap::new();
+
+    for (i, face) in faces.iter().enumerate() {
+        for cycle in face.all_cycles() {
+            for half_edge in cycle.half_edges() {
+                let j = *faces_by_global_edge
+                    .entry(half_edge.global_form().id())
+                    .or_insert(i);
+
+                let [a, b] = [root(&mut parents, i), root(&mut parents, j)];
+                parents[a.max(b)] = a.min(b);
+            }
+        }
+    }
+
+    let mut components: Vec<Vec<Handle<Face>>> = Vec::new();
+    let mut component_of_root = HashMap::new();
+
+    for (i, face) in faces.into_iter().enumerate() {
+        let root = root(&mut parents, i);
+        let component = *component_of_root.entry(root).or_insert_with(|| {
+            components.push(Vec::new());
+            components.len() - 1
+        });
+
+        components[component].push(face);
+    }
+
+    components
+}
+
+struct Healer<'r> {
+    objects: &'r Objects,
+    report: HealingReport,
+
+    /// The global vertex that each global vertex was merged into
+    global_vertices: HashMap<ObjectId, Handle<GlobalVertex>>,
+
+    /// The healed global edge of each half-edge
+    global_edges: HashMap<ObjectId, Handle<GlobalEdge>>,
+
+    surface_vertices: HashMap<ObjectId, Handle<SurfaceVertex>>,
+    curves: HashMap<(ObjectId, ObjectId, [ObjectId; 2]), Handle<Curve>>,
+}
+
+impl<'r> Healer<'r> {
+    fn new(
+        faces: &FaceSet,
+        config: &ValidationConfig,
+        objects: &'r Objects,
+    ) -> Result<Self, ValidationError> {
+        let half_edges = faces
+            .into_iter()
+            .flat_map(|face| face.all_cycles())
+            .flat_map(|cycle| cycle.half_edges())
+            .collect::<Vec<_>>();
+
+        let mut healer = Self {
+            objects,
+            report: HealingReport::default(),
+            global_vertices: HashMap::new(),
+            global_edges: HashMap::new(),
+            surface_vertices: HashMap::new(),
+            curves: HashMap::new(),
+        };
+
+        healer.merge_global_vertices(&half_edges, config)?;
+        healer.merge_global_edges(&half_edges, config)?;
+
+        Ok(healer)
+    }
+
+    fn merge_global_vertices(
+        &mut self,
+        half_edges: &[&Handle<HalfEdge>],
+        config: &ValidationConfig,
+    ) -> Result<(), ValidationError> {
+        // The vertices are processed in the order of their ids, which makes
+        // the result independent of the order of the faces.
+        let global_vertices = half_edges
+            .iter()
+            .flat_map(|half_edge| half_edge.vertices())
+            .map(|vertex| vertex.global_form())
+            .map(|global_vertex| (global_vertex.id(), global_vertex))
+            .collect::<BTreeMap<_, _>>();
+
+        let merged_into = cluster_global_vertices(
+            global_vertices.values().copied(),
+            config.distinct_min_distance,
+        );
+
+        // The merged vertex must lie on the surfaces of all the vertices that
+        // were merged into it. Collect those surfaces, together with the
+        // surface coordinates of the vertices.
+        let mut surfaces = BTreeMap::new();
+        for vertex in
+            half_edges.iter().flat_map(|half_edge| half_edge.vertices())
+        {
+            let surface_form = vertex.surface_form();
+            let merged = merged_into[&vertex.global_form().id()];
+
+            surfaces
+                .entry(merged.id())
+                .or_insert_with(BTreeMap::new)
+                .entry(surface_form.surface().id())
+                .or_insert_with(|| {
+                    (surface_form.surface(), surface_form.position())
+                });
+        }
+
+        let mut healed_vertices = HashMap::new();
+        for (id, merged) in merged_into {
+            if id != merged.id() {
+                self.report.merged_global_vertices += 1;
+            }
+
+            let healed = match healed_vertices.get(&merged.id()) {
+                Some(healed) => Handle::clone(healed),
+                None => {
+                    let surfaces = surfaces[&merged.id()]
+                        .values()
+                        .copied()
+                        .collect::<Vec<_>>();
+                    let position =
+                        point_on_surfaces(merged.position(), &surfaces, config);
+
+                    let healed = if position.distance_to(&merged.position())
+                        > config.identical_max_distance
+                    {
+                        GlobalVertex::from_position(position)
+                            .insert(self.objects)?
+                    } else {
+                        merged.clone()
+                    };
+
+                    healed_vertices.insert(merged.id(), healed.clone());
+                    healed
+                }
+            };
+
+            self.global_vertices.insert(id, healed);
+        }
+
+        Ok(())
+    }
+
+    fn merge_global_edges(
+        &mut self,
+        half_edges: &[&Handle<HalfEdge>],
+        config: &ValidationConfig,
+    ) -> Result<(), ValidationError> {
+        // Half-edges can only share a global edge, if they connect the same
+        // global vertices. But that's not enough, as there can be multiple
+        // edges between two vertices (think of two semicircles forming a
+        // circle). To tell those apart, we also compare the midpoints of the
+        // half-edges.
+        let mut half_edges_by_vertices = BTreeMap::new();
+        for &half_edge in half_edges {
+            let mut vertices = self
+                .merged_global_vertices(half_edge)
+                .map(|vertex| vertex.id());
+            vertices.sort();
+
+            half_edges_by_vertices
+                .entry(vertices)
+                .or_insert_with(Vec::new)
+                .push((half_edge, midpoint(half_edge)));
+        }
+
+        for half_edges in half_edges_by_vertices.into_values() {
+            let mut groups: Vec<(Point<3>, Vec<&Handle<HalfEdge>>)> =
+                Vec::new();
+
+            for (half_edge, midpoint) in half_edges {
+                let group = groups.iter_mut().find(|(other, _)| {
+                    other.distance_to(&midpoint) < config.distinct_min_distance
+                });
+
+                match group {
+                    Some((_, group)) => group.push(half_edge),
+                    None => groups.push((midpoint, vec![half_edge])),
+                }
+            }
+
+            for (_, group) in groups {
+                self.merge_global_edge(&group)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn merge_global_edge(
+        &mut self,
+        half_edges: &[&Handle<HalfEdge>],
+    ) -> Result<(), ValidationError> {
+        let first = half_edges[0];
+
+        let global_edge_ids = half_edges
+            .iter()
+            .map(|half_edge| half_edge.global_form().id())
+            .collect::<BTreeSet<_>>();
+        let global_curve_ids = half_edges
+            .iter()
+            .map(|half_edge| half_edge.global_form().curve().id())
+            .collect::<BTreeSet<_>>();
+
+        self.report.merged_global_edges += global_edge_ids.len() - 1;
+        self.report.merged_global_curves += global_curve_ids.len() - 1;
+
+        let vertices = self.merged_global_vertices(first);
+        let vertices_unchanged = half_edges.iter().all(|half_edge| {
+            half_edge.vertices().iter().all(|vertex| {
+                let global_vertex = vertex.global_form();
+                self.global_vertices[&global_vertex.id()].id()
+                    == global_vertex.id()
+            })
+        });
+
+        let global_edge = if global_edge_ids.len() == 1 && vertices_unchanged {
+            first.global_form().clone()
+        } else {
+            GlobalEdge::new(first.global_form().curve().clone(), vertices)
+                .insert(self.objects)?
+        };
+
+        for half_edge in half_edges {
+            self.global_edges
+                .insert(half_edge.id(), global_edge.clone());
+        }
+
+        Ok(())
+    }
+
+    fn heal_face(
+        &mut self,
+        face: Handle<Face>,
+    ) -> Result<Handle<Face>, ValidationError> {
+        let exterior = self.heal_cycle(face.exterior())?;
+        let interiors = face
+            .interiors()
+            .map(|cycle| self.heal_cycle(cycle))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let unchanged = exterior.id() == face.exterior().id()
+            && interiors
+                .iter()
+                .zip(face.interiors())
+                .all(|(healed, original)| healed.id() == original.id());
+        if unchanged {
+            return Ok(face);
+        }
+
+        Ok(
+            Face::new(exterior, interiors, face.color())
+                .insert(self.objects)?,
+        )
+    }
+
+    fn heal_cycle(
+        &mut self,
+        cycle: &Handle<Cycle>,
+    ) -> Result<Handle<Cycle>, ValidationError> {
+        let half_edges = cycle
+            .half_edges()
+            .map(|half_edge| self.heal_half_edge(half_edge))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let unchanged = half_edges
+            .iter()
+            .zip(cycle.half_edges())
+            .all(|(healed, original)| healed.id() == original.id());
+        if unchanged {
+            return Ok(cycle.clone());
+        }
+
+        Ok(Cycle::new(half_edges).insert(self.objects)?)
+    }
+
+    fn heal_half_edge(
+        &mut self,
+        half_edge: &Handle<HalfEdge>,
+    ) -> Result<Handle<HalfEdge>, ValidationError> {
+        let global_edge = self.global_edges[&half_edge.id()].clone();
+
+        let [back, front] = half_edge.vertices();
+        let surface_vertices = [
+            self.heal_surface_vertex(back.surface_form())?,
+            self.heal_surface_vertex(front.surface_form())?,
+        ];
+
+        let curve =
+            self.heal_curve(half_edge, &surface_vertices, &global_edge)?;
+
+        let [back_surface, front_surface] = surface_vertices;
+        let vertices = [
+            self.heal_vertex(back, &curve, back_surface)?,
+            self.heal_vertex(front, &curve, front_surface)?,
+        ];
+
+        let unchanged = global_edge.id() == half_edge.global_form().id()
+            && vertices
+                .iter()
+                .zip(half_edge.vertices())
+                .all(|(healed, original)| healed.id() == original.id());
+        if unchanged {
+            return Ok(half_edge.clone());
+        }
+
+        Ok(HalfEdge::new(vertices, global_edge).insert(self.objects)?)
+    }
+
+    fn heal_curve(
+        &mut self,
+        half_edge: &HalfEdge,
+        surface_vertices: &[Handle<SurfaceVertex>; 2],
+        global_edge: &GlobalEdge,
+    ) -> Result<Handle<Curve>, ValidationError> {
+        let curve = half_edge.curve();
+        let global_curve = global_edge.curve();
+
+        let vertices_unchanged = surface_vertices
+            .iter()
+            .zip(half_edge.vertices())
+            .all(|(healed, vertex)| healed.id() == vertex.surface_form().id());
+        if vertices_unchanged && curve.global_form().id() == global_curve.id() {
+            return Ok(curve.clone());
+        }
+
+        let [back, front] = surface_vertices;
+        let key = (curve.id(), global_curve.id(), [back.id(), front.id()]);
+        if let Some(curve) = self.curves.get(&key) {
+            return Ok(curve.clone());
+        }
+
+        let path = if vertices_unchanged {
+            curve.path()
+        } else {
+            healed_path(half_edge, surface_vertices)
+        };
+
+        let healed =
+            Curve::new(curve.surface().clone(), path, global_curve.clone())
+                .insert(self.objects)?;
+        self.curves.insert(key, healed.clone());
+
+        Ok(healed)
+    }
+
+    fn heal_vertex(
+        &mut self,
+        vertex: &Handle<Vertex>,
+        curve: &Handle<Curve>,
+        surface_form: Handle<SurfaceVertex>,
+    ) -> Result<Handle<Vertex>, ValidationError> {
+        if curve.id() == vertex.curve().id()
+            && surface_form.id() == vertex.surface_form().id()
+        {
+            return Ok(vertex.clone());
+        }
+
+        Ok(Vertex::new(vertex.position(), curve.clone(), surface_form)
+            .insert(self.objects)?)
+    }
+
+    fn heal_surface_vertex(
+        &mut self,
+        surface_vertex: &Handle<SurfaceVertex>,
+    ) -> Result<Handle<SurfaceVertex>, ValidationError> {
+        let global_form =
+            &self.global_vertices[&surface_vertex.global_form().id()];
+        if global_form.id() == surface_vertex.global_form().id() {
+            return Ok(surface_vertex.clone());
+        }
+
+        if let Some(healed) = self.surface_vertices.get(&surface_vertex.id()) {
+            return Ok(healed.clone());
+        }
+
+        // The merged global vertex might be at a slightly different position.
+        // The surface vertex is moved there, to stay consistent with it.
+        let position = point_to_surface_coords(
+            surface_vertex.surface(),
+            global_form.position(),
+            surface_vertex.position(),
+        );
+
+        let healed = SurfaceVertex::new(
+            position,
+            surface_vertex.surface().clone(),
+            global_form.clone(),
+        )
+        .insert(self.objects)?;
+        self.surface_vertices
+            .insert(surface_vertex.id(), healed.clone());
+
+        Ok(healed)
+    }
+
+    fn merged_global_vertices(
+        &self,
+        half_edge: &HalfEdge,
+    ) -> [Handle<GlobalVertex>; 2] {
+        let [back, front] = half_edge.vertices();
+        [back, front].map(|vertex| {
+            self.global_vertices[&vertex.global_form().id()].clone()
+        })
+    }
+}
+
+/// Find the global vertex that each global vertex is merged into
+///
+/// Vertices that are closer to each other than `distance` are merged into the
+/// one that comes first.
+fn cluster_global_vertices<'r>(
+    global_vertices: impl IntoIterator<Item = &'r Handle<GlobalVertex>>,
+    distance: Scalar,
+) -> BTreeMap<ObjectId, &'r Handle<GlobalVertex>> {
+    let mut merged_into = BTreeMap::new();
+
+    if !distance.is_positive() {
+        for global_vertex in global_vertices {
+            merged_into.insert(global_vertex.id(), global_vertex);
+        }
+        return merged_into;
+    }
+
+    // The vertices are sorted into cells of a grid. Vertices that are close
+    // enough to be merged are in the same or in neighboring cells.
+    let cell_of = |point: Point<3>| {
+        point
+            .coords
+            .components
+            .map(|c| (c / distance).floor().into_f64() as i64)
+    };
+    let mut cells: HashMap<[i64; 3], Vec<&Handle<GlobalVertex>>> =
+        HashMap::new();
+
+    for global_vertex in global_vertices {
+        let position = global_vertex.position();
+        let [x, y, z] = cell_of(position);
+
+        // The cell coordinates saturate for very small distances, so the
+        // neighbors have to be computed without overflowing.
+        let around = |c: i64| c.saturating_sub(1)..=c.saturating_add(1);
+        let existing = around(x)
+            .flat_map(|x| {
+                around(y).flat_map(move |y| around(z).map(move |z| [x, y, z]))
+            })
+            .find_map(|cell| {
+                cells.get(&cell)?.iter().copied().find(|other| {
+                    other.position().distance_to(&position) < distance
+                })
+            });
+
+        let merged = match existing {
+            Some(existing) => existing,
+            None => {
+                cells.entry([x, y, z]).or_default().push(global_vertex);
+                global_vertex
+            }
+        };
+
+        merged_into.insert(global_vertex.id(), merged);
+    }
+
+    merged_into
+}
+
+/// Find a point near the given one, that lies on all of the surfaces
+///
+/// The point is projected onto each surface in turn, until it lies on all of
+/// them. For surfaces that intersect, this converges towards their
+/// intersection. For surfaces that don't, the result is left on the last
+/// surface, and validating the healed objects will fail.
+///
+/// Each surface comes with the surface coordinates of a point close to where
+/// the result is expected, to tell apart the periodic coordinates of curved
+/// surfaces.
+fn point_on_surfaces(
+    mut point: Point<3>,
+    surfaces: &[(&Handle<Surface>, Point<2>)],
+    config: &ValidationConfig,
+) -> Point<3> {
+    const MAX_ITERATIONS: usize = 64;
+
+    let project = |surface: &Surface, point, near| {
+        surface.point_from_surface_coords(point_to_surface_coords(
+            surface, point, near,
+        ))
+    };
+
+    for _ in 0..MAX_ITERATIONS {
+        let on_all_surfaces = surfaces.iter().all(|&(surface, near)| {
+            project(surface, point, near).distance_to(&point)
+                <= config.identical_max_distance / 2.
+        });
+        if on_all_surfaces {
+            break;
+        }
+
+        for &(surface, near) in surfaces {
+            point = project(surface, point, near);
+        }
+    }
+
+    point
+}
+
+/// Compute the path of a half-edge, after its surface vertices have moved
+///
+/// Lines are rebuilt to go through the moved vertices. Circles can't be
+/// adjusted like that, so they are moved by the average distance that their
+/// vertices moved. For vertices that were moved by the same amount, which is
+/// the case for faces that were transformed separately, that is exact.
+fn healed_path(
+    half_edge: &HalfEdge,
+    surface_vertices: &[Handle<SurfaceVertex>; 2],
+) -> SurfacePath {
+    let [back, front] = half_edge.vertices();
+    let [back_surface, front_surface] = surface_vertices;
+
+    match half_edge.curve().path() {
+        SurfacePath::Line(_) if back.position() != front.position() => {
+            SurfacePath::Line(Line::from_points_with_line_coords([
+                (back.position(), back_surface.position()),
+                (front.position(), front_surface.position()),
+            ]))
+        }
+        SurfacePath::Line(line) => SurfacePath::Line(line),
+        SurfacePath::Circle(circle) => {
+            let offset = ((back_surface.position()
+                - back.surface_form().position())
+                + (front_surface.position() - front.surface_form().position()))
+                / 2.;
+
+            SurfacePath::Circle(Circle::new(
+                circle.center() + offset,
+                circle.a(),
+                circle.b(),
+            ))
+        }
+    }
+}
+
+/// Convert a point to the coordinates of a surface
+///
+/// The point is projected onto the surface. Surfaces that are swept from a
+/// circle are periodic in their u-coordinate. Of all the coordinates that
+/// describe the point, the one closest to `near` is returned.
+fn point_to_surface_coords(
+    surface: &Surface,
+    point: Point<3>,
+    near: Point<2>,
+) -> Point<2> {
+    let v = surface.v();
+
+    match surface.u() {
+        GlobalPath::Line(line) => {
+            let u = line.direction();
+            let w = point - line.origin();
+
+            // Solve `w = u * s + v * t` for `s` and `t`, in the least squares
+            // sense.
+            let [uu, uv, vv] = [u.dot(&u), u.dot(&v), v.dot(&v)];
+            let [wu, wv] = [w.dot(&u), w.dot(&v)];
+            let det = uu * vv - uv * uv;
+
+            Point::from([(wu * vv - wv * uv) / det, (wv * uu - wu * uv) / det])
+        }
+        GlobalPath::Circle(circle) => {
+            let normal = circle.a().cross(&circle.b());
+            let w = point - circle.center();
+
+            let t = w.dot(&normal) / v.dot(&normal);
+            let w = w - v * t;
+
+            let s = Scalar::atan2(w.dot(&circle.b()), w.dot(&circle.a()));
+            let s = s + ((near.u - s) / Scalar::TAU).round() * Scalar::TAU;
+
+            Point::from([s, t])
+        }
+    }
+}
+
+/// Compute the midpoint of a half-edge, in global coordinates
+fn midpoint(half_edge: &HalfEdge) -> Point<3> {
+    let [back, front] = half_edge.vertices();
+    let midpoint = (back.position().t + front.position().t) / 2.;
+
+    let point_surface =
+        half_edge.curve().path().point_from_path_coords([midpoint]);
+    half_edge.surface().point_from_surface_coords(point_surface)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        algorithms::{heal::Heal, transform::TransformObject},
+        objects::{FaceSet, Objects, Shell},
+        validate::ValidationConfig,
+    };
+
+    #[test]
+    fn heal_closed_shell() -> anyhow::Result<()> {
+        let objects = Objects::new();
+
+        let shell = Shell::builder(&objects)
+            .with_cube_from_edge_length(1.)
+            .build()?;
+
+        let (healed, report) =
+            shell.clone().heal(&ValidationConfig::default(), &objects)?;
+
+        assert!(report.is_empty());
+        assert_eq!(healed, shell);
+
+        Ok(())
+    }
+
+    #[test]
+    fn heal_separately_transformed_faces() -> anyhow::Result<()> {
+        let objects = Objects::new();
+
+        let shell = Shell::builder(&objects)
+            .with_cube_from_edge_length(1.)
+            .build()?;
+
+        // Translating the faces one by one creates new global vertices and
+        // edges for each of them. The faces no longer form a closed shell.
+        let faces = shell
+            .faces()
+            .clone()
+            .into_iter()
+            .map(|face| face.translate([1., 2., 3.], &objects))
+            .collect::<Result<FaceSet, _>>()?;
+        assert!(objects.shells.insert(Shell::new(faces.clone())).is_err());
+
+        let (shells, report) =
+            faces.heal(&ValidationConfig::default(), &objects)?;
+
+        // Each of the 12 edges of the cube was duplicated.
+        assert!(report.merged_global_vertices > 0);
+        assert_eq!(report.merged_global_edges, 12);
+        assert_eq!(shells.len(), 1);
+        assert_eq!(shells[0].faces().into_iter().count(), 6);
+
+        Ok(())
+    }
+
+    #[test]
+    fn heal_faces_that_are_slightly_apart() -> anyhow::Result<()> {
+        let objects = Objects::new();
+        let config = ValidationConfig::default();
+
+        let shell = Shell::builder(&objects)
+            .with_cube_from_edge_length(1.)
+            .build()?;
+
+        // The faces are moved by different amounts, which are too large for
+        // their vertices to be considered identical, but too small for them to
+        // be considered distinct.
+        let offset = config.distinct_min_distance.into_f64() / 10.;
+        let faces = shell
+            .faces()
+            .clone()
+            .into_iter()
+            .enumerate()
+            .map(|(i, face)| {
+                let i = i as f64;
+                face.translate([i * offset, -i * offset, 0.], &objects)
+            })
+            .collect::<Result<FaceSet, _>>()?;
+
+        let (shells, report) = faces.heal(&config, &objects)?;
+
+        assert_eq!(report.merged_global_edges, 12);
+        assert_eq!(shells.len(), 1);
+        assert_eq!(shells[0].faces().into_iter().count(), 6);
+
+        Ok(())
+    }
+
+    #[test]
+    fn heal_disjoint_bodies_into_separate_shells() -> anyhow::Result<()> {
+        let objects = Objects::new();
+
+        let a = Shell::builder(&objects)
+            .with_cube_from_edge_length(1.)
+            .build()?;
+        let b = a.clone().translate([2., 0., 0.], &objects)?;
+
+        let faces = a
+            .faces()
+            .clone()
+            .into_iter()
+            .chain(b.faces().clone())
+            .collect::<FaceSet>();
+
+        let (shells, _) = faces.heal(&ValidationConfig::default(), &objects)?;
+
+        assert_eq!(shells.len(), 2);
+        for shell in shells {
+            assert_eq!(shell.faces().into_iter().count(), 6);
+        }
+
+        Ok(())
+    }
+}
@@ -27,7 +27,7 @@ impl LineSegmentIntersection {
         // Find vector that is orthogonal to `segment`.
         let n = {
             let ab = b - a;
-            Vector::from([ab.v, ab.u])
+            Vector::from([-ab.v, ab.u])
         };
 
         let n_dot_origin = n.dot(&(b - line.origin()));
@@ -86,6 +86,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn compute_one_hit_diagonal() {
+        let line =
+            Line::from_origin_and_direction(Point::origin(), Vector::unit_u());
+
+        assert_eq!(
+            LineSegmentIntersection::compute(
+                &line,
+                &Segment::from_points([[0., -1.], [2., 1.]]),
+            ),
+            Some(LineSegmentIntersection::Point {
+                point_on_line: Point::from([Scalar::ONE])
+            }),
+        );
+    }
+
     #[test]
     fn compute_coincident() {
         let line =
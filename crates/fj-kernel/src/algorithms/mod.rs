@@ -4,8 +4,10 @@
 //! on their respective purpose.
 
 pub mod approx;
+pub mod heal;
 pub mod intersect;
 pub mod reverse;
+pub mod section;
 pub mod sweep;
 pub mod transform;
 pub mod triangulate;
@@ -0,0 +1,894 @@
+//! Planar sections of solids
+//!
+//! See [`Section`].
+
+use fj_math::{
+    nest_polygons, signed_area, winding_number, Aabb, Line, Plane, Point,
+    Scalar, Vector,
+};
+
+use crate::{
+    builder::FaceBuilder,
+    insert::Insert,
+    objects::{Face, Objects, Sketch, Solid, Surface},
+    partial::HasPartial,
+    path::{GlobalPath, SurfacePath},
+    storage::Handle,
+    validate::{ValidationConfig, ValidationError},
+};
+
+use super::approx::{Approx, Tolerance};
+
+/// Compute the section of an object with a plane
+///
+/// The section is a [`Sketch`] on the plane. Each region of the plane that is
+/// inside the object becomes a face, with the holes in that region as the
+/// face's interior cycles.
+///
+/// Planar and cylindrical faces are supported, and their edges can be straight
+/// lines or circles. Where the plane crosses a cylindrical face at an angle,
+/// the section is an ellipse, which is approximated within `tolerance`. All
+/// other intersections are computed exactly, and the edges of the section are
+/// straight lines. An error is returned, if the plane is not planar.
+///
+/// # Implementation Note
+///
+/// Every face of the object that the plane crosses is intersected with the
+/// plane, and the resulting line segments are then joined into cycles. This
+/// requires the plane to cross the object cleanly. Faces that lie within the
+/// plane don't contribute to the section, and a plane that only touches a
+/// vertex or an edge can lead to an incomplete section, which results in an
+/// error.
+pub trait Section {
+    /// Compute the section of the object with the given plane
+    fn section(
+        self,
+        plane: Handle<Surface>,
+        tolerance: impl Into<Tolerance>,
+        objects: &Objects,
+    ) -> Result<Handle<Sketch>, SectionError>;
+}
+
+impl Section for &Solid {
+    fn section(
+        self,
+        plane: Handle<Surface>,
+        tolerance: impl Into<Tolerance>,
+        objects: &Objects,
+    ) -> Result<Handle<Sketch>, SectionError> {
+        let tolerance = tolerance.into();
+
+        let geometry = match plane.u() {
+            GlobalPath::Line(line) => Plane::from_parametric(
+                line.origin(),
+                line.direction(),
+                plane.v(),
+            ),
+            GlobalPath::Circle(_) => return Err(SectionError::NotPlanar),
+        };
+
+        let distance = ValidationConfig::default().distinct_min_distance;
+        let mut segments: Vec<[Point<2>; 2]> = Vec::new();
+
+        for face in self.shells().flat_map(|shell| shell.faces()) {
+            let boundary = Boundary::new(face, tolerance);
+            if !boundary.is_crossed_by(&geometry, tolerance) {
+                continue;
+            }
+
+            let to_plane = |point: Point<2>| {
+                let point = face.surface().point_from_surface_coords(point);
+                Point {
+                    coords: geometry
+                        .project_vector(&(point - geometry.origin())),
+                }
+            };
+
+            for cut in Cut::compute(face.surface(), &geometry) {
+                for run in cut.runs_within(&boundary, tolerance) {
+                    let points = run
+                        .into_iter()
+                        .map(|param| to_plane(cut.point(param)))
+                        .collect::<Vec<_>>();
+
+                    for segment in points.windows(2) {
+                        let segment = [segment[0], segment[1]];
+
+                        // A plane that crosses the seam of a cylindrical face
+                        // finds the same segment on both sides of it.
+                        let is_duplicate = segments.iter().any(|[a, b]| {
+                            let [c, d] = segment;
+                            let is_near = |a: &Point<2>, b: &Point<2>| {
+                                a.distance_to(b) <= distance
+                            };
+
+                            (is_near(a, &c) && is_near(b, &d))
+                                || (is_near(a, &d) && is_near(b, &c))
+                        });
+                        if !is_duplicate {
+                            segments.push(segment);
+                        }
+                    }
+                }
+            }
+        }
+
+        let polygons = join_segments(segments, distance)?;
+
+        let mut faces = Vec::new();
+        for (exterior, interiors) in nest(polygons) {
+            let mut face = Face::partial()
+                .with_surface(plane.clone())
+                .with_exterior_polygon_from_points(exterior);
+            for interior in interiors {
+                face = face.with_interior_polygon_from_points(interior);
+            }
+
+            faces.push(
+                face.build(objects)?
+                    .insert(objects)
+                    .map_err(ValidationError::from)?,
+            );
+        }
+
+        Ok(Sketch::builder(objects).with_faces(faces).build())
+    }
+}
+
+/// Error computing a section
+///
+/// See [`Section`].
+#[derive(Debug, thiserror::Error)]
+pub enum SectionError {
+    /// The plane is not planar
+    #[error("Sections can only be computed with a plane")]
+    NotPlanar,
+
+    /// The intersections of the faces with the plane don't form closed cycles
+    ///
+    /// This can happen, if the plane only touches the object at a vertex or an
+    /// edge.
+    #[error("Section is incomplete; intersection ends at {point:?}")]
+    Incomplete {
+        /// The point on the plane where the intersection ends
+        point: Point<2>,
+    },
+
+    /// The faces of the section are invalid
+    #[error(transparent)]
+    Validation(#[from] ValidationError),
+}
+
+/// The boundary of a face, in the face's surface coordinates
+struct Boundary<'r> {
+    face: &'r Face,
+    polygons: Vec<Vec<Point<2>>>,
+    global_points: Vec<Point<3>>,
+    aabb: Aabb<2>,
+}
+
+impl<'r> Boundary<'r> {
+    fn new(face: &'r Face, tolerance: Tolerance) -> Self {
+        let approx = face
+            .all_cycles()
+            .map(|cycle| cycle.approx(tolerance).points())
+            .collect::<Vec<_>>();
+
+        let polygons = approx
+            .iter()
+            .map(|points| points.iter().map(|point| point.local_form).collect())
+            .collect::<Vec<Vec<_>>>();
+        let global_points = approx
+            .iter()
+            .flatten()
+            .map(|point| point.global_form)
+            .collect();
+        let aabb = Aabb::<2>::from_points(polygons.iter().flatten().copied());
+
+        Self {
+            face,
+            polygons,
+            global_points,
+            aabb,
+        }
+    }
+
+    /// Indicate whether the plane passes between the points of the boundary
+    fn is_crossed_by(&self, plane: &Plane, tolerance: Tolerance) -> bool {
+        let normal = plane.normal();
+        let distances = self
+            .global_points
+            .iter()
+            .map(|point| normal.dot(&(point - plane.origin())))
+            .collect::<Vec<_>>();
+
+        let min = distances.iter().min().copied().unwrap_or(Scalar::MAX);
+        let max = distances.iter().max().copied().unwrap_or(-Scalar::MAX);
+
+        min <= tolerance.inner() && max >= -tolerance.inner()
+    }
+
+    /// Indicate whether a point is within the face
+    ///
+    /// Uses the approximation of the boundary, so the result is only reliable
+    /// for points that are further away from the boundary than the tolerance.
+    fn contains(&self, point: Point<2>) -> bool {
+        if !self.aabb.contains(point) {
+            return false;
+        }
+
+        let winding_number: i32 = self
+            .polygons
+            .iter()
+            .map(|polygon| winding_number(polygon, point))
+            .sum();
+
+        winding_number % 2 != 0
+    }
+
+    /// The paths of all edges of the face, in surface coordinates
+    fn paths(&self) -> impl Iterator<Item = SurfacePath> + 'r {
+        self.face
+            .all_cycles()
+            .flat_map(|cycle| cycle.half_edges())
+            .map(|half_edge| half_edge.curve().path())
+    }
+}
+
+/// The intersection of a plane with the surface of a face
+///
+/// This is defined in the surface coordinates of the face.
+enum Cut {
+    /// The plane intersects the surface in a line
+    Line(Line<2>),
+
+    /// The plane crosses a cylindrical surface at an angle
+    ///
+    /// The intersection is an ellipse. At the surface coordinate `s`, it is at
+    /// `t = (k - a * cos(s) - b * sin(s)) / c`.
+    Ellipse {
+        a: Scalar,
+        b: Scalar,
+        c: Scalar,
+        k: Scalar,
+
+        /// The semi-major axis of the ellipse
+        radius: Scalar,
+    },
+}
+
+impl Cut {
+    /// Compute the intersections of a plane with a surface
+    ///
+    /// A point `u(s) + v * t` on the surface is on the plane, if
+    /// `n * (u(s) - o) + t * (n * v) = 0`, where `n` is the normal of the plane
+    /// and `o` its origin.
+    fn compute(surface: &Surface, plane: &Plane) -> Vec<Self> {
+        let normal = plane.normal();
+        let v = surface.v();
+        let nv = normal.dot(&v);
+
+        match surface.u() {
+            GlobalPath::Line(line) => {
+                // `a * s + b * t + c = 0`
+                let a = normal.dot(&line.direction());
+                let b = nv;
+                let c = normal.dot(&(line.origin() - plane.origin()));
+
+                if is_negligible(a, line.direction()) && is_negligible(b, v) {
+                    // The surface is parallel to the plane, or lies within it.
+                    // Either way, it doesn't contribute to the section.
+                    return Vec::new();
+                }
+
+                let normal = Vector::from([a, b]);
+                let origin = Point {
+                    coords: normal * -c / normal.dot(&normal),
+                };
+                let direction = Vector::from([-b, a]);
+
+                vec![Self::Line(Line::from_origin_and_direction(
+                    origin, direction,
+                ))]
+            }
+            GlobalPath::Circle(circle) => {
+                let a = normal.dot(&circle.a());
+                let b = normal.dot(&circle.b());
+                let k = -normal.dot(&(circle.center() - plane.origin()));
+
+                if !is_negligible(nv, v) {
+                    let cos = nv.abs() / v.magnitude();
+                    return vec![Self::Ellipse {
+                        a,
+                        b,
+                        c: nv,
+                        k,
+                        radius: circle.radius() / cos,
+                    }];
+                }
+
+                // The plane is parallel to the axis of the cylinder, and
+                // intersects it in lines along that axis. Those might be found
+                // outside of the range of surface coordinates that the face
+                // covers, so also look for them one revolution further.
+                solve_trig(a, b, k)
+                    .into_iter()
+                    .flat_map(|s| [s - Scalar::TAU, s, s + Scalar::TAU])
+                    .map(|s| {
+                        Self::Line(Line::from_origin_and_direction(
+                            Point::from([s, Scalar::ZERO]),
+                            Vector::unit_v(),
+                        ))
+                    })
+                    .collect()
+            }
+        }
+    }
+
+    /// The point on the cut at the given parameter
+    fn point(&self, param: Scalar) -> Point<2> {
+        match self {
+            Self::Line(line) => line.point_from_line_coords([param]),
+            Self::Ellipse { a, b, c, k, .. } => {
+                let (sin, cos) = param.sin_cos();
+                Point::from([param, (*k - *a * cos - *b * sin) / *c])
+            }
+        }
+    }
+
+    /// Find the runs of parameters along which the cut is within the face
+    ///
+    /// Lines result in runs that consist of their two end points. Ellipses are
+    /// approximated, and result in runs of as many points as are required for
+    /// that.
+    fn runs_within(
+        &self,
+        boundary: &Boundary,
+        tolerance: Tolerance,
+    ) -> Vec<Vec<Scalar>> {
+        let mut params = match self {
+            Self::Line(line) => boundary
+                .paths()
+                .flat_map(|path| line_crossings(line, &path))
+                .collect::<Vec<_>>(),
+            Self::Ellipse { radius, .. } => {
+                let [min, max] = [boundary.aabb.min.u, boundary.aabb.max.u];
+
+                let mut params = boundary
+                    .paths()
+                    .flat_map(|path| self.ellipse_crossings(&path))
+                    .flat_map(|s| [s - Scalar::TAU, s, s + Scalar::TAU])
+                    .filter(|&s| s >= min && s <= max)
+                    .collect::<Vec<_>>();
+
+                let increment = approx_increment(*radius, tolerance);
+                let mut s = (min / increment).floor() * increment;
+                while s < max {
+                    if s > min {
+                        params.push(s);
+                    }
+                    s += increment;
+                }
+                params.extend([min, max]);
+
+                params
+            }
+        };
+
+        params.sort();
+        params.dedup();
+
+        let mut runs = Vec::new();
+        let mut run = Vec::new();
+
+        for pair in params.windows(2) {
+            let [a, b] = [pair[0], pair[1]];
+            let is_inside = boundary.contains(self.point((a + b) / 2.));
+
+            match (is_inside, run.is_empty()) {
+                (true, true) => run.extend([a, b]),
+                (true, false) => run.push(b),
+                (false, true) => {}
+                (false, false) => runs.push(std::mem::take(&mut run)),
+            }
+        }
+        if !run.is_empty() {
+            runs.push(run);
+        }
+
+        if let Self::Line(_) = self {
+            // Lines don't need any points in between.
+            for run in &mut runs {
+                if let (Some(&first), Some(&last)) = (run.first(), run.last()) {
+                    *run = vec![first, last];
+                }
+            }
+        }
+
+        runs
+    }
+
+    /// Find the surface coordinates `s` where the ellipse crosses a path
+    ///
+    /// The sides of cylindrical faces are straight lines along either axis of
+    /// the surface coordinates. Those crossings are computed exactly. Others
+    /// are left to the approximation.
+    fn ellipse_crossings(&self, path: &SurfacePath) -> Vec<Scalar> {
+        let (a, b, c, k) = match self {
+            Self::Ellipse { a, b, c, k, .. } => (*a, *b, *c, *k),
+            Self::Line(_) => return Vec::new(),
+        };
+        let line = match path {
+            SurfacePath::Line(line) => line,
+            SurfacePath::Circle(_) => return Vec::new(),
+        };
+
+        let direction = line.direction();
+        if is_negligible(direction.u, direction) {
+            // A line of constant `s`
+            vec![line.origin().u]
+        } else if is_negligible(direction.v, direction) {
+            // A line of constant `t`
+            solve_trig(a, b, k - c * line.origin().v)
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+/// Find the line coordinates where a line crosses a path
+///
+/// The path is considered in its entirety, regardless of where its edge ends.
+/// Checking which parts of the line are actually within the face is left to
+/// the caller.
+fn line_crossings(line: &Line<2>, path: &SurfacePath) -> Vec<Scalar> {
+    let origin = line.origin();
+    let direction = line.direction();
+
+    match path {
+        SurfacePath::Line(other) => {
+            let denominator = direction.cross2d(&other.direction());
+            if is_negligible(
+                denominator,
+                direction * other.direction().magnitude(),
+            ) {
+                return Vec::new();
+            }
+
+            let offset = other.origin() - origin;
+            vec![offset.cross2d(&other.direction()) / denominator]
+        }
+        SurfacePath::Circle(circle) => {
+            // Solve `|origin + direction * x - center| = radius` for `x`.
+            let offset = origin - circle.center();
+
+            let a = direction.dot(&direction);
+            let b = offset.dot(&direction);
+            let c = offset.dot(&offset) - circle.radius() * circle.radius();
+
+            let discriminant = b * b - a * c;
+            if discriminant < Scalar::ZERO {
+                return Vec::new();
+            }
+
+            let root = Scalar::from(discriminant.into_f64().sqrt());
+            vec![(-b - root) / a, (-b + root) / a]
+        }
+    }
+}
+
+/// Solve `a * cos(s) + b * sin(s) = k` for `s`
+///
+/// Returns the solutions between `0` and `2 * PI`.
+fn solve_trig(a: Scalar, b: Scalar, k: Scalar) -> Vec<Scalar> {
+    let r = Vector::from([a, b]).magnitude();
+    if r == Scalar::ZERO || k.abs() > r {
+        return Vec::new();
+    }
+
+    let phi = Scalar::atan2(b, a);
+    let delta = (k / r).acos();
+
+    [phi - delta, phi + delta]
+        .into_iter()
+        .map(|s| (s % Scalar::TAU + Scalar::TAU) % Scalar::TAU)
+        .collect()
+}
+
+/// The increment between points that approximate a circle or ellipse
+///
+/// Works like the approximation of circles, with the semi-major axis of an
+/// ellipse taking the place of the radius.
+fn approx_increment(radius: Scalar, tolerance: Tolerance) -> Scalar {
+    let ratio = tolerance.inner() / radius;
+    let ratio = if ratio < Scalar::ONE {
+        ratio
+    } else {
+        Scalar::ONE
+    };
+
+    let num_points = Scalar::max(Scalar::PI / (Scalar::ONE - ratio).acos(), 3.);
+    Scalar::TAU / num_points.ceil()
+}
+
+/// Indicate whether a component of a vector is negligible compared to its size
+fn is_negligible<const D: usize>(component: Scalar, of: Vector<D>) -> bool {
+    component.abs() <= of.magnitude() * Scalar::from(1e-12)
+}
+
+/// Join line segments into closed polygons
+///
+/// Segments are joined where their end points are closer than `distance`.
+/// Their direction doesn't matter. Degenerate polygons are dropped. Returns an
+/// error, if the segments can't be joined into closed polygons.
+fn join_segments(
+    segments: Vec<[Point<2>; 2]>,
+    distance: Scalar,
+) -> Result<Vec<Vec<Point<2>>>, SectionError> {
+    let mut segments = segments
+        .into_iter()
+        .filter(|[a, b]| a.distance_to(b) > distance)
+        .collect::<Vec<_>>();
+
+    let mut polygons = Vec::new();
+
+    while let Some([start, mut end]) = segments.pop() {
+        let mut polygon = vec![start];
+
+        while end.distance_to(&start) > distance {
+            let next = segments.iter().enumerate().find_map(|(i, [a, b])| {
+                if a.distance_to(&end) <= distance {
+                    Some((i, *b))
+                } else if b.distance_to(&end) <= distance {
+                    Some((i, *a))
+                } else {
+                    None
+                }
+            });
+
+            let (i, next) = match next {
+                Some(next) => next,
+                None => return Err(SectionError::Incomplete { point: end }),
+            };
+
+            segments.swap_remove(i);
+            polygon.push(end);
+            end = next;
+        }
+
+        if polygon.len() >= 3 {
+            polygons.push(polygon);
+        }
+    }
+
+    Ok(polygons)
+}
+
+/// Sort polygons into exteriors, and the interiors within them
+///
+/// See [`nest_polygons`]. Exteriors are made counter-clockwise, interiors
+/// clockwise.
+fn nest(
+    polygons: Vec<Vec<Point<2>>>,
+) -> Vec<(Vec<Point<2>>, Vec<Vec<Point<2>>>)> {
+    let oriented = |i: usize, ccw: bool| {
+        let mut polygon = polygons[i].clone();
+        if signed_area(&polygon).is_positive() != ccw {
+            polygon.reverse();
+        }
+        polygon
+    };
+
+    let slices = polygons.iter().map(Vec::as_slice).collect::<Vec<_>>();
+
+    nest_polygons(&slices)
+        .into_iter()
+        .map(|(exterior, interiors)| {
+            let interiors = interiors
+                .into_iter()
+                .map(|interior| oriented(interior, false))
+                .collect();
+            (oriented(exterior, true), interiors)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{signed_area, Point, Scalar};
+
+    use crate::{
+        algorithms::{
+            reverse::Reverse, sweep::Sweep, transform::TransformObject,
+        },
+        builder::{FaceBuilder, HalfEdgeBuilder},
+        insert::Insert,
+        objects::{Cycle, Face, HalfEdge, Objects, Sketch, Solid, Surface},
+        partial::HasPartial,
+        storage::Handle,
+    };
+
+    use super::{join_segments, nest, Section, SectionError};
+
+    #[test]
+    fn section_of_cube() -> anyhow::Result<()> {
+        let objects = Objects::new();
+
+        let solid = Solid::builder(&objects)
+            .with_cube_from_edge_length(2.)
+            .build()?;
+        let plane = objects
+            .surfaces
+            .xy_plane()
+            .translate([0., 0., 0.25], &objects)?;
+
+        let sketch = solid.section(plane, TOLERANCE, &objects)?;
+
+        let faces = sketch.faces().into_iter().collect::<Vec<_>>();
+        assert_eq!(faces.len(), 1);
+
+        let face = faces[0];
+        assert_eq!(face.interiors().count(), 0);
+
+        let mut points = face
+            .exterior()
+            .half_edges()
+            .map(|half_edge| {
+                let [vertex, _] = half_edge.vertices();
+                vertex.surface_form().position()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(points.len(), 4);
+
+        let corner = Point::from([-1., -1.]);
+        points.sort_by_key(|point| point.distance_to(&corner));
+        assert!(points[0].distance_to(&corner) < Scalar::from(1e-9));
+
+        Ok(())
+    }
+
+    #[test]
+    fn section_with_interior_cycle() -> anyhow::Result<()> {
+        let objects = Objects::new();
+
+        let surface = objects.surfaces.xy_plane();
+        let face = Face::partial()
+            .with_surface(surface.clone())
+            .with_exterior_polygon_from_points([
+                [0., 0.],
+                [3., 0.],
+                [3., 3.],
+                [0., 3.],
+            ])
+            .with_interior_polygon_from_points([
+                [1., 1.],
+                [1., 2.],
+                [2., 2.],
+                [2., 1.],
+            ])
+            .build(&objects)?
+            .insert(&objects)?;
+        let solid = Sketch::builder(&objects)
+            .with_surface(surface.clone())
+            .with_faces([face])
+            .build()
+            .sweep([0., 0., 1.], &objects)?;
+
+        let plane = surface.translate([0., 0., 0.5], &objects)?;
+        let sketch = solid.section(plane, TOLERANCE, &objects)?;
+
+        let faces = sketch.faces().into_iter().collect::<Vec<_>>();
+        assert_eq!(faces.len(), 1);
+        assert_eq!(faces[0].interiors().count(), 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn section_of_cylinder() -> anyhow::Result<()> {
+        let objects = Objects::new();
+        let cylinder = cylinder(&objects)?;
+
+        let plane = objects
+            .surfaces
+            .xy_plane()
+            .translate([0., 0., 0.5], &objects)?;
+        let sketch = cylinder.section(plane, TOLERANCE, &objects)?;
+
+        let points = exteriors(&sketch);
+        assert_eq!(points.len(), 1);
+
+        for point in &points[0] {
+            let distance = point.distance_to(&Point::origin());
+            assert!((distance - Scalar::ONE).abs() < Scalar::from(1e-9));
+        }
+
+        let area = signed_area(&points[0]);
+        assert!(area < Scalar::PI);
+        assert!(area > Scalar::PI * (1. - 2. * TOLERANCE));
+
+        Ok(())
+    }
+
+    #[test]
+    fn section_of_cylinder_at_an_angle() -> anyhow::Result<()> {
+        let objects = Objects::new();
+        let cylinder = cylinder(&objects)?;
+
+        // The plane is tilted by 20 degrees around the x-axis. That is steep
+        // enough for a noticeable ellipse, but doesn't reach the top or bottom
+        // of the cylinder.
+        let (sin, cos) = (20_f64.to_radians()).sin_cos();
+        let plane = objects.surfaces.insert(Surface::plane_from_points([
+            [0., 0., 0.5],
+            [1., 0., 0.5],
+            [0., cos, 0.5 + sin],
+        ]))?;
+        let sketch = cylinder.section(plane, TOLERANCE, &objects)?;
+
+        let points = exteriors(&sketch);
+        assert_eq!(points.len(), 1);
+
+        // The section is an ellipse, with a semi-major axis of `1 / cos`.
+        let area = signed_area(&points[0]);
+        assert!(area < Scalar::PI / cos);
+        assert!(area > Scalar::PI / cos * (1. - 2. * TOLERANCE / cos));
+
+        // A steeper plane also crosses the top and bottom of the cylinder, and
+        // the parts of the section from those need to line up with the rest.
+        let plane = objects.surfaces.insert(Surface::plane_from_points([
+            [0., 0., 0.5],
+            [1., 0., 0.5],
+            [0., 1., 1.5],
+        ]))?;
+        let sketch = cylinder.section(plane, TOLERANCE, &objects)?;
+        assert_eq!(exteriors(&sketch).len(), 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn section_of_cylinder_along_its_axis() -> anyhow::Result<()> {
+        let objects = Objects::new();
+        let cylinder = cylinder(&objects)?;
+
+        // This plane also contains the seam of the cylinder's side face.
+        let plane = objects.surfaces.xz_plane();
+        let sketch = cylinder.section(plane, TOLERANCE, &objects)?;
+
+        let points = exteriors(&sketch);
+        assert_eq!(points.len(), 1);
+        assert_eq!(points[0].len(), 4);
+        assert!((signed_area(&points[0]) - 2.).abs() < Scalar::from(1e-9));
+
+        Ok(())
+    }
+
+    #[test]
+    fn section_of_block_with_hole() -> anyhow::Result<()> {
+        let objects = Objects::new();
+
+        let surface = objects.surfaces.xy_plane();
+        let hole = HalfEdge::partial()
+            .with_surface(surface.clone())
+            .update_as_circle_from_radius(1., &objects)?
+            .build(&objects)?
+            .insert(&objects)?;
+        let hole = objects.cycles.insert(Cycle::new([hole]))?;
+        let face = Face::partial()
+            .with_surface(surface.clone())
+            .with_exterior_polygon_from_points([
+                [-2., -2.],
+                [2., -2.],
+                [2., 2.],
+                [-2., 2.],
+            ])
+            .with_interiors([hole.reverse(&objects)?])
+            .build(&objects)?
+            .insert(&objects)?;
+        let block = Sketch::builder(&objects)
+            .with_surface(surface.clone())
+            .with_faces([face])
+            .build()
+            .sweep([0., 0., 1.], &objects)?;
+
+        let plane = surface.translate([0., 0., 0.5], &objects)?;
+        let sketch = block.section(plane, TOLERANCE, &objects)?;
+
+        let faces = sketch.faces().into_iter().collect::<Vec<_>>();
+        assert_eq!(faces.len(), 1);
+        assert_eq!(faces[0].interiors().count(), 1);
+
+        // The plane through the middle of the hole cuts the block in two.
+        let plane = objects.surfaces.yz_plane();
+        let sketch = block.section(plane, TOLERANCE, &objects)?;
+
+        let points = exteriors(&sketch);
+        assert_eq!(points.len(), 2);
+        for points in points {
+            assert!((signed_area(&points) - 1.).abs() < Scalar::from(1e-9));
+        }
+
+        Ok(())
+    }
+
+    const TOLERANCE: f64 = 0.001;
+
+    fn cylinder(objects: &Objects) -> anyhow::Result<Handle<Solid>> {
+        let surface = objects.surfaces.xy_plane();
+        let half_edge = HalfEdge::partial()
+            .with_surface(surface.clone())
+            .update_as_circle_from_radius(1., objects)?
+            .build(objects)?
+            .insert(objects)?;
+        let face = Face::partial()
+            .with_exterior(objects.cycles.insert(Cycle::new([half_edge]))?)
+            .build(objects)?
+            .insert(objects)?;
+        let cylinder = Sketch::builder(objects)
+            .with_surface(surface)
+            .with_faces([face])
+            .build()
+            .sweep([0., 0., 1.], objects)?;
+
+        Ok(cylinder)
+    }
+
+    /// The points of the exteriors of a sketch's faces
+    fn exteriors(sketch: &Sketch) -> Vec<Vec<Point<2>>> {
+        sketch
+            .faces()
+            .into_iter()
+            .map(|face| {
+                face.exterior()
+                    .half_edges()
+                    .map(|half_edge| {
+                        let [vertex, _] = half_edge.vertices();
+                        vertex.surface_form().position()
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn join_incomplete_segments() {
+        let segments = [
+            [[0., 0.], [1., 0.]],
+            [[1., 0.], [1., 1.]],
+            [[1., 1.], [0., 1.]],
+        ]
+        .map(|segment| segment.map(Point::from))
+        .to_vec();
+
+        assert!(matches!(
+            join_segments(segments, Scalar::from(1e-9)),
+            Err(SectionError::Incomplete { .. })
+        ));
+    }
+
+    #[test]
+    fn nest_polygons_into_faces() {
+        let polygon = |points: [[f64; 2]; 4]| points.map(Point::from).to_vec();
+
+        // A square with a hole, and an island within the hole. All of them are
+        // counter-clockwise, as they could come out of `join_segments`.
+        let outer = polygon([[0., 0.], [5., 0.], [5., 5.], [0., 5.]]);
+        let hole = polygon([[1., 1.], [4., 1.], [4., 4.], [1., 4.]]);
+        let island = polygon([[2., 2.], [3., 2.], [3., 3.], [2., 3.]]);
+
+        let nested = nest(vec![island, outer, hole]);
+        assert_eq!(nested.len(), 2);
+
+        // Exteriors are returned in the order they were passed in.
+        let (exterior, interiors) = &nested[0];
+        assert!(fj_math::signed_area(exterior) > Scalar::ZERO);
+        assert_eq!(exterior[0], Point::from([2., 2.]));
+        assert!(interiors.is_empty());
+
+        let (exterior, interiors) = &nested[1];
+        assert!(fj_math::signed_area(exterior) > Scalar::ZERO);
+        assert_eq!(interiors.len(), 1);
+        assert!(fj_math::signed_area(&interiors[0]) < Scalar::ZERO);
+    }
+}
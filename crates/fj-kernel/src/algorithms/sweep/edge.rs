@@ -1,12 +1,13 @@
 use fj_interop::{ext::ArrayExt, mesh::Color};
 use fj_math::{Line, Scalar, Vector};
+use try_insert_ext::EntryInsertExt;
 
 use crate::{
-    algorithms::{reverse::Reverse, transform::TransformObject},
+    algorithms::reverse::Reverse,
     insert::Insert,
     objects::{
-        Curve, Cycle, Face, GlobalEdge, HalfEdge, Objects, SurfaceVertex,
-        Vertex,
+        Curve, Cycle, Face, GlobalCurve, GlobalEdge, HalfEdge, Objects,
+        SurfaceVertex, Vertex,
     },
     partial::HasPartial,
     path::SurfacePath,
@@ -108,13 +109,23 @@ impl Sweep for (Handle<HalfEdge>, Color) {
                     (vertex.position(), [vertex.position().t, Scalar::ONE])
                 });
 
-            let curve = {
-                let global = bottom_edge
-                    .curve()
-                    .global_form()
-                    .clone()
-                    .translate(path, objects)?;
+            // The top edge might be shared with the top face of a swept face,
+            // so make sure it's only created once.
+            let global = cache
+                .top_global_edge
+                .entry(edge.global_form().id())
+                .or_try_insert_with(|| {
+                    let curve = objects.global_curves.insert(GlobalCurve)?;
+                    objects.global_edges.insert(GlobalEdge::new(
+                        curve,
+                        surface_vertices.clone().map(|surface_vertex| {
+                            surface_vertex.global_form().clone()
+                        }),
+                    ))
+                })?
+                .clone();
 
+            let curve = {
                 // Please note that creating a line here is correct, even if the
                 // global curve is a circle. Projected into the side surface, it
                 // is going to be a line either way.
@@ -123,16 +134,13 @@ impl Sweep for (Handle<HalfEdge>, Color) {
                         points_curve_and_surface,
                     ));
 
-                objects.curves.insert(Curve::new(surface, path, global))?
+                objects.curves.insert(Curve::new(
+                    surface,
+                    path,
+                    global.curve().clone(),
+                ))?
             };
 
-            let global = objects.global_edges.insert(GlobalEdge::new(
-                curve.global_form().clone(),
-                surface_vertices
-                    .clone()
-                    .map(|surface_vertex| surface_vertex.global_form().clone()),
-            ))?;
-
             let vertices = bottom_vertices
                 .each_ref_ext()
                 .zip_ext(surface_vertices)
@@ -1,8 +1,14 @@
+use std::collections::BTreeMap;
+
+use fj_interop::ext::ArrayExt;
 use fj_math::{Scalar, Vector};
+use try_insert_ext::EntryInsertExt;
 
 use crate::{
     algorithms::{reverse::Reverse, transform::TransformObject},
-    objects::{Face, Objects, Shell},
+    objects::{
+        Curve, Cycle, Face, HalfEdge, Objects, Shell, SurfaceVertex, Vertex,
+    },
     path::GlobalPath,
     storage::Handle,
     validate::ValidationError,
@@ -47,17 +53,6 @@ impl Sweep for Handle<Face> {
         };
         faces.push(bottom_face);
 
-        let top_face = {
-            let mut face = self.clone().translate(path, objects)?;
-
-            if is_negative_sweep {
-                face = face.reverse(objects)?;
-            };
-
-            face
-        };
-        faces.push(top_face);
-
         // Generate side faces
         for cycle in self.all_cycles() {
             for half_edge in cycle.half_edges() {
@@ -74,7 +69,93 @@ impl Sweep for Handle<Face> {
             }
         }
 
-        Ok(Shell::builder(objects).with_faces(faces).build())
+        // The top face can't just be a translated copy of the original face, as
+        // it needs to share its edges with the side faces.
+        let top_face = {
+            let surface = self.surface().clone().translate(path, objects)?;
+
+            let mut surface_vertices = BTreeMap::new();
+            let mut cycles = Vec::new();
+
+            for cycle in self.all_cycles() {
+                let mut half_edges = Vec::new();
+
+                for half_edge in cycle.half_edges() {
+                    let global_form = cache
+                        .top_global_edge
+                        .get(&half_edge.global_form().id())
+                        .expect("Expected side face to create top edge")
+                        .clone();
+
+                    let curve = objects.curves.insert(Curve::new(
+                        surface.clone(),
+                        half_edge.curve().path(),
+                        global_form.curve().clone(),
+                    ))?;
+
+                    let vertices =
+                        half_edge.vertices().each_ref_ext().try_map_ext(
+                            |vertex| -> Result<_, ValidationError> {
+                                let surface_form = surface_vertices
+                                    .entry(vertex.surface_form().id())
+                                    .or_try_insert_with(|| {
+                                        let global_form = cache
+                                        .global_vertex
+                                        .get(&vertex.global_form().id())
+                                        .expect(
+                                            "Expected side edge to create top \
+                                            vertex",
+                                        )
+                                        .clone();
+
+                                        objects.surface_vertices.insert(
+                                            SurfaceVertex::new(
+                                                vertex
+                                                    .surface_form()
+                                                    .position(),
+                                                surface.clone(),
+                                                global_form,
+                                            ),
+                                        )
+                                    })?
+                                    .clone();
+
+                                Ok(objects.vertices.insert(Vertex::new(
+                                    vertex.position(),
+                                    curve.clone(),
+                                    surface_form,
+                                ))?)
+                            },
+                        )?;
+
+                    half_edges.push(
+                        objects
+                            .half_edges
+                            .insert(HalfEdge::new(vertices, global_form))?,
+                    );
+                }
+
+                cycles.push(objects.cycles.insert(Cycle::new(half_edges))?);
+            }
+
+            let mut cycles = cycles.into_iter();
+            let exterior = cycles.next().expect("Face must have exterior");
+
+            let mut face = objects.faces.insert(Face::new(
+                exterior,
+                cycles,
+                self.color(),
+            ))?;
+
+            if is_negative_sweep {
+                face = face.reverse(objects)?;
+            };
+
+            face
+        };
+        faces.push(top_face);
+
+        Ok(objects.shells.insert(Shell::new(faces))?)
     }
 }
 
@@ -11,7 +11,7 @@ use std::collections::BTreeMap;
 use fj_math::Vector;
 
 use crate::{
-    objects::{GlobalVertex, Objects},
+    objects::{GlobalEdge, GlobalVertex, Objects},
     storage::{Handle, ObjectId},
     validate::ValidationError,
 };
@@ -42,9 +42,28 @@ pub trait Sweep: Sized {
 
 /// A cache used for sweeping
 ///
+/// Makes sure that objects that are created more than once while sweeping
+/// (like the edges that neighboring side faces share) are only created once,
+/// and shared between the objects that use them. This is required for the
+/// resulting shells to be closed.
+///
 /// See [`Sweep`].
 #[derive(Default)]
 pub struct SweepCache {
     /// Cache for global vertices
+    ///
+    /// Maps the ID of a swept global vertex to the global vertex at the end of
+    /// the sweep.
     pub global_vertex: BTreeMap<ObjectId, Handle<GlobalVertex>>,
+
+    /// Cache for global edges that are created by sweeping global vertices
+    ///
+    /// Maps the ID of a swept global vertex to the resulting global edge.
+    pub global_edge: BTreeMap<ObjectId, Handle<GlobalEdge>>,
+
+    /// Cache for global edges at the end of a sweep
+    ///
+    /// Maps the ID of a swept global edge to the global edge at the end of the
+    /// sweep.
+    pub top_global_edge: BTreeMap<ObjectId, Handle<GlobalEdge>>,
 }
@@ -137,8 +137,6 @@ impl Sweep for Handle<GlobalVertex> {
         cache: &mut SweepCache,
         objects: &Objects,
     ) -> Result<Self::Swept, ValidationError> {
-        let curve = objects.global_curves.insert(GlobalCurve)?;
-
         let a = self.clone();
         let b = cache
             .global_vertex
@@ -151,9 +149,16 @@ impl Sweep for Handle<GlobalVertex> {
             .clone();
 
         let vertices = [a, b];
-        let global_edge = objects
-            .global_edges
-            .insert(GlobalEdge::new(curve, vertices.clone()))?;
+        let global_edge = cache
+            .global_edge
+            .entry(self.id())
+            .or_try_insert_with(|| {
+                let curve = objects.global_curves.insert(GlobalCurve)?;
+                objects
+                    .global_edges
+                    .insert(GlobalEdge::new(curve, vertices.clone()))
+            })?
+            .clone();
 
         // The vertices of the returned `GlobalEdge` are in normalized order,
         // which means the order can't be relied upon by the caller. Return the
@@ -1,9 +1,10 @@
 use fj_math::Transform;
 
 use crate::{
+    algorithms::reverse::Reverse,
     insert::Insert,
-    objects::{Face, FaceSet, Objects},
-    partial::{HasPartial, PartialFace},
+    objects::{Cycle, Face, FaceSet, Objects},
+    partial::{HasPartial, MaybePartial, PartialFace},
     validate::ValidationError,
 };
 
@@ -19,20 +20,40 @@ impl TransformObject for PartialFace {
             .surface()
             .map(|surface| surface.transform(transform, objects))
             .transpose()?;
+        // A transform that flips handedness (like a mirroring) also flips the
+        // handedness of the surface coordinate system. Reversing the cycles
+        // compensates for that, so the face keeps pointing outward.
+        let flips_handedness = transform.flips_handedness();
+
         let exterior = self
             .exterior()
             .into_partial()
             .transform(transform, objects)?
             .with_surface(surface.clone());
+        let exterior: MaybePartial<Cycle> = if flips_handedness {
+            exterior
+                .build(objects)?
+                .insert(objects)?
+                .reverse(objects)?
+                .into()
+        } else {
+            exterior.into()
+        };
         let interiors = self
             .interiors()
             .map(|cycle| -> Result<_, ValidationError> {
-                Ok(cycle
+                let cycle = cycle
                     .into_partial()
                     .transform(transform, objects)?
                     .with_surface(surface.clone())
                     .build(objects)?
-                    .insert(objects)?)
+                    .insert(objects)?;
+
+                if flips_handedness {
+                    return cycle.reverse(objects);
+                }
+
+                Ok(cycle)
             })
             .collect::<Result<Vec<_>, _>>()?;
 
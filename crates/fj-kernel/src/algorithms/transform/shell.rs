@@ -1,13 +1,22 @@
 use fj_math::Transform;
 
 use crate::{
-    objects::{Objects, Shell},
+    algorithms::heal::heal_faces,
+    insert::Insert,
+    objects::{FaceSet, Objects, Shell},
     storage::Handle,
-    validate::ValidationError,
+    validate::{ValidationConfig, ValidationError},
 };
 
 use super::TransformObject;
 
+/// # Implementation Note
+///
+/// Faces are transformed one by one, which means the transformed faces no
+/// longer share their edges. To get a closed shell again, they are healed
+/// afterwards. Transforming shared objects only once would be more efficient,
+/// but that requires the transformation to keep track of the objects it has
+/// already transformed.
 impl TransformObject for Handle<Shell> {
     fn transform(
         self,
@@ -21,7 +30,38 @@ impl TransformObject for Handle<Shell> {
             .map(|face| -> Result<_, ValidationError> {
                 face.transform(transform, objects)
             })
-            .collect::<Result<Vec<_>, _>>()?;
-        Ok(Shell::builder(objects).with_faces(faces).build())
+            .collect::<Result<FaceSet, _>>()?;
+
+        let (faces, _) =
+            heal_faces(faces, &ValidationConfig::default(), objects)?;
+        Ok(Shell::new(faces).insert(objects)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::Transform;
+
+    use crate::{
+        algorithms::transform::TransformObject,
+        objects::{Objects, Shell, Solid},
+    };
+
+    #[test]
+    fn mirror_keeps_shell_oriented_outward() -> anyhow::Result<()> {
+        let objects = Objects::new();
+
+        let shell = Shell::builder(&objects)
+            .with_cube_from_edge_length(1.)
+            .build()?;
+
+        let shell =
+            shell.transform(&Transform::mirror([1., 0., 0.]), &objects)?;
+        assert_eq!(shell.faces().into_iter().count(), 6);
+
+        // Inserting the solid validates the orientation of its shell.
+        Solid::builder(&objects).with_shells([shell]).build()?;
+
+        Ok(())
     }
 }
@@ -3,11 +3,15 @@
 mod delaunay;
 mod polygon;
 
+use std::{panic, thread};
+
 use fj_interop::mesh::Mesh;
 use fj_math::Point;
 
 use self::{delaunay::TriangulationPoint, polygon::Polygon};
 
+use crate::threads::ThreadPermit;
+
 use super::approx::{face::FaceApprox, Approx, Tolerance};
 
 /// Triangulate a shape
@@ -34,10 +38,20 @@ where
     fn triangulate_into_mesh(self, mesh: &mut Mesh<Point<3>>) {
         let (approx, tolerance) = self;
 
-        let approx = approx.approx(tolerance);
-
-        for approx in approx {
-            approx.triangulate_into_mesh(mesh);
+        // The approximation has to happen in one go, as the faces share a
+        // cache. That makes sure that the edges that neighboring faces share
+        // are approximated in the exact same way, which is required for the
+        // resulting mesh to be watertight.
+        //
+        // Triangulating the face approximations, on the other hand, doesn't
+        // require any shared state, and is where most of the time is spent. So
+        // that part is done in parallel.
+        let approx = approx.approx(tolerance).into_iter().collect();
+
+        for face_mesh in triangulate_faces_in_parallel(approx) {
+            for triangle in face_mesh.triangles() {
+                mesh.push_triangle(triangle.inner, triangle.color);
+            }
         }
     }
 }
@@ -77,6 +91,70 @@ impl Triangulate for FaceApprox {
     }
 }
 
+/// Triangulate the provided face approximations using the available threads
+///
+/// Threads are only spawned, as far as a [`ThreadPermit`] is available for
+/// them. If none are (which is always the case on WebAssembly), the faces are
+/// triangulated in the current thread.
+///
+/// Returns one mesh per thread that was used. The order of the returned meshes
+/// (and of the triangles within them) matches the order of the provided faces,
+/// so the result is deterministic, regardless of the number of threads.
+fn triangulate_faces_in_parallel(
+    faces: Vec<FaceApprox>,
+) -> Vec<Mesh<Point<3>>> {
+    // The current thread takes part in the work, so it doesn't need a permit.
+    let permits = ThreadPermit::acquire_many(faces.len().saturating_sub(1));
+    if permits.is_empty() {
+        return vec![triangulate_faces(faces)];
+    }
+
+    let num_threads = permits.len() + 1;
+    let faces_per_thread = (faces.len() + num_threads - 1) / num_threads;
+
+    let mut chunks = Vec::new();
+    let mut faces = faces.into_iter().peekable();
+    while faces.peek().is_some() {
+        chunks.push(faces.by_ref().take(faces_per_thread).collect::<Vec<_>>());
+    }
+
+    let mut chunks = chunks.into_iter();
+    let first = chunks.next().unwrap_or_default();
+
+    thread::scope(|scope| {
+        let threads = chunks
+            .zip(permits)
+            .map(|(chunk, permit)| {
+                scope.spawn(move || {
+                    let _permit = permit;
+                    triangulate_faces(chunk)
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let mut meshes = vec![triangulate_faces(first)];
+        meshes.extend(threads.into_iter().map(|thread| {
+            // If triangulation panicked in another thread, we want it to look
+            // like it did so in this one.
+            thread
+                .join()
+                .unwrap_or_else(|err| panic::resume_unwind(err))
+        }));
+
+        meshes
+    })
+}
+
+fn triangulate_faces(faces: Vec<FaceApprox>) -> Mesh<Point<3>> {
+    let mut mesh = Mesh::new();
+
+    for face in faces {
+        face.triangulate_into_mesh(&mut mesh);
+    }
+
+    mesh
+}
+
 #[cfg(test)]
 mod tests {
     use fj_interop::mesh::Mesh;
@@ -86,9 +164,10 @@ mod tests {
         algorithms::approx::{Approx, Tolerance},
         builder::FaceBuilder,
         insert::Insert,
-        objects::{Face, Objects},
+        objects::{Face, Objects, Shell},
         partial::HasPartial,
         storage::Handle,
+        threads::ThreadPermit,
     };
 
     use super::Triangulate;
@@ -224,6 +303,30 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn triangulate_without_available_threads() -> anyhow::Result<()> {
+        let objects = Objects::new();
+
+        let shell = Shell::builder(&objects)
+            .with_cube_from_edge_length(1.)
+            .build()?;
+        let tolerance = Tolerance::from_scalar(Scalar::ONE)?;
+
+        let parallel = (&*shell, tolerance).triangulate();
+
+        // Other computations might be using all available threads.
+        let permits = ThreadPermit::acquire_many(usize::MAX);
+        let sequential = (&*shell, tolerance).triangulate();
+        drop(permits);
+
+        assert_eq!(
+            parallel.triangles().collect::<Vec<_>>(),
+            sequential.triangles().collect::<Vec<_>>()
+        );
+
+        Ok(())
+    }
+
     fn triangulate(face: Handle<Face>) -> anyhow::Result<Mesh<Point<3>>> {
         let tolerance = Tolerance::from_scalar(Scalar::ONE)?;
         Ok(face.approx(tolerance).triangulate())
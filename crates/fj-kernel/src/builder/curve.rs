@@ -1,4 +1,4 @@
-use fj_math::{Point, Scalar, Vector};
+use fj_math::{Circle, Point, Scalar, Vector};
 
 use crate::{partial::PartialCurve, path::SurfacePath};
 
@@ -13,6 +13,13 @@ pub trait CurveBuilder {
     /// Update partial curve as a circle, from the provided radius
     fn update_as_circle_from_radius(self, radius: impl Into<Scalar>) -> Self;
 
+    /// Update partial curve as a circle, from the provided center and radius
+    fn update_as_circle_from_center_and_radius(
+        self,
+        center: impl Into<Point<2>>,
+        radius: impl Into<Scalar>,
+    ) -> Self;
+
     /// Update partial curve as a line, from the provided points
     fn update_as_line_from_points(
         self,
@@ -36,7 +43,17 @@ impl CurveBuilder for PartialCurve {
     }
 
     fn update_as_circle_from_radius(self, radius: impl Into<Scalar>) -> Self {
-        self.with_path(Some(SurfacePath::circle_from_radius(radius)))
+        self.update_as_circle_from_center_and_radius(Point::origin(), radius)
+    }
+
+    fn update_as_circle_from_center_and_radius(
+        self,
+        center: impl Into<Point<2>>,
+        radius: impl Into<Scalar>,
+    ) -> Self {
+        self.with_path(Some(SurfacePath::Circle(
+            Circle::from_center_and_radius(center, radius),
+        )))
     }
 
     fn update_as_line_from_points(
@@ -35,6 +35,16 @@ pub trait HalfEdgeBuilder: Sized {
         objects: &Objects,
     ) -> Result<Self, ValidationError>;
 
+    /// Update partial half-edge as a circle, from the given center and radius
+    ///
+    /// See [`HalfEdgeBuilder::update_as_circle_from_radius`].
+    fn update_as_circle_from_center_and_radius(
+        self,
+        center: impl Into<Point<2>>,
+        radius: impl Into<Scalar>,
+        objects: &Objects,
+    ) -> Result<Self, ValidationError>;
+
     /// Update partial half-edge as a line segment, from the given points
     fn update_as_line_segment_from_points(
         self,
@@ -64,12 +74,25 @@ impl HalfEdgeBuilder for PartialHalfEdge {
         self,
         radius: impl Into<Scalar>,
         objects: &Objects,
+    ) -> Result<Self, ValidationError> {
+        self.update_as_circle_from_center_and_radius(
+            Point::origin(),
+            radius,
+            objects,
+        )
+    }
+
+    fn update_as_circle_from_center_and_radius(
+        self,
+        center: impl Into<Point<2>>,
+        radius: impl Into<Scalar>,
+        objects: &Objects,
     ) -> Result<Self, ValidationError> {
         let curve = self
             .curve()
             .into_partial()
             .with_global_form(Some(self.extract_global_curve()))
-            .update_as_circle_from_radius(radius);
+            .update_as_circle_from_center_and_radius(center, radius);
 
         let path = curve.path().expect("Expected path that was just created");
 
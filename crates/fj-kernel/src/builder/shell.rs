@@ -4,7 +4,7 @@ use fj_interop::ext::{ArrayExt, SliceExt};
 use fj_math::Scalar;
 
 use crate::{
-    algorithms::transform::TransformObject,
+    algorithms::{reverse::Reverse, transform::TransformObject},
     builder::{FaceBuilder, HalfEdgeBuilder},
     insert::Insert,
     objects::{
@@ -13,6 +13,7 @@ use crate::{
     },
     partial::HasPartial,
     storage::Handle,
+    validate::ValidationError,
 };
 
 /// API for building a [`Shell`]
@@ -87,6 +88,9 @@ impl<'a> ShellBuilder<'a> {
                 })
                 .collect::<Vec<_>>();
 
+            // The side surfaces are defined by vectors that have the length of
+            // the cube's edges. In surface coordinates, those edges have a
+            // length of one.
             let bottoms = bottom
                 .exterior()
                 .half_edges()
@@ -96,7 +100,7 @@ impl<'a> ShellBuilder<'a> {
                         .with_global_form(half_edge.global_form().clone())
                         .update_as_line_segment_from_points(
                             surface.clone(),
-                            [[Z, Z], [edge_length, Z]],
+                            [[Z, Z], [Scalar::ONE, Z]],
                         )
                         .build(self.objects)
                         .unwrap()
@@ -114,7 +118,7 @@ impl<'a> ShellBuilder<'a> {
 
                     let from = from.surface_form().clone();
                     let to = SurfaceVertex::partial()
-                        .with_position(Some(from.position() + [Z, edge_length]))
+                        .with_position(Some(from.position() + [Z, Scalar::ONE]))
                         .with_surface(Some(surface.clone()));
 
                     HalfEdge::partial()
@@ -146,7 +150,7 @@ impl<'a> ShellBuilder<'a> {
                         let to = to.surface_form().clone();
                         let from = SurfaceVertex::partial()
                             .with_position(Some(
-                                to.position() + [Z, edge_length],
+                                to.position() + [Z, Scalar::ONE],
                             ))
                             .with_surface(Some(surface.clone()))
                             .with_global_form(Some(from.global_form().clone()));
@@ -161,6 +165,9 @@ impl<'a> ShellBuilder<'a> {
                                 Vertex::partial().with_surface_form(from),
                                 Vertex::partial().with_surface_form(to),
                             ])
+                            .with_global_form(
+                                side_up_prev.global_form().clone(),
+                            )
                             .update_as_line_segment()
                             .build(self.objects)
                             .unwrap()
@@ -292,6 +299,14 @@ impl<'a> ShellBuilder<'a> {
                 .unwrap()
         };
 
+        // The bottom and top faces were built with their exteriors winding
+        // counter-clockwise and clockwise, respectively, when viewed from
+        // above. That makes them point into the cube, and their half-edges run
+        // in the same direction as those of the side faces that they share a
+        // global edge with. Reversing them fixes both.
+        let bottom = bottom.reverse(self.objects).unwrap();
+        let top = top.reverse(self.objects).unwrap();
+
         self.faces.extend([bottom]);
         self.faces.extend(sides);
         self.faces.extend([top]);
@@ -300,7 +315,8 @@ impl<'a> ShellBuilder<'a> {
     }
 
     /// Build the [`Shell`]
-    pub fn build(self) -> Handle<Shell> {
-        self.objects.shells.insert(Shell::new(self.faces)).unwrap()
+    pub fn build(self) -> Result<Handle<Shell>, ValidationError> {
+        let shell = self.objects.shells.insert(Shell::new(self.faces))?;
+        Ok(shell)
     }
 }
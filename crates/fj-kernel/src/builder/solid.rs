@@ -5,6 +5,7 @@ use fj_math::Scalar;
 use crate::{
     objects::{Objects, Shell, Solid},
     storage::Handle,
+    validate::ValidationError,
 };
 
 /// API for building a [`Solid`]
@@ -35,13 +36,15 @@ impl<'a> SolidBuilder<'a> {
     ) -> Self {
         let shell = Shell::builder(self.objects)
             .with_cube_from_edge_length(edge_length)
-            .build();
+            .build()
+            .unwrap();
         self.shells.insert(shell);
         self
     }
 
     /// Build the [`Solid`]
-    pub fn build(self) -> Handle<Solid> {
-        self.objects.solids.insert(Solid::new(self.shells)).unwrap()
+    pub fn build(self) -> Result<Handle<Solid>, ValidationError> {
+        let solid = self.objects.solids.insert(Solid::new(self.shells))?;
+        Ok(solid)
     }
 }
@@ -8,6 +8,7 @@ use crate::{
         Solid, Surface, Vertex,
     },
     storage::Handle,
+    validate::{ObjectKind, ObjectPath, ValidationConfig, ValidationReport},
 };
 
 /// Access iterators over all objects of a shape, or part of it
@@ -138,6 +139,21 @@ pub trait ObjectIters<'r> {
 
         iter
     }
+
+    /// Validate all objects, adding any issues to the report
+    ///
+    /// Implementations for object types validate the object itself, before
+    /// moving on to the objects it references. See [`ValidationReport`].
+    fn validate_into_report(
+        &'r self,
+        path: &ObjectPath,
+        config: &ValidationConfig,
+        report: &mut ValidationReport,
+    ) {
+        for object in self.referenced_objects() {
+            object.validate_into_report(path, config, report);
+        }
+    }
 }
 
 impl<'r> ObjectIters<'r> for Handle<Curve> {
@@ -148,6 +164,22 @@ impl<'r> ObjectIters<'r> for Handle<Curve> {
     fn curve_iter(&'r self) -> Iter<&'r Curve> {
         Iter::from_object(self)
     }
+
+    fn validate_into_report(
+        &'r self,
+        path: &ObjectPath,
+        config: &ValidationConfig,
+        report: &mut ValidationReport,
+    ) {
+        report.validate_object(
+            self,
+            &**self,
+            ObjectKind::Curve,
+            Some(self.id()),
+            path,
+            config,
+        );
+    }
 }
 
 impl<'r> ObjectIters<'r> for Handle<Cycle> {
@@ -164,6 +196,22 @@ impl<'r> ObjectIters<'r> for Handle<Cycle> {
     fn cycle_iter(&'r self) -> Iter<&'r Cycle> {
         Iter::from_object(self)
     }
+
+    fn validate_into_report(
+        &'r self,
+        path: &ObjectPath,
+        config: &ValidationConfig,
+        report: &mut ValidationReport,
+    ) {
+        report.validate_object(
+            self,
+            &**self,
+            ObjectKind::Cycle,
+            Some(self.id()),
+            path,
+            config,
+        );
+    }
 }
 
 impl<'r> ObjectIters<'r> for Handle<Face> {
@@ -180,6 +228,22 @@ impl<'r> ObjectIters<'r> for Handle<Face> {
     fn face_iter(&'r self) -> Iter<&'r Face> {
         Iter::from_object(self)
     }
+
+    fn validate_into_report(
+        &'r self,
+        path: &ObjectPath,
+        config: &ValidationConfig,
+        report: &mut ValidationReport,
+    ) {
+        report.validate_object(
+            self,
+            &**self,
+            ObjectKind::Face,
+            Some(self.id()),
+            path,
+            config,
+        );
+    }
 }
 
 impl<'r> ObjectIters<'r> for Handle<GlobalCurve> {
@@ -190,6 +254,22 @@ impl<'r> ObjectIters<'r> for Handle<GlobalCurve> {
     fn global_curve_iter(&'r self) -> Iter<&'r Handle<GlobalCurve>> {
         Iter::from_object(self)
     }
+
+    fn validate_into_report(
+        &'r self,
+        path: &ObjectPath,
+        config: &ValidationConfig,
+        report: &mut ValidationReport,
+    ) {
+        report.validate_object(
+            self,
+            &**self,
+            ObjectKind::GlobalCurve,
+            Some(self.id()),
+            path,
+            config,
+        );
+    }
 }
 
 impl<'r> ObjectIters<'r> for Handle<GlobalVertex> {
@@ -200,6 +280,22 @@ impl<'r> ObjectIters<'r> for Handle<GlobalVertex> {
     fn global_vertex_iter(&'r self) -> Iter<&'r GlobalVertex> {
         Iter::from_object(self)
     }
+
+    fn validate_into_report(
+        &'r self,
+        path: &ObjectPath,
+        config: &ValidationConfig,
+        report: &mut ValidationReport,
+    ) {
+        report.validate_object(
+            self,
+            &**self,
+            ObjectKind::GlobalVertex,
+            Some(self.id()),
+            path,
+            config,
+        );
+    }
 }
 
 impl<'r> ObjectIters<'r> for Handle<HalfEdge> {
@@ -216,6 +312,22 @@ impl<'r> ObjectIters<'r> for Handle<HalfEdge> {
     fn half_edge_iter(&'r self) -> Iter<&'r Handle<HalfEdge>> {
         Iter::from_object(self)
     }
+
+    fn validate_into_report(
+        &'r self,
+        path: &ObjectPath,
+        config: &ValidationConfig,
+        report: &mut ValidationReport,
+    ) {
+        report.validate_object(
+            self,
+            &**self,
+            ObjectKind::HalfEdge,
+            Some(self.id()),
+            path,
+            config,
+        );
+    }
 }
 
 impl<'r> ObjectIters<'r> for Handle<Shell> {
@@ -232,6 +344,22 @@ impl<'r> ObjectIters<'r> for Handle<Shell> {
     fn shell_iter(&'r self) -> Iter<&'r Shell> {
         Iter::from_object(self)
     }
+
+    fn validate_into_report(
+        &'r self,
+        path: &ObjectPath,
+        config: &ValidationConfig,
+        report: &mut ValidationReport,
+    ) {
+        report.validate_object(
+            self,
+            &**self,
+            ObjectKind::Shell,
+            Some(self.id()),
+            path,
+            config,
+        );
+    }
 }
 
 impl<'r> ObjectIters<'r> for Sketch {
@@ -248,6 +376,22 @@ impl<'r> ObjectIters<'r> for Sketch {
     fn sketch_iter(&'r self) -> Iter<&'r Sketch> {
         Iter::from_object(self)
     }
+
+    fn validate_into_report(
+        &'r self,
+        path: &ObjectPath,
+        config: &ValidationConfig,
+        report: &mut ValidationReport,
+    ) {
+        report.validate_object(
+            self,
+            self,
+            ObjectKind::Sketch,
+            None,
+            path,
+            config,
+        );
+    }
 }
 
 impl<'r> ObjectIters<'r> for Solid {
@@ -264,6 +408,22 @@ impl<'r> ObjectIters<'r> for Solid {
     fn solid_iter(&'r self) -> Iter<&'r Solid> {
         Iter::from_object(self)
     }
+
+    fn validate_into_report(
+        &'r self,
+        path: &ObjectPath,
+        config: &ValidationConfig,
+        report: &mut ValidationReport,
+    ) {
+        report.validate_object(
+            self,
+            self,
+            ObjectKind::Solid,
+            None,
+            path,
+            config,
+        );
+    }
 }
 
 impl<'r> ObjectIters<'r> for Handle<Surface> {
@@ -274,6 +434,22 @@ impl<'r> ObjectIters<'r> for Handle<Surface> {
     fn surface_iter(&'r self) -> Iter<&'r Surface> {
         Iter::from_object(self)
     }
+
+    fn validate_into_report(
+        &'r self,
+        path: &ObjectPath,
+        config: &ValidationConfig,
+        report: &mut ValidationReport,
+    ) {
+        report.validate_object(
+            self,
+            &**self,
+            ObjectKind::Surface,
+            Some(self.id()),
+            path,
+            config,
+        );
+    }
 }
 
 impl<'r> ObjectIters<'r> for Handle<Vertex> {
@@ -287,6 +463,22 @@ impl<'r> ObjectIters<'r> for Handle<Vertex> {
     fn vertex_iter(&'r self) -> Iter<&'r Handle<Vertex>> {
         Iter::from_object(self)
     }
+
+    fn validate_into_report(
+        &'r self,
+        path: &ObjectPath,
+        config: &ValidationConfig,
+        report: &mut ValidationReport,
+    ) {
+        report.validate_object(
+            self,
+            &**self,
+            ObjectKind::Vertex,
+            Some(self.id()),
+            path,
+            config,
+        );
+    }
 }
 
 // This implementation is useful for test code.
@@ -524,12 +716,12 @@ mod tests {
     }
 
     #[test]
-    fn shell() {
+    fn shell() -> anyhow::Result<()> {
         let objects = Objects::new();
 
         let object = Shell::builder(&objects)
             .with_cube_from_edge_length(1.)
-            .build();
+            .build()?;
 
         assert_eq!(24, object.curve_iter().count());
         assert_eq!(6, object.cycle_iter().count());
@@ -542,6 +734,8 @@ mod tests {
         assert_eq!(0, object.solid_iter().count());
         assert_eq!(6, object.surface_iter().count());
         assert_eq!(48, object.vertex_iter().count());
+
+        Ok(())
     }
 
     #[test]
@@ -572,12 +766,12 @@ mod tests {
     }
 
     #[test]
-    fn solid() {
+    fn solid() -> anyhow::Result<()> {
         let objects = Objects::new();
 
         let object = Solid::builder(&objects)
             .with_cube_from_edge_length(1.)
-            .build();
+            .build()?;
 
         assert_eq!(24, object.curve_iter().count());
         assert_eq!(6, object.cycle_iter().count());
@@ -590,6 +784,8 @@ mod tests {
         assert_eq!(1, object.solid_iter().count());
         assert_eq!(6, object.surface_iter().count());
         assert_eq!(48, object.vertex_iter().count());
+
+        Ok(())
     }
 
     #[test]
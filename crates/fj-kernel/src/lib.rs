@@ -95,4 +95,5 @@ pub mod objects;
 pub mod partial;
 pub mod path;
 pub mod storage;
+pub mod threads;
 pub mod validate;
@@ -104,6 +104,7 @@ use crate::{
     storage::{Handle, Store},
     validate::{
         CycleValidationError, FaceValidationError, HalfEdgeValidationError,
+        ShellValidationError, SolidValidationError,
         SurfaceVertexValidationError, Validate, VertexValidationError,
     },
 };
@@ -289,7 +290,10 @@ pub struct Shells {
 
 impl Shells {
     /// Insert a [`Shell`] into the store
-    pub fn insert(&self, shell: Shell) -> Result<Handle<Shell>, Infallible> {
+    pub fn insert(
+        &self,
+        shell: Shell,
+    ) -> Result<Handle<Shell>, ShellValidationError> {
         shell.validate()?;
         Ok(self.store.insert(shell))
     }
@@ -317,7 +321,10 @@ pub struct Solids {
 
 impl Solids {
     /// Insert a [`Solid`] into the store
-    pub fn insert(&self, solid: Solid) -> Result<Handle<Solid>, Infallible> {
+    pub fn insert(
+        &self,
+        solid: Solid,
+    ) -> Result<Handle<Solid>, SolidValidationError> {
         solid.validate()?;
         Ok(self.store.insert(solid))
     }
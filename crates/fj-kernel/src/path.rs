@@ -143,6 +143,12 @@ impl GlobalPath {
     }
 
     /// Transform the path
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the path is a circle and the transform doesn't preserve
+    /// that (which is the case for non-uniform scalings). Curved surfaces
+    /// that result from sweeping a circle can't be scaled non-uniformly.
     #[must_use]
     pub fn transform(self, transform: &Transform) -> Self {
         match self {
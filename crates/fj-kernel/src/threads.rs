@@ -0,0 +1,75 @@
+//! Budget for the threads that computations spawn
+//!
+//! See [`ThreadPermit`].
+
+use std::{
+    num::NonZeroUsize,
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+};
+
+static THREADS_IN_USE: AtomicUsize = AtomicUsize::new(0);
+
+/// Permission to spawn a thread
+///
+/// Computations that run in parallel might be nested within each other (like
+/// the triangulation of a shape that is computed in parallel with another
+/// one). All of them draw from the same budget, so the number of threads in
+/// use doesn't multiply.
+///
+/// Only as many permits are handed out at any one time, as there are threads
+/// available in addition to the current one. The permit is returned when it is
+/// dropped.
+pub struct ThreadPermit(());
+
+impl ThreadPermit {
+    /// Acquire a permit, if a thread is available
+    pub fn acquire() -> Option<Self> {
+        THREADS_IN_USE
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |in_use| {
+                (in_use < Self::max_threads()).then_some(in_use + 1)
+            })
+            .ok()
+            .map(|_| Self(()))
+    }
+
+    /// Acquire up to `max` permits, as many as there are threads available
+    pub fn acquire_many(max: usize) -> Vec<Self> {
+        let mut permits = Vec::new();
+
+        while permits.len() < max {
+            match Self::acquire() {
+                Some(permit) => permits.push(permit),
+                None => break,
+            }
+        }
+
+        permits
+    }
+
+    /// The number of permits that are currently handed out
+    pub fn in_use() -> usize {
+        THREADS_IN_USE.load(Ordering::Acquire)
+    }
+
+    /// The maximum number of permits that are handed out at any one time
+    ///
+    /// This is zero on WebAssembly, where spawning threads panics. Everything
+    /// runs in the current thread there.
+    pub fn max_threads() -> usize {
+        if cfg!(target_arch = "wasm32") {
+            return 0;
+        }
+
+        thread::available_parallelism()
+            .map(NonZeroUsize::get)
+            .unwrap_or(1)
+            - 1
+    }
+}
+
+impl Drop for ThreadPermit {
+    fn drop(&mut self) {
+        THREADS_IN_USE.fetch_sub(1, Ordering::AcqRel);
+    }
+}
@@ -2,26 +2,20 @@ use std::convert::Infallible;
 
 use crate::objects::{Curve, GlobalCurve};
 
-use super::{Validate, ValidationConfig};
+use super::{Validate, ValidationCheck};
 
 impl Validate for Curve {
     type Error = Infallible;
 
-    fn validate_with_config(
-        &self,
-        _: &ValidationConfig,
-    ) -> Result<(), Self::Error> {
-        Ok(())
+    fn checks() -> &'static [ValidationCheck<Self>] {
+        &[]
     }
 }
 
 impl Validate for GlobalCurve {
     type Error = Infallible;
 
-    fn validate_with_config(
-        &self,
-        _: &ValidationConfig,
-    ) -> Result<(), Self::Error> {
-        Ok(())
+    fn checks() -> &'static [ValidationCheck<Self>] {
+        &[]
     }
 }
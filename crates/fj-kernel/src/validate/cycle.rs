@@ -1,26 +1,26 @@
+use fj_math::{Aabb, Circle, Line, Point, Scalar, Segment, Vector};
 use itertools::Itertools;
 
 use crate::{
-    objects::{Cycle, SurfaceVertex},
+    algorithms::intersect::LineSegmentIntersection,
+    objects::{Cycle, HalfEdge, SurfaceVertex},
+    path::SurfacePath,
     storage::Handle,
 };
 
-use super::{Validate, ValidationConfig};
+use super::{Validate, ValidationCheck, ValidationConfig};
 
 impl Validate for Cycle {
     type Error = CycleValidationError;
 
-    fn validate_with_config(
-        &self,
-        _: &ValidationConfig,
-    ) -> Result<(), Self::Error> {
-        CycleValidationError::check_half_edge_connections(self)?;
-
+    fn checks() -> &'static [ValidationCheck<Self>] {
         // We don't need to check that all half-edges are defined in the same
         // surface. We already check that they are connected by identical
         // surface vertices, so that would be redundant.
-
-        Ok(())
+        &[
+            CycleValidationError::check_half_edge_connections,
+            CycleValidationError::check_self_intersection,
+        ]
     }
 }
 
@@ -40,10 +40,56 @@ pub enum CycleValidationError {
         /// The back vertex of the next half-edge
         next: Handle<SurfaceVertex>,
     },
+
+    /// Half-edges of the cycle intersect each other
+    #[error(
+        "`HalfEdge`s of `Cycle` intersect each other\n\
+        - Intersection points: {points:#?}\n\
+        - `HalfEdge`s: {half_edges:#?}"
+    )]
+    SelfIntersection {
+        /// The half-edges that intersect
+        half_edges: [Handle<HalfEdge>; 2],
+
+        /// The points where the half-edges intersect, in global coordinates
+        points: Vec<Point<3>>,
+    },
+
+    /// Half-edges of the cycle overlap each other
+    #[error(
+        "`HalfEdge`s of `Cycle` overlap each other\n\
+        - Points bounding the overlap: {points:#?}\n\
+        - `HalfEdge`s: {half_edges:#?}"
+    )]
+    Overlap {
+        /// The half-edges that overlap
+        half_edges: [Handle<HalfEdge>; 2],
+
+        /// The points that bound the overlap, in global coordinates
+        points: Vec<Point<3>>,
+    },
 }
 
 impl CycleValidationError {
-    fn check_half_edge_connections(cycle: &Cycle) -> Result<(), Self> {
+    /// Access the points where the problem is located
+    pub(super) fn points(&self) -> Vec<Point<3>> {
+        match self {
+            Self::HalfEdgeConnection { prev, next } => {
+                vec![
+                    prev.global_form().position(),
+                    next.global_form().position(),
+                ]
+            }
+            Self::SelfIntersection { points, .. }
+            | Self::Overlap { points, .. } => points.clone(),
+        }
+    }
+
+    fn check_half_edge_connections(
+        cycle: &Cycle,
+        _: &ValidationConfig,
+        errors: &mut Vec<Self>,
+    ) {
         for (a, b) in cycle.half_edges().circular_tuple_windows() {
             let [_, prev] = a.vertices();
             let [next, _] = b.vertices();
@@ -52,27 +98,390 @@ impl CycleValidationError {
             let next = next.surface_form();
 
             if prev.id() != next.id() {
-                return Err(Self::HalfEdgeConnection {
+                errors.push(Self::HalfEdgeConnection {
                     prev: prev.clone(),
                     next: next.clone(),
                 });
             }
         }
+    }
 
-        Ok(())
+    fn check_self_intersection(
+        cycle: &Cycle,
+        config: &ValidationConfig,
+        errors: &mut Vec<Self>,
+    ) {
+        let half_edges = cycle.half_edges().collect::<Vec<_>>();
+
+        for [a, b] in candidate_pairs(&half_edges, config) {
+            let [a, b] = [half_edges[a], half_edges[b]];
+
+            let intersection = match HalfEdgeIntersection::compute(a, b, config)
+            {
+                Some(intersection) => intersection,
+                None => continue,
+            };
+
+            let half_edges = [a.clone(), b.clone()];
+
+            match intersection {
+                HalfEdgeIntersection::Points(points) => {
+                    // Neighboring half-edges always touch where they are
+                    // connected. That's not an intersection.
+                    let points = points
+                        .into_iter()
+                        .filter(|&point| {
+                            !shared_vertices(a, b).any(|vertex| {
+                                (vertex.position() - point).magnitude()
+                                    < config.distinct_min_distance
+                            })
+                        })
+                        .collect::<Vec<_>>();
+
+                    if !points.is_empty() {
+                        errors.push(Self::SelfIntersection {
+                            points: points_to_global(a, points),
+                            half_edges,
+                        });
+                    }
+                }
+                HalfEdgeIntersection::Overlap(points) => {
+                    errors.push(Self::Overlap {
+                        points: points_to_global(a, points),
+                        half_edges,
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// Find the pairs of half-edges that might intersect
+///
+/// Comparing every half-edge with every other one would take quadratic time.
+/// Instead, the bounding boxes of the half-edges are sorted along the u-axis,
+/// and each one is only compared to those that follow it, until one starts
+/// beyond its end (sweep and prune).
+///
+/// Returns pairs of indices into `half_edges`, with the smaller index first.
+/// The bounding boxes are enlarged by the minimum distance between distinct
+/// objects, so half-edges that touch are included. Half-edges that are not
+/// supported by [`HalfEdgeIntersection`] are left out.
+pub(super) fn candidate_pairs(
+    half_edges: &[&Handle<HalfEdge>],
+    config: &ValidationConfig,
+) -> Vec<[usize; 2]> {
+    let mut aabbs = half_edges
+        .iter()
+        .enumerate()
+        .filter_map(|(i, half_edge)| {
+            let geometry = EdgeGeometry::from_half_edge(half_edge)?;
+            Some((i, geometry.aabb(config.distinct_min_distance)))
+        })
+        .collect::<Vec<_>>();
+    aabbs.sort_by_key(|(_, aabb)| aabb.min.u);
+
+    let mut pairs = Vec::new();
+
+    for (k, (i, a)) in aabbs.iter().enumerate() {
+        for (j, b) in &aabbs[k + 1..] {
+            if b.min.u > a.max.u {
+                break;
+            }
+
+            if b.min.v <= a.max.v && a.min.v <= b.max.v {
+                pairs.push([*i.min(j), *i.max(j)]);
+            }
+        }
+    }
+
+    pairs.sort();
+    pairs
+}
+
+/// The intersection between two half-edges, in surface coordinates
+///
+/// Used to validate cycles, and the faces they bound.
+pub(super) enum HalfEdgeIntersection {
+    /// The half-edges intersect at one or more points
+    Points(Vec<Point<2>>),
+
+    /// The half-edges overlap
+    ///
+    /// Contains the points that bound the overlapping section. For coincident
+    /// circles, that's just a point on the circle.
+    Overlap(Vec<Point<2>>),
+}
+
+impl HalfEdgeIntersection {
+    /// Compute the intersection between two half-edges
+    ///
+    /// Both half-edges must be defined in the same surface.
+    ///
+    /// # Implementation Note
+    ///
+    /// Only line segments and full circles are supported, as that is all the
+    /// kernel currently creates. Circles are expected to be actual circles (as
+    /// opposed to ellipses), which is the case for all circles created by the
+    /// builder API. Half-edges that are circular arcs are ignored.
+    pub(super) fn compute(
+        a: &HalfEdge,
+        b: &HalfEdge,
+        config: &ValidationConfig,
+    ) -> Option<Self> {
+        let a = EdgeGeometry::from_half_edge(a)?;
+        let b = EdgeGeometry::from_half_edge(b)?;
+
+        use EdgeGeometry::{Circle, Segment};
+
+        match (a, b) {
+            (Segment(a), Segment(b)) => segment_segment(a, b, config),
+            (Segment(segment), Circle(circle))
+            | (Circle(circle), Segment(segment)) => {
+                segment_circle(segment, circle)
+            }
+            (Circle(a), Circle(b)) => circle_circle(a, b, config),
+        }
+    }
+}
+
+enum EdgeGeometry {
+    Segment(Segment<2>),
+    Circle(Circle<2>),
+}
+
+impl EdgeGeometry {
+    fn from_half_edge(half_edge: &HalfEdge) -> Option<Self> {
+        let [back, front] = half_edge.vertices();
+
+        match half_edge.curve().path() {
+            SurfacePath::Line(_) => {
+                Some(Self::Segment(Segment::from_points([
+                    back.surface_form().position(),
+                    front.surface_form().position(),
+                ])))
+            }
+            SurfacePath::Circle(circle) => {
+                if back.surface_form().id() != front.surface_form().id() {
+                    // Circular arcs are not supported.
+                    return None;
+                }
+
+                Some(Self::Circle(circle))
+            }
+        }
+    }
+
+    fn aabb(&self, margin: Scalar) -> Aabb<2> {
+        let [min, max] = match self {
+            Self::Segment(segment) => {
+                let [a, b] = segment.points();
+                [[a.u.min(b.u), a.v.min(b.v)], [a.u.max(b.u), a.v.max(b.v)]]
+            }
+            Self::Circle(circle) => {
+                let center = circle.center();
+                let [a, b] = [circle.a(), circle.b()];
+
+                let extent_u = (a.u * a.u + b.u * b.u).into_f64().sqrt();
+                let extent_v = (a.v * a.v + b.v * b.v).into_f64().sqrt();
+
+                [
+                    [center.u - extent_u, center.v - extent_v],
+                    [center.u + extent_u, center.v + extent_v],
+                ]
+            }
+        };
+
+        Aabb {
+            min: Point::from(min) - Vector::from([margin, margin]),
+            max: Point::from(max) + Vector::from([margin, margin]),
+        }
+    }
+}
+
+fn segment_segment(
+    a: Segment<2>,
+    b: Segment<2>,
+    config: &ValidationConfig,
+) -> Option<HalfEdgeIntersection> {
+    // Line coordinates on this line are `0` at the start of `a` and `1` at its
+    // end.
+    let line = Line::from_points(a.points());
+    let length = line.direction().magnitude();
+
+    if length == Scalar::ZERO {
+        // Degenerate half-edges are caught by the half-edge validation.
+        return None;
+    }
+
+    match LineSegmentIntersection::compute(&line, &b)? {
+        LineSegmentIntersection::Point { point_on_line } => {
+            let t = point_on_line.t;
+            if t < Scalar::ZERO || t > Scalar::ONE {
+                return None;
+            }
+
+            Some(HalfEdgeIntersection::Points(vec![
+                line.point_from_line_coords(point_on_line)
+            ]))
+        }
+        LineSegmentIntersection::Coincident { points_on_line } => {
+            let [b_start, b_end] = points_on_line.map(|point| point.t);
+
+            let start = b_start.min(b_end).max(Scalar::ZERO);
+            let end = b_start.max(b_end).min(Scalar::ONE);
+
+            if end < start {
+                return None;
+            }
+
+            let start = line.point_from_line_coords([start]);
+            let end = line.point_from_line_coords([end]);
+
+            // If the overlap is shorter than the minimum distance between
+            // distinct points, the segments only touch.
+            if (end - start).magnitude() < config.distinct_min_distance {
+                return Some(HalfEdgeIntersection::Points(vec![start]));
+            }
+
+            Some(HalfEdgeIntersection::Overlap(vec![start, end]))
+        }
+    }
+}
+
+fn segment_circle(
+    segment: Segment<2>,
+    circle: Circle<2>,
+) -> Option<HalfEdgeIntersection> {
+    // Solve `|start + t * direction - center| = radius` for `t`.
+    let [start, end] = segment.points();
+    let direction = end - start;
+    let offset = start - circle.center();
+    let radius = circle.radius();
+
+    let a = direction.dot(&direction);
+    let b = offset.dot(&direction) * 2.;
+    let c = offset.dot(&offset) - radius * radius;
+
+    if a == Scalar::ZERO {
+        return None;
     }
+
+    let discriminant = b * b - a * c * 4.;
+    if discriminant < Scalar::ZERO {
+        return None;
+    }
+
+    let root = Scalar::from_f64(discriminant.into_f64().sqrt());
+
+    let mut points = Vec::new();
+    for t in [(-b - root) / (a * 2.), (-b + root) / (a * 2.)] {
+        if t < Scalar::ZERO || t > Scalar::ONE {
+            continue;
+        }
+
+        let point = start + direction * t;
+        if !points.contains(&point) {
+            points.push(point);
+        }
+    }
+
+    if points.is_empty() {
+        return None;
+    }
+
+    Some(HalfEdgeIntersection::Points(points))
+}
+
+fn circle_circle(
+    a: Circle<2>,
+    b: Circle<2>,
+    config: &ValidationConfig,
+) -> Option<HalfEdgeIntersection> {
+    let [r_a, r_b] = [a.radius(), b.radius()];
+
+    let center_to_center = b.center() - a.center();
+    let distance = center_to_center.magnitude();
+
+    if distance < config.distinct_min_distance {
+        if (r_a - r_b).abs() < config.distinct_min_distance {
+            return Some(HalfEdgeIntersection::Overlap(vec![
+                a.point_from_circle_coords([0.])
+            ]));
+        }
+
+        // Concentric circles with different radii never intersect.
+        return None;
+    }
+
+    if distance > r_a + r_b || distance < (r_a - r_b).abs() {
+        return None;
+    }
+
+    // The intersection points lie on the line that is perpendicular to the
+    // line between the centers, at this distance from the center of `a`.
+    let along = (r_a * r_a - r_b * r_b + distance * distance) / (distance * 2.);
+    let across = Scalar::from_f64(
+        (r_a * r_a - along * along)
+            .max(Scalar::ZERO)
+            .into_f64()
+            .sqrt(),
+    );
+
+    let direction = center_to_center / distance;
+    let perpendicular = Vector::from([-direction.v, direction.u]);
+
+    let base = a.center() + direction * along;
+    let mut points = vec![base + perpendicular * across];
+    if across >= config.distinct_min_distance {
+        points.push(base - perpendicular * across);
+    }
+
+    Some(HalfEdgeIntersection::Points(points))
+}
+
+/// Iterate over the surface vertices that two half-edges share
+pub(super) fn shared_vertices<'r>(
+    a: &'r HalfEdge,
+    b: &'r HalfEdge,
+) -> impl Iterator<Item = &'r Handle<SurfaceVertex>> {
+    a.vertices()
+        .iter()
+        .map(|vertex| vertex.surface_form())
+        .filter(|a_vertex| {
+            b.vertices()
+                .iter()
+                .any(|b_vertex| b_vertex.surface_form().id() == a_vertex.id())
+        })
+}
+
+/// Convert points in the surface of a half-edge into global coordinates
+pub(super) fn points_to_global(
+    half_edge: &HalfEdge,
+    points: Vec<Point<2>>,
+) -> Vec<Point<3>> {
+    points
+        .into_iter()
+        .map(|point| half_edge.surface().point_from_surface_coords(point))
+        .collect()
 }
 
 #[cfg(test)]
 mod tests {
+    use std::f64::consts::TAU;
+
+    use itertools::Itertools;
+
     use crate::{
         builder::{CycleBuilder, HalfEdgeBuilder, VertexBuilder},
         insert::Insert,
         objects::{Cycle, Objects},
         partial::HasPartial,
-        validate::{Validate, ValidationError},
+        validate::{Validate, ValidationConfig, ValidationError},
     };
 
+    use super::{candidate_pairs, HalfEdgeIntersection};
+
     #[test]
     fn cycle_half_edge_connections() -> anyhow::Result<()> {
         let objects = Objects::new();
@@ -116,4 +525,90 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn cycle_self_intersection() -> anyhow::Result<()> {
+        let objects = Objects::new();
+
+        let valid = Cycle::partial()
+            .with_poly_chain_from_points(
+                objects.surfaces.xy_plane(),
+                [[0., 0.], [1., 0.], [1., 1.], [0., 1.]],
+            )
+            .close_with_line_segment()
+            .build(&objects)?;
+        let invalid = Cycle::partial()
+            .with_poly_chain_from_points(
+                objects.surfaces.xy_plane(),
+                [[0., 0.], [1., 1.], [1., 0.], [0., 1.]],
+            )
+            .close_with_line_segment()
+            .build(&objects)?;
+
+        assert!(valid.validate().is_ok());
+        assert!(invalid.validate().is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn cycle_overlap() -> anyhow::Result<()> {
+        let objects = Objects::new();
+
+        let valid = Cycle::partial()
+            .with_poly_chain_from_points(
+                objects.surfaces.xy_plane(),
+                [[0., 0.], [1., 0.], [2., 0.], [1., 1.]],
+            )
+            .close_with_line_segment()
+            .build(&objects)?;
+        let invalid = Cycle::partial()
+            .with_poly_chain_from_points(
+                objects.surfaces.xy_plane(),
+                [[0., 0.], [2., 0.], [1., 0.], [1., 1.]],
+            )
+            .close_with_line_segment()
+            .build(&objects)?;
+
+        assert!(valid.validate().is_ok());
+        assert!(invalid.validate().is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn candidate_pairs_include_all_intersections() -> anyhow::Result<()> {
+        let objects = Objects::new();
+        let config = ValidationConfig::default();
+
+        let num_vertices = 64;
+        let points = (0..num_vertices).map(|i| {
+            let angle = TAU * i as f64 / num_vertices as f64;
+            [angle.cos(), angle.sin()]
+        });
+        let cycle = Cycle::partial()
+            .with_poly_chain_from_points(objects.surfaces.xy_plane(), points)
+            .close_with_line_segment()
+            .build(&objects)?;
+
+        let half_edges = cycle.half_edges().collect::<Vec<_>>();
+        let pairs = candidate_pairs(&half_edges, &config);
+
+        for (a, b) in (0..half_edges.len()).tuple_combinations() {
+            let intersection = HalfEdgeIntersection::compute(
+                half_edges[a],
+                half_edges[b],
+                &config,
+            );
+
+            if intersection.is_some() {
+                assert!(pairs.contains(&[a, b]));
+            }
+        }
+
+        // Only neighboring half-edges should be close enough to be compared.
+        assert!(pairs.len() < num_vertices * 2);
+
+        Ok(())
+    }
 }
@@ -11,36 +11,29 @@ use crate::{
     storage::Handle,
 };
 
-use super::{Validate, ValidationConfig};
+use super::{Validate, ValidationCheck, ValidationConfig};
 
 impl Validate for HalfEdge {
     type Error = HalfEdgeValidationError;
 
-    fn validate_with_config(
-        &self,
-        config: &ValidationConfig,
-    ) -> Result<(), Self::Error> {
-        HalfEdgeValidationError::check_curve_identity(self)?;
-        HalfEdgeValidationError::check_global_curve_identity(self)?;
-        HalfEdgeValidationError::check_global_vertex_identity(self)?;
-        HalfEdgeValidationError::check_vertex_positions(self, config)?;
-
-        // We don't need to check anything about surfaces here. We already check
-        // curves, which makes sure the vertices are consistent with each other,
-        // and the validation of those vertices checks the surfaces.
-
-        Ok(())
+    fn checks() -> &'static [ValidationCheck<Self>] {
+        // We don't need to check anything about surfaces here. We already
+        // check curves, which makes sure the vertices are consistent with each
+        // other, and the validation of those vertices checks the surfaces.
+        &[
+            HalfEdgeValidationError::check_curve_identity,
+            HalfEdgeValidationError::check_global_curve_identity,
+            HalfEdgeValidationError::check_global_vertex_identity,
+            HalfEdgeValidationError::check_vertex_positions,
+        ]
     }
 }
 
 impl Validate for GlobalEdge {
     type Error = Infallible;
 
-    fn validate_with_config(
-        &self,
-        _: &ValidationConfig,
-    ) -> Result<(), Self::Error> {
-        Ok(())
+    fn checks() -> &'static [ValidationCheck<Self>] {
+        &[]
     }
 }
 
@@ -112,36 +105,62 @@ pub enum HalfEdgeValidationError {
 }
 
 impl HalfEdgeValidationError {
-    fn check_curve_identity(half_edge: &HalfEdge) -> Result<(), Self> {
+    /// Access the points where the problem is located
+    pub(super) fn points(&self) -> Vec<Point<3>> {
+        match self {
+            Self::GlobalVertexMismatch {
+                global_vertices_from_vertices,
+                global_vertices_from_global_form,
+            } => global_vertices_from_vertices
+                .iter()
+                .chain(global_vertices_from_global_form)
+                .map(|global_vertex| global_vertex.position())
+                .collect(),
+            // These concern the half-edge as a whole.
+            Self::CurveMismatch { .. }
+            | Self::GlobalCurveMismatch { .. }
+            | Self::VerticesAreCoincident { .. } => Vec::new(),
+        }
+    }
+
+    fn check_curve_identity(
+        half_edge: &HalfEdge,
+        _: &ValidationConfig,
+        errors: &mut Vec<Self>,
+    ) {
         let back_curve = half_edge.back().curve();
         let front_curve = half_edge.front().curve();
 
         if back_curve.id() != front_curve.id() {
-            return Err(HalfEdgeValidationError::CurveMismatch {
+            errors.push(HalfEdgeValidationError::CurveMismatch {
                 back_curve: back_curve.clone(),
                 front_curve: front_curve.clone(),
             });
         }
-
-        Ok(())
     }
 
-    fn check_global_curve_identity(half_edge: &HalfEdge) -> Result<(), Self> {
+    fn check_global_curve_identity(
+        half_edge: &HalfEdge,
+        _: &ValidationConfig,
+        errors: &mut Vec<Self>,
+    ) {
         let global_curve_from_curve = half_edge.curve().global_form();
         let global_curve_from_global_form = half_edge.global_form().curve();
 
         if global_curve_from_curve.id() != global_curve_from_global_form.id() {
-            return Err(Self::GlobalCurveMismatch {
+            errors.push(Self::GlobalCurveMismatch {
                 global_curve_from_curve: global_curve_from_curve.clone(),
                 global_curve_from_global_form: global_curve_from_global_form
                     .clone(),
             });
         }
-
-        Ok(())
     }
 
-    fn check_global_vertex_identity(half_edge: &HalfEdge) -> Result<(), Self> {
+    fn check_global_vertex_identity(
+        half_edge: &HalfEdge,
+        _: &ValidationConfig,
+        errors: &mut Vec<Self>,
+    ) {
         let global_vertices_from_vertices = {
             let (global_vertices_from_vertices, _) =
                 VerticesInNormalizedOrder::new(
@@ -166,33 +185,30 @@ impl HalfEdgeValidationError {
             .map(|global_vertex| global_vertex.id());
 
         if ids_from_vertices != ids_from_global_form {
-            return Err(Self::GlobalVertexMismatch {
+            errors.push(Self::GlobalVertexMismatch {
                 global_vertices_from_vertices,
                 global_vertices_from_global_form,
             });
         }
-
-        Ok(())
     }
 
     fn check_vertex_positions(
         half_edge: &HalfEdge,
         config: &ValidationConfig,
-    ) -> Result<(), Self> {
+        errors: &mut Vec<Self>,
+    ) {
         let back_position = half_edge.back().position();
         let front_position = half_edge.front().position();
 
         let distance = (back_position - front_position).magnitude();
 
         if distance < config.distinct_min_distance {
-            return Err(Self::VerticesAreCoincident {
+            errors.push(Self::VerticesAreCoincident {
                 back_position,
                 front_position,
                 distance,
             });
         }
-
-        Ok(())
     }
 }
 
@@ -1,22 +1,24 @@
-use fj_math::Winding;
+use fj_math::{Point, Winding};
 
 use crate::{
-    objects::{Cycle, Face, Surface},
+    objects::{Cycle, Face, HalfEdge, Surface},
     storage::Handle,
 };
 
-use super::{Validate, ValidationConfig};
+use super::{
+    cycle::{candidate_pairs, points_to_global, HalfEdgeIntersection},
+    cycle_points, Validate, ValidationCheck, ValidationConfig,
+};
 
 impl Validate for Face {
     type Error = FaceValidationError;
 
-    fn validate_with_config(
-        &self,
-        _: &ValidationConfig,
-    ) -> Result<(), Self::Error> {
-        FaceValidationError::check_surface_identity(self)?;
-        FaceValidationError::check_interior_winding(self)?;
-        Ok(())
+    fn checks() -> &'static [ValidationCheck<Self>] {
+        &[
+            FaceValidationError::check_surface_identity,
+            FaceValidationError::check_interior_winding,
+            FaceValidationError::check_cycle_intersections,
+        ]
     }
 }
 
@@ -58,46 +60,129 @@ pub enum FaceValidationError {
         /// The face
         face: Face,
     },
+
+    /// Cycles of [`Face`] intersect each other
+    #[error(
+        "`Cycle`s of `Face` intersect each other\n\
+        - Intersection points: {points:#?}\n\
+        - Intersecting `HalfEdge`s: {half_edges:#?}\n\
+        - `Cycle`s: {cycles:#?}"
+    )]
+    CycleIntersection {
+        /// The cycles that intersect
+        cycles: [Handle<Cycle>; 2],
+
+        /// The half-edges of the cycles that intersect
+        half_edges: [Handle<HalfEdge>; 2],
+
+        /// The points where the cycles intersect, in global coordinates
+        ///
+        /// If the half-edges overlap, these are the points that bound the
+        /// overlap.
+        points: Vec<Point<3>>,
+    },
 }
 
 impl FaceValidationError {
-    fn check_surface_identity(face: &Face) -> Result<(), Self> {
+    /// Access the points where the problem is located
+    pub(super) fn points(&self) -> Vec<Point<3>> {
+        match self {
+            Self::SurfaceMismatch { interior, .. } => cycle_points(interior),
+            Self::InvalidInteriorWinding { face, .. } => face
+                .interiors()
+                .flat_map(|interior| cycle_points(interior))
+                .collect(),
+            Self::CycleIntersection { points, .. } => points.clone(),
+        }
+    }
+
+    fn check_surface_identity(
+        face: &Face,
+        _: &ValidationConfig,
+        errors: &mut Vec<Self>,
+    ) {
         let surface = face.surface();
 
         for interior in face.interiors() {
             if surface.id() != interior.surface().id() {
-                return Err(Self::SurfaceMismatch {
+                errors.push(Self::SurfaceMismatch {
                     surface: surface.clone(),
                     interior: interior.clone(),
                     face: face.clone(),
                 });
             }
         }
-
-        Ok(())
     }
 
-    fn check_interior_winding(face: &Face) -> Result<(), Self> {
+    fn check_interior_winding(
+        face: &Face,
+        _: &ValidationConfig,
+        errors: &mut Vec<Self>,
+    ) {
         let exterior_winding = face.exterior().winding();
 
         for interior in face.interiors() {
             let interior_winding = interior.winding();
 
             if exterior_winding == interior_winding {
-                return Err(Self::InvalidInteriorWinding {
+                errors.push(Self::InvalidInteriorWinding {
                     exterior_winding,
                     interior_winding,
                     face: face.clone(),
                 });
             }
-            assert_ne!(
-                exterior_winding,
-                interior.winding(),
-                "Interior cycles must have opposite winding of exterior cycle"
-            );
         }
+    }
 
-        Ok(())
+    fn check_cycle_intersections(
+        face: &Face,
+        config: &ValidationConfig,
+        errors: &mut Vec<Self>,
+    ) {
+        let surface = face.surface();
+
+        // Cycles in other surfaces are already covered by the surface identity
+        // check. Intersections can only be computed within the same surface.
+        let mut cycles = Vec::new();
+        let mut half_edges = Vec::new();
+        for cycle in face.all_cycles() {
+            if cycle.surface().id() != surface.id() {
+                continue;
+            }
+
+            for half_edge in cycle.half_edges() {
+                cycles.push(cycle);
+                half_edges.push(half_edge);
+            }
+        }
+
+        for [a, b] in candidate_pairs(&half_edges, config) {
+            if cycles[a].id() == cycles[b].id() {
+                // Intersections within a cycle are checked by the cycle
+                // validation.
+                continue;
+            }
+
+            let intersection = HalfEdgeIntersection::compute(
+                half_edges[a],
+                half_edges[b],
+                config,
+            );
+
+            // Cycles of a face must not even touch. Otherwise, the region that
+            // the face covers would not be well-defined.
+            let points = match intersection {
+                Some(HalfEdgeIntersection::Points(points))
+                | Some(HalfEdgeIntersection::Overlap(points)) => points,
+                None => continue,
+            };
+
+            errors.push(Self::CycleIntersection {
+                cycles: [cycles[a].clone(), cycles[b].clone()],
+                half_edges: [half_edges[a].clone(), half_edges[b].clone()],
+                points: points_to_global(half_edges[a], points),
+            });
+        }
     }
 }
 
@@ -118,7 +203,7 @@ mod tests {
 
         let valid = Face::partial()
             .with_surface(objects.surfaces.xy_plane())
-            .with_exterior_polygon_from_points([[0., 0.], [3., 0.], [0., 3.]])
+            .with_exterior_polygon_from_points([[0., 0.], [4., 0.], [0., 4.]])
             .with_interior_polygon_from_points([[1., 1.], [1., 2.], [2., 1.]])
             .build(&objects)?;
         let invalid = {
@@ -146,7 +231,7 @@ mod tests {
 
         let valid = Face::partial()
             .with_surface(objects.surfaces.xy_plane())
-            .with_exterior_polygon_from_points([[0., 0.], [3., 0.], [0., 3.]])
+            .with_exterior_polygon_from_points([[0., 0.], [4., 0.], [0., 4.]])
             .with_interior_polygon_from_points([[1., 1.], [1., 2.], [2., 1.]])
             .build(&objects)?;
         let invalid = {
@@ -164,4 +249,25 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn face_cycle_intersection() -> anyhow::Result<()> {
+        let objects = Objects::new();
+
+        let valid = Face::partial()
+            .with_surface(objects.surfaces.xy_plane())
+            .with_exterior_polygon_from_points([[0., 0.], [4., 0.], [0., 4.]])
+            .with_interior_polygon_from_points([[1., 1.], [1., 2.], [2., 1.]])
+            .build(&objects)?;
+        let invalid = Face::partial()
+            .with_surface(objects.surfaces.xy_plane())
+            .with_exterior_polygon_from_points([[0., 0.], [4., 0.], [0., 4.]])
+            .with_interior_polygon_from_points([[1., 1.], [1., 4.], [2., 1.]])
+            .build(&objects)?;
+
+        assert!(valid.validate().is_ok());
+        assert!(invalid.validate().is_err());
+
+        Ok(())
+    }
 }
@@ -4,6 +4,7 @@ mod curve;
 mod cycle;
 mod edge;
 mod face;
+mod report;
 mod shell;
 mod sketch;
 mod solid;
@@ -14,19 +15,30 @@ pub use self::{
     cycle::CycleValidationError,
     edge::HalfEdgeValidationError,
     face::FaceValidationError,
+    report::{
+        ObjectKind, ObjectPath, Severity, ValidationIssue, ValidationReport,
+    },
+    shell::ShellValidationError,
+    solid::SolidValidationError,
     vertex::{SurfaceVertexValidationError, VertexValidationError},
 };
 
 use std::convert::Infallible;
 
-use fj_math::Scalar;
+use fj_interop::ext::ArrayExt;
+use fj_math::{Point, Scalar};
+
+use crate::{
+    objects::{Cycle, HalfEdge},
+    storage::Handle,
+};
 
 /// Validate an object
 ///
 /// This trait is used automatically when inserting an object into a store.
-pub trait Validate: Sized {
+pub trait Validate: Sized + 'static {
     /// The error that validation of the implementing type can result in
-    type Error: Into<ValidationError>;
+    type Error: Into<ValidationError> + 'static;
 
     /// Validate the object using default configuration
     fn validate(&self) -> Result<(), Self::Error> {
@@ -34,12 +46,54 @@ pub trait Validate: Sized {
     }
 
     /// Validate the object
+    ///
+    /// Returns the first error that was found, without running the remaining
+    /// checks. Use [`Validate::collect_errors`] to get all errors.
     fn validate_with_config(
         &self,
         config: &ValidationConfig,
-    ) -> Result<(), Self::Error>;
+    ) -> Result<(), Self::Error> {
+        let mut errors = Vec::new();
+
+        for check in Self::checks() {
+            check(self, config, &mut errors);
+
+            if !errors.is_empty() {
+                return Err(errors.swap_remove(0));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Validate the object, collecting all errors
+    ///
+    /// In contrast to [`Validate::validate_with_config`], this doesn't stop at
+    /// the first error. Only the object itself is validated, not the objects it
+    /// references. Use [`ValidationReport`] for that.
+    fn collect_errors(
+        &self,
+        config: &ValidationConfig,
+        errors: &mut Vec<Self::Error>,
+    ) {
+        for check in Self::checks() {
+            check(self, config, errors);
+        }
+    }
+
+    /// Access the checks that make up the validation of the object
+    ///
+    /// Each check adds the errors it finds to the provided list. Checks are run
+    /// in order, so cheap checks should come before expensive ones.
+    fn checks() -> &'static [ValidationCheck<Self>];
 }
 
+/// A single check, as part of the validation of an object
+///
+/// See [`Validate::checks`].
+pub type ValidationCheck<T> =
+    fn(&T, &ValidationConfig, &mut Vec<<T as Validate>::Error>);
+
 /// Configuration required for the validation process
 #[derive(Debug, Clone, Copy)]
 pub struct ValidationConfig {
@@ -88,6 +142,14 @@ pub enum ValidationError {
     #[error(transparent)]
     HalfEdge(#[from] HalfEdgeValidationError),
 
+    /// `Shell` validation error
+    #[error(transparent)]
+    Shell(#[from] ShellValidationError),
+
+    /// `Solid` validation error
+    #[error(transparent)]
+    Solid(#[from] SolidValidationError),
+
     /// `SurfaceVertex` position didn't match `GlobalVertex`
     #[error(transparent)]
     SurfaceVertex(#[from] SurfaceVertexValidationError),
@@ -97,6 +159,61 @@ pub enum ValidationError {
     Vertex(#[from] VertexValidationError),
 }
 
+impl ValidationError {
+    /// Access the points where the problem is located
+    ///
+    /// Can be used to highlight the problem. Returns an empty list, if the
+    /// problem has no specific location, but concerns the object as a whole.
+    pub fn points(&self) -> Vec<Point<3>> {
+        match self {
+            Self::Cycle(err) => err.points(),
+            Self::Face(err) => err.points(),
+            Self::HalfEdge(err) => err.points(),
+            Self::Shell(err) => err.points(),
+            Self::Solid(err) => err.points(),
+            Self::SurfaceVertex(err) => err.points(),
+            Self::Vertex(err) => err.points(),
+        }
+    }
+
+    /// Access the severity of the error
+    pub fn severity(&self) -> Severity {
+        // The match is kept exhaustive, so that new variants have to be
+        // considered here.
+        match self {
+            // A half-edge that is shorter than the minimum distance between
+            // distinct objects is suspicious, but it is still connected to its
+            // neighbors, and can be approximated.
+            Self::HalfEdge(
+                HalfEdgeValidationError::VerticesAreCoincident { .. },
+            ) => Severity::Warning,
+
+            // All other errors indicate objects that are invalid, and can't be
+            // relied upon by the rest of the kernel.
+            Self::Cycle(_)
+            | Self::Face(_)
+            | Self::HalfEdge(_)
+            | Self::Shell(_)
+            | Self::Solid(_)
+            | Self::SurfaceVertex(_)
+            | Self::Vertex(_) => Severity::Error,
+        }
+    }
+}
+
+/// Access the positions of the vertices of a cycle
+fn cycle_points(cycle: &Cycle) -> Vec<Point<3>> {
+    cycle.half_edges().flat_map(half_edge_points).collect()
+}
+
+/// Access the positions of the vertices of a half-edge
+fn half_edge_points(half_edge: &Handle<HalfEdge>) -> [Point<3>; 2] {
+    half_edge
+        .vertices()
+        .each_ref_ext()
+        .map(|vertex| vertex.global_form().position())
+}
+
 impl From<Infallible> for ValidationError {
     fn from(infallible: Infallible) -> Self {
         match infallible {}
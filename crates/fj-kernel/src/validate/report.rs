@@ -0,0 +1,374 @@
+use std::{collections::BTreeSet, fmt};
+
+use fj_math::Point;
+
+use crate::{iter::ObjectIters, storage::ObjectId};
+
+use super::{Validate, ValidationConfig, ValidationError};
+
+/// A report of all validation issues within a tree of objects
+///
+/// Objects are validated when they are inserted into their store, but that
+/// validation stops at the first error, and the resulting error doesn't say
+/// where in the tree of objects it occurred. A `ValidationReport`, on the other
+/// hand, walks the whole tree of objects (using [`ObjectIters`]), and collects
+/// every issue, together with the path to the object that caused it.
+///
+/// Objects that are referenced from multiple places are only validated once.
+/// Their issues are reported under the path they were first encountered at.
+#[derive(Debug, Default)]
+pub struct ValidationReport {
+    issues: Vec<ValidationIssue>,
+    visited: BTreeSet<ObjectId>,
+}
+
+impl ValidationReport {
+    /// Validate an object and all objects it references
+    pub fn for_object<'r>(
+        object: &'r (impl ObjectIters<'r> + ?Sized),
+        config: &ValidationConfig,
+    ) -> Self {
+        let mut report = Self::default();
+        object.validate_into_report(
+            &ObjectPath::default(),
+            config,
+            &mut report,
+        );
+        report
+    }
+
+    /// Access the issues in the report
+    pub fn issues(&self) -> impl Iterator<Item = &ValidationIssue> {
+        self.issues.iter()
+    }
+
+    /// Indicate whether the report contains no issues
+    pub fn is_empty(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// Indicate whether the report contains any issues of [`Severity::Error`]
+    pub fn has_errors(&self) -> bool {
+        self.issues
+            .iter()
+            .any(|issue| issue.severity == Severity::Error)
+    }
+
+    /// Validate an object and the objects it references into the report
+    ///
+    /// This is a low-level method, intended for implementation of
+    /// [`ObjectIters::validate_into_report`].
+    pub(crate) fn validate_object<'r, T>(
+        &mut self,
+        object: &'r dyn ObjectIters<'r>,
+        validate: &T,
+        kind: ObjectKind,
+        id: Option<ObjectId>,
+        path: &ObjectPath,
+        config: &ValidationConfig,
+    ) where
+        T: Validate,
+    {
+        if let Some(id) = id {
+            if !self.visited.insert(id) {
+                return;
+            }
+        }
+
+        let path = path.join(kind, id);
+
+        let mut errors = Vec::new();
+        validate.collect_errors(config, &mut errors);
+
+        for error in errors {
+            let error = error.into();
+
+            // Not every problem has a specific location. In that case, we can
+            // at least highlight the whole object.
+            let mut points = error.points();
+            if points.is_empty() {
+                points = object
+                    .global_vertex_iter()
+                    .map(|global_vertex| global_vertex.position())
+                    .collect();
+            }
+
+            self.issues.push(ValidationIssue {
+                path: path.clone(),
+                severity: error.severity(),
+                error,
+                points,
+            });
+        }
+
+        for object in object.referenced_objects() {
+            object.validate_into_report(&path, config, self);
+        }
+    }
+}
+
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.issues.is_empty() {
+            return write!(f, "No validation issues found");
+        }
+
+        for issue in &self.issues {
+            writeln!(f, "{issue}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// An issue found during validation
+///
+/// See [`ValidationReport`].
+#[derive(Debug)]
+pub struct ValidationIssue {
+    /// The path to the object that caused the issue
+    pub path: ObjectPath,
+
+    /// The severity of the issue
+    pub severity: Severity,
+
+    /// The validation error
+    pub error: ValidationError,
+
+    /// Points that can be used to highlight the issue
+    ///
+    /// These are the points where the problem is located. If it has no
+    /// specific location, these are the positions of all global vertices of
+    /// the object.
+    pub points: Vec<Point<3>>,
+}
+
+impl ValidationIssue {
+    /// Access a one-line summary of the issue
+    ///
+    /// The full error message can be very long, as it contains the debug
+    /// output of the objects involved.
+    pub fn summary(&self) -> String {
+        let message = self.error.to_string();
+        let summary = message.lines().next().unwrap_or_default();
+
+        format!("{}: {summary} (at {})", self.severity, self.path)
+    }
+}
+
+impl fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.summary())
+    }
+}
+
+/// The severity of a [`ValidationIssue`]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub enum Severity {
+    /// The object is suspicious, but can still be used
+    Warning,
+
+    /// The object is invalid
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self {
+            Self::Warning => "warning",
+            Self::Error => "error",
+        };
+
+        write!(f, "{severity}")
+    }
+}
+
+/// The path to an object within a tree of objects
+///
+/// See [`ValidationReport`].
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ObjectPath(Vec<(ObjectKind, Option<ObjectId>)>);
+
+impl ObjectPath {
+    /// Access the segments of the path, starting at the root object
+    pub fn segments(
+        &self,
+    ) -> impl Iterator<Item = &(ObjectKind, Option<ObjectId>)> {
+        self.0.iter()
+    }
+
+    fn join(&self, kind: ObjectKind, id: Option<ObjectId>) -> Self {
+        let mut path = self.clone();
+        path.0.push((kind, id));
+        path
+    }
+}
+
+impl fmt::Display for ObjectPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (kind, id)) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, " → ")?;
+            }
+
+            write!(f, "{kind}")?;
+            if let Some(id) = id {
+                write!(f, " ({id:?})")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The kind of an object
+///
+/// See [`ObjectPath`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub enum ObjectKind {
+    /// A [`Curve`](crate::objects::Curve)
+    Curve,
+
+    /// A [`Cycle`](crate::objects::Cycle)
+    Cycle,
+
+    /// A [`Face`](crate::objects::Face)
+    Face,
+
+    /// A [`GlobalCurve`](crate::objects::GlobalCurve)
+    GlobalCurve,
+
+    /// A [`GlobalVertex`](crate::objects::GlobalVertex)
+    GlobalVertex,
+
+    /// A [`HalfEdge`](crate::objects::HalfEdge)
+    HalfEdge,
+
+    /// A [`Shell`](crate::objects::Shell)
+    Shell,
+
+    /// A [`Sketch`](crate::objects::Sketch)
+    Sketch,
+
+    /// A [`Solid`](crate::objects::Solid)
+    Solid,
+
+    /// A [`Surface`](crate::objects::Surface)
+    Surface,
+
+    /// A [`Vertex`](crate::objects::Vertex)
+    Vertex,
+}
+
+impl fmt::Display for ObjectKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = match self {
+            Self::Curve => "curve",
+            Self::Cycle => "cycle",
+            Self::Face => "face",
+            Self::GlobalCurve => "global curve",
+            Self::GlobalVertex => "global vertex",
+            Self::HalfEdge => "half-edge",
+            Self::Shell => "shell",
+            Self::Sketch => "sketch",
+            Self::Solid => "solid",
+            Self::Surface => "surface",
+            Self::Vertex => "vertex",
+        };
+
+        write!(f, "{kind}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::Scalar;
+
+    use crate::{
+        builder::FaceBuilder,
+        insert::Insert,
+        objects::{Face, Objects, Shell},
+        partial::HasPartial,
+        validate::{
+            ObjectKind, Severity, Validate, ValidationConfig, ValidationError,
+        },
+    };
+
+    use super::ValidationReport;
+
+    #[test]
+    fn report_valid_shell() -> anyhow::Result<()> {
+        let objects = Objects::new();
+
+        let shell = Shell::builder(&objects)
+            .with_cube_from_edge_length(1.)
+            .build()?;
+
+        let report =
+            ValidationReport::for_object(&shell, &ValidationConfig::default());
+        assert!(report.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn open_shell_is_an_error() -> anyhow::Result<()> {
+        let objects = Objects::new();
+
+        let cube = Shell::builder(&objects)
+            .with_cube_from_edge_length(1.)
+            .build()?;
+        let shell = Shell::new(cube.faces().into_iter().skip(1).cloned());
+
+        let mut errors = Vec::new();
+        shell.collect_errors(&ValidationConfig::default(), &mut errors);
+
+        assert!(!errors.is_empty());
+        for error in errors {
+            let error = ValidationError::from(error);
+            assert_eq!(error.severity(), Severity::Error);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn report_all_errors_with_path() -> anyhow::Result<()> {
+        let objects = Objects::new();
+
+        let face = Face::partial()
+            .with_surface(objects.surfaces.xy_plane())
+            .with_exterior_polygon_from_points([[0., 0.], [1., 0.], [0., 1.]])
+            .build(&objects)?
+            .insert(&objects)?;
+
+        // With this configuration, all edges of the face are too short.
+        let config = ValidationConfig {
+            distinct_min_distance: Scalar::from_f64(2.),
+            ..ValidationConfig::default()
+        };
+        let report = ValidationReport::for_object(&face, &config);
+
+        // Short edges are suspicious, but don't make the face invalid.
+        assert!(!report.is_empty());
+        assert!(!report.has_errors());
+        assert_eq!(report.issues().count(), 3);
+
+        for issue in report.issues() {
+            assert_eq!(issue.severity, Severity::Warning);
+            assert!(issue.summary().starts_with("warning: "));
+
+            let kinds = issue
+                .path
+                .segments()
+                .map(|&(kind, _)| kind)
+                .collect::<Vec<_>>();
+            assert_eq!(
+                kinds,
+                [ObjectKind::Face, ObjectKind::Cycle, ObjectKind::HalfEdge]
+            );
+        }
+
+        Ok(())
+    }
+}
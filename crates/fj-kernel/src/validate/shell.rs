@@ -1,16 +1,291 @@
-use std::convert::Infallible;
+use std::collections::{BTreeMap, BTreeSet};
 
-use crate::objects::Shell;
+use fj_math::Point;
 
-use super::{Validate, ValidationConfig};
+use crate::{
+    objects::{GlobalEdge, HalfEdge, Shell},
+    storage::{Handle, ObjectId},
+};
+
+use super::{half_edge_points, Validate, ValidationCheck, ValidationConfig};
 
 impl Validate for Shell {
-    type Error = Infallible;
+    type Error = ShellValidationError;
+
+    fn checks() -> &'static [ValidationCheck<Self>] {
+        &[
+            ShellValidationError::check_edges,
+            ShellValidationError::check_euler_characteristic,
+        ]
+    }
+}
+
+/// [`Shell`] validation error
+#[derive(Debug, thiserror::Error)]
+pub enum ShellValidationError {
+    /// [`Shell`] is not closed
+    #[error(
+        "`Shell` is not closed; `GlobalEdge` is only used by one `HalfEdge`\n\
+        - `GlobalEdge`: {global_edge:#?}\n\
+        - `HalfEdge`: {half_edge:#?}"
+    )]
+    NotClosed {
+        /// The global edge that is only used by one half-edge
+        global_edge: Handle<GlobalEdge>,
+
+        /// The half-edge that uses the global edge
+        half_edge: Handle<HalfEdge>,
+    },
+
+    /// [`Shell`] is not manifold
+    #[error(
+        "`Shell` is not manifold; `GlobalEdge` is used by more than two \
+        `HalfEdge`s\n\
+        - `GlobalEdge`: {global_edge:#?}\n\
+        - `HalfEdge`s: {half_edges:#?}"
+    )]
+    NonManifoldEdge {
+        /// The global edge that is used by more than two half-edges
+        global_edge: Handle<GlobalEdge>,
+
+        /// The half-edges that use the global edge
+        half_edges: Vec<Handle<HalfEdge>>,
+    },
+
+    /// [`Shell`] is not consistently oriented
+    #[error(
+        "`Shell` is not consistently oriented; `HalfEdge`s that share a \
+        `GlobalEdge` have the same direction\n\
+        - `GlobalEdge`: {global_edge:#?}\n\
+        - `HalfEdge`s: {half_edges:#?}"
+    )]
+    InconsistentOrientation {
+        /// The global edge that both half-edges share
+        global_edge: Handle<GlobalEdge>,
+
+        /// The half-edges that have the same direction
+        half_edges: [Handle<HalfEdge>; 2],
+    },
+
+    /// [`Shell`] has an invalid Euler characteristic
+    #[error(
+        "`Shell` has invalid Euler characteristic ({euler_characteristic}); \
+        must be even and not larger than 2\n\
+        - Number of `GlobalVertex` objects: {num_vertices}\n\
+        - Number of `GlobalEdge`s: {num_edges}\n\
+        - Number of `Face`s: {num_faces}\n\
+        - Number of interior `Cycle`s: {num_interiors}"
+    )]
+    InvalidEulerCharacteristic {
+        /// The Euler characteristic of the shell
+        ///
+        /// This takes faces with holes into account, as per the
+        /// Euler-Poincaré formula (`V - E + F - R`).
+        euler_characteristic: i64,
+
+        /// The number of global vertices in the shell
+        num_vertices: usize,
+
+        /// The number of global edges in the shell
+        num_edges: usize,
+
+        /// The number of faces in the shell
+        num_faces: usize,
 
-    fn validate_with_config(
-        &self,
+        /// The number of interior cycles of all faces in the shell
+        num_interiors: usize,
+    },
+}
+
+impl ShellValidationError {
+    /// Access the points where the problem is located
+    pub(super) fn points(&self) -> Vec<Point<3>> {
+        match self {
+            Self::NotClosed { half_edge, .. } => {
+                half_edge_points(half_edge).to_vec()
+            }
+            Self::NonManifoldEdge { half_edges, .. } => half_edges
+                .first()
+                .map(|half_edge| half_edge_points(half_edge).to_vec())
+                .unwrap_or_default(),
+            Self::InconsistentOrientation { half_edges, .. } => {
+                half_edge_points(&half_edges[0]).to_vec()
+            }
+            // This concerns the shell as a whole.
+            Self::InvalidEulerCharacteristic { .. } => Vec::new(),
+        }
+    }
+
+    fn check_edges(
+        shell: &Shell,
         _: &ValidationConfig,
-    ) -> Result<(), Self::Error> {
+        errors: &mut Vec<Self>,
+    ) {
+        let mut half_edges_by_global_edge = BTreeMap::new();
+
+        for face in shell.faces() {
+            for cycle in face.all_cycles() {
+                for half_edge in cycle.half_edges() {
+                    half_edges_by_global_edge
+                        .entry(half_edge.global_form().id())
+                        .or_insert_with(Vec::new)
+                        .push(half_edge.clone());
+                }
+            }
+        }
+
+        for half_edges in half_edges_by_global_edge.into_values() {
+            match half_edges.as_slice() {
+                [half_edge] => {
+                    errors.push(Self::NotClosed {
+                        global_edge: half_edge.global_form().clone(),
+                        half_edge: half_edge.clone(),
+                    });
+                }
+                [a, b] => {
+                    let [a_back, a_front] = global_vertex_ids(a);
+                    let [b_back, _] = global_vertex_ids(b);
+
+                    // If an edge starts and ends at the same global vertex
+                    // (which is the case for circles), we can't tell its
+                    // direction from its vertices. Neighboring faces still
+                    // can't be inconsistently oriented in that case, as that
+                    // would be caught by the checks for the other edges of
+                    // those faces.
+                    let is_closed = a_back == a_front;
+
+                    if !is_closed && a_back == b_back {
+                        errors.push(Self::InconsistentOrientation {
+                            global_edge: a.global_form().clone(),
+                            half_edges: [a.clone(), b.clone()],
+                        });
+                    }
+                }
+                half_edges => {
+                    errors.push(Self::NonManifoldEdge {
+                        global_edge: half_edges[0].global_form().clone(),
+                        half_edges: half_edges.to_vec(),
+                    });
+                }
+            }
+        }
+    }
+
+    fn check_euler_characteristic(
+        shell: &Shell,
+        _: &ValidationConfig,
+        errors: &mut Vec<Self>,
+    ) {
+        let mut vertices = BTreeSet::new();
+        let mut edges = BTreeSet::new();
+        let mut num_interiors = 0;
+
+        for face in shell.faces() {
+            num_interiors += face.interiors().count();
+
+            for cycle in face.all_cycles() {
+                for half_edge in cycle.half_edges() {
+                    vertices.extend(global_vertex_ids(half_edge));
+                    edges.insert(half_edge.global_form().id());
+                }
+            }
+        }
+
+        let num_vertices = vertices.len();
+        let num_edges = edges.len();
+        let num_faces = shell.faces().into_iter().count();
+
+        // Every closed, orientable surface has an Euler characteristic of
+        // `2 - 2g`, with `g` being its genus (its number of handles).
+        let euler_characteristic = num_vertices as i64 - num_edges as i64
+            + num_faces as i64
+            - num_interiors as i64;
+
+        if euler_characteristic > 2 || euler_characteristic % 2 != 0 {
+            errors.push(Self::InvalidEulerCharacteristic {
+                euler_characteristic,
+                num_vertices,
+                num_edges,
+                num_faces,
+                num_interiors,
+            });
+        }
+    }
+}
+
+fn global_vertex_ids(half_edge: &HalfEdge) -> [ObjectId; 2] {
+    half_edge
+        .vertices()
+        .clone()
+        .map(|vertex| vertex.global_form().id())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        algorithms::reverse::Reverse,
+        objects::{Objects, Shell},
+        validate::{ShellValidationError, Validate},
+    };
+
+    #[test]
+    fn shell_not_closed() -> anyhow::Result<()> {
+        let objects = Objects::new();
+
+        let valid = Shell::builder(&objects)
+            .with_cube_from_edge_length(1.)
+            .build()?;
+        let invalid = Shell::new(valid.faces().into_iter().skip(1).cloned());
+
+        assert!(valid.validate().is_ok());
+        assert!(invalid.validate().is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn shell_inconsistent_orientation() -> anyhow::Result<()> {
+        let objects = Objects::new();
+
+        let valid = Shell::builder(&objects)
+            .with_cube_from_edge_length(1.)
+            .build()?;
+        let invalid = {
+            let mut faces = valid.faces().into_iter().cloned();
+
+            let reversed = faces.next().unwrap().reverse(&objects)?;
+            Shell::new(faces.chain([reversed]))
+        };
+
+        assert!(valid.validate().is_ok());
+        assert!(invalid.validate().is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn shell_not_closed_points() -> anyhow::Result<()> {
+        let objects = Objects::new();
+
+        let valid = Shell::builder(&objects)
+            .with_cube_from_edge_length(1.)
+            .build()?;
+        let invalid = Shell::new(valid.faces().into_iter().skip(1).cloned());
+
+        let mut errors = Vec::new();
+        invalid.collect_errors(&Default::default(), &mut errors);
+
+        // The shell is open along the four edges of the missing face. Only
+        // those edges should be highlighted, not the whole shell.
+        let not_closed = errors
+            .iter()
+            .filter(|err| matches!(err, ShellValidationError::NotClosed { .. }))
+            .collect::<Vec<_>>();
+        assert_eq!(not_closed.len(), 4);
+        for err in not_closed {
+            assert_eq!(err.points().len(), 2);
+        }
+
         Ok(())
     }
 }
@@ -2,15 +2,12 @@ use std::convert::Infallible;
 
 use crate::objects::Sketch;
 
-use super::{Validate, ValidationConfig};
+use super::{Validate, ValidationCheck};
 
 impl Validate for Sketch {
     type Error = Infallible;
 
-    fn validate_with_config(
-        &self,
-        _: &ValidationConfig,
-    ) -> Result<(), Self::Error> {
-        Ok(())
+    fn checks() -> &'static [ValidationCheck<Self>] {
+        &[]
     }
 }
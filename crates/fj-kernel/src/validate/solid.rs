@@ -1,16 +1,534 @@
-use std::convert::Infallible;
+use std::collections::BTreeMap;
 
-use crate::objects::Solid;
+use fj_math::{winding_number, Aabb, Point, Scalar, Vector};
 
-use super::{Validate, ValidationConfig};
+use crate::{
+    algorithms::approx::{Approx, Tolerance},
+    objects::{GlobalEdge, Shell, Solid, Surface},
+    path::{GlobalPath, SurfacePath},
+    storage::Handle,
+};
+
+use super::{
+    cycle_points, half_edge_points, Validate, ValidationCheck, ValidationConfig,
+};
 
 impl Validate for Solid {
-    type Error = Infallible;
+    type Error = SolidValidationError;
+
+    fn checks() -> &'static [ValidationCheck<Self>] {
+        // The shells themselves have already been validated when they were
+        // inserted. No need to check that each of them is closed.
+        &[
+            SolidValidationError::check_shells_disjoint,
+            SolidValidationError::check_orientation,
+        ]
+    }
+}
+
+/// [`Solid`] validation error
+#[derive(Debug, thiserror::Error)]
+pub enum SolidValidationError {
+    /// [`Shell`]s of [`Solid`] share a [`GlobalEdge`]
+    #[error(
+        "`Shell`s of `Solid` share a `GlobalEdge`\n\
+        - `GlobalEdge`: {global_edge:#?}\n\
+        - `Shell`s: {shells:#?}"
+    )]
+    SharedGlobalEdge {
+        /// The global edge that is shared between shells
+        global_edge: Handle<GlobalEdge>,
+
+        /// The shells that share the global edge
+        shells: [Handle<Shell>; 2],
+    },
+
+    /// Outer [`Shell`] of [`Solid`] is oriented inward
+    #[error(
+        "Outer `Shell` of `Solid` is oriented inward; must be oriented \
+        outward\n\
+        - Signed volume enclosed by the `Shell`: {volume}\n\
+        - `Shell`: {shell:#?}"
+    )]
+    OrientedInward {
+        /// The shell that is oriented inward
+        shell: Handle<Shell>,
+
+        /// The signed volume that the shell encloses
+        ///
+        /// This is computed from an approximation of the shell, and only its
+        /// sign is meaningful.
+        volume: Scalar,
+    },
+
+    /// [`Shell`] that bounds a cavity of [`Solid`] is oriented outward
+    #[error(
+        "`Shell` that bounds a cavity of `Solid` is oriented outward; must be \
+        oriented inward\n\
+        - Signed volume enclosed by the `Shell`: {volume}\n\
+        - `Shell`: {shell:#?}"
+    )]
+    CavityOrientedOutward {
+        /// The shell that is oriented outward
+        shell: Handle<Shell>,
+
+        /// The signed volume that the shell encloses
+        ///
+        /// This is computed from an approximation of the shell, and only its
+        /// sign is meaningful.
+        volume: Scalar,
+    },
+}
+
+impl SolidValidationError {
+    /// Access the points where the problem is located
+    pub(super) fn points(&self) -> Vec<Point<3>> {
+        match self {
+            Self::SharedGlobalEdge { global_edge, .. } => global_edge
+                .vertices()
+                .access_in_normalized_order()
+                .iter()
+                .map(|global_vertex| global_vertex.position())
+                .collect(),
+            Self::OrientedInward { shell, .. }
+            | Self::CavityOrientedOutward { shell, .. } => shell
+                .faces()
+                .into_iter()
+                .flat_map(|face| face.all_cycles())
+                .flat_map(|cycle| cycle_points(cycle))
+                .collect(),
+        }
+    }
 
-    fn validate_with_config(
-        &self,
+    fn check_shells_disjoint(
+        solid: &Solid,
         _: &ValidationConfig,
-    ) -> Result<(), Self::Error> {
+        errors: &mut Vec<Self>,
+    ) {
+        let mut shells_by_global_edge = BTreeMap::new();
+
+        for shell in solid.shells() {
+            for face in shell.faces() {
+                for cycle in face.all_cycles() {
+                    for half_edge in cycle.half_edges() {
+                        let global_edge = half_edge.global_form();
+
+                        let (_, shells): &mut (_, Vec<&Handle<Shell>>) =
+                            shells_by_global_edge
+                                .entry(global_edge.id())
+                                .or_insert_with(|| (global_edge, Vec::new()));
+
+                        if !shells.iter().any(|other| other.id() == shell.id())
+                        {
+                            shells.push(shell);
+                        }
+                    }
+                }
+            }
+        }
+
+        for (global_edge, shells) in shells_by_global_edge.into_values() {
+            if let [a, b, ..] = shells.as_slice() {
+                errors.push(Self::SharedGlobalEdge {
+                    global_edge: global_edge.clone(),
+                    shells: [(*a).clone(), (*b).clone()],
+                });
+            }
+        }
+    }
+
+    /// Check that the shells of the solid are oriented correctly
+    ///
+    /// Shells that bound the solid from the outside must be oriented outward.
+    /// Shells that bound a cavity within it must be oriented inward. A shell
+    /// bounds a cavity, if it is within an odd number of other shells.
+    fn check_orientation(
+        solid: &Solid,
+        _: &ValidationConfig,
+        errors: &mut Vec<Self>,
+    ) {
+        // If a shell has no extent, it can't be valid, but that's something
+        // the shell's own validation needs to catch.
+        let shells = solid
+            .shells()
+            .filter_map(|shell| Some((shell, ShellBoundary::approx(shell)?)))
+            .collect::<Vec<_>>();
+
+        for (i, (shell, boundary)) in shells.iter().enumerate() {
+            let volume = boundary.signed_volume();
+
+            let is_cavity = match boundary.vertex() {
+                Some(vertex) => {
+                    let around = shells
+                        .iter()
+                        .enumerate()
+                        .filter(|&(j, (_, other))| {
+                            j != i && other.contains(vertex)
+                        })
+                        .count();
+                    around % 2 == 1
+                }
+                None => false,
+            };
+
+            if is_cavity && volume > Scalar::ZERO {
+                errors.push(Self::CavityOrientedOutward {
+                    shell: (*shell).clone(),
+                    volume,
+                });
+            }
+            if !is_cavity && volume < Scalar::ZERO {
+                errors.push(Self::OrientedInward {
+                    shell: (*shell).clone(),
+                    volume,
+                });
+            }
+        }
+    }
+}
+
+/// The approximated boundaries of the faces of a shell
+///
+/// This is much cheaper to compute than a triangulation of the shell, but
+/// still enough to compute the volume it encloses, and whether it contains a
+/// point.
+struct ShellBoundary {
+    faces: Vec<(Surface, Vec<Vec<Point<2>>>)>,
+}
+
+impl ShellBoundary {
+    /// Approximate the boundaries of the faces of the shell
+    ///
+    /// Returns `None`, if the shell has no extent.
+    fn approx(shell: &Shell) -> Option<Self> {
+        // The boundaries only need to be approximated coarsely. To choose a
+        // tolerance, estimate the size of the shell from its vertices and the
+        // radii of its curves.
+        let mut points = Vec::new();
+        let mut size = Scalar::ZERO;
+
+        for face in shell.faces() {
+            if let GlobalPath::Circle(circle) = face.surface().u() {
+                size = size.max(circle.a().magnitude());
+            }
+
+            for cycle in face.all_cycles() {
+                for half_edge in cycle.half_edges() {
+                    if let SurfacePath::Circle(circle) =
+                        half_edge.curve().path()
+                    {
+                        size = size.max(circle.a().magnitude());
+                    }
+
+                    points.extend(half_edge_points(half_edge));
+                }
+            }
+        }
+
+        if !points.is_empty() {
+            size = size.max(Aabb::<3>::from_points(points).size().magnitude());
+        }
+
+        let tolerance = Tolerance::from_scalar(size / 100.).ok()?;
+
+        let faces = shell
+            .faces()
+            .into_iter()
+            .map(|face| {
+                let cycles = face
+                    .all_cycles()
+                    .map(|cycle| {
+                        cycle
+                            .approx(tolerance)
+                            .points()
+                            .into_iter()
+                            .map(|point| point.local_form)
+                            .collect()
+                    })
+                    .collect();
+
+                (face.surface().clone_object(), cycles)
+            })
+            .collect();
+
+        Some(Self { faces })
+    }
+
+    /// Access a vertex of the shell
+    fn vertex(&self) -> Option<Point<3>> {
+        let (surface, cycles) = self.faces.first()?;
+        let point = cycles.first()?.first()?;
+
+        Some(surface.point_from_surface_coords(*point))
+    }
+
+    /// Compute the signed volume enclosed by the shell
+    ///
+    /// The volume is positive, if the shell is oriented outward.
+    fn signed_volume(&self) -> Scalar {
+        // By the divergence theorem, the volume is a third of the integral of
+        // `p · n` over the surface of the shell. For each face, Green's theorem
+        // turns that into an integral over the boundary of the face, in
+        // surface coordinates. Both kinds of surfaces have a closed-form
+        // antiderivative for that, which makes this exact, up to the
+        // approximation of the boundaries.
+        let mut volume = Scalar::ZERO;
+
+        for (surface, cycles) in &self.faces {
+            for cycle in cycles {
+                for segment in cycle.windows(2) {
+                    let [a, b] = [segment[0], segment[1]];
+                    let [f_a, f_b] =
+                        [a, b].map(|point| antiderivative(surface, point.u));
+
+                    volume += (f_a + f_b) / 2. * (b.v - a.v);
+                }
+            }
+        }
+
+        volume / 3.
+    }
+
+    /// Determine whether the shell contains a point
+    ///
+    /// The point must not be on the shell.
+    fn contains(&self, point: Point<3>) -> bool {
+        // A ray from the point crosses the shell an odd number of times, if
+        // the point is inside of it. The direction of the ray is chosen such,
+        // that it's unlikely to exactly hit an edge.
+        let direction = Vector::from([0.5377, 0.6021, 0.5902]);
+
+        let mut crossings = 0;
+        for (surface, cycles) in &self.faces {
+            for hit in intersect_ray(surface, point, direction) {
+                let within_face = [-Scalar::TAU, Scalar::ZERO, Scalar::TAU]
+                    .into_iter()
+                    .any(|offset| {
+                        let hit = Point::from([hit.u + offset, hit.v]);
+                        cycles
+                            .iter()
+                            .map(|cycle| winding_number(cycle, hit))
+                            .sum::<i32>()
+                            != 0
+                    });
+
+                if within_face {
+                    crossings += 1;
+                }
+            }
+        }
+
+        crossings % 2 == 1
+    }
+}
+
+/// Antiderivative, with regard to `u`, of `p · (∂p/∂u × ∂p/∂v)` on a surface
+///
+/// `p` is a point on the surface. That expression only depends on `u`.
+fn antiderivative(surface: &Surface, u: Scalar) -> Scalar {
+    let v = surface.v();
+
+    match surface.u() {
+        GlobalPath::Line(line) => {
+            let normal = line.direction().cross(&v);
+            line.origin().coords.dot(&normal) * u
+        }
+        GlobalPath::Circle(circle) => {
+            let center = circle.center().coords;
+            let a = circle.a().cross(&v);
+            let b = circle.b().cross(&v);
+            let (sin, cos) = u.sin_cos();
+
+            circle.a().dot(&b) * u + center.dot(&a) * cos + center.dot(&b) * sin
+        }
+    }
+}
+
+/// Compute the intersections of a ray with a surface, in surface coordinates
+///
+/// Only intersections in the direction of the ray are returned.
+fn intersect_ray(
+    surface: &Surface,
+    origin: Point<3>,
+    direction: Vector<3>,
+) -> Vec<Point<2>> {
+    let v = surface.v();
+    let mut hits = Vec::new();
+
+    match surface.u() {
+        GlobalPath::Line(line) => {
+            // Solve `origin + direction * t = line.origin() + u * s + v * t`,
+            // using Cramer's rule.
+            let u = line.direction();
+            let w = origin - line.origin();
+
+            let det = u.dot(&v.cross(&-direction));
+            if det == Scalar::ZERO {
+                return hits;
+            }
+
+            let s = w.dot(&v.cross(&-direction)) / det;
+            let t = u.dot(&w.cross(&-direction)) / det;
+            let distance = u.dot(&v.cross(&w)) / det;
+
+            if distance > Scalar::ZERO {
+                hits.push(Point::from([s, t]));
+            }
+        }
+        GlobalPath::Circle(circle) => {
+            // Move the ray along `v` into the plane of the circle. Then
+            // intersect it with the circle.
+            let normal = circle.a().cross(&circle.b());
+            let w = origin - circle.center();
+
+            let normal_v = normal.dot(&v);
+            if normal_v == Scalar::ZERO {
+                return hits;
+            }
+
+            let t_of = |distance: Scalar| {
+                (normal.dot(&w) + normal.dot(&direction) * distance) / normal_v
+            };
+            let w_0 = w - v * (normal.dot(&w) / normal_v);
+            let w_1 = direction - v * (normal.dot(&direction) / normal_v);
+
+            let a = w_1.dot(&w_1);
+            let b = w_0.dot(&w_1) * 2.;
+            let c = w_0.dot(&w_0) - circle.a().dot(&circle.a());
+
+            let discriminant = b * b - a * c * 4.;
+            if a == Scalar::ZERO || discriminant < Scalar::ZERO {
+                return hits;
+            }
+            let root = Scalar::from(discriminant.into_f64().sqrt());
+
+            for distance in [(-b - root) / (a * 2.), (-b + root) / (a * 2.)] {
+                if distance <= Scalar::ZERO {
+                    continue;
+                }
+
+                let q = w_0 + w_1 * distance;
+                let s = Scalar::atan2(q.dot(&circle.b()), q.dot(&circle.a()));
+
+                hits.push(Point::from([s, t_of(distance)]));
+            }
+        }
+    }
+
+    hits
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        algorithms::{reverse::Reverse, transform::TransformObject},
+        insert::Insert,
+        objects::{Objects, Shell, Solid},
+        storage::Handle,
+        validate::{SolidValidationError, Validate},
+    };
+
+    fn reversed(
+        shell: &Handle<Shell>,
+        objects: &Objects,
+    ) -> anyhow::Result<Handle<Shell>> {
+        let faces = shell
+            .faces()
+            .into_iter()
+            .cloned()
+            .map(|face| face.reverse(objects))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Shell::new(faces).insert(objects)?)
+    }
+
+    #[test]
+    fn solid_shells_disjoint() -> anyhow::Result<()> {
+        let objects = Objects::new();
+
+        let a = Shell::builder(&objects)
+            .with_cube_from_edge_length(1.)
+            .build()?;
+        let b = Shell::builder(&objects)
+            .with_cube_from_edge_length(2.)
+            .build()?
+            .translate([3., 0., 0.], &objects)?;
+
+        let solid = Solid::new([a, b]);
+        assert!(solid.validate().is_ok());
+
+        Ok(())
+    }
+
+    #[test]
+    fn solid_with_cavity() -> anyhow::Result<()> {
+        let objects = Objects::new();
+
+        let outer = Shell::builder(&objects)
+            .with_cube_from_edge_length(2.)
+            .build()?;
+        let inner = Shell::builder(&objects)
+            .with_cube_from_edge_length(1.)
+            .build()?;
+
+        let valid = Solid::new([outer.clone(), reversed(&inner, &objects)?]);
+        let invalid = Solid::new([outer, inner]);
+
+        assert!(valid.validate().is_ok());
+        assert!(matches!(
+            invalid.validate(),
+            Err(SolidValidationError::CavityOrientedOutward { .. })
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn solid_shells_share_global_edge() -> anyhow::Result<()> {
+        let objects = Objects::new();
+
+        let a = Shell::builder(&objects)
+            .with_cube_from_edge_length(1.)
+            .build()?;
+        let b = Shell::new(
+            a.faces()
+                .into_iter()
+                .cloned()
+                .map(|face| face.reverse(&objects))
+                .collect::<Result<Vec<_>, _>>()?,
+        )
+        .insert(&objects)?;
+
+        let solid = Solid::new([a, b]);
+
+        let mut errors = Vec::new();
+        solid.collect_errors(&Default::default(), &mut errors);
+        assert!(errors.iter().any(|err| matches!(
+            err,
+            SolidValidationError::SharedGlobalEdge { .. }
+        )));
+
+        Ok(())
+    }
+
+    #[test]
+    fn solid_oriented_inward() -> anyhow::Result<()> {
+        let objects = Objects::new();
+
+        let valid = Solid::builder(&objects)
+            .with_cube_from_edge_length(1.)
+            .build()?;
+        let invalid = {
+            let shell = valid.shells().next().unwrap();
+            Solid::new([reversed(shell, &objects)?])
+        };
+
+        assert!(valid.validate().is_ok());
+        assert!(matches!(
+            invalid.validate(),
+            Err(SolidValidationError::OrientedInward { .. })
+        ));
+
         Ok(())
     }
 }
@@ -2,15 +2,12 @@ use std::convert::Infallible;
 
 use crate::objects::Surface;
 
-use super::{Validate, ValidationConfig};
+use super::{Validate, ValidationCheck};
 
 impl Validate for Surface {
     type Error = Infallible;
 
-    fn validate_with_config(
-        &self,
-        _: &ValidationConfig,
-    ) -> Result<(), Self::Error> {
-        Ok(())
+    fn checks() -> &'static [ValidationCheck<Self>] {
+        &[]
     }
 }
@@ -7,41 +7,32 @@ use crate::{
     storage::Handle,
 };
 
-use super::{Validate, ValidationConfig};
+use super::{Validate, ValidationCheck, ValidationConfig};
 
 impl Validate for Vertex {
     type Error = VertexValidationError;
 
-    fn validate_with_config(
-        &self,
-        config: &ValidationConfig,
-    ) -> Result<(), Self::Error> {
-        VertexValidationError::check_surface_identity(self)?;
-        VertexValidationError::check_position(self, config)?;
-        Ok(())
+    fn checks() -> &'static [ValidationCheck<Self>] {
+        &[
+            VertexValidationError::check_surface_identity,
+            VertexValidationError::check_position,
+        ]
     }
 }
 
 impl Validate for SurfaceVertex {
     type Error = SurfaceVertexValidationError;
 
-    fn validate_with_config(
-        &self,
-        config: &ValidationConfig,
-    ) -> Result<(), Self::Error> {
-        SurfaceVertexValidationError::check_position(self, config)?;
-        Ok(())
+    fn checks() -> &'static [ValidationCheck<Self>] {
+        &[SurfaceVertexValidationError::check_position]
     }
 }
 
 impl Validate for GlobalVertex {
     type Error = Infallible;
 
-    fn validate_with_config(
-        &self,
-        _: &ValidationConfig,
-    ) -> Result<(), Self::Error> {
-        Ok(())
+    fn checks() -> &'static [ValidationCheck<Self>] {
+        &[]
     }
 }
 
@@ -86,24 +77,37 @@ pub enum VertexValidationError {
 }
 
 impl VertexValidationError {
-    fn check_surface_identity(vertex: &Vertex) -> Result<(), Self> {
+    /// Access the points where the problem is located
+    pub(super) fn points(&self) -> Vec<Point<3>> {
+        match self {
+            Self::SurfaceMismatch { .. } => Vec::new(),
+            Self::PositionMismatch { surface_vertex, .. } => {
+                vec![surface_vertex.global_form().position()]
+            }
+        }
+    }
+
+    fn check_surface_identity(
+        vertex: &Vertex,
+        _: &ValidationConfig,
+        errors: &mut Vec<Self>,
+    ) {
         let curve_surface = vertex.curve().surface();
         let surface_form_surface = vertex.surface_form().surface();
 
         if curve_surface.id() != surface_form_surface.id() {
-            return Err(VertexValidationError::SurfaceMismatch {
+            errors.push(VertexValidationError::SurfaceMismatch {
                 curve_surface: curve_surface.clone(),
                 surface_form_surface: surface_form_surface.clone(),
             });
         }
-
-        Ok(())
     }
 
     fn check_position(
         vertex: &Vertex,
         config: &ValidationConfig,
-    ) -> Result<(), Self> {
+        errors: &mut Vec<Self>,
+    ) {
         let curve_position_as_surface = vertex
             .curve()
             .path()
@@ -113,15 +117,13 @@ impl VertexValidationError {
         let distance = curve_position_as_surface.distance_to(&surface_position);
 
         if distance > config.identical_max_distance {
-            return Err(VertexValidationError::PositionMismatch {
+            errors.push(VertexValidationError::PositionMismatch {
                 vertex: vertex.clone(),
                 surface_vertex: vertex.surface_form().clone_object(),
                 curve_position_as_surface,
                 distance,
             });
         }
-
-        Ok(())
     }
 }
 
@@ -152,10 +154,22 @@ pub enum SurfaceVertexValidationError {
 }
 
 impl SurfaceVertexValidationError {
+    /// Access the points where the problem is located
+    pub(super) fn points(&self) -> Vec<Point<3>> {
+        match self {
+            Self::PositionMismatch {
+                global_vertex,
+                surface_position_as_global,
+                ..
+            } => vec![global_vertex.position(), *surface_position_as_global],
+        }
+    }
+
     fn check_position(
         surface_vertex: &SurfaceVertex,
         config: &ValidationConfig,
-    ) -> Result<(), Self> {
+        errors: &mut Vec<Self>,
+    ) {
         let surface_position_as_global = surface_vertex
             .surface()
             .point_from_surface_coords(surface_vertex.position());
@@ -164,15 +178,13 @@ impl SurfaceVertexValidationError {
         let distance = surface_position_as_global.distance_to(&global_position);
 
         if distance > config.identical_max_distance {
-            return Err(Self::PositionMismatch {
+            errors.push(Self::PositionMismatch {
                 surface_vertex: surface_vertex.clone(),
                 global_vertex: surface_vertex.global_form().clone_object(),
                 surface_position_as_global,
                 distance,
             });
         }
-
-        Ok(())
     }
 }
 
@@ -42,6 +42,7 @@ mod line;
 mod plane;
 mod point;
 mod poly_chain;
+mod polygon;
 mod scalar;
 mod segment;
 mod transform;
@@ -56,6 +57,7 @@ pub use self::{
     plane::Plane,
     point::Point,
     poly_chain::PolyChain,
+    polygon::{assign_interiors, nest_polygons, signed_area, winding_number},
     scalar::{Scalar, Sign},
     segment::Segment,
     transform::Transform,
@@ -62,25 +62,30 @@ impl Plane {
     }
 
     /// Project a vector into the plane
+    ///
+    /// Returns the coordinates of the projected vector in terms of the plane's
+    /// u- and v-vectors. Those don't need to be normalized or orthogonal.
     pub fn project_vector(&self, vector: &Vector<3>) -> Vector<2> {
-        Vector::from([
-            self.u().scalar_projection_onto(vector),
-            self.v().scalar_projection_onto(vector),
-        ])
+        // Solve `u * s + v * t = vector` for `s` and `t`, in the least-squares
+        // sense. That takes care of the component of `vector` that is not
+        // parallel to the plane.
+        let uu = self.u().dot(&self.u());
+        let uv = self.u().dot(&self.v());
+        let vv = self.v().dot(&self.v());
+
+        let a = self.u().dot(vector);
+        let b = self.v().dot(vector);
+
+        let det = uu * vv - uv * uv;
+
+        Vector::from([(a * vv - b * uv) / det, (b * uu - a * uv) / det])
     }
 
     /// Project a line into the plane
     pub fn project_line(&self, line: &Line<3>) -> Line<2> {
-        let line_origin_relative_to_plane = line.origin() - self.origin();
         let line_origin_in_plane = Point {
-            coords: Vector::from([
-                self.u()
-                    .scalar_projection_onto(&line_origin_relative_to_plane),
-                self.v()
-                    .scalar_projection_onto(&line_origin_relative_to_plane),
-            ]),
+            coords: self.project_vector(&(line.origin() - self.origin())),
         };
-
         let line_direction_in_plane = self.project_vector(&line.direction());
 
         Line::from_origin_and_direction(
@@ -89,3 +94,27 @@ impl Plane {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{Line, Point, Vector};
+
+    use super::Plane;
+
+    #[test]
+    fn project_line() {
+        let plane = Plane::from_parametric(
+            Point::from([1., 1., 1.]),
+            Vector::from([2., 0., 0.]),
+            Vector::from([1., 0., 2.]),
+        );
+        let line = Line::from_origin_and_direction(
+            Point::from([2., 5., 3.]),
+            Vector::from([4., 3., 0.]),
+        );
+
+        let projected = plane.project_line(&line);
+        assert_eq!(projected.origin(), Point::from([0., 1.]));
+        assert_eq!(projected.direction(), Vector::from([2., 0.]));
+    }
+}
@@ -73,6 +73,31 @@ impl<const D: usize> PolyChain<D> {
     }
 }
 
+impl PolyChain<2> {
+    /// Indicate whether the polygon bounded by the chain contains a point
+    ///
+    /// Uses the even-odd rule. The chain is expected to be closed (see
+    /// [`PolyChain::close`]). Points on the boundary might or might not be
+    /// considered to be contained.
+    pub fn contains_point(&self, point: impl Into<Point<2>>) -> bool {
+        let point = point.into();
+        let mut is_inside = false;
+
+        for segment in self.segments() {
+            let [a, b] = segment.points();
+
+            if (a.v > point.v) != (b.v > point.v) {
+                let u = a.u + (point.v - a.v) / (b.v - a.v) * (b.u - a.u);
+                if point.u < u {
+                    is_inside = !is_inside;
+                }
+            }
+        }
+
+        is_inside
+    }
+}
+
 impl<P, Ps, const D: usize> From<Ps> for PolyChain<D>
 where
     P: Into<Point<D>>,
@@ -82,3 +107,27 @@ where
         Self::from_points(points)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::PolyChain;
+
+    #[test]
+    fn contains_point() {
+        let polygon = PolyChain::from_points([
+            [0., 0.],
+            [2., 0.],
+            [2., 2.],
+            [1., 1.],
+            [0., 2.],
+        ])
+        .close();
+
+        assert!(polygon.contains_point([1., 0.5]));
+        assert!(polygon.contains_point([1.5, 1.5]));
+
+        assert!(!polygon.contains_point([1., 1.5]));
+        assert!(!polygon.contains_point([3., 1.]));
+        assert!(!polygon.contains_point([-1., 1.]));
+    }
+}
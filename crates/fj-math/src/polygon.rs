@@ -0,0 +1,186 @@
+use crate::{Point, Scalar};
+
+/// Sort polygons into exteriors, and the interiors within them
+///
+/// A polygon that is within an even number of other polygons is an exterior.
+/// Any other polygon is an interior of the smallest exterior around it.
+///
+/// Polygons are given as their points, with the last point connected to the
+/// first. They are expected not to intersect each other, so checking whether
+/// the first point of a polygon is within another polygon is enough to decide
+/// whether the whole polygon is.
+///
+/// Returns the index of each exterior, together with the indices of its
+/// interiors. The orientation of the polygons doesn't matter, and isn't
+/// changed. Use [`signed_area`] to make them consistent, if required.
+pub fn nest_polygons<P>(polygons: &[&[P]]) -> Vec<(usize, Vec<usize>)>
+where
+    P: Copy + Into<Point<2>>,
+{
+    let mut exteriors = Vec::new();
+    let mut interiors = Vec::new();
+
+    for (i, polygon) in polygons.iter().enumerate() {
+        let around = match polygon.first() {
+            Some(&point) => polygons
+                .iter()
+                .enumerate()
+                .filter(|&(j, other)| {
+                    j != i && winding_number(other, point) != 0
+                })
+                .count(),
+            None => continue,
+        };
+
+        if around % 2 == 0 {
+            exteriors.push(i);
+        } else {
+            interiors.push(i);
+        }
+    }
+
+    assign_interiors(polygons, exteriors, interiors)
+}
+
+/// Assign interiors to the exteriors around them
+///
+/// This is the second half of [`nest_polygons`], for callers that decide what
+/// is an exterior and what is an interior by other means (like a fill rule).
+/// Each interior is assigned to the smallest exterior that contains it.
+/// Interiors that are not within any exterior are dropped.
+pub fn assign_interiors<P>(
+    polygons: &[&[P]],
+    exteriors: Vec<usize>,
+    interiors: impl IntoIterator<Item = usize>,
+) -> Vec<(usize, Vec<usize>)>
+where
+    P: Copy + Into<Point<2>>,
+{
+    let areas = exteriors
+        .iter()
+        .map(|&i| signed_area(polygons[i]).abs())
+        .collect::<Vec<_>>();
+
+    let mut nested = exteriors
+        .into_iter()
+        .map(|i| (i, Vec::new()))
+        .collect::<Vec<_>>();
+
+    for i in interiors {
+        let point = match polygons[i].first() {
+            Some(&point) => point,
+            None => continue,
+        };
+
+        let parent = nested
+            .iter()
+            .enumerate()
+            .filter(|(_, &(j, _))| winding_number(polygons[j], point) != 0)
+            .min_by_key(|&(k, _)| areas[k])
+            .map(|(k, _)| k);
+
+        if let Some(parent) = parent {
+            nested[parent].1.push(i);
+        }
+    }
+
+    nested
+}
+
+/// Compute the signed area of a polygon
+///
+/// The last point of the polygon is connected to the first. The area is
+/// positive, if the polygon is counter-clockwise, and negative, if it is
+/// clockwise.
+pub fn signed_area<P>(polygon: &[P]) -> Scalar
+where
+    P: Copy + Into<Point<2>>,
+{
+    let mut area = Scalar::ZERO;
+
+    for [a, b] in edges(polygon) {
+        area += a.u * b.v - b.u * a.v;
+    }
+
+    area / Scalar::TWO
+}
+
+/// Compute the winding number of a polygon around a point
+///
+/// The last point of the polygon is connected to the first. The winding number
+/// is positive, if the polygon winds around the point counter-clockwise, and
+/// negative, if it winds around it clockwise. It is zero, if the point is
+/// outside of the polygon. Points on the boundary might or might not be
+/// considered to be outside.
+pub fn winding_number<P>(polygon: &[P], point: impl Into<Point<2>>) -> i32
+where
+    P: Copy + Into<Point<2>>,
+{
+    let point = point.into();
+    let mut winding_number = 0;
+
+    for [a, b] in edges(polygon) {
+        let side =
+            (b.u - a.u) * (point.v - a.v) - (point.u - a.u) * (b.v - a.v);
+
+        if a.v <= point.v && b.v > point.v && side > Scalar::ZERO {
+            winding_number += 1;
+        }
+        if a.v > point.v && b.v <= point.v && side < Scalar::ZERO {
+            winding_number -= 1;
+        }
+    }
+
+    winding_number
+}
+
+fn edges<P>(polygon: &[P]) -> impl Iterator<Item = [Point<2>; 2]> + '_
+where
+    P: Copy + Into<Point<2>>,
+{
+    polygon
+        .iter()
+        .zip(polygon.iter().cycle().skip(1))
+        .map(|(&a, &b)| [a.into(), b.into()])
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Scalar;
+
+    use super::{nest_polygons, signed_area, winding_number};
+
+    fn square(min: f64, max: f64) -> Vec<[f64; 2]> {
+        vec![[min, min], [max, min], [max, max], [min, max]]
+    }
+
+    #[test]
+    fn nest_polygons_within_each_other() {
+        let outer = square(0., 10.);
+        let hole = square(1., 9.);
+        let island = square(2., 8.);
+        let inner_hole = square(3., 7.);
+        let mut other = square(20., 30.);
+        other.reverse();
+
+        // The order of the polygons must not matter.
+        let polygons = [&island[..], &other, &inner_hole, &outer, &hole];
+        let nested = nest_polygons(&polygons);
+
+        assert_eq!(nested, vec![(0, vec![2]), (1, vec![]), (3, vec![4])]);
+    }
+
+    #[test]
+    fn signed_area_and_winding_number() {
+        let ccw = square(0., 2.);
+        let mut cw = ccw.clone();
+        cw.reverse();
+
+        assert_eq!(signed_area(&ccw), Scalar::from(4.));
+        assert_eq!(signed_area(&cw), Scalar::from(-4.));
+
+        assert_eq!(winding_number(&ccw, [1., 1.]), 1);
+        assert_eq!(winding_number(&cw, [1., 1.]), -1);
+        assert_eq!(winding_number(&ccw, [3., 1.]), 0);
+    }
+}
@@ -40,6 +40,42 @@ impl Transform {
         ))
     }
 
+    /// Construct a scaling
+    ///
+    /// Each component of `factors` defines the scaling factor along the
+    /// respective axis. If not all factors are equal, the scaling is
+    /// non-uniform.
+    pub fn scaling(factors: impl Into<Vector<3>>) -> Self {
+        let factors = factors.into();
+
+        Self(nalgebra::Transform::from_matrix_unchecked(
+            nalgebra::OMatrix::new_nonuniform_scaling(&factors.to_na()),
+        ))
+    }
+
+    /// Construct a mirroring across a plane through the origin
+    ///
+    /// The plane is defined by its normal vector, whose length is irrelevant.
+    pub fn mirror(normal: impl Into<Vector<3>>) -> Self {
+        let normal = normal.into().normalize().to_na();
+        let reflection =
+            nalgebra::Matrix3::identity() - normal * normal.transpose() * 2.;
+
+        Self(nalgebra::Transform::from_matrix_unchecked(
+            reflection.to_homogeneous(),
+        ))
+    }
+
+    /// Indicate whether the transform flips the handedness of space
+    ///
+    /// This is the case for transforms that mirror, or scale by a negative
+    /// factor along an odd number of axes. Anything oriented (like the front
+    /// side of a face) ends up oriented the wrong way, after being transformed
+    /// by such a transform.
+    pub fn flips_handedness(&self) -> bool {
+        self.0.matrix().fixed_resize::<3, 3>(0.).determinant() < 0.
+    }
+
     /// Transform the given point
     pub fn transform_point(&self, point: &Point<3>) -> Point<3> {
         Point::from(self.0.transform_point(&point.to_na()))
@@ -80,6 +116,11 @@ impl Transform {
     }
 
     /// Transform the given circle
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the transformed circle is no longer a circle. This can be
+    /// the case for non-uniform scalings.
     pub fn transform_circle(&self, circle: &Circle<3>) -> Circle<3> {
         Circle::new(
             self.transform_point(&circle.center()),
@@ -121,10 +162,25 @@ impl Transform {
 
     /// Transform the given axis-aligned bounding box
     pub fn transform_aabb(&self, aabb: &Aabb<3>) -> Aabb<3> {
-        Aabb {
-            min: self.transform_point(&aabb.min),
-            max: self.transform_point(&aabb.max),
-        }
+        // Transforming only `min` and `max` is not enough. Rotations and
+        // mirrorings can change which corners end up being the extremes.
+        let [min, max] = [aabb.min, aabb.max];
+        let corners = [
+            [min.x, min.y, min.z],
+            [min.x, min.y, max.z],
+            [min.x, max.y, min.z],
+            [min.x, max.y, max.z],
+            [max.x, min.y, min.z],
+            [max.x, min.y, max.z],
+            [max.x, max.y, min.z],
+            [max.x, max.y, max.z],
+        ];
+
+        Aabb::<3>::from_points(
+            corners
+                .into_iter()
+                .map(|corner| self.transform_point(&Point::from(corner))),
+        )
     }
 
     /// Exposes the data of this Transform as a slice of f64.
@@ -157,7 +213,7 @@ impl ops::Mul<Self> for Transform {
 mod tests {
     use approx::assert_abs_diff_eq;
 
-    use crate::{Line, Point, Scalar, Vector};
+    use crate::{Aabb, Line, Point, Scalar, Vector};
 
     use super::Transform;
 
@@ -212,4 +268,51 @@ mod tests {
             epsilon = 1e-8,
         );
     }
+
+    #[test]
+    fn scaling() {
+        let scaling = Transform::scaling([1., 2., 3.]);
+
+        assert_abs_diff_eq!(
+            scaling.transform_point(&Point::from([1., 1., 1.])),
+            Point::from([1., 2., 3.]),
+            epsilon = Scalar::from(1e-8),
+        );
+        assert!(!scaling.flips_handedness());
+        assert!(Transform::scaling([-1., 1., 1.]).flips_handedness());
+    }
+
+    #[test]
+    fn mirror() {
+        let mirror = Transform::mirror([0., 0., 2.]);
+
+        assert_abs_diff_eq!(
+            mirror.transform_point(&Point::from([1., 2., 3.])),
+            Point::from([1., 2., -3.]),
+            epsilon = Scalar::from(1e-8),
+        );
+        assert!(mirror.flips_handedness());
+        assert!(!(mirror * mirror).flips_handedness());
+    }
+
+    #[test]
+    fn transform_aabb() {
+        let aabb = Aabb {
+            min: Point::from([0., 0., 0.]),
+            max: Point::from([1., 2., 3.]),
+        };
+
+        let aabb = Transform::mirror([1., 0., 0.]).transform_aabb(&aabb);
+
+        assert_abs_diff_eq!(
+            aabb.min,
+            Point::from([-1., 0., 0.]),
+            epsilon = Scalar::from(1e-8),
+        );
+        assert_abs_diff_eq!(
+            aabb.max,
+            Point::from([0., 2., 3.]),
+            epsilon = Scalar::from(1e-8),
+        );
+    }
 }
@@ -0,0 +1,476 @@
+//! Caching of shape processing results across evaluations
+//!
+//! See [`Cache`].
+
+use std::{
+    any::{Any, TypeId},
+    collections::{hash_map::DefaultHasher, HashMap},
+    hash::{Hash, Hasher},
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+use fj_interop::{debug::DebugInfo, mesh::Mesh};
+use fj_kernel::{
+    algorithms::approx::Tolerance,
+    objects::{Objects, Surface},
+    storage::Handle,
+};
+use fj_math::{Point, Scalar};
+
+/// Cache for the results of processing shapes
+///
+/// Computing the boundary representation of a shape and triangulating it can
+/// be expensive. Often, only a small part of a model changes between
+/// evaluations (for example, when a single parameter is tweaked), which means
+/// most results of the previous evaluation can be reused.
+///
+/// Results are keyed by a 128-bit hash of the [`fj`] shape they were computed
+/// from (see [`HashShape`]), and by the tolerance they were computed with.
+/// Results are shared with the cache, so reusing them doesn't require a copy.
+/// Any [`DebugInfo`] that was produced while computing a result is cached too,
+/// and added to the debug info again, whenever the result is reused.
+///
+/// Results that weren't used during an evaluation are evicted, once
+/// [`Cache::finish_evaluation`] is called.
+///
+/// # Implementation Note
+///
+/// Boundary representations reference objects in the [`Objects`] store that
+/// was used to compute them, and mixing objects from different stores in one
+/// shape is not supported. For that reason, the cache only holds B-reps from
+/// one store at a time. Requesting a B-rep with another store evicts all B-reps
+/// of the previous one, which also frees that store. Meshes don't reference any
+/// objects, and are kept regardless.
+///
+/// To reuse B-reps across evaluations, use the same store for all of them, as
+/// [`ShapeProcessor`] does.
+///
+/// [`ShapeProcessor`]: crate::shape_processor::ShapeProcessor
+#[derive(Default)]
+pub struct Cache {
+    inner: Mutex<Inner>,
+}
+
+impl Cache {
+    /// Construct an empty instance of `Cache`
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Return the cached B-rep of `shape`, or compute and cache it
+    ///
+    /// B-reps are keyed by the tolerance too, as curves that the kernel can't
+    /// represent exactly (like those of text) are approximated when computing
+    /// them.
+    ///
+    /// The lock on the cache is not held while `compute` runs, so `compute` is
+    /// free to access the cache recursively, or from other threads.
+    pub fn brep<S, E>(
+        &self,
+        shape: &S,
+        objects: &Objects,
+        tolerance: Tolerance,
+        debug_info: &mut DebugInfo,
+        compute: impl FnOnce(&mut DebugInfo) -> Result<S::Brep, E>,
+    ) -> Result<S::Brep, E>
+    where
+        S: crate::Shape + HashShape + 'static,
+        S::Brep: Clone + Send + Sync + 'static,
+    {
+        // Any object will do to identify the store. The cache holds on to it,
+        // which keeps the store alive, and its id unique, as long as B-reps
+        // from that store are cached.
+        let store = objects.surfaces.xy_plane();
+        let key = Key::new::<S, S::Brep>(shape, tolerance);
+
+        let brep =
+            self.get_or_compute(key, Some(store), debug_info, compute)?;
+        Ok(S::Brep::clone(&brep))
+    }
+
+    /// Return the cached mesh of `shape`, or compute and cache it
+    ///
+    /// See [`Cache::brep`].
+    pub fn mesh<E>(
+        &self,
+        shape: &fj::Shape,
+        tolerance: Tolerance,
+        debug_info: &mut DebugInfo,
+        compute: impl FnOnce(&mut DebugInfo) -> Result<Mesh<Point<3>>, E>,
+    ) -> Result<Arc<Mesh<Point<3>>>, E> {
+        let key = Key::new::<_, Mesh<Point<3>>>(shape, tolerance);
+        self.get_or_compute(key, None, debug_info, compute)
+    }
+
+    /// Evict all results that have not been used since the last call
+    ///
+    /// This should be called after every evaluation of a model. Otherwise, the
+    /// cache would keep on growing, as the model changes.
+    pub fn finish_evaluation(&self) {
+        self.lock().entries.retain(|_, entry| {
+            let used = entry.used;
+            entry.used = false;
+            used
+        });
+    }
+
+    /// Return the number of cached results
+    pub fn len(&self) -> usize {
+        self.lock().entries.len()
+    }
+
+    /// Indicate whether the cache is empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn get_or_compute<T, E>(
+        &self,
+        key: Key,
+        store: Option<Handle<Surface>>,
+        debug_info: &mut DebugInfo,
+        compute: impl FnOnce(&mut DebugInfo) -> Result<T, E>,
+    ) -> Result<Arc<T>, E>
+    where
+        T: Send + Sync + 'static,
+    {
+        let cached = {
+            let mut inner = self.lock();
+            if let Some(store) = &store {
+                inner.switch_store(store);
+            }
+            inner.get(&key)
+        };
+        if let Some((value, cached_debug_info)) = cached {
+            debug_info.extend(DebugInfo::clone(&cached_debug_info));
+            return Ok(value);
+        }
+
+        let mut new_debug_info = DebugInfo::new();
+        let value = Arc::new(compute(&mut new_debug_info)?);
+        let new_debug_info = Arc::new(new_debug_info);
+
+        let mut inner = self.lock();
+
+        // The store might have been switched, while the lock wasn't held. The
+        // result can't be reused then.
+        let is_current = match (&store, &inner.store) {
+            (Some(store), Some(current)) => store.id() == current.id(),
+            (Some(_), None) => false,
+            (None, _) => true,
+        };
+        if is_current {
+            inner.entries.insert(
+                key,
+                Entry {
+                    value: value.clone(),
+                    debug_info: new_debug_info.clone(),
+                    is_brep: store.is_some(),
+                    used: true,
+                },
+            );
+        }
+        drop(inner);
+
+        debug_info.extend(DebugInfo::clone(&new_debug_info));
+
+        Ok(value)
+    }
+
+    fn lock(&self) -> MutexGuard<Inner> {
+        // The lock is never held while running code that could panic, so it
+        // should never be poisoned. But if it is, the cached data is still
+        // fine.
+        self.inner.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+#[derive(Default)]
+struct Inner {
+    entries: HashMap<Key, Entry>,
+
+    /// The store that the cached B-reps are from
+    store: Option<Handle<Surface>>,
+}
+
+impl Inner {
+    fn switch_store(&mut self, store: &Handle<Surface>) {
+        if let Some(current) = &self.store {
+            if current.id() == store.id() {
+                return;
+            }
+        }
+
+        self.entries.retain(|_, entry| !entry.is_brep);
+        self.store = Some(store.clone());
+    }
+
+    fn get<T>(&mut self, key: &Key) -> Option<(Arc<T>, Arc<DebugInfo>)>
+    where
+        T: Send + Sync + 'static,
+    {
+        let entry = self.entries.get_mut(key)?;
+        let value = entry.value.clone().downcast::<T>().ok()?;
+
+        entry.used = true;
+        Some((value, entry.debug_info.clone()))
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+struct Key {
+    shape_type: TypeId,
+    shape_hash: [u64; 2],
+    value_type: TypeId,
+    tolerance: Tolerance,
+}
+
+impl Key {
+    fn new<S, T>(shape: &S, tolerance: Tolerance) -> Self
+    where
+        S: HashShape + 'static,
+        T: 'static,
+    {
+        // A 64-bit hash would make collisions unlikely, but not so unlikely
+        // that we could ignore them. Instead of keeping a copy of each shape
+        // around to rule them out, we combine two independent hashes.
+        let shape_hash = [0u8, 1].map(|seed| {
+            let mut hasher = DefaultHasher::new();
+            seed.hash(&mut hasher);
+            shape.hash_shape(&mut hasher);
+            hasher.finish()
+        });
+
+        Self {
+            shape_type: TypeId::of::<S>(),
+            shape_hash,
+            value_type: TypeId::of::<T>(),
+            tolerance,
+        }
+    }
+}
+
+struct Entry {
+    value: Arc<dyn Any + Send + Sync>,
+    debug_info: Arc<DebugInfo>,
+    is_brep: bool,
+    used: bool,
+}
+
+/// Hash an [`fj`] shape
+///
+/// The [`fj`] shape types can't implement [`Hash`], as they contain
+/// floating-point numbers. Implementations of this trait hash those numbers as
+/// [`Scalar`]s, which are consistent with the shapes' [`PartialEq`]
+/// implementations.
+///
+/// Implemented for all operations from the [`fj`] crate.
+pub trait HashShape {
+    /// Feed the shape into the provided hasher
+    fn hash_shape<H: Hasher>(&self, state: &mut H);
+}
+
+impl HashShape for f64 {
+    fn hash_shape<H: Hasher>(&self, state: &mut H) {
+        Scalar::from_f64(*self).hash(state);
+    }
+}
+
+impl<T, const N: usize> HashShape for [T; N]
+where
+    T: HashShape,
+{
+    fn hash_shape<H: Hasher>(&self, state: &mut H) {
+        for element in self {
+            element.hash_shape(state);
+        }
+    }
+}
+
+impl<T> HashShape for Vec<T>
+where
+    T: HashShape,
+{
+    fn hash_shape<H: Hasher>(&self, state: &mut H) {
+        self.len().hash(state);
+        for element in self {
+            element.hash_shape(state);
+        }
+    }
+}
+
+impl<T> HashShape for fj::abi::ffi_safe::Option<T>
+where
+    T: HashShape,
+{
+    fn hash_shape<H: Hasher>(&self, state: &mut H) {
+        let value: Option<&T> = self.as_ref().into();
+
+        value.is_some().hash(state);
+        if let Some(value) = value {
+            value.hash_shape(state);
+        }
+    }
+}
+
+impl HashShape for fj::Angle {
+    fn hash_shape<H: Hasher>(&self, state: &mut H) {
+        self.rad().hash_shape(state);
+    }
+}
+
+impl HashShape for fj::Shape {
+    fn hash_shape<H: Hasher>(&self, state: &mut H) {
+        match self {
+            Self::Group(shape) => {
+                0u8.hash(state);
+                shape.hash_shape(state);
+            }
+            Self::Shape2d(shape) => {
+                1u8.hash(state);
+                shape.hash_shape(state);
+            }
+            Self::Sweep(shape) => {
+                2u8.hash(state);
+                shape.hash_shape(state);
+            }
+            Self::Transform(shape) => {
+                3u8.hash(state);
+                shape.hash_shape(state);
+            }
+            Self::LinearPattern(shape) => {
+                4u8.hash(state);
+                shape.hash_shape(state);
+            }
+            Self::CircularPattern(shape) => {
+                5u8.hash(state);
+                shape.hash_shape(state);
+            }
+        }
+    }
+}
+
+impl HashShape for fj::Shape2d {
+    fn hash_shape<H: Hasher>(&self, state: &mut H) {
+        match self {
+            Self::Difference(shape) => {
+                0u8.hash(state);
+                shape.hash_shape(state);
+            }
+            Self::Sketch(shape) => {
+                1u8.hash(state);
+                shape.hash_shape(state);
+            }
+            Self::Text(shape) => {
+                2u8.hash(state);
+                shape.hash_shape(state);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use fj::syntax::*;
+    use fj_interop::debug::{DebugInfo, ValidationIssue};
+    use fj_kernel::{algorithms::approx::Tolerance, objects::Objects};
+
+    use crate::{shape_processor::Error, test_utils::compute_brep};
+
+    use super::Cache;
+
+    #[test]
+    fn reuse_unchanged_results() {
+        let cache = Cache::new();
+
+        let shape: fj::Shape = [[0., 0.], [1., 0.], [0., 1.]].sketch().into();
+        let tolerance = Tolerance::from_scalar(0.1).unwrap();
+
+        let mut computations = 0;
+        for _ in 0..2 {
+            cache
+                .mesh(&shape, tolerance, &mut DebugInfo::new(), |_| {
+                    computations += 1;
+                    Ok::<_, Infallible>(Default::default())
+                })
+                .unwrap();
+            cache.finish_evaluation();
+        }
+
+        assert_eq!(computations, 1);
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn evict_unused_results() {
+        let cache = Cache::new();
+
+        let a: fj::Shape = [[0., 0.], [1., 0.], [0., 1.]].sketch().into();
+        let b: fj::Shape = [[0., 0.], [2., 0.], [0., 2.]].sketch().into();
+        let tolerance = Tolerance::from_scalar(0.1).unwrap();
+
+        for shape in [a, b] {
+            cache
+                .mesh(&shape, tolerance, &mut DebugInfo::new(), |_| {
+                    Ok::<_, Infallible>(Default::default())
+                })
+                .unwrap();
+            cache.finish_evaluation();
+        }
+
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn replay_debug_info() {
+        let cache = Cache::new();
+
+        let shape: fj::Shape = [[0., 0.], [1., 0.], [0., 1.]].sketch().into();
+        let tolerance = Tolerance::from_scalar(0.1).unwrap();
+
+        for _ in 0..2 {
+            let mut debug_info = DebugInfo::new();
+            cache
+                .mesh(&shape, tolerance, &mut debug_info, |debug_info| {
+                    debug_info.validation_issues.push(ValidationIssue {
+                        message: String::from("issue"),
+                        points: Vec::new(),
+                    });
+                    Ok::<_, Infallible>(Default::default())
+                })
+                .unwrap();
+
+            assert_eq!(debug_info.validation_issues.len(), 1);
+        }
+    }
+
+    #[test]
+    fn reuse_breps_only_with_same_objects() -> Result<(), Error> {
+        let cache = Cache::new();
+
+        let shape: fj::Shape2d = [[0., 0.], [1., 0.], [0., 1.]].sketch().into();
+
+        let face_ids = |objects: &Objects| -> Result<_, Error> {
+            let sketch = compute_brep(&shape, objects, &cache)?;
+            Ok(sketch
+                .faces()
+                .into_iter()
+                .map(|face| face.id())
+                .collect::<Vec<_>>())
+        };
+
+        let objects = Objects::new();
+        let other_objects = Objects::new();
+
+        assert_eq!(face_ids(&objects)?, face_ids(&objects)?);
+
+        // Switching to another store replaces the B-reps of the previous one.
+        let len = cache.len();
+        assert_ne!(face_ids(&objects)?, face_ids(&other_objects)?);
+        assert_eq!(cache.len(), len);
+
+        Ok(())
+    }
+}
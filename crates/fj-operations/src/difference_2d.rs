@@ -1,17 +1,23 @@
-use std::ops::Deref;
+use std::{hash::Hasher, ops::Deref};
 
 use fj_interop::{debug::DebugInfo, ext::ArrayExt, mesh::Color};
 use fj_kernel::{
-    algorithms::reverse::Reverse,
+    algorithms::{approx::Tolerance, reverse::Reverse},
     insert::Insert,
     iter::ObjectIters,
     objects::{Face, Objects, Sketch},
     partial::HasPartial,
+    path::SurfacePath,
     validate::ValidationError,
 };
-use fj_math::Aabb;
+use fj_math::{Aabb, PolyChain};
 
-use super::Shape;
+use super::{
+    cache::{Cache, HashShape},
+    compute_brep_in_parallel,
+    shape_processor::Error,
+    Shape,
+};
 
 impl Shape for fj::Difference2d {
     type Brep = Sketch;
@@ -19,73 +25,75 @@ impl Shape for fj::Difference2d {
     fn compute_brep(
         &self,
         objects: &Objects,
+        cache: &Cache,
+        tolerance: Tolerance,
         debug_info: &mut DebugInfo,
-    ) -> Result<Self::Brep, ValidationError> {
-        // This method assumes that `b` is fully contained within `a`:
+    ) -> Result<Self::Brep, Error> {
+        // This method assumes that each face of `b` is fully contained within a
+        // face of `a`:
         // https://github.com/hannobraun/Fornjot/issues/92
 
-        let mut faces = Vec::new();
-
-        let mut exteriors = Vec::new();
-        let mut interiors = Vec::new();
-
-        let [a, b] = self
-            .shapes()
-            .each_ref_ext()
-            .try_map_ext(|shape| shape.compute_brep(objects, debug_info))?;
-
-        if let Some(face) = a.face_iter().next() {
-            // If there's at least one face to subtract from, we can proceed.
-
-            let surface = face.surface();
+        let [a, b] = compute_brep_in_parallel(
+            self.shapes().each_ref_ext(),
+            objects,
+            cache,
+            tolerance,
+            debug_info,
+        )?;
+
+        // Each face of `a` results in one face of the difference. The faces of
+        // `b` become holes in the face of `a` that contains them.
+        let mut differences = a
+            .face_iter()
+            .map(|face| {
+                // The interiors of `a` already have the right orientation.
+                // They stay holes in the resulting face.
+                let interiors = face.interiors().cloned().collect::<Vec<_>>();
+                (face, exterior_polygon(face), interiors)
+            })
+            .collect::<Vec<_>>();
 
-            for face in a.face_iter() {
-                assert_eq!(
-                    surface,
-                    face.surface(),
-                    "Trying to subtract faces with different surfaces.",
-                );
-
-                exteriors.push(face.exterior().clone());
-                for cycle in face.interiors() {
-                    interiors.push(cycle.clone().reverse(objects)?);
-                }
-            }
+        let mut faces = Vec::new();
 
-            for face in b.face_iter() {
-                assert_eq!(
-                    surface,
-                    face.surface(),
-                    "Trying to subtract faces with different surfaces.",
+        for face in b.face_iter() {
+            let point = face.exterior().half_edges().next().map(|half_edge| {
+                half_edge.vertices()[0].surface_form().position()
+            });
+            let containing = differences.iter_mut().find(|(a, exterior, _)| {
+                a.surface() == face.surface()
+                    && point
+                        .map_or(false, |point| exterior.contains_point(point))
+            });
+            let (_, _, interiors) = match containing {
+                Some(containing) => containing,
+                None => return Err(Error::DifferenceNotContained),
+            };
+
+            interiors.push(face.exterior().clone().reverse(objects)?);
+
+            // The holes of a subtracted face (think of the letter "o") are
+            // left standing, as separate faces.
+            for cycle in face.interiors() {
+                faces.push(
+                    Face::partial()
+                        .with_exterior(cycle.clone().reverse(objects)?)
+                        .with_color(Color(self.color()))
+                        .build(objects)?
+                        .insert(objects)
+                        .map_err(ValidationError::from)?,
                 );
-
-                interiors.push(face.exterior().clone().reverse(objects)?);
             }
+        }
 
-            // Faces only support one exterior, while the code here comes from
-            // the time when a face could have multiple exteriors. This was only
-            // a special case, i.e. faces that connected to themselves, and I
-            // have my doubts that this code was ever correct in the first
-            // place.
-            //
-            // Anyway, the following should make sure that at least any problems
-            // this code causes become obvious. I don't know if this can ever
-            // trigger, but better safe than sorry.
-            let exterior = exteriors
-                .pop()
-                .expect("Can't construct face without an exterior");
-            assert!(
-                exteriors.is_empty(),
-                "Can't construct face with multiple exteriors"
-            );
-
+        for (face, _, interiors) in differences {
             faces.push(
                 Face::partial()
-                    .with_exterior(exterior)
+                    .with_exterior(face.exterior().clone())
                     .with_interiors(interiors)
                     .with_color(Color(self.color()))
                     .build(objects)?
-                    .insert(objects)?,
+                    .insert(objects)
+                    .map_err(ValidationError::from)?,
             );
         }
 
@@ -93,10 +101,140 @@ impl Shape for fj::Difference2d {
         Ok(difference.deref().clone())
     }
 
-    fn bounding_volume(&self) -> Aabb<3> {
+    fn bounding_volume(&self) -> Result<Aabb<3>, Error> {
         // This is a conservative estimate of the bounding box: It's never going
         // to be bigger than the bounding box of the original shape that another
         // is being subtracted from.
         self.shapes()[0].bounding_volume()
     }
 }
+
+impl HashShape for fj::Difference2d {
+    fn hash_shape<H: Hasher>(&self, state: &mut H) {
+        for shape in self.shapes() {
+            shape.hash_shape(state);
+        }
+    }
+}
+
+/// Approximate the exterior of a face as a polygon, in surface coordinates
+///
+/// The polygon is only used to decide which face contains a point, so circles
+/// are approximated coarsely.
+fn exterior_polygon(face: &Face) -> PolyChain<2> {
+    const CIRCLE_SEGMENTS: u32 = 32;
+
+    let mut points = Vec::new();
+
+    for half_edge in face.exterior().half_edges() {
+        let [back, front] = half_edge.vertices();
+
+        match half_edge.curve().path() {
+            SurfacePath::Line(_) => {
+                points.push(back.surface_form().position());
+            }
+            path @ SurfacePath::Circle(_) => {
+                let [start, end] =
+                    [back, front].map(|vertex| vertex.position().t);
+
+                for i in 0..CIRCLE_SEGMENTS {
+                    let t = start
+                        + (end - start) * f64::from(i)
+                            / f64::from(CIRCLE_SEGMENTS);
+                    points.push(path.point_from_path_coords([t]));
+                }
+            }
+        }
+    }
+
+    PolyChain::from_points(points).close()
+}
+
+#[cfg(test)]
+mod tests {
+    use fj::syntax::*;
+    use fj_kernel::objects::Objects;
+
+    use crate::{
+        cache::Cache, shape_processor::Error, test_utils::compute_brep,
+    };
+
+    #[test]
+    fn difference_with_multiple_exteriors() -> Result<(), Error> {
+        let objects = Objects::new();
+
+        // Two glyphs, each a bar that covers `[2, 3]` along u (the second one
+        // offset by 6), and `[0, 7]` along v.
+        let bars = fj::Text::from_str("||", 10.);
+        let hole = [[2.25, 1.], [2.75, 1.], [2.75, 2.], [2.25, 2.]].sketch();
+
+        let difference =
+            fj::Difference2d::from_shapes([bars.clone().into(), hole.into()]);
+        let sketch = compute_brep(&difference, &objects, &Cache::new())?;
+
+        let mut num_interiors = sketch
+            .faces()
+            .into_iter()
+            .map(|face| face.interiors().count())
+            .collect::<Vec<_>>();
+        num_interiors.sort();
+        assert_eq!(num_interiors, [0, 1]);
+
+        let outside = [[4., 1.], [5., 1.], [5., 2.], [4., 2.]].sketch();
+        let difference =
+            fj::Difference2d::from_shapes([bars.into(), outside.into()]);
+        let result = compute_brep(&difference, &objects, &Cache::new());
+        assert!(matches!(result, Err(Error::DifferenceNotContained)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn difference_keeps_holes_of_shape_subtracted_from() -> Result<(), Error> {
+        let objects = Objects::new();
+
+        let square = [[0., 0.], [4., 0.], [4., 4.], [0., 4.]].sketch();
+        let a = [[1., 1.], [2., 1.], [2., 2.], [1., 2.]].sketch();
+        let b = [[2.5, 2.5], [3., 2.5], [3., 3.], [2.5, 3.]].sketch();
+
+        let with_hole =
+            fj::Difference2d::from_shapes([square.into(), a.into()]);
+        let difference =
+            fj::Difference2d::from_shapes([with_hole.into(), b.into()]);
+        let sketch = compute_brep(&difference, &objects, &Cache::new())?;
+
+        let faces = sketch.faces().into_iter().collect::<Vec<_>>();
+        assert_eq!(faces.len(), 1);
+
+        // Both holes must be wound opposite to the exterior. The hole that
+        // already existed in the shape being subtracted from must not be
+        // reversed a second time.
+        let face = faces[0];
+        assert_eq!(face.interiors().count(), 2);
+        for interior in face.interiors() {
+            assert_ne!(interior.winding(), face.exterior().winding());
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn difference_of_circles() -> Result<(), Error> {
+        let objects = Objects::new();
+
+        let circle =
+            |radius| fj::Sketch::from_circle(fj::Circle::from_radius(radius));
+
+        let difference = fj::Difference2d::from_shapes([
+            circle(2.).into(),
+            circle(1.).into(),
+        ]);
+        let sketch = compute_brep(&difference, &objects, &Cache::new())?;
+
+        let faces = sketch.faces().into_iter().collect::<Vec<_>>();
+        assert_eq!(faces.len(), 1);
+        assert_eq!(faces[0].interiors().count(), 1);
+
+        Ok(())
+    }
+}
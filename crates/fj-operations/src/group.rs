@@ -1,11 +1,18 @@
+use std::hash::Hasher;
+
 use fj_interop::debug::DebugInfo;
 use fj_kernel::{
+    algorithms::approx::Tolerance,
     objects::{FaceSet, Objects},
-    validate::ValidationError,
 };
 use fj_math::Aabb;
 
-use super::Shape;
+use super::{
+    cache::{Cache, HashShape},
+    compute_brep_in_parallel,
+    shape_processor::Error,
+    Shape,
+};
 
 impl Shape for fj::Group {
     type Brep = FaceSet;
@@ -13,12 +20,21 @@ impl Shape for fj::Group {
     fn compute_brep(
         &self,
         objects: &Objects,
+        cache: &Cache,
+        tolerance: Tolerance,
         debug_info: &mut DebugInfo,
-    ) -> Result<Self::Brep, ValidationError> {
+    ) -> Result<Self::Brep, Error> {
         let mut faces = FaceSet::new();
 
-        let a = self.a.compute_brep(objects, debug_info)?;
-        let b = self.b.compute_brep(objects, debug_info)?;
+        // The members of a group are independent of each other, so they can
+        // be computed in parallel.
+        let [a, b] = compute_brep_in_parallel(
+            [&self.a, &self.b],
+            objects,
+            cache,
+            tolerance,
+            debug_info,
+        )?;
 
         faces.extend(a);
         faces.extend(b);
@@ -26,10 +42,17 @@ impl Shape for fj::Group {
         Ok(faces)
     }
 
-    fn bounding_volume(&self) -> Aabb<3> {
-        let a = self.a.bounding_volume();
-        let b = self.b.bounding_volume();
+    fn bounding_volume(&self) -> Result<Aabb<3>, Error> {
+        let a = self.a.bounding_volume()?;
+        let b = self.b.bounding_volume()?;
+
+        Ok(a.merged(&b))
+    }
+}
 
-        a.merged(&b)
+impl HashShape for fj::Group {
+    fn hash_shape<H: Hasher>(&self, state: &mut H) {
+        self.a.hash_shape(state);
+        self.b.hash_shape(state);
     }
 }
@@ -16,38 +16,58 @@
 
 #![warn(missing_docs)]
 
+pub mod cache;
 pub mod shape_processor;
 
 mod difference_2d;
 mod group;
+mod pattern;
 mod sketch;
 mod sweep;
+mod text;
 mod transform;
 
+#[cfg(test)]
+mod test_utils;
+
+use std::{panic, thread};
+
 use fj_interop::debug::DebugInfo;
 use fj_kernel::{
+    algorithms::approx::Tolerance,
     objects::{FaceSet, Objects, Sketch},
-    validate::ValidationError,
+    threads::ThreadPermit,
 };
 use fj_math::Aabb;
 
+use self::{cache::Cache, shape_processor::Error};
+
 /// Implemented for all operations from the [`fj`] crate
 pub trait Shape {
     /// The type that is used for the shape's boundary representation
     type Brep;
 
     /// Compute the boundary representation of the shape
+    ///
+    /// Implementations that consist of other shapes should pass on `cache`,
+    /// so the boundary representations of those can be reused, if they have
+    /// been computed before.
     fn compute_brep(
         &self,
         objects: &Objects,
+        cache: &Cache,
+        tolerance: Tolerance,
         debug_info: &mut DebugInfo,
-    ) -> Result<Self::Brep, ValidationError>;
+    ) -> Result<Self::Brep, Error>;
 
     /// Access the axis-aligned bounding box of a shape
     ///
     /// If a shape is empty, its [`Aabb`]'s `min` and `max` points must be equal
     /// (but are otherwise not specified).
-    fn bounding_volume(&self) -> Aabb<3>;
+    ///
+    /// Returns an error, if the shape is invalid in a way that prevents its
+    /// extent from being determined, like text with a broken font.
+    fn bounding_volume(&self) -> Result<Aabb<3>, Error>;
 }
 
 impl Shape for fj::Shape {
@@ -56,30 +76,51 @@ impl Shape for fj::Shape {
     fn compute_brep(
         &self,
         objects: &Objects,
+        cache: &Cache,
+        tolerance: Tolerance,
         debug_info: &mut DebugInfo,
-    ) -> Result<Self::Brep, ValidationError> {
-        match self {
-            Self::Shape2d(shape) => {
-                Ok(shape.compute_brep(objects, debug_info)?.faces().clone())
-            }
-            Self::Group(shape) => shape.compute_brep(objects, debug_info),
-            Self::Sweep(shape) => Ok(shape
-                .compute_brep(objects, debug_info)?
-                .shells()
-                .map(|shell| shell.faces().clone())
-                .reduce(|mut a, b| {
-                    a.extend(b);
-                    a
-                })
-                .unwrap_or_default()),
-            Self::Transform(shape) => shape.compute_brep(objects, debug_info),
-        }
+    ) -> Result<Self::Brep, Error> {
+        cache.brep(
+            self,
+            objects,
+            tolerance,
+            debug_info,
+            |debug_info| match self {
+                Self::CircularPattern(shape) => {
+                    shape.compute_brep(objects, cache, tolerance, debug_info)
+                }
+                Self::LinearPattern(shape) => {
+                    shape.compute_brep(objects, cache, tolerance, debug_info)
+                }
+                Self::Shape2d(shape) => Ok(shape
+                    .compute_brep(objects, cache, tolerance, debug_info)?
+                    .faces()
+                    .clone()),
+                Self::Group(shape) => {
+                    shape.compute_brep(objects, cache, tolerance, debug_info)
+                }
+                Self::Sweep(shape) => Ok(shape
+                    .compute_brep(objects, cache, tolerance, debug_info)?
+                    .shells()
+                    .map(|shell| shell.faces().clone())
+                    .reduce(|mut a, b| {
+                        a.extend(b);
+                        a
+                    })
+                    .unwrap_or_default()),
+                Self::Transform(shape) => {
+                    shape.compute_brep(objects, cache, tolerance, debug_info)
+                }
+            },
+        )
     }
 
-    fn bounding_volume(&self) -> Aabb<3> {
+    fn bounding_volume(&self) -> Result<Aabb<3>, Error> {
         match self {
             Self::Shape2d(shape) => shape.bounding_volume(),
+            Self::CircularPattern(shape) => shape.bounding_volume(),
             Self::Group(shape) => shape.bounding_volume(),
+            Self::LinearPattern(shape) => shape.bounding_volume(),
             Self::Sweep(shape) => shape.bounding_volume(),
             Self::Transform(shape) => shape.bounding_volume(),
         }
@@ -92,18 +133,131 @@ impl Shape for fj::Shape2d {
     fn compute_brep(
         &self,
         objects: &Objects,
+        cache: &Cache,
+        tolerance: Tolerance,
         debug_info: &mut DebugInfo,
-    ) -> Result<Self::Brep, ValidationError> {
-        match self {
-            Self::Difference(shape) => shape.compute_brep(objects, debug_info),
-            Self::Sketch(shape) => shape.compute_brep(objects, debug_info),
-        }
+    ) -> Result<Self::Brep, Error> {
+        cache.brep(
+            self,
+            objects,
+            tolerance,
+            debug_info,
+            |debug_info| match self {
+                Self::Difference(shape) => {
+                    shape.compute_brep(objects, cache, tolerance, debug_info)
+                }
+                Self::Sketch(shape) => {
+                    shape.compute_brep(objects, cache, tolerance, debug_info)
+                }
+                Self::Text(shape) => {
+                    shape.compute_brep(objects, cache, tolerance, debug_info)
+                }
+            },
+        )
     }
 
-    fn bounding_volume(&self) -> Aabb<3> {
+    fn bounding_volume(&self) -> Result<Aabb<3>, Error> {
         match self {
             Self::Difference(shape) => shape.bounding_volume(),
             Self::Sketch(shape) => shape.bounding_volume(),
+            Self::Text(shape) => shape.bounding_volume(),
+        }
+    }
+}
+
+/// Compute the boundary representations of two independent shapes in parallel
+///
+/// Debug info from both computations ends up in `debug_info`.
+fn compute_brep_in_parallel<S>(
+    [a, b]: [&S; 2],
+    objects: &Objects,
+    cache: &Cache,
+    tolerance: Tolerance,
+    debug_info: &mut DebugInfo,
+) -> Result<[S::Brep; 2], Error>
+where
+    S: Shape + Sync,
+    S::Brep: Send,
+{
+    let (a, b) = join(
+        debug_info,
+        |debug_info| a.compute_brep(objects, cache, tolerance, debug_info),
+        |debug_info| b.compute_brep(objects, cache, tolerance, debug_info),
+    );
+
+    Ok([a?, b?])
+}
+
+/// Run two independent computations in parallel
+///
+/// If a thread is available, the second computation runs in it, while the
+/// first one runs in the current thread. Otherwise, both run in the current
+/// thread, one after the other. Each computation gets its own [`DebugInfo`],
+/// and both end up in `debug_info` afterwards.
+///
+/// This is called recursively for nested shapes, so the number of threads
+/// that can be spawned by it at any one time is limited. See [`ThreadPermit`].
+fn join<A, B>(
+    debug_info: &mut DebugInfo,
+    a: impl FnOnce(&mut DebugInfo) -> A,
+    b: impl FnOnce(&mut DebugInfo) -> B + Send,
+) -> (A, B)
+where
+    B: Send,
+{
+    let mut debug_info_b = DebugInfo::new();
+
+    let (a, b) = match ThreadPermit::acquire() {
+        Some(permit) => thread::scope(|scope| {
+            let b = scope.spawn(|| {
+                let _permit = permit;
+                b(&mut debug_info_b)
+            });
+            let a = a(debug_info);
+
+            // If the computation panicked in the other thread, we want it to
+            // look like it did so in this one.
+            let b = b.join().unwrap_or_else(|err| panic::resume_unwind(err));
+
+            (a, b)
+        }),
+        None => {
+            let a = a(debug_info);
+            let b = b(&mut debug_info_b);
+
+            (a, b)
+        }
+    };
+
+    debug_info.extend(debug_info_b);
+
+    (a, b)
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_interop::debug::DebugInfo;
+    use fj_kernel::threads::ThreadPermit;
+
+    use super::join;
+
+    #[test]
+    fn join_limits_threads() {
+        fn sum(depth: u32, debug_info: &mut DebugInfo) -> u64 {
+            assert!(ThreadPermit::in_use() <= ThreadPermit::max_threads());
+
+            if depth == 0 {
+                return 1;
+            }
+
+            let (a, b) = join(
+                debug_info,
+                |debug_info| sum(depth - 1, debug_info),
+                |debug_info| sum(depth - 1, debug_info),
+            );
+            a + b
         }
+
+        assert_eq!(sum(12, &mut DebugInfo::new()), 4096);
     }
 }
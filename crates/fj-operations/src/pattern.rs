@@ -0,0 +1,286 @@
+use std::{
+    f64::consts::TAU,
+    hash::{Hash, Hasher},
+};
+
+use fj_interop::debug::DebugInfo;
+use fj_kernel::{
+    algorithms::{approx::Tolerance, transform::TransformObject},
+    objects::{FaceSet, Objects},
+};
+use fj_math::{Aabb, Point, Transform, Vector};
+
+use super::{
+    cache::{Cache, HashShape},
+    shape_processor::Error,
+    Shape,
+};
+
+impl Shape for fj::LinearPattern {
+    type Brep = FaceSet;
+
+    fn compute_brep(
+        &self,
+        objects: &Objects,
+        cache: &Cache,
+        tolerance: Tolerance,
+        debug_info: &mut DebugInfo,
+    ) -> Result<Self::Brep, Error> {
+        let num_instances = instances(self.count(), self.skipped()).count();
+        if num_instances > 1 && self.spacing() == [0.; 3] {
+            return Err(Error::CoincidentInstances);
+        }
+
+        let instance = self
+            .shape()
+            .compute_brep(objects, cache, tolerance, debug_info)?;
+        place_instances(instance, linear_transforms(self), objects)
+    }
+
+    fn bounding_volume(&self) -> Result<Aabb<3>, Error> {
+        bounding_volume(self.shape(), linear_transforms(self))
+    }
+}
+
+impl HashShape for fj::LinearPattern {
+    fn hash_shape<H: Hasher>(&self, state: &mut H) {
+        self.shape().hash_shape(state);
+        self.count().hash(state);
+        self.spacing().hash_shape(state);
+        self.skipped().hash(state);
+    }
+}
+
+impl Shape for fj::CircularPattern {
+    type Brep = FaceSet;
+
+    fn compute_brep(
+        &self,
+        objects: &Objects,
+        cache: &Cache,
+        tolerance: Tolerance,
+        debug_info: &mut DebugInfo,
+    ) -> Result<Self::Brep, Error> {
+        if has_coincident_instances(self) {
+            return Err(Error::CoincidentInstances);
+        }
+
+        let instance = self
+            .shape()
+            .compute_brep(objects, cache, tolerance, debug_info)?;
+        place_instances(instance, circular_transforms(self), objects)
+    }
+
+    fn bounding_volume(&self) -> Result<Aabb<3>, Error> {
+        bounding_volume(self.shape(), circular_transforms(self))
+    }
+}
+
+impl HashShape for fj::CircularPattern {
+    fn hash_shape<H: Hasher>(&self, state: &mut H) {
+        self.shape().hash_shape(state);
+        self.count().hash(state);
+        self.axis().hash_shape(state);
+        self.angle().hash_shape(state);
+        self.skipped().hash(state);
+    }
+}
+
+/// The angle below which two instances of a circular pattern are considered to
+/// be at the same position, in radians
+const EPSILON: f64 = 1e-9;
+
+fn linear_transforms(
+    pattern: &fj::LinearPattern,
+) -> impl Iterator<Item = (u32, Transform)> + '_ {
+    let spacing = Vector::from(pattern.spacing());
+
+    instances(pattern.count(), pattern.skipped())
+        .map(move |i| (i, Transform::translation(spacing * f64::from(i))))
+}
+
+fn circular_transforms(
+    pattern: &fj::CircularPattern,
+) -> impl Iterator<Item = (u32, Transform)> + '_ {
+    let axis = Vector::from(pattern.axis()).normalize();
+    let angle = pattern.angle().rad();
+
+    instances(pattern.count(), pattern.skipped())
+        .map(move |i| (i, Transform::rotation(axis * angle * f64::from(i))))
+}
+
+/// Indicate whether multiple instances of the pattern are at the same position
+///
+/// That is the case, if the angle between them is a multiple of a full turn.
+fn has_coincident_instances(pattern: &fj::CircularPattern) -> bool {
+    let mut angles = instances(pattern.count(), pattern.skipped())
+        .map(|i| (pattern.angle().rad() * f64::from(i)).rem_euclid(TAU))
+        .collect::<Vec<_>>();
+    angles.sort_by(f64::total_cmp);
+
+    let (first, last) = match (angles.first(), angles.last()) {
+        (Some(first), Some(last)) if angles.len() > 1 => (first, last),
+        _ => return false,
+    };
+
+    angles.windows(2).any(|pair| pair[1] - pair[0] < EPSILON)
+        || first + TAU - last < EPSILON
+}
+
+fn instances(count: u32, skipped: &[u32]) -> impl Iterator<Item = u32> + '_ {
+    (0..count).filter(|i| !skipped.contains(i))
+}
+
+/// Place a transformed copy of `instance` for each of the transforms
+///
+/// The B-rep of the repeated shape is only computed once. The instance at
+/// index `0` isn't transformed at all, and shares its faces with the original
+/// shape.
+fn place_instances(
+    instance: FaceSet,
+    transforms: impl Iterator<Item = (u32, Transform)>,
+    objects: &Objects,
+) -> Result<FaceSet, Error> {
+    let mut faces = FaceSet::new();
+
+    for (i, transform) in transforms {
+        if i == 0 {
+            faces.extend(instance.clone());
+            continue;
+        }
+
+        faces.extend(instance.clone().transform(&transform, objects)?);
+    }
+
+    Ok(faces)
+}
+
+fn bounding_volume(
+    shape: &fj::Shape,
+    transforms: impl Iterator<Item = (u32, Transform)>,
+) -> Result<Aabb<3>, Error> {
+    let aabb = shape.bounding_volume()?;
+
+    Ok(transforms
+        .map(|(_, transform)| transform.transform_aabb(&aabb))
+        .reduce(|a, b| a.merged(&b))
+        .unwrap_or(Aabb {
+            min: Point::origin(),
+            max: Point::origin(),
+        }))
+}
+
+#[cfg(test)]
+mod tests {
+    use fj::syntax::*;
+    use fj_interop::debug::DebugInfo;
+    use fj_kernel::{algorithms::approx::Tolerance, objects::Objects};
+    use fj_math::Point;
+
+    use crate::{
+        cache::Cache, shape_processor::Error, test_utils::compute_brep,
+        Shape as _,
+    };
+
+    #[test]
+    fn linear_pattern_skips_instances() -> Result<(), Error> {
+        let objects = Objects::new();
+        let cache = Cache::new();
+
+        let shape: fj::Shape = [[0., 0.], [1., 0.], [0., 1.]]
+            .sketch()
+            .sweep([0., 0., 1.])
+            .into();
+        let pattern: fj::Shape =
+            shape.linear_pattern(3, [2., 0., 0.]).skip([1]).into();
+
+        let instance = compute_brep(&shape, &objects, &cache)?;
+        let faces = compute_brep(&pattern, &objects, &cache)?;
+
+        let num_instance_faces = instance.clone().into_iter().count();
+        assert_eq!(faces.clone().into_iter().count(), num_instance_faces * 2);
+
+        // The first instance is shared with the original shape.
+        for face in &instance {
+            assert!(faces.find(face).is_some());
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn circular_pattern_places_instances() -> Result<(), Error> {
+        let objects = Objects::new();
+        let cache = Cache::new();
+
+        let shape: fj::Shape = [[1., 0.], [2., 0.], [1., 1.]]
+            .sketch()
+            .sweep([0., 0., 1.])
+            .into();
+        let pattern: fj::Shape = shape
+            .circular_pattern(4, [0., 0., 1.], fj::Angle::from_rev(0.25))
+            .into();
+
+        let instance = compute_brep(&shape, &objects, &cache)?;
+        let faces = compute_brep(&pattern, &objects, &cache)?;
+
+        let num_instance_faces = instance.into_iter().count();
+        assert_eq!(faces.into_iter().count(), num_instance_faces * 4);
+
+        let aabb = pattern.bounding_volume()?;
+        assert_eq!(aabb.min, Point::from([-2., -2., 0.]));
+        assert_eq!(aabb.max, Point::from([2., 2., 1.]));
+
+        Ok(())
+    }
+
+    #[test]
+    fn pattern_without_instances() -> Result<(), Error> {
+        let objects = Objects::new();
+        let cache = Cache::new();
+
+        let shape: fj::Shape = [[0., 0.], [1., 0.], [0., 1.]]
+            .sketch()
+            .sweep([0., 0., 1.])
+            .into();
+        let pattern: fj::Shape = shape.linear_pattern(0, [2., 0., 0.]).into();
+
+        let faces = compute_brep(&pattern, &objects, &cache)?;
+        assert_eq!(faces.into_iter().count(), 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn pattern_with_coincident_instances() {
+        let objects = Objects::new();
+        let cache = Cache::new();
+
+        let shape: fj::Shape = [[1., 0.], [2., 0.], [1., 1.]]
+            .sketch()
+            .sweep([0., 0., 1.])
+            .into();
+
+        let linear: fj::Shape = shape.linear_pattern(2, [0., 0., 0.]).into();
+        let circular: fj::Shape = shape
+            .circular_pattern(3, [0., 0., 1.], fj::Angle::from_rev(0.5))
+            .into();
+
+        for pattern in [linear, circular] {
+            let result = compute_brep(&pattern, &objects, &cache);
+            assert!(matches!(result, Err(Error::CoincidentInstances)));
+        }
+
+        // Leaving out all but one of the coincident instances is fine.
+        let pattern: fj::Shape =
+            shape.linear_pattern(2, [0., 0., 0.]).skip([1]).into();
+        assert!(pattern
+            .compute_brep(
+                &objects,
+                &cache,
+                Tolerance::from(0.001),
+                &mut DebugInfo::new()
+            )
+            .is_ok());
+    }
+}
@@ -1,51 +1,82 @@
 //! API for processing shapes
 
-use fj_interop::{debug::DebugInfo, processed_shape::ProcessedShape};
+use std::sync::Arc;
+
+use fj_interop::{
+    debug::{DebugInfo, ValidationIssue},
+    mesh::{Mesh, MeshTree},
+    processed_shape::ProcessedShape,
+};
 use fj_kernel::{
     algorithms::{
         approx::{InvalidTolerance, Tolerance},
+        heal::Heal,
+        section::{Section, SectionError},
         triangulate::Triangulate,
     },
-    objects::Objects,
-    validate::ValidationError,
+    objects::{Objects, Sketch, Solid, Surface},
+    storage::Handle,
+    validate::{ValidationConfig, ValidationError, ValidationReport},
 };
-use fj_math::Scalar;
+use fj_math::{Aabb, Point, Scalar};
 
-use crate::Shape as _;
+use crate::{cache::Cache, join, Shape};
 
 /// Processes an [`fj::Shape`] into a [`ProcessedShape`]
+///
+/// Results are cached, and reused when the same processor is used to process
+/// a shape again. If only parts of a model change between evaluations, only
+/// those parts need to be processed again. To make that possible for boundary
+/// representations too, all shapes are computed in the same [`Objects`] store.
 pub struct ShapeProcessor {
     /// The tolerance value used for creating the triangle mesh
     pub tolerance: Option<Tolerance>,
+
+    /// Whether to validate the whole shape after processing it
+    ///
+    /// Any issues found are added to the debug info of the processed shape.
+    /// See [`ShapeProcessor::validation_report`] for details.
+    pub validate: bool,
+
+    objects: Objects,
+    cache: Cache,
 }
 
 impl ShapeProcessor {
+    /// Construct a new instance of `ShapeProcessor`
+    pub fn new(tolerance: Option<Tolerance>) -> Self {
+        Self {
+            tolerance,
+            validate: false,
+            objects: Objects::new(),
+            cache: Cache::new(),
+        }
+    }
+
     /// Process an [`fj::Shape`] into [`ProcessedShape`]
     pub fn process(&self, shape: &fj::Shape) -> Result<ProcessedShape, Error> {
-        let aabb = shape.bounding_volume();
-
-        let tolerance = match self.tolerance {
-            None => {
-                // Compute a reasonable default for the tolerance value. To do
-                // this, we just look at the smallest non-zero extent of the
-                // bounding box and divide that by some value.
-                let mut min_extent = Scalar::MAX;
-                for extent in aabb.size().components {
-                    if extent > Scalar::ZERO && extent < min_extent {
-                        min_extent = extent;
-                    }
-                }
-
-                let tolerance = min_extent / Scalar::from_f64(1000.);
-                Tolerance::from_scalar(tolerance)?
-            }
-            Some(user_defined_tolerance) => user_defined_tolerance,
-        };
+        let aabb = shape.bounding_volume()?;
+        let tolerance = self.tolerance_for(&aabb)?;
 
-        let objects = Objects::new();
         let mut debug_info = DebugInfo::new();
-        let shape = shape.compute_brep(&objects, &mut debug_info)?;
-        let mesh = (&shape, tolerance).triangulate();
+        let mesh = self.mesh(shape, tolerance, &mut debug_info)?;
+        let mesh = unwrap_or_clone(mesh);
+
+        if self.validate {
+            let report =
+                self.validation_report_with_tolerance(shape, tolerance)?;
+            debug_info
+                .validation_issues
+                .extend(report.issues().map(|issue| ValidationIssue {
+                    message: issue.summary(),
+                    points: issue.points.clone(),
+                }));
+        }
+
+        // Only evict results from the cache, if the evaluation was successful.
+        // Otherwise, a single mistake while editing the model would throw away
+        // everything that was cached.
+        self.cache.finish_evaluation();
 
         Ok(ProcessedShape {
             aabb,
@@ -53,6 +84,219 @@ impl ShapeProcessor {
             debug_info,
         })
     }
+
+    /// Process an [`fj::Shape`] into a tree of meshes
+    ///
+    /// Unlike the mesh of a [`ProcessedShape`], this keeps the meshes of group
+    /// members separate. This is useful for exporting to file formats that can
+    /// represent the structure of a model.
+    pub fn mesh_tree(&self, shape: &fj::Shape) -> Result<MeshTree, Error> {
+        let tolerance = self.tolerance(shape)?;
+
+        let mut debug_info = DebugInfo::new();
+        self.tree(shape, tolerance, &mut debug_info)
+    }
+
+    /// The tolerance value used for approximating the shape
+    ///
+    /// This is the user-defined tolerance, if there is one. Otherwise, a
+    /// reasonable value is derived from the size of the shape.
+    pub fn tolerance(&self, shape: &impl Shape) -> Result<Tolerance, Error> {
+        if let Some(user_defined_tolerance) = self.tolerance {
+            return Ok(user_defined_tolerance);
+        }
+
+        self.tolerance_for(&shape.bounding_volume()?)
+    }
+
+    /// The tolerance value for a shape with the given bounding volume
+    ///
+    /// See [`ShapeProcessor::tolerance`].
+    fn tolerance_for(&self, aabb: &Aabb<3>) -> Result<Tolerance, Error> {
+        if let Some(user_defined_tolerance) = self.tolerance {
+            return Ok(user_defined_tolerance);
+        }
+
+        // Compute a reasonable default for the tolerance value. To do this, we
+        // just look at the smallest non-zero extent of the bounding box and
+        // divide that by some value.
+        let mut min_extent = Scalar::MAX;
+        for extent in aabb.size().components {
+            if extent > Scalar::ZERO && extent < min_extent {
+                min_extent = extent;
+            }
+        }
+
+        let tolerance = min_extent / Scalar::from_f64(1000.);
+        Ok(Tolerance::from_scalar(tolerance)?)
+    }
+
+    /// Compute the solid that an [`fj::Shape`] bounds
+    ///
+    /// The faces of the shape are healed into one shell per body. This is
+    /// useful for operations that need a solid instead of loose faces, like
+    /// creating drawings.
+    pub fn solid(&self, shape: &fj::Shape) -> Result<Handle<Solid>, Error> {
+        let tolerance = self.tolerance(shape)?;
+        self.solid_with_tolerance(shape, tolerance)
+    }
+
+    /// Compute the sections of an [`fj::Shape`] with the provided planes
+    ///
+    /// Returns one sketch per plane, in surface coordinates of that plane. See
+    /// [`Section`] for details, and limitations.
+    pub fn sections(
+        &self,
+        shape: &fj::Shape,
+        planes: impl IntoIterator<Item = Surface>,
+    ) -> Result<Vec<Handle<Sketch>>, Error> {
+        let tolerance = self.tolerance(shape)?;
+        let solid = self.solid_with_tolerance(shape, tolerance)?;
+
+        let mut sections = Vec::new();
+        for plane in planes {
+            let plane = self
+                .objects
+                .surfaces
+                .insert(plane)
+                .map_err(ValidationError::from)?;
+            sections.push(solid.section(plane, tolerance, &self.objects)?);
+        }
+
+        Ok(sections)
+    }
+
+    /// Compute the sketch of an [`fj::Shape2d`]
+    ///
+    /// This is useful for exporting to 2D file formats, which need the exact
+    /// geometry instead of a triangle mesh.
+    pub fn sketch(&self, shape: &fj::Shape2d) -> Result<Sketch, Error> {
+        let tolerance = self.tolerance(shape)?;
+
+        let mut debug_info = DebugInfo::new();
+        let sketch = shape.compute_brep(
+            &self.objects,
+            &self.cache,
+            tolerance,
+            &mut debug_info,
+        )?;
+
+        Ok(sketch)
+    }
+
+    /// Validate the boundary representation of an [`fj::Shape`]
+    ///
+    /// Objects are already validated when they are created, but that
+    /// validation stops at the first error. This method collects all issues,
+    /// and reports where in the shape they occurred.
+    pub fn validation_report(
+        &self,
+        shape: &fj::Shape,
+    ) -> Result<ValidationReport, Error> {
+        let tolerance = self.tolerance(shape)?;
+        self.validation_report_with_tolerance(shape, tolerance)
+    }
+}
+
+impl ShapeProcessor {
+    fn validation_report_with_tolerance(
+        &self,
+        shape: &fj::Shape,
+        tolerance: Tolerance,
+    ) -> Result<ValidationReport, Error> {
+        let mut debug_info = DebugInfo::new();
+        let brep = shape.compute_brep(
+            &self.objects,
+            &self.cache,
+            tolerance,
+            &mut debug_info,
+        )?;
+
+        Ok(ValidationReport::for_object(
+            &brep,
+            &ValidationConfig::default(),
+        ))
+    }
+
+    fn solid_with_tolerance(
+        &self,
+        shape: &fj::Shape,
+        tolerance: Tolerance,
+    ) -> Result<Handle<Solid>, Error> {
+        let mut debug_info = DebugInfo::new();
+        let faces = shape.compute_brep(
+            &self.objects,
+            &self.cache,
+            tolerance,
+            &mut debug_info,
+        )?;
+        let (shells, _) =
+            faces.heal(&ValidationConfig::default(), &self.objects)?;
+
+        Ok(Solid::builder(&self.objects).with_shells(shells).build()?)
+    }
+
+    fn tree(
+        &self,
+        shape: &fj::Shape,
+        tolerance: Tolerance,
+        debug_info: &mut DebugInfo,
+    ) -> Result<MeshTree, Error> {
+        if let fj::Shape::Group(group) = shape {
+            let (a, b) = join(
+                debug_info,
+                |debug_info| self.tree(&group.a, tolerance, debug_info),
+                |debug_info| self.tree(&group.b, tolerance, debug_info),
+            );
+
+            return Ok(MeshTree::Group(vec![a?, b?]));
+        }
+
+        let mesh = self.mesh(shape, tolerance, debug_info)?;
+        Ok(MeshTree::Mesh(unwrap_or_clone(mesh)))
+    }
+
+    fn mesh(
+        &self,
+        shape: &fj::Shape,
+        tolerance: Tolerance,
+        debug_info: &mut DebugInfo,
+    ) -> Result<Arc<Mesh<Point<3>>>, Error> {
+        // The members of a group don't affect each other, so we can mesh them
+        // separately. That way, changing one of them doesn't require
+        // triangulating the other again.
+        if let fj::Shape::Group(group) = shape {
+            let (a, b) = join(
+                debug_info,
+                |debug_info| self.mesh(&group.a, tolerance, debug_info),
+                |debug_info| self.mesh(&group.b, tolerance, debug_info),
+            );
+
+            let mut mesh = Mesh::new();
+            for triangle in a?.triangles().chain(b?.triangles()) {
+                mesh.push_triangle(triangle.inner, triangle.color);
+            }
+
+            return Ok(Arc::new(mesh));
+        }
+
+        self.cache.mesh(shape, tolerance, debug_info, |debug_info| {
+            let brep = shape.compute_brep(
+                &self.objects,
+                &self.cache,
+                tolerance,
+                debug_info,
+            )?;
+            Ok((&brep, tolerance).triangulate())
+        })
+    }
+}
+
+/// Take the mesh out of the `Arc`, or clone it, if it's still shared
+///
+/// Meshes are still shared, if they are cached.
+fn unwrap_or_clone(mesh: Arc<Mesh<Point<3>>>) -> Mesh<Point<3>> {
+    Arc::try_unwrap(mesh).unwrap_or_else(|mesh| Mesh::clone(&mesh))
 }
 
 /// A shape processing error
@@ -66,4 +310,87 @@ pub enum Error {
     /// Model has zero size
     #[error("Model has zero size")]
     Extent(#[from] InvalidTolerance),
+
+    /// Transform scales by zero, or by a factor that isn't finite
+    #[error("Can't scale by zero or infinity (scale factors: {0:?})")]
+    InvalidScale([f64; 3]),
+
+    /// Transform mirrors across a plane without a valid normal
+    #[error("Mirror plane needs a finite, non-zero normal (normal: {0:?})")]
+    InvalidMirrorNormal([f64; 3]),
+
+    /// Transform rotates around an axis without a valid direction
+    #[error("Rotation needs a finite, non-zero axis (axis: {0:?})")]
+    InvalidRotationAxis([f64; 3]),
+
+    /// Transform scales a shape that contains circles non-uniformly
+    #[error(
+        "Can't scale shapes that contain circles non-uniformly (scale \
+        factors: {0:?})"
+    )]
+    NonUniformScale([f64; 3]),
+
+    /// Text uses a font that can't be parsed
+    #[error("Failed to parse font")]
+    Font(#[from] ttf_parser::FaceParsingError),
+
+    /// Shape being subtracted is not contained in the other shape
+    #[error(
+        "Shape being subtracted is not contained in the shape it's subtracted \
+        from"
+    )]
+    DifferenceNotContained,
+
+    /// Pattern places multiple instances at the same position
+    #[error("Pattern places multiple instances at the same position")]
+    CoincidentInstances,
+
+    /// Section of the shape can't be computed
+    #[error("Error computing section")]
+    Section(#[from] SectionError),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Error, ShapeProcessor};
+
+    #[test]
+    fn solid_of_disjoint_bodies() -> Result<(), Error> {
+        let cube = |x: f64| -> fj::Shape {
+            let square = fj::Sketch::from_points(vec![
+                [x, 0.],
+                [x + 1., 0.],
+                [x + 1., 1.],
+                [x, 1.],
+            ]);
+            fj::Sweep::from_path(square.into(), [0., 0., 1.]).into()
+        };
+        let shape = fj::Group {
+            a: cube(0.),
+            b: cube(2.),
+        };
+
+        let processor = ShapeProcessor::new(None);
+        let solid = processor.solid(&shape.into())?;
+
+        assert_eq!(solid.shells().count(), 2);
+
+        Ok(())
+    }
+
+    #[test]
+    fn reuse_breps_across_evaluations() -> Result<(), Error> {
+        let shape: fj::Shape2d =
+            fj::Sketch::from_points(vec![[0., 0.], [1., 0.], [0., 1.]]).into();
+
+        let processor = ShapeProcessor::new(None);
+        let face_ids = || -> Result<Vec<_>, Error> {
+            let sketch = processor.sketch(&shape)?;
+            Ok(sketch.faces().into_iter().map(|face| face.id()).collect())
+        };
+
+        assert_eq!(face_ids()?, face_ids()?);
+
+        Ok(())
+    }
 }
@@ -1,7 +1,11 @@
-use std::ops::Deref;
+use std::{
+    hash::{Hash, Hasher},
+    ops::Deref,
+};
 
 use fj_interop::{debug::DebugInfo, mesh::Color};
 use fj_kernel::{
+    algorithms::approx::Tolerance,
     builder::{FaceBuilder, HalfEdgeBuilder},
     insert::Insert,
     objects::{Cycle, Face, HalfEdge, Objects, Sketch},
@@ -10,7 +14,11 @@ use fj_kernel::{
 };
 use fj_math::{Aabb, Point};
 
-use super::Shape;
+use super::{
+    cache::{Cache, HashShape},
+    shape_processor::Error,
+    Shape,
+};
 
 impl Shape for fj::Sketch {
     type Brep = Sketch;
@@ -18,8 +26,10 @@ impl Shape for fj::Sketch {
     fn compute_brep(
         &self,
         objects: &Objects,
+        _: &Cache,
+        _: Tolerance,
         _: &mut DebugInfo,
-    ) -> Result<Self::Brep, ValidationError> {
+    ) -> Result<Self::Brep, Error> {
         let surface = objects.surfaces.xy_plane();
 
         let face = match self.chain() {
@@ -29,16 +39,25 @@ impl Shape for fj::Sketch {
 
                 let half_edge = HalfEdge::partial()
                     .with_surface(surface)
-                    .update_as_circle_from_radius(circle.radius(), objects)?
+                    .update_as_circle_from_center_and_radius(
+                        circle.center(),
+                        circle.radius(),
+                        objects,
+                    )?
                     .build(objects)?
-                    .insert(objects)?;
-                let cycle = objects.cycles.insert(Cycle::new([half_edge]))?;
+                    .insert(objects)
+                    .map_err(ValidationError::from)?;
+                let cycle = objects
+                    .cycles
+                    .insert(Cycle::new([half_edge]))
+                    .map_err(ValidationError::from)?;
 
                 Face::partial()
                     .with_exterior(cycle)
                     .with_color(Color(self.color()))
                     .build(objects)?
-                    .insert(objects)?
+                    .insert(objects)
+                    .map_err(ValidationError::from)?
             }
             fj::Chain::PolyChain(poly_chain) => {
                 let points =
@@ -49,7 +68,8 @@ impl Shape for fj::Sketch {
                     .with_exterior_polygon_from_points(points)
                     .with_color(Color(self.color()))
                     .build(objects)?
-                    .insert(objects)?
+                    .insert(objects)
+                    .map_err(ValidationError::from)?
             }
         };
 
@@ -57,12 +77,17 @@ impl Shape for fj::Sketch {
         Ok(sketch.deref().clone())
     }
 
-    fn bounding_volume(&self) -> Aabb<3> {
-        match self.chain() {
-            fj::Chain::Circle(circle) => Aabb {
-                min: Point::from([-circle.radius(), -circle.radius(), 0.0]),
-                max: Point::from([circle.radius(), circle.radius(), 0.0]),
-            },
+    fn bounding_volume(&self) -> Result<Aabb<3>, Error> {
+        let aabb = match self.chain() {
+            fj::Chain::Circle(circle) => {
+                let [x, y] = circle.center();
+                let r = circle.radius();
+
+                Aabb {
+                    min: Point::from([x - r, y - r, 0.0]),
+                    max: Point::from([x + r, y + r, 0.0]),
+                }
+            }
             fj::Chain::PolyChain(poly_chain) => Aabb::<3>::from_points(
                 poly_chain
                     .to_points()
@@ -70,6 +95,26 @@ impl Shape for fj::Sketch {
                     .map(Point::from)
                     .map(Point::to_xyz),
             ),
+        };
+
+        Ok(aabb)
+    }
+}
+
+impl HashShape for fj::Sketch {
+    fn hash_shape<H: Hasher>(&self, state: &mut H) {
+        match self.chain() {
+            fj::Chain::Circle(circle) => {
+                0u8.hash(state);
+                circle.radius().hash_shape(state);
+                circle.center().hash_shape(state);
+            }
+            fj::Chain::PolyChain(poly_chain) => {
+                1u8.hash(state);
+                poly_chain.to_points().hash_shape(state);
+            }
         }
+
+        self.color().hash(state);
     }
 }
@@ -1,14 +1,18 @@
-use std::ops::Deref;
+use std::{hash::Hasher, ops::Deref};
 
 use fj_interop::debug::DebugInfo;
 use fj_kernel::{
-    algorithms::sweep::Sweep,
+    algorithms::{approx::Tolerance, sweep::Sweep},
     objects::{Objects, Solid},
     validate::ValidationError,
 };
 use fj_math::{Aabb, Vector};
 
-use super::Shape;
+use super::{
+    cache::{Cache, HashShape},
+    shape_processor::Error,
+    Shape,
+};
 
 impl Shape for fj::Sweep {
     type Brep = Solid;
@@ -16,10 +20,17 @@ impl Shape for fj::Sweep {
     fn compute_brep(
         &self,
         objects: &Objects,
+        cache: &Cache,
+        tolerance: Tolerance,
         debug_info: &mut DebugInfo,
-    ) -> Result<Self::Brep, ValidationError> {
-        let sketch = self.shape().compute_brep(objects, debug_info)?;
-        let sketch = objects.sketches.insert(sketch)?;
+    ) -> Result<Self::Brep, Error> {
+        let sketch = self
+            .shape()
+            .compute_brep(objects, cache, tolerance, debug_info)?;
+        let sketch = objects
+            .sketches
+            .insert(sketch)
+            .map_err(ValidationError::from)?;
 
         let path = Vector::from(self.path());
 
@@ -27,14 +38,17 @@ impl Shape for fj::Sweep {
         Ok(solid.deref().clone())
     }
 
-    fn bounding_volume(&self) -> Aabb<3> {
-        self.shape()
-            .bounding_volume()
-            .merged(&Aabb::<3>::from_points(
-                self.shape()
-                    .bounding_volume()
-                    .vertices()
-                    .map(|v| v + self.path()),
-            ))
+    fn bounding_volume(&self) -> Result<Aabb<3>, Error> {
+        let aabb = self.shape().bounding_volume()?;
+        Ok(aabb.merged(&Aabb::<3>::from_points(
+            aabb.vertices().map(|v| v + self.path()),
+        )))
+    }
+}
+
+impl HashShape for fj::Sweep {
+    fn hash_shape<H: Hasher>(&self, state: &mut H) {
+        self.shape().hash_shape(state);
+        self.path().hash_shape(state);
     }
 }
@@ -0,0 +1,20 @@
+//! Utilities that are shared between tests
+
+use fj_interop::debug::DebugInfo;
+use fj_kernel::{algorithms::approx::Tolerance, objects::Objects};
+
+use crate::{cache::Cache, shape_processor::Error, Shape};
+
+/// Compute the boundary representation of a shape, with a fine tolerance
+pub fn compute_brep<S: Shape>(
+    shape: &S,
+    objects: &Objects,
+    cache: &Cache,
+) -> Result<S::Brep, Error> {
+    shape.compute_brep(
+        objects,
+        cache,
+        Tolerance::from(0.001),
+        &mut DebugInfo::new(),
+    )
+}
@@ -0,0 +1,1216 @@
+//! The font that is used, if no other font is specified
+//!
+//! A block font, based on a 5x7 pixel grid, with two additional rows below the
+//! baseline for descenders. It only covers printable ASCII characters.
+
+use std::collections::BTreeSet;
+
+use super::{
+    outline::{Contour, Glyph},
+    Font,
+};
+
+/// The size of a pixel, in units of the em square
+const PIXEL_SIZE: f64 = 0.1;
+
+/// The horizontal distance between two glyphs, in pixels
+const ADVANCE: usize = 6;
+
+/// The number of pixel rows below the baseline
+const DESCENT: usize = 2;
+
+const WIDTH: usize = 5;
+const HEIGHT: usize = 9;
+
+pub struct DefaultFont;
+
+impl Font for DefaultFont {
+    fn glyph(&self, c: char, _: Option<f64>) -> Option<Glyph> {
+        let advance = ADVANCE as f64 * PIXEL_SIZE;
+
+        // Unsupported characters are left out, but still take up space, to
+        // make it obvious that something is missing.
+        let contours = GLYPHS
+            .iter()
+            .find(|&&(glyph, _)| glyph == c)
+            .map(|(_, rows)| trace(rows))
+            .unwrap_or_default();
+
+        Some(Glyph { contours, advance })
+    }
+
+    fn line_height(&self) -> f64 {
+        (HEIGHT + 3) as f64 * PIXEL_SIZE
+    }
+}
+
+/// Trace the outline of a glyph bitmap
+///
+/// Pixels that only touch diagonally would result in contours that touch
+/// themselves or each other, which faces don't support. To prevent that, the
+/// bitmap is traced at twice its resolution, and such diagonal gaps are
+/// filled with a sub-pixel.
+fn trace(rows: &[u8; HEIGHT]) -> Vec<Contour> {
+    const SCALE: usize = 2;
+
+    let width = WIDTH * SCALE;
+    let height = HEIGHT * SCALE;
+
+    // `pixels[y][x]`, with `y` pointing up.
+    let mut pixels = vec![vec![false; width]; height];
+    for (y, row) in pixels.iter_mut().enumerate() {
+        let bits = rows[HEIGHT - 1 - y / SCALE];
+
+        for (x, pixel) in row.iter_mut().enumerate() {
+            *pixel = bits & (1 << (WIDTH - 1 - x / SCALE)) != 0;
+        }
+    }
+
+    fill_diagonal_gaps(&mut pixels);
+
+    // Every pixel contributes its boundary, wound counter-clockwise. Where two
+    // pixels share an edge, the two opposing boundary edges cancel out.
+    let mut edges = BTreeSet::new();
+    for (y, row) in pixels.iter().enumerate() {
+        for (x, &pixel) in row.iter().enumerate() {
+            if !pixel {
+                continue;
+            }
+
+            let corners = [[x, y], [x + 1, y], [x + 1, y + 1], [x, y + 1]];
+            for (i, &a) in corners.iter().enumerate() {
+                let b = corners[(i + 1) % corners.len()];
+
+                if !edges.remove(&(b, a)) {
+                    edges.insert((a, b));
+                }
+            }
+        }
+    }
+
+    let pixel_size = PIXEL_SIZE / SCALE as f64;
+    let descent = (DESCENT * SCALE) as f64;
+
+    let mut contours = Vec::new();
+    while let Some(&(start, _)) = edges.iter().next() {
+        let mut corners = Vec::new();
+
+        let mut current = start;
+        loop {
+            let &(a, b) = edges
+                .range((current, [0, 0])..)
+                .next()
+                .filter(|&&(a, _)| a == current)
+                .expect("Boundary edges of pixels always form closed loops");
+            edges.remove(&(a, b));
+
+            corners.push(a);
+            current = b;
+
+            if current == start {
+                break;
+            }
+        }
+
+        let contour = remove_collinear(&corners)
+            .into_iter()
+            .map(|[x, y]| {
+                [x as f64 * pixel_size, (y as f64 - descent) * pixel_size]
+            })
+            .collect();
+        contours.push(contour);
+    }
+
+    contours
+}
+
+fn fill_diagonal_gaps(pixels: &mut [Vec<bool>]) {
+    let mut changed = true;
+
+    while changed {
+        changed = false;
+
+        for y in 0..pixels.len() - 1 {
+            for x in 0..pixels[y].len() - 1 {
+                let [a, b] = [pixels[y][x], pixels[y][x + 1]];
+                let [c, d] = [pixels[y + 1][x], pixels[y + 1][x + 1]];
+
+                if a && d && !b && !c {
+                    pixels[y][x + 1] = true;
+                    changed = true;
+                }
+                if b && c && !a && !d {
+                    pixels[y][x] = true;
+                    changed = true;
+                }
+            }
+        }
+    }
+}
+
+fn remove_collinear(corners: &[[usize; 2]]) -> Vec<[usize; 2]> {
+    let n = corners.len();
+
+    (0..n)
+        .filter(|&i| {
+            let [px, py] = corners[(i + n - 1) % n];
+            let [x, y] = corners[i];
+            let [nx, ny] = corners[(i + 1) % n];
+
+            // All edges are axis-aligned, so a corner is only needed where the
+            // direction changes.
+            !(px == x && x == nx || py == y && y == ny)
+        })
+        .map(|i| corners[i])
+        .collect()
+}
+
+/// The glyph bitmaps, one row per element, from top to bottom
+#[rustfmt::skip]
+const GLYPHS: [(char, [u8; HEIGHT]); 95] = [
+    (' ', [
+        0b00000,
+        0b00000,
+        0b00000,
+        0b00000,
+        0b00000,
+        0b00000,
+        0b00000,
+        0b00000,
+        0b00000,
+    ]),
+    ('!', [
+        0b00100,
+        0b00100,
+        0b00100,
+        0b00100,
+        0b00100,
+        0b00000,
+        0b00100,
+        0b00000,
+        0b00000,
+    ]),
+    ('"', [
+        0b01010,
+        0b01010,
+        0b01010,
+        0b00000,
+        0b00000,
+        0b00000,
+        0b00000,
+        0b00000,
+        0b00000,
+    ]),
+    ('#', [
+        0b01010,
+        0b01010,
+        0b11111,
+        0b01010,
+        0b11111,
+        0b01010,
+        0b01010,
+        0b00000,
+        0b00000,
+    ]),
+    ('$', [
+        0b00100,
+        0b01111,
+        0b10100,
+        0b01110,
+        0b00101,
+        0b11110,
+        0b00100,
+        0b00000,
+        0b00000,
+    ]),
+    ('%', [
+        0b11000,
+        0b11001,
+        0b00010,
+        0b00100,
+        0b01000,
+        0b10011,
+        0b00011,
+        0b00000,
+        0b00000,
+    ]),
+    ('&', [
+        0b01100,
+        0b10010,
+        0b10100,
+        0b01000,
+        0b10101,
+        0b10010,
+        0b01101,
+        0b00000,
+        0b00000,
+    ]),
+    ('\'', [
+        0b01100,
+        0b00100,
+        0b01000,
+        0b00000,
+        0b00000,
+        0b00000,
+        0b00000,
+        0b00000,
+        0b00000,
+    ]),
+    ('(', [
+        0b00010,
+        0b00100,
+        0b01000,
+        0b01000,
+        0b01000,
+        0b00100,
+        0b00010,
+        0b00000,
+        0b00000,
+    ]),
+    (')', [
+        0b01000,
+        0b00100,
+        0b00010,
+        0b00010,
+        0b00010,
+        0b00100,
+        0b01000,
+        0b00000,
+        0b00000,
+    ]),
+    ('*', [
+        0b00000,
+        0b00100,
+        0b10101,
+        0b01110,
+        0b10101,
+        0b00100,
+        0b00000,
+        0b00000,
+        0b00000,
+    ]),
+    ('+', [
+        0b00000,
+        0b00100,
+        0b00100,
+        0b11111,
+        0b00100,
+        0b00100,
+        0b00000,
+        0b00000,
+        0b00000,
+    ]),
+    (',', [
+        0b00000,
+        0b00000,
+        0b00000,
+        0b00000,
+        0b01100,
+        0b00100,
+        0b01000,
+        0b00000,
+        0b00000,
+    ]),
+    ('-', [
+        0b00000,
+        0b00000,
+        0b00000,
+        0b11111,
+        0b00000,
+        0b00000,
+        0b00000,
+        0b00000,
+        0b00000,
+    ]),
+    ('.', [
+        0b00000,
+        0b00000,
+        0b00000,
+        0b00000,
+        0b00000,
+        0b01100,
+        0b01100,
+        0b00000,
+        0b00000,
+    ]),
+    ('/', [
+        0b00000,
+        0b00001,
+        0b00010,
+        0b00100,
+        0b01000,
+        0b10000,
+        0b00000,
+        0b00000,
+        0b00000,
+    ]),
+    ('0', [
+        0b01110,
+        0b10001,
+        0b10011,
+        0b10101,
+        0b11001,
+        0b10001,
+        0b01110,
+        0b00000,
+        0b00000,
+    ]),
+    ('1', [
+        0b00100,
+        0b01100,
+        0b00100,
+        0b00100,
+        0b00100,
+        0b00100,
+        0b01110,
+        0b00000,
+        0b00000,
+    ]),
+    ('2', [
+        0b01110,
+        0b10001,
+        0b00001,
+        0b00010,
+        0b00100,
+        0b01000,
+        0b11111,
+        0b00000,
+        0b00000,
+    ]),
+    ('3', [
+        0b11111,
+        0b00010,
+        0b00100,
+        0b00010,
+        0b00001,
+        0b10001,
+        0b01110,
+        0b00000,
+        0b00000,
+    ]),
+    ('4', [
+        0b00010,
+        0b00110,
+        0b01010,
+        0b10010,
+        0b11111,
+        0b00010,
+        0b00010,
+        0b00000,
+        0b00000,
+    ]),
+    ('5', [
+        0b11111,
+        0b10000,
+        0b11110,
+        0b00001,
+        0b00001,
+        0b10001,
+        0b01110,
+        0b00000,
+        0b00000,
+    ]),
+    ('6', [
+        0b00110,
+        0b01000,
+        0b10000,
+        0b11110,
+        0b10001,
+        0b10001,
+        0b01110,
+        0b00000,
+        0b00000,
+    ]),
+    ('7', [
+        0b11111,
+        0b00001,
+        0b00010,
+        0b00100,
+        0b01000,
+        0b01000,
+        0b01000,
+        0b00000,
+        0b00000,
+    ]),
+    ('8', [
+        0b01110,
+        0b10001,
+        0b10001,
+        0b01110,
+        0b10001,
+        0b10001,
+        0b01110,
+        0b00000,
+        0b00000,
+    ]),
+    ('9', [
+        0b01110,
+        0b10001,
+        0b10001,
+        0b01111,
+        0b00001,
+        0b00010,
+        0b01100,
+        0b00000,
+        0b00000,
+    ]),
+    (':', [
+        0b00000,
+        0b01100,
+        0b01100,
+        0b00000,
+        0b01100,
+        0b01100,
+        0b00000,
+        0b00000,
+        0b00000,
+    ]),
+    (';', [
+        0b00000,
+        0b01100,
+        0b01100,
+        0b00000,
+        0b01100,
+        0b00100,
+        0b01000,
+        0b00000,
+        0b00000,
+    ]),
+    ('<', [
+        0b00010,
+        0b00100,
+        0b01000,
+        0b10000,
+        0b01000,
+        0b00100,
+        0b00010,
+        0b00000,
+        0b00000,
+    ]),
+    ('=', [
+        0b00000,
+        0b00000,
+        0b11111,
+        0b00000,
+        0b11111,
+        0b00000,
+        0b00000,
+        0b00000,
+        0b00000,
+    ]),
+    ('>', [
+        0b01000,
+        0b00100,
+        0b00010,
+        0b00001,
+        0b00010,
+        0b00100,
+        0b01000,
+        0b00000,
+        0b00000,
+    ]),
+    ('?', [
+        0b01110,
+        0b10001,
+        0b00001,
+        0b00010,
+        0b00100,
+        0b00000,
+        0b00100,
+        0b00000,
+        0b00000,
+    ]),
+    ('@', [
+        0b01110,
+        0b10001,
+        0b00001,
+        0b01101,
+        0b10101,
+        0b10101,
+        0b01110,
+        0b00000,
+        0b00000,
+    ]),
+    ('A', [
+        0b01110,
+        0b10001,
+        0b10001,
+        0b10001,
+        0b11111,
+        0b10001,
+        0b10001,
+        0b00000,
+        0b00000,
+    ]),
+    ('B', [
+        0b11110,
+        0b10001,
+        0b10001,
+        0b11110,
+        0b10001,
+        0b10001,
+        0b11110,
+        0b00000,
+        0b00000,
+    ]),
+    ('C', [
+        0b01110,
+        0b10001,
+        0b10000,
+        0b10000,
+        0b10000,
+        0b10001,
+        0b01110,
+        0b00000,
+        0b00000,
+    ]),
+    ('D', [
+        0b11100,
+        0b10010,
+        0b10001,
+        0b10001,
+        0b10001,
+        0b10010,
+        0b11100,
+        0b00000,
+        0b00000,
+    ]),
+    ('E', [
+        0b11111,
+        0b10000,
+        0b10000,
+        0b11110,
+        0b10000,
+        0b10000,
+        0b11111,
+        0b00000,
+        0b00000,
+    ]),
+    ('F', [
+        0b11111,
+        0b10000,
+        0b10000,
+        0b11110,
+        0b10000,
+        0b10000,
+        0b10000,
+        0b00000,
+        0b00000,
+    ]),
+    ('G', [
+        0b01110,
+        0b10001,
+        0b10000,
+        0b10111,
+        0b10001,
+        0b10001,
+        0b01111,
+        0b00000,
+        0b00000,
+    ]),
+    ('H', [
+        0b10001,
+        0b10001,
+        0b10001,
+        0b11111,
+        0b10001,
+        0b10001,
+        0b10001,
+        0b00000,
+        0b00000,
+    ]),
+    ('I', [
+        0b01110,
+        0b00100,
+        0b00100,
+        0b00100,
+        0b00100,
+        0b00100,
+        0b01110,
+        0b00000,
+        0b00000,
+    ]),
+    ('J', [
+        0b00111,
+        0b00010,
+        0b00010,
+        0b00010,
+        0b00010,
+        0b10010,
+        0b01100,
+        0b00000,
+        0b00000,
+    ]),
+    ('K', [
+        0b10001,
+        0b10010,
+        0b10100,
+        0b11000,
+        0b10100,
+        0b10010,
+        0b10001,
+        0b00000,
+        0b00000,
+    ]),
+    ('L', [
+        0b10000,
+        0b10000,
+        0b10000,
+        0b10000,
+        0b10000,
+        0b10000,
+        0b11111,
+        0b00000,
+        0b00000,
+    ]),
+    ('M', [
+        0b10001,
+        0b11011,
+        0b10101,
+        0b10101,
+        0b10001,
+        0b10001,
+        0b10001,
+        0b00000,
+        0b00000,
+    ]),
+    ('N', [
+        0b10001,
+        0b10001,
+        0b11001,
+        0b10101,
+        0b10011,
+        0b10001,
+        0b10001,
+        0b00000,
+        0b00000,
+    ]),
+    ('O', [
+        0b01110,
+        0b10001,
+        0b10001,
+        0b10001,
+        0b10001,
+        0b10001,
+        0b01110,
+        0b00000,
+        0b00000,
+    ]),
+    ('P', [
+        0b11110,
+        0b10001,
+        0b10001,
+        0b11110,
+        0b10000,
+        0b10000,
+        0b10000,
+        0b00000,
+        0b00000,
+    ]),
+    ('Q', [
+        0b01110,
+        0b10001,
+        0b10001,
+        0b10001,
+        0b10101,
+        0b10010,
+        0b01101,
+        0b00000,
+        0b00000,
+    ]),
+    ('R', [
+        0b11110,
+        0b10001,
+        0b10001,
+        0b11110,
+        0b10100,
+        0b10010,
+        0b10001,
+        0b00000,
+        0b00000,
+    ]),
+    ('S', [
+        0b01111,
+        0b10000,
+        0b10000,
+        0b01110,
+        0b00001,
+        0b00001,
+        0b11110,
+        0b00000,
+        0b00000,
+    ]),
+    ('T', [
+        0b11111,
+        0b00100,
+        0b00100,
+        0b00100,
+        0b00100,
+        0b00100,
+        0b00100,
+        0b00000,
+        0b00000,
+    ]),
+    ('U', [
+        0b10001,
+        0b10001,
+        0b10001,
+        0b10001,
+        0b10001,
+        0b10001,
+        0b01110,
+        0b00000,
+        0b00000,
+    ]),
+    ('V', [
+        0b10001,
+        0b10001,
+        0b10001,
+        0b10001,
+        0b10001,
+        0b01010,
+        0b00100,
+        0b00000,
+        0b00000,
+    ]),
+    ('W', [
+        0b10001,
+        0b10001,
+        0b10001,
+        0b10101,
+        0b10101,
+        0b10101,
+        0b01010,
+        0b00000,
+        0b00000,
+    ]),
+    ('X', [
+        0b10001,
+        0b10001,
+        0b01010,
+        0b00100,
+        0b01010,
+        0b10001,
+        0b10001,
+        0b00000,
+        0b00000,
+    ]),
+    ('Y', [
+        0b10001,
+        0b10001,
+        0b10001,
+        0b01010,
+        0b00100,
+        0b00100,
+        0b00100,
+        0b00000,
+        0b00000,
+    ]),
+    ('Z', [
+        0b11111,
+        0b00001,
+        0b00010,
+        0b00100,
+        0b01000,
+        0b10000,
+        0b11111,
+        0b00000,
+        0b00000,
+    ]),
+    ('[', [
+        0b01110,
+        0b01000,
+        0b01000,
+        0b01000,
+        0b01000,
+        0b01000,
+        0b01110,
+        0b00000,
+        0b00000,
+    ]),
+    ('\\', [
+        0b00000,
+        0b10000,
+        0b01000,
+        0b00100,
+        0b00010,
+        0b00001,
+        0b00000,
+        0b00000,
+        0b00000,
+    ]),
+    (']', [
+        0b01110,
+        0b00010,
+        0b00010,
+        0b00010,
+        0b00010,
+        0b00010,
+        0b01110,
+        0b00000,
+        0b00000,
+    ]),
+    ('^', [
+        0b00100,
+        0b01010,
+        0b10001,
+        0b00000,
+        0b00000,
+        0b00000,
+        0b00000,
+        0b00000,
+        0b00000,
+    ]),
+    ('_', [
+        0b00000,
+        0b00000,
+        0b00000,
+        0b00000,
+        0b00000,
+        0b00000,
+        0b11111,
+        0b00000,
+        0b00000,
+    ]),
+    ('`', [
+        0b01000,
+        0b00100,
+        0b00010,
+        0b00000,
+        0b00000,
+        0b00000,
+        0b00000,
+        0b00000,
+        0b00000,
+    ]),
+    ('a', [
+        0b00000,
+        0b00000,
+        0b01110,
+        0b00001,
+        0b01111,
+        0b10001,
+        0b01111,
+        0b00000,
+        0b00000,
+    ]),
+    ('b', [
+        0b10000,
+        0b10000,
+        0b10110,
+        0b11001,
+        0b10001,
+        0b10001,
+        0b11110,
+        0b00000,
+        0b00000,
+    ]),
+    ('c', [
+        0b00000,
+        0b00000,
+        0b01110,
+        0b10000,
+        0b10000,
+        0b10001,
+        0b01110,
+        0b00000,
+        0b00000,
+    ]),
+    ('d', [
+        0b00001,
+        0b00001,
+        0b01101,
+        0b10011,
+        0b10001,
+        0b10001,
+        0b01111,
+        0b00000,
+        0b00000,
+    ]),
+    ('e', [
+        0b00000,
+        0b00000,
+        0b01110,
+        0b10001,
+        0b11111,
+        0b10000,
+        0b01110,
+        0b00000,
+        0b00000,
+    ]),
+    ('f', [
+        0b00110,
+        0b01001,
+        0b01000,
+        0b11100,
+        0b01000,
+        0b01000,
+        0b01000,
+        0b00000,
+        0b00000,
+    ]),
+    ('g', [
+        0b00000,
+        0b00000,
+        0b01111,
+        0b10001,
+        0b10001,
+        0b10001,
+        0b01111,
+        0b00001,
+        0b01110,
+    ]),
+    ('h', [
+        0b10000,
+        0b10000,
+        0b10110,
+        0b11001,
+        0b10001,
+        0b10001,
+        0b10001,
+        0b00000,
+        0b00000,
+    ]),
+    ('i', [
+        0b00100,
+        0b00000,
+        0b01100,
+        0b00100,
+        0b00100,
+        0b00100,
+        0b01110,
+        0b00000,
+        0b00000,
+    ]),
+    ('j', [
+        0b00010,
+        0b00000,
+        0b00110,
+        0b00010,
+        0b00010,
+        0b00010,
+        0b00010,
+        0b10010,
+        0b01100,
+    ]),
+    ('k', [
+        0b10000,
+        0b10000,
+        0b10010,
+        0b10100,
+        0b11000,
+        0b10100,
+        0b10010,
+        0b00000,
+        0b00000,
+    ]),
+    ('l', [
+        0b01100,
+        0b00100,
+        0b00100,
+        0b00100,
+        0b00100,
+        0b00100,
+        0b01110,
+        0b00000,
+        0b00000,
+    ]),
+    ('m', [
+        0b00000,
+        0b00000,
+        0b11010,
+        0b10101,
+        0b10101,
+        0b10101,
+        0b10101,
+        0b00000,
+        0b00000,
+    ]),
+    ('n', [
+        0b00000,
+        0b00000,
+        0b10110,
+        0b11001,
+        0b10001,
+        0b10001,
+        0b10001,
+        0b00000,
+        0b00000,
+    ]),
+    ('o', [
+        0b00000,
+        0b00000,
+        0b01110,
+        0b10001,
+        0b10001,
+        0b10001,
+        0b01110,
+        0b00000,
+        0b00000,
+    ]),
+    ('p', [
+        0b00000,
+        0b00000,
+        0b11110,
+        0b10001,
+        0b10001,
+        0b10001,
+        0b11110,
+        0b10000,
+        0b10000,
+    ]),
+    ('q', [
+        0b00000,
+        0b00000,
+        0b01111,
+        0b10001,
+        0b10001,
+        0b10001,
+        0b01111,
+        0b00001,
+        0b00001,
+    ]),
+    ('r', [
+        0b00000,
+        0b00000,
+        0b10110,
+        0b11001,
+        0b10000,
+        0b10000,
+        0b10000,
+        0b00000,
+        0b00000,
+    ]),
+    ('s', [
+        0b00000,
+        0b00000,
+        0b01111,
+        0b10000,
+        0b01110,
+        0b00001,
+        0b11110,
+        0b00000,
+        0b00000,
+    ]),
+    ('t', [
+        0b01000,
+        0b01000,
+        0b11100,
+        0b01000,
+        0b01000,
+        0b01001,
+        0b00110,
+        0b00000,
+        0b00000,
+    ]),
+    ('u', [
+        0b00000,
+        0b00000,
+        0b10001,
+        0b10001,
+        0b10001,
+        0b10011,
+        0b01101,
+        0b00000,
+        0b00000,
+    ]),
+    ('v', [
+        0b00000,
+        0b00000,
+        0b10001,
+        0b10001,
+        0b10001,
+        0b01010,
+        0b00100,
+        0b00000,
+        0b00000,
+    ]),
+    ('w', [
+        0b00000,
+        0b00000,
+        0b10001,
+        0b10001,
+        0b10101,
+        0b10101,
+        0b01010,
+        0b00000,
+        0b00000,
+    ]),
+    ('x', [
+        0b00000,
+        0b00000,
+        0b10001,
+        0b01010,
+        0b00100,
+        0b01010,
+        0b10001,
+        0b00000,
+        0b00000,
+    ]),
+    ('y', [
+        0b00000,
+        0b00000,
+        0b10001,
+        0b10001,
+        0b10001,
+        0b10001,
+        0b01111,
+        0b00001,
+        0b01110,
+    ]),
+    ('z', [
+        0b00000,
+        0b00000,
+        0b11111,
+        0b00010,
+        0b00100,
+        0b01000,
+        0b11111,
+        0b00000,
+        0b00000,
+    ]),
+    ('{', [
+        0b00010,
+        0b00100,
+        0b00100,
+        0b01000,
+        0b00100,
+        0b00100,
+        0b00010,
+        0b00000,
+        0b00000,
+    ]),
+    ('|', [
+        0b00100,
+        0b00100,
+        0b00100,
+        0b00100,
+        0b00100,
+        0b00100,
+        0b00100,
+        0b00000,
+        0b00000,
+    ]),
+    ('}', [
+        0b01000,
+        0b00100,
+        0b00100,
+        0b00010,
+        0b00100,
+        0b00100,
+        0b01000,
+        0b00000,
+        0b00000,
+    ]),
+    ('~', [
+        0b00000,
+        0b00000,
+        0b01000,
+        0b10101,
+        0b00010,
+        0b00000,
+        0b00000,
+        0b00000,
+        0b00000,
+    ]),
+];
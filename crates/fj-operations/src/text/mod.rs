@@ -0,0 +1,252 @@
+mod default_font;
+mod outline;
+
+use std::{
+    hash::{Hash, Hasher},
+    ops::Deref,
+};
+
+use fj_interop::{debug::DebugInfo, mesh::Color};
+use fj_kernel::{
+    algorithms::approx::Tolerance,
+    builder::FaceBuilder,
+    insert::Insert,
+    objects::{Face, Objects, Sketch},
+    partial::HasPartial,
+    validate::ValidationError,
+};
+use fj_math::{Aabb, Point};
+
+use super::{
+    cache::{Cache, HashShape},
+    shape_processor::Error,
+    Shape,
+};
+
+use self::outline::{Contour, Glyph};
+
+impl Shape for fj::Text {
+    type Brep = Sketch;
+
+    fn compute_brep(
+        &self,
+        objects: &Objects,
+        _: &Cache,
+        tolerance: Tolerance,
+        _: &mut DebugInfo,
+    ) -> Result<Self::Brep, Error> {
+        let surface = objects.surfaces.xy_plane();
+
+        let mut faces = Vec::new();
+        for glyph in layout(self, Some(tolerance))? {
+            for (exterior, interiors) in outline::nest(glyph) {
+                let mut face = Face::partial()
+                    .with_surface(surface.clone())
+                    .with_exterior_polygon_from_points(exterior);
+                for interior in interiors {
+                    face = face.with_interior_polygon_from_points(interior);
+                }
+
+                faces.push(
+                    face.with_color(Color(self.color()))
+                        .build(objects)?
+                        .insert(objects)
+                        .map_err(ValidationError::from)?,
+                );
+            }
+        }
+
+        let sketch = Sketch::builder(objects).with_faces(faces).build();
+        Ok(sketch.deref().clone())
+    }
+
+    fn bounding_volume(&self) -> Result<Aabb<3>, Error> {
+        // Without a tolerance, curves are represented by their control
+        // points. A curve is contained in the convex hull of those, so the
+        // bounding volume still contains the whole text.
+        let points = layout(self, None)?
+            .into_iter()
+            .flatten()
+            .flatten()
+            .map(|point| Point::from(point).to_xyz())
+            .collect::<Vec<_>>();
+
+        if points.is_empty() {
+            return Ok(Aabb {
+                min: Point::origin(),
+                max: Point::origin(),
+            });
+        }
+
+        Ok(Aabb::<3>::from_points(points))
+    }
+}
+
+impl HashShape for fj::Text {
+    fn hash_shape<H: Hasher>(&self, state: &mut H) {
+        self.text().hash(state);
+        self.size().hash_shape(state);
+
+        match self.font() {
+            fj::Font::Default => {
+                0u8.hash(state);
+            }
+            fj::Font::Data(data) => {
+                1u8.hash(state);
+                data.as_bytes().hash(state);
+            }
+        }
+
+        self.alignment().hash(state);
+        self.color().hash(state);
+    }
+}
+
+/// The glyphs of a font, in units of the font's em square
+trait Font {
+    /// Access the glyph for a character
+    ///
+    /// Curves are approximated with line segments, within `tolerance` (in
+    /// units of the em square). If no tolerance is provided, curves are
+    /// represented by their control points instead.
+    ///
+    /// Returns `None`, if the font has no glyph for the character, not even a
+    /// replacement glyph.
+    fn glyph(&self, c: char, tolerance: Option<f64>) -> Option<Glyph>;
+
+    /// Access the distance between the baselines of two lines
+    fn line_height(&self) -> f64;
+}
+
+/// Lay out the glyphs of the text
+///
+/// Returns the contours of each glyph, scaled to the font size and moved into
+/// their final position.
+///
+/// Curves are approximated within `tolerance`. See [`Font::glyph`].
+///
+/// Returns an error, if the text uses a font that can't be parsed.
+fn layout(
+    text: &fj::Text,
+    tolerance: Option<Tolerance>,
+) -> Result<Vec<Vec<Contour>>, Error> {
+    let glyphs = match text.font() {
+        fj::Font::Default => {
+            layout_with_font(text, &default_font::DefaultFont, tolerance)
+        }
+        fj::Font::Data(data) => {
+            let font = outline::TrueTypeFont::parse(data.as_bytes())?;
+            layout_with_font(text, &font, tolerance)
+        }
+    };
+
+    Ok(glyphs)
+}
+
+fn layout_with_font(
+    text: &fj::Text,
+    font: &impl Font,
+    tolerance: Option<Tolerance>,
+) -> Vec<Vec<Contour>> {
+    let size = text.size();
+    let mut glyphs = Vec::new();
+
+    // Glyphs are scaled to the font size, so the tolerance needs to be scaled
+    // the opposite way.
+    let tolerance =
+        tolerance.map(|tolerance| tolerance.inner().into_f64() / size);
+
+    for (i, line) in text.text().lines().enumerate() {
+        let line_glyphs = line
+            .chars()
+            .filter_map(|c| font.glyph(c, tolerance))
+            .collect::<Vec<_>>();
+
+        let width: f64 = line_glyphs.iter().map(|glyph| glyph.advance).sum();
+        let mut x = match text.alignment() {
+            fj::Alignment::Left => 0.,
+            fj::Alignment::Center => -width / 2.,
+            fj::Alignment::Right => -width,
+        };
+        let y = -font.line_height() * i as f64;
+
+        for glyph in line_glyphs {
+            let contours = glyph
+                .contours
+                .into_iter()
+                .map(|contour| {
+                    contour
+                        .into_iter()
+                        .map(|[u, v]| [(u + x) * size, (v + y) * size])
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>();
+
+            if !contours.is_empty() {
+                glyphs.push(contours);
+            }
+
+            x += glyph.advance;
+        }
+    }
+
+    glyphs
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_kernel::objects::Objects;
+
+    use crate::{
+        cache::Cache, shape_processor::Error, test_utils::compute_brep,
+        Shape as _,
+    };
+
+    #[test]
+    fn default_font_glyphs_are_valid() -> Result<(), Error> {
+        let objects = Objects::new();
+
+        // Every printable ASCII character, to make sure the built-in font only
+        // produces valid faces.
+        let text = (' '..='~').collect::<String>();
+        let text = fj::Text::from_str(&text, 10.);
+
+        let sketch = compute_brep(&text, &objects, &Cache::new())?;
+        assert!(sketch.faces().into_iter().count() >= 94);
+
+        Ok(())
+    }
+
+    #[test]
+    fn glyphs_with_holes() -> Result<(), Error> {
+        let objects = Objects::new();
+
+        let text = fj::Text::from_str("oB", 10.);
+        let sketch = compute_brep(&text, &objects, &Cache::new())?;
+
+        let faces = sketch.faces().into_iter().collect::<Vec<_>>();
+        assert_eq!(faces.len(), 2);
+
+        let num_holes = faces
+            .iter()
+            .map(|face| face.interiors().count())
+            .collect::<Vec<_>>();
+        assert_eq!(num_holes.iter().sum::<usize>(), 3);
+
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_font() {
+        let objects = Objects::new();
+
+        let text = fj::Text::from_str("a", 10.)
+            .with_font(fj::Font::from_bytes(b"not a font".to_vec()));
+
+        assert!(matches!(text.bounding_volume(), Err(Error::Font(_))));
+        assert!(matches!(
+            compute_brep(&text, &objects, &Cache::new()),
+            Err(Error::Font(_))
+        ));
+    }
+}
@@ -0,0 +1,270 @@
+use fj_math::{nest_polygons, signed_area};
+
+use super::Font;
+
+/// A closed contour of a glyph outline
+///
+/// The last point is connected to the first one.
+pub type Contour = Vec<[f64; 2]>;
+
+/// A glyph, in units of the font's em square
+pub struct Glyph {
+    /// The contours that make up the outline of the glyph
+    pub contours: Vec<Contour>,
+
+    /// The horizontal distance to the next glyph
+    pub advance: f64,
+}
+
+/// Sort the contours of a glyph into exteriors and their interiors
+///
+/// Fonts don't agree on the winding of exterior contours, so what's an
+/// exterior and what's an interior is decided by nesting instead: A contour
+/// that is contained in an even number of other contours is an exterior.
+///
+/// The returned exteriors are wound counter-clockwise, the interiors
+/// clockwise.
+pub fn nest(contours: Vec<Contour>) -> Vec<(Contour, Vec<Contour>)> {
+    let polygons = contours.iter().map(Vec::as_slice).collect::<Vec<_>>();
+
+    nest_polygons(&polygons)
+        .into_iter()
+        .map(|(exterior, interiors)| {
+            let interiors = interiors
+                .into_iter()
+                .map(|interior| oriented(contours[interior].clone(), false))
+                .collect();
+            (oriented(contours[exterior].clone(), true), interiors)
+        })
+        .collect()
+}
+
+fn oriented(mut contour: Contour, ccw: bool) -> Contour {
+    if signed_area(&contour).is_positive() != ccw {
+        contour.reverse();
+    }
+
+    contour
+}
+
+/// A TrueType or OpenType font
+pub struct TrueTypeFont<'r> {
+    face: ttf_parser::Face<'r>,
+}
+
+impl<'r> TrueTypeFont<'r> {
+    pub fn parse(data: &'r [u8]) -> Result<Self, ttf_parser::FaceParsingError> {
+        let face = ttf_parser::Face::parse(data, 0)?;
+        Ok(Self { face })
+    }
+
+    fn units_per_em(&self) -> f64 {
+        f64::from(self.face.units_per_em())
+    }
+}
+
+impl Font for TrueTypeFont<'_> {
+    fn glyph(&self, c: char, tolerance: Option<f64>) -> Option<Glyph> {
+        // Characters that aren't in the font are rendered using the
+        // replacement glyph, which is always at index 0.
+        let id = self.face.glyph_index(c).unwrap_or(ttf_parser::GlyphId(0));
+
+        let scale = 1. / self.units_per_em();
+
+        let tolerance = tolerance.map(|tolerance| tolerance / scale);
+
+        let mut builder = OutlineCollector::new(scale, tolerance);
+        self.face.outline_glyph(id, &mut builder);
+
+        let advance = self.face.glyph_hor_advance(id)?;
+
+        Some(Glyph {
+            contours: builder.contours,
+            advance: f64::from(advance) * scale,
+        })
+    }
+
+    fn line_height(&self) -> f64 {
+        let ascender = f64::from(self.face.ascender());
+        let descender = f64::from(self.face.descender());
+        let line_gap = f64::from(self.face.line_gap());
+
+        (ascender - descender + line_gap) / self.units_per_em()
+    }
+}
+
+/// Collects the contours of a glyph outline, approximating curves
+///
+/// Points are collected in font units, and scaled once a contour is closed.
+/// The tolerance is in font units too.
+struct OutlineCollector {
+    scale: f64,
+    tolerance: Option<f64>,
+    contours: Vec<Contour>,
+    current: Contour,
+}
+
+impl OutlineCollector {
+    fn new(scale: f64, tolerance: Option<f64>) -> Self {
+        Self {
+            scale,
+            tolerance,
+            contours: Vec::new(),
+            current: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, point: [f64; 2]) {
+        if self.current.last() != Some(&point) {
+            self.current.push(point);
+        }
+    }
+
+    fn last(&self) -> [f64; 2] {
+        self.current.last().copied().unwrap_or_default()
+    }
+
+    /// Approximate a curve with line segments
+    ///
+    /// `deviation` is an upper bound for the distance between the curve and a
+    /// single line segment from its start to its end point. Approximating the
+    /// curve with `n` segments reduces that distance to `deviation / n²`, which
+    /// determines the number of segments required to stay within the
+    /// tolerance.
+    ///
+    /// Without a tolerance, the curve is represented by `control_points`
+    /// instead.
+    fn curve(
+        &mut self,
+        deviation: f64,
+        control_points: &[[f64; 2]],
+        point_at: impl Fn(f64) -> [f64; 2],
+    ) {
+        let tolerance = match self.tolerance {
+            Some(tolerance) => tolerance,
+            None => {
+                for &point in control_points {
+                    self.push(point);
+                }
+                return;
+            }
+        };
+
+        let num_segments = (deviation / tolerance).sqrt().ceil().max(1.) as u32;
+
+        for i in 1..=num_segments {
+            let t = f64::from(i) / f64::from(num_segments);
+            self.push(point_at(t));
+        }
+    }
+}
+
+/// The length of the second difference of three control points
+fn second_difference(a: [f64; 2], b: [f64; 2], c: [f64; 2]) -> f64 {
+    let [x, y] = [0, 1].map(|i| a[i] - 2. * b[i] + c[i]);
+    x.hypot(y)
+}
+
+impl ttf_parser::OutlineBuilder for OutlineCollector {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.close();
+        self.push([x.into(), y.into()]);
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        self.push([x.into(), y.into()]);
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        let p0 = self.last();
+        let p1: [f64; 2] = [x1.into(), y1.into()];
+        let p2: [f64; 2] = [x.into(), y.into()];
+
+        // The second derivative of the curve is `2 * (p0 - 2 * p1 + p2)`.
+        let deviation = second_difference(p0, p1, p2) / 4.;
+
+        self.curve(deviation, &[p1, p2], |t| {
+            let s = 1. - t;
+            let [a, b, c] = [s * s, 2. * s * t, t * t];
+
+            [0, 1].map(|i| a * p0[i] + b * p1[i] + c * p2[i])
+        });
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        let p0 = self.last();
+        let p1: [f64; 2] = [x1.into(), y1.into()];
+        let p2: [f64; 2] = [x2.into(), y2.into()];
+        let p3: [f64; 2] = [x.into(), y.into()];
+
+        // The second derivative of the curve is bounded by
+        // `6 * max(|p0 - 2 * p1 + p2|, |p1 - 2 * p2 + p3|)`.
+        let deviation = 3.
+            * second_difference(p0, p1, p2).max(second_difference(p1, p2, p3))
+            / 4.;
+
+        self.curve(deviation, &[p1, p2, p3], |t| {
+            let s = 1. - t;
+            let [a, b, c, d] =
+                [s * s * s, 3. * s * s * t, 3. * s * t * t, t * t * t];
+
+            [0, 1].map(|i| a * p0[i] + b * p1[i] + c * p2[i] + d * p3[i])
+        });
+    }
+
+    fn close(&mut self) {
+        let mut contour = std::mem::take(&mut self.current);
+
+        if contour.len() > 1 && contour.first() == contour.last() {
+            contour.pop();
+        }
+        if contour.len() >= 3 {
+            let scale = self.scale;
+            self.contours.push(
+                contour.into_iter().map(|p| p.map(|c| c * scale)).collect(),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ttf_parser::OutlineBuilder as _;
+
+    use super::OutlineCollector;
+
+    #[test]
+    fn curve_segments_depend_on_tolerance() {
+        let num_points = |tolerance| {
+            let mut collector = OutlineCollector::new(1., tolerance);
+            collector.move_to(0., 0.);
+            collector.quad_to(50., 100., 100., 0.);
+            collector.current.len()
+        };
+
+        // Without a tolerance, only the control points are used.
+        assert_eq!(num_points(None), 3);
+
+        let coarse = num_points(Some(1.));
+        let fine = num_points(Some(0.01));
+        assert!(coarse > 3);
+        assert!(fine > coarse);
+    }
+
+    #[test]
+    fn curve_is_approximated_within_tolerance() {
+        let tolerance = 0.1;
+
+        let mut collector = OutlineCollector::new(1., Some(tolerance));
+        collector.move_to(0., 0.);
+        collector.quad_to(50., 100., 100., 0.);
+
+        // The curve is a parabola through `(0, 0)`, `(50, 50)` and
+        // `(100, 0)`. Check the midpoints of all segments against it.
+        for segment in collector.current.windows(2) {
+            let [x, y] = [0, 1].map(|i| (segment[0][i] + segment[1][i]) / 2.);
+            let expected = x * (100. - x) / 50.;
+            assert!((y - expected).abs() <= tolerance);
+        }
+    }
+}
@@ -1,12 +1,18 @@
+use std::hash::Hasher;
+
 use fj_interop::debug::DebugInfo;
 use fj_kernel::{
-    algorithms::transform::TransformObject,
+    algorithms::{approx::Tolerance, transform::TransformObject},
     objects::{FaceSet, Objects},
-    validate::ValidationError,
+    path::{GlobalPath, SurfacePath},
 };
 use fj_math::{Aabb, Transform, Vector};
 
-use super::Shape;
+use super::{
+    cache::{Cache, HashShape},
+    shape_processor::Error,
+    Shape,
+};
 
 impl Shape for fj::Transform {
     type Brep = FaceSet;
@@ -14,23 +20,183 @@ impl Shape for fj::Transform {
     fn compute_brep(
         &self,
         objects: &Objects,
+        cache: &Cache,
+        tolerance: Tolerance,
         debug_info: &mut DebugInfo,
-    ) -> Result<Self::Brep, ValidationError> {
+    ) -> Result<Self::Brep, Error> {
+        let scale = self.scale;
+        let transform = make_transform(self)?;
+
         let faces = self
             .shape
-            .compute_brep(objects, debug_info)?
-            .transform(&make_transform(self), objects)?;
+            .compute_brep(objects, cache, tolerance, debug_info)?;
+
+        let is_uniform = scale.iter().all(|&factor| factor == scale[0]);
+        if !is_uniform && contains_circles(&faces) {
+            return Err(Error::NonUniformScale(scale));
+        }
+
+        let faces = faces.transform(&transform, objects)?;
 
         Ok(faces)
     }
 
-    fn bounding_volume(&self) -> Aabb<3> {
-        make_transform(self).transform_aabb(&self.shape.bounding_volume())
+    fn bounding_volume(&self) -> Result<Aabb<3>, Error> {
+        let aabb = self.shape.bounding_volume()?;
+        Ok(make_transform(self)?.transform_aabb(&aabb))
+    }
+}
+
+impl HashShape for fj::Transform {
+    fn hash_shape<H: Hasher>(&self, state: &mut H) {
+        self.shape.hash_shape(state);
+        self.axis.hash_shape(state);
+        self.angle.hash_shape(state);
+        self.offset.hash_shape(state);
+        self.scale.hash_shape(state);
+        self.mirror.hash_shape(state);
+    }
+}
+
+impl HashShape for fj::Mirror {
+    fn hash_shape<H: Hasher>(&self, state: &mut H) {
+        self.origin.hash_shape(state);
+        self.normal.hash_shape(state);
     }
 }
 
-fn make_transform(transform: &fj::Transform) -> Transform {
-    let axis = Vector::from(transform.axis).normalize();
-    Transform::translation(transform.offset)
-        * Transform::rotation(axis * transform.angle.rad())
+/// Convert the shape's transform into a kernel transform
+///
+/// Returns an error, if the transform can't be applied to a shape.
+fn make_transform(transform: &fj::Transform) -> Result<Transform, Error> {
+    let scale = transform.scale;
+    if scale
+        .iter()
+        .any(|&factor| factor == 0. || !factor.is_finite())
+    {
+        return Err(Error::InvalidScale(scale));
+    }
+
+    let mirror: Option<&fj::Mirror> = transform.mirror.as_ref().into();
+    let mirror = match mirror {
+        Some(mirror) => {
+            if !is_direction(mirror.normal) {
+                return Err(Error::InvalidMirrorNormal(mirror.normal));
+            }
+
+            let origin = Vector::from(mirror.origin);
+            Transform::translation(origin)
+                * Transform::mirror(mirror.normal)
+                * Transform::translation(-origin)
+        }
+        None => Transform::identity(),
+    };
+
+    let rotation = if transform.angle.rad() == 0. {
+        Transform::identity()
+    } else {
+        if !is_direction(transform.axis) {
+            return Err(Error::InvalidRotationAxis(transform.axis));
+        }
+
+        let axis = Vector::from(transform.axis).normalize();
+        Transform::rotation(axis * transform.angle.rad())
+    };
+
+    Ok(Transform::translation(transform.offset)
+        * rotation
+        * mirror
+        * Transform::scaling(scale))
+}
+
+/// Indicate whether a vector can be normalized into a direction
+fn is_direction(vector: [f64; 3]) -> bool {
+    vector.iter().all(|component| component.is_finite())
+        && vector.iter().any(|&component| component != 0.)
+}
+
+/// Indicate whether any of the faces are bounded by, or curved like, a circle
+///
+/// Scaling a circle non-uniformly results in an ellipse, which the kernel
+/// can't represent.
+fn contains_circles(faces: &FaceSet) -> bool {
+    faces.into_iter().any(|face| {
+        if let GlobalPath::Circle(_) = face.surface().u() {
+            return true;
+        }
+
+        face.all_cycles().flat_map(|cycle| cycle.half_edges()).any(
+            |half_edge| {
+                matches!(half_edge.curve().path(), SurfacePath::Circle(_))
+            },
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use fj::syntax::*;
+    use fj_kernel::objects::Objects;
+
+    use crate::{
+        cache::Cache, shape_processor::Error, test_utils::compute_brep,
+    };
+
+    #[test]
+    fn scale_by_invalid_factors() {
+        let square = [[0., 0.], [1., 0.], [1., 1.], [0., 1.]].sketch();
+        let sweep = square.sweep([0., 0., 1.]);
+
+        for factor in [0., f64::NAN, f64::INFINITY] {
+            let shape = sweep.scale_non_uniform([1., factor, 1.]);
+            assert!(matches!(
+                compute_brep(&shape, &Objects::new(), &Cache::new()),
+                Err(Error::InvalidScale(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn scale_by_negative_factors() {
+        let square = [[0., 0.], [1., 0.], [1., 1.], [0., 1.]].sketch();
+        let sweep = square.sweep([0., 0., 1.]);
+
+        for shape in [sweep.scale(-1.), sweep.scale_non_uniform([-1., 1., 1.])]
+        {
+            assert!(
+                compute_brep(&shape, &Objects::new(), &Cache::new()).is_ok()
+            );
+        }
+    }
+
+    #[test]
+    fn mirror_across_plane_without_normal() {
+        let square = [[0., 0.], [1., 0.], [1., 1.], [0., 1.]].sketch();
+        let shape = square.sweep([0., 0., 1.]).mirror([0.; 3], [0.; 3]);
+
+        assert!(matches!(
+            compute_brep(&shape, &Objects::new(), &Cache::new()),
+            Err(Error::InvalidMirrorNormal(_))
+        ));
+    }
+
+    #[test]
+    fn scale_circles_non_uniformly() {
+        let circle: fj::Shape2d =
+            fj::Sketch::from_circle(fj::Circle::from_radius(1.)).into();
+        let cylinder = circle.sweep([0., 0., 1.]);
+
+        let shape = cylinder.scale_non_uniform([1., 2., 1.]);
+        assert!(matches!(
+            compute_brep(&shape, &Objects::new(), &Cache::new()),
+            Err(Error::NonUniformScale(_))
+        ));
+
+        let shape = cylinder.scale(2.);
+        assert!(compute_brep(&shape, &Objects::new(), &Cache::new()).is_ok());
+
+        let square = [[0., 0.], [1., 0.], [1., 1.], [0., 1.]].sketch();
+        let shape = square.sweep([0., 0., 1.]).scale_non_uniform([1., 2., 1.]);
+        assert!(compute_brep(&shape, &Objects::new(), &Cache::new()).is_ok());
+    }
 }
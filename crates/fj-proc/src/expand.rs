@@ -2,12 +2,16 @@ use proc_macro2::TokenStream;
 use quote::{quote, ToTokens};
 
 use crate::parse::{
-    ArgumentMetadata, Constraint, ConstraintKind, ExtractedArgument,
-    GeometryFunction, Initializer, Metadata, Model,
+    ArgumentMetadata, ArgumentType, Constraint, ConstraintKind,
+    ExtractedArgument, GeometryFunction, Initializer, Metadata, Model, Preset,
 };
 
 impl Initializer {
     fn register(&self) -> TokenStream {
+        let units = self.units.as_ref().map(|units| {
+            quote! { .with_units(fj::models::LengthUnit::#units) }
+        });
+
         quote! {
             const _: () = {
                 fj::register_model!(|host| {
@@ -18,7 +22,8 @@ impl Initializer {
                             .with_short_description(env!("CARGO_PKG_DESCRIPTION"))
                             .with_homepage(env!("CARGO_PKG_HOMEPAGE"))
                             .with_repository(env!("CARGO_PKG_REPOSITORY"))
-                            .with_license(env!("CARGO_PKG_LICENSE")),
+                            .with_license(env!("CARGO_PKG_LICENSE"))
+                            #units,
                     )
                 });
             };
@@ -28,7 +33,7 @@ impl Initializer {
 
 impl ToTokens for Initializer {
     fn to_tokens(&self, tokens: &mut TokenStream) {
-        let Initializer { model } = self;
+        let Initializer { model, .. } = self;
 
         tokens.extend(self.register());
         model.to_tokens(tokens);
@@ -61,22 +66,45 @@ impl ToTokens for Model {
 
 impl ToTokens for Metadata {
     fn to_tokens(&self, tokens: &mut TokenStream) {
-        let Metadata { name, arguments } = self;
+        let Metadata {
+            name,
+            arguments,
+            presets,
+        } = self;
 
         tokens.extend(quote! {
             fn metadata(&self) -> fj::models::ModelMetadata {
                 fj::models::ModelMetadata::new(#name)
                 #( .with_argument(#arguments) )*
+                #( .with_preset(#presets) )*
             }
         });
     }
 }
 
+impl ToTokens for Preset {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let Preset { name, values } = self;
+
+        let name = name.to_string();
+        let (arguments, values): (Vec<_>, Vec<_>) = values
+            .iter()
+            .map(|(argument, value)| (argument.to_string(), value))
+            .unzip();
+
+        tokens.extend(quote! {
+            fj::models::PresetMetadata::new(#name)
+            #( .with_value(#arguments, #values) )*
+        });
+    }
+}
+
 impl ToTokens for ArgumentMetadata {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         let ArgumentMetadata {
             name,
             default_value,
+            argument_type,
         } = self;
 
         tokens.extend(quote! { fj::models::ArgumentMetadata::new(#name) });
@@ -86,6 +114,27 @@ impl ToTokens for ArgumentMetadata {
                 .with_default_value(stringify!(#default_value))
             });
         }
+
+        if let Some(parameter_type) = argument_type.parameter_type() {
+            tokens.extend(quote! { .with_type(#parameter_type) });
+        }
+    }
+}
+
+impl ArgumentType {
+    /// The `fj::models::ParameterType` the host should parse the argument as
+    fn parameter_type(&self) -> Option<TokenStream> {
+        match self {
+            ArgumentType::Parameter(ty) => Some(quote! {
+                <#ty as fj::models::Parameter>::parameter_type()
+            }),
+            ArgumentType::Choice(choices) => Some(quote! {
+                fj::models::ParameterType::Choice(
+                    vec![#( String::from(#choices) ),*]
+                )
+            }),
+            ArgumentType::Text => None,
+        }
     }
 }
 
@@ -128,21 +177,35 @@ impl ToTokens for ExtractedArgument {
             ident,
             ty,
             default_value,
+            argument_type,
         } = self;
 
         let name = ident.to_string();
+        let argument = match argument_type.parameter_type() {
+            Some(parameter_type) => {
+                quote! { ctx.get_value(#name, &#parameter_type)? }
+            }
+            None => quote! { ctx.get_argument(#name) },
+        };
+        let value = match argument_type {
+            ArgumentType::Parameter(_) => {
+                quote! { fj::models::Parameter::from_value(value)? }
+            }
+            ArgumentType::Choice(_) => quote! { value.to_string().parse()? },
+            ArgumentType::Text => quote! { value.parse()? },
+        };
         let t = match default_value {
             Some(default) => quote! {
-                let #ident: #ty = match ctx.get_argument(#name) {
-                    Some(value) => value.parse()?,
+                let #ident: #ty = match #argument {
+                    Some(value) => #value,
                     None => #default
                 };
             },
             None => {
                 let error_message = format!("Expected {name}");
                 quote! {
-                    let #ident: #ty = match ctx.get_argument(#name) {
-                        Some(value) => value.parse()?,
+                    let #ident: #ty = match #argument {
+                        Some(value) => #value,
                         None => return Err(#error_message.into()),
                     };
                 }
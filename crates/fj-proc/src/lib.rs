@@ -50,6 +50,21 @@ use syn::{parse_macro_input, FnArg, ItemFn};
 /// fn cylinder(height: f64, label: String, is_horizontal: bool) -> fj::Shape { todo!() }
 /// ```
 ///
+/// Integers, floats, `bool`, `String`, `[f64; 2]`, and `[f64; 3]` are passed
+/// as typed parameters (see `fj::models::Parameter`). The host knows their
+/// type, and checks the values it is given before evaluating the model.
+/// Arguments can also be restricted to a fixed set of names.
+///
+/// ```rust
+/// # use fj_proc::model;
+/// #[model]
+/// fn bolt(
+///     offset: [f64; 3],
+///     #[param(default = "hex".to_string(), choices = ["hex", "round"])]
+///     head: String,
+/// ) -> fj::Shape { todo!() }
+/// ```
+///
 /// Constraints and default values can be added to an argument using the
 /// `#[param]` attribute.
 ///
@@ -85,11 +100,37 @@ use syn::{parse_macro_input, FnArg, ItemFn};
 ///
 /// fn assert_convertible(e: std::env::VarError) -> fj::models::Error { e.into() }
 /// ```
+///
+/// A model can declare presets, named sets of values for its arguments. Hosts
+/// can offer them to the user as a starting point. Arguments that a preset
+/// doesn't list keep their default value.
+///
+/// ```rust
+/// #[fj::model(presets(small(width = 5.0), large(width = 50.0, height = 2.0)))]
+/// pub fn model(
+///     #[param(default = 10.0)] width: f64,
+///     #[param(default = 5.0)] height: f64,
+/// ) -> fj::Shape {
+///     todo!()
+/// }
+/// ```
+///
+/// Coordinates are measured in millimeters, unless the model declares another
+/// unit of length. Exported files are scaled accordingly.
+///
+/// ```rust
+/// #[fj::model(units = Inch)]
+/// pub fn model() -> fj::Shape {
+///     let circle = fj::Circle::from_radius(0.5);
+///     fj::Sketch::from_circle(circle).into()
+/// }
+/// ```
 #[proc_macro_attribute]
-pub fn model(_: TokenStream, input: TokenStream) -> TokenStream {
+pub fn model(attribute: TokenStream, input: TokenStream) -> TokenStream {
+    let attribute = parse_macro_input!(attribute as parse::ModelAttribute);
     let item = parse_macro_input!(input as syn::ItemFn);
 
-    match parse::parse(&item) {
+    match parse::parse(&item, attribute) {
         Ok(init) => {
             let item = without_param_attrs(item);
 
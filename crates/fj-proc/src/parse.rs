@@ -1,13 +1,189 @@
 use proc_macro2::Ident;
 use syn::{
-    bracketed, parenthesized, parse::Parse, parse_quote, Expr, ItemFn,
-    ReturnType, Type,
+    bracketed, parenthesized, parse::Parse, parse_quote, Expr, ExprLit,
+    ExprUnary, ItemFn, Lit, LitStr, ReturnType, Type, TypePath, UnOp,
 };
 
 /// The call to `fj::register_model!()`.
 #[derive(Debug)]
 pub(crate) struct Initializer {
     pub(crate) model: Model,
+    pub(crate) units: Option<Ident>,
+}
+
+/// The arguments of the `#[fj::model]` attribute.
+///
+/// ```text
+/// #[fj::model(units = Inch, presets(small(width = 1.0)))]
+///             ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+/// ```
+#[derive(Debug, Default)]
+pub(crate) struct ModelAttribute {
+    /// The variant of `fj::models::LengthUnit` that the model is measured in
+    pub(crate) units: Option<Ident>,
+
+    /// Named sets of values for the model's arguments
+    pub(crate) presets: Vec<Preset>,
+}
+
+impl Parse for ModelAttribute {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let params = syn::punctuated::Punctuated::<
+            ModelParam,
+            syn::Token![,],
+        >::parse_terminated(input)?;
+
+        let mut attribute = Self::default();
+
+        for param in params {
+            let param = match param {
+                ModelParam::Value(param) => *param,
+                ModelParam::Presets(presets) => {
+                    attribute.presets.extend(presets);
+                    continue;
+                }
+            };
+
+            if param.ident != *"units" {
+                return Err(syn::Error::new_spanned(
+                    param.ident.clone(),
+                    format!(
+                        "Unknown argument \"{}\" found, expected \"units\" or \
+                        \"presets\"",
+                        param.ident
+                    ),
+                ));
+            }
+
+            match &param.val {
+                Expr::Path(path) if path.path.get_ident().is_some() => {
+                    attribute.units = path.path.get_ident().cloned();
+                }
+                val => {
+                    return Err(syn::Error::new_spanned(
+                        val,
+                        "Expected a unit of length, like `Millimeter` or \
+                        `Inch`",
+                    ))
+                }
+            }
+        }
+
+        Ok(attribute)
+    }
+}
+
+/// One argument of the `#[fj::model]` attribute.
+enum ModelParam {
+    /// An argument like `units = Inch`
+    Value(Box<DefaultParam>),
+
+    /// The list of presets, `presets(...)`
+    Presets(Vec<Preset>),
+}
+
+impl Parse for ModelParam {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        if !(input.peek(syn::Ident) && input.peek2(syn::token::Paren)) {
+            return Ok(Self::Value(Box::new(input.parse()?)));
+        }
+
+        let ident: Ident = input.parse()?;
+        if ident != *"presets" {
+            return Err(syn::Error::new_spanned(
+                ident.clone(),
+                format!(
+                    "Unknown argument \"{}\" found, expected \"presets\"",
+                    ident
+                ),
+            ));
+        }
+
+        let content;
+        parenthesized!(content in input);
+        let presets = syn::punctuated::Punctuated::<
+            Preset,
+            syn::Token![,],
+        >::parse_terminated(&content)?;
+
+        Ok(Self::Presets(presets.into_iter().collect()))
+    }
+}
+
+/// A named set of values for the model's arguments.
+///
+/// ```text
+/// #[fj::model(presets(small(width = 1.0, style = "flat")))]
+///                     ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+/// ```
+#[derive(Debug)]
+pub(crate) struct Preset {
+    pub(crate) name: Ident,
+
+    /// The arguments, and the text of their values
+    pub(crate) values: Vec<(Ident, String)>,
+}
+
+impl Parse for Preset {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let name: Ident = input.parse()?;
+
+        let content;
+        parenthesized!(content in input);
+        let params = syn::punctuated::Punctuated::<
+            DefaultParam,
+            syn::Token![,],
+        >::parse_terminated(&content)?;
+
+        let values = params
+            .into_iter()
+            .map(|param| {
+                let value = literal_text(&param.val).ok_or_else(|| {
+                    syn::Error::new_spanned(
+                        &param.val,
+                        "Expected a literal value, like `2`, `\"round\"`, or \
+                        `[1.0, 2.0]`",
+                    )
+                })?;
+                Ok((param.ident, value))
+            })
+            .collect::<syn::Result<_>>()?;
+
+        Ok(Self { name, values })
+    }
+}
+
+/// The text of a literal value, as a host would pass it to the model
+fn literal_text(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Lit(ExprLit { lit, .. }) => match lit {
+            Lit::Str(lit) => Some(lit.value()),
+            Lit::Int(lit) => Some(lit.base10_digits().to_owned()),
+            Lit::Float(lit) => Some(lit.base10_digits().to_owned()),
+            Lit::Bool(lit) => Some(lit.value.to_string()),
+            _ => None,
+        },
+        Expr::Unary(ExprUnary {
+            op: UnOp::Neg(_),
+            expr,
+            ..
+        }) => match &**expr {
+            Expr::Lit(ExprLit {
+                lit: Lit::Int(_) | Lit::Float(_),
+                ..
+            }) => Some(format!("-{}", literal_text(expr)?)),
+            _ => None,
+        },
+        Expr::Array(array) => {
+            let elems = array
+                .elems
+                .iter()
+                .map(literal_text)
+                .collect::<Option<Vec<_>>>()?;
+            Some(format!("[{}]", elems.join(", ")))
+        }
+        _ => None,
+    }
 }
 
 /// The generated `Model` struct and its `fj::Model` impl.
@@ -22,6 +198,7 @@ pub(crate) struct Model {
 pub(crate) struct Metadata {
     pub(crate) name: String,
     pub(crate) arguments: Vec<ArgumentMetadata>,
+    pub(crate) presets: Vec<Preset>,
 }
 
 /// Metadata for a specific argument.
@@ -29,6 +206,7 @@ pub(crate) struct Metadata {
 pub(crate) struct ArgumentMetadata {
     pub(crate) name: String,
     pub(crate) default_value: Option<Expr>,
+    pub(crate) argument_type: ArgumentType,
 }
 
 /// The `<_ as fj::Model>::shape()` function.
@@ -45,6 +223,20 @@ pub(crate) struct ExtractedArgument {
     pub(crate) ident: Ident,
     pub(crate) ty: Type,
     pub(crate) default_value: Option<Expr>,
+    pub(crate) argument_type: ArgumentType,
+}
+
+/// How the host passes the value of an argument to the model.
+#[derive(Debug, Clone)]
+pub(crate) enum ArgumentType {
+    /// The argument's type implements `fj::models::Parameter`
+    Parameter(Box<Type>),
+
+    /// One of the names given by `#[param(choices = [...])]`
+    Choice(Vec<LitStr>),
+
+    /// Any other type, which is parsed using `FromStr`
+    Text,
 }
 
 #[derive(Debug)]
@@ -60,10 +252,47 @@ pub(crate) enum ConstraintKind {
     Max,
 }
 
-pub(crate) fn parse(f: &ItemFn) -> syn::Result<Initializer> {
-    let model = parse_model(f)?;
+pub(crate) fn parse(
+    f: &ItemFn,
+    attribute: ModelAttribute,
+) -> syn::Result<Initializer> {
+    let mut model = parse_model(f)?;
 
-    Ok(Initializer { model })
+    for (i, preset) in attribute.presets.iter().enumerate() {
+        if attribute.presets[..i]
+            .iter()
+            .any(|other| other.name == preset.name)
+        {
+            return Err(syn::Error::new_spanned(
+                preset.name.clone(),
+                format!("Duplicate preset \"{}\"", preset.name),
+            ));
+        }
+
+        for (argument, _) in &preset.values {
+            let is_argument = model
+                .metadata
+                .arguments
+                .iter()
+                .any(|arg| *argument == arg.name);
+
+            if !is_argument {
+                return Err(syn::Error::new_spanned(
+                    argument.clone(),
+                    format!(
+                        "Unknown argument \"{}\" in preset \"{}\"",
+                        argument, preset.name
+                    ),
+                ));
+            }
+        }
+    }
+    model.metadata.presets = attribute.presets;
+
+    Ok(Initializer {
+        model,
+        units: attribute.units,
+    })
 }
 
 fn parse_model(item: &ItemFn) -> syn::Result<Model> {
@@ -75,26 +304,35 @@ fn parse_model(item: &ItemFn) -> syn::Result<Model> {
         .iter()
         .map(|inp| parse_quote!(#inp))
         .collect();
+    let argument_types = args
+        .iter()
+        .map(Argument::argument_type)
+        .collect::<syn::Result<Vec<_>>>()?;
 
     let metadata = Metadata {
         name: geometry_function.to_string(),
         arguments: args
             .iter()
-            .map(|a| ArgumentMetadata {
+            .zip(&argument_types)
+            .map(|(a, argument_type)| ArgumentMetadata {
                 name: a.ident.to_string(),
                 default_value: a.default(),
+                argument_type: argument_type.clone(),
             })
             .collect(),
+        presets: Vec::new(),
     };
 
     let geometry = GeometryFunction {
         geometry_function,
         arguments: args
             .iter()
-            .map(|a| ExtractedArgument {
+            .zip(argument_types)
+            .map(|(a, argument_type)| ExtractedArgument {
                 ident: a.ident.clone(),
                 default_value: a.default(),
                 ty: a.ty.clone(),
+                argument_type,
             })
             .collect(),
         constraints: args.iter().flat_map(argument_constraints).collect(),
@@ -163,6 +401,106 @@ impl Argument {
             .and_then(|attr| attr.get_default())
             .map(|param| param.val)
     }
+
+    fn argument_type(&self) -> syn::Result<ArgumentType> {
+        let choices = self
+            .attr
+            .as_ref()
+            .and_then(|attr| attr.get_parameter("choices"));
+
+        if let Some(choices) = choices {
+            let error = || {
+                syn::Error::new_spanned(
+                    &choices.val,
+                    "Expected a list of names, like `[\"round\", \"flat\"]`",
+                )
+            };
+
+            let elems = match &choices.val {
+                Expr::Array(array) if !array.elems.is_empty() => &array.elems,
+                _ => return Err(error()),
+            };
+            let choices = elems
+                .iter()
+                .map(|elem| match elem {
+                    Expr::Lit(ExprLit {
+                        lit: Lit::Str(choice),
+                        ..
+                    }) => Ok(choice.clone()),
+                    _ => Err(error()),
+                })
+                .collect::<syn::Result<_>>()?;
+
+            return Ok(ArgumentType::Choice(choices));
+        }
+
+        if is_parameter_type(&self.ty) {
+            Ok(ArgumentType::Parameter(Box::new(self.ty.clone())))
+        } else {
+            Ok(ArgumentType::Text)
+        }
+    }
+}
+
+/// Whether `fj::models::Parameter` is implemented for a type
+///
+/// This can only be decided by looking at the type's path. Primitive types and
+/// `String` are recognized by their name, or by their full path, like
+/// `std::primitive::u64`. Other paths that end in those names refer to other
+/// types. Arguments of those types are parsed using `FromStr`.
+fn is_parameter_type(ty: &Type) -> bool {
+    const PRIMITIVES: &[&str] = &[
+        "i8", "i16", "i32", "i64", "isize", "u8", "u16", "u32", "u64", "usize",
+        "f32", "f64", "bool",
+    ];
+
+    match ty {
+        Type::Path(path) => {
+            PRIMITIVES
+                .iter()
+                .any(|primitive| is_primitive(path, primitive))
+                || is_path(path, &["String"])
+                || is_path(path, &["std", "string", "String"])
+                || is_path(path, &["alloc", "string", "String"])
+        }
+        Type::Array(array) => {
+            let is_f64 = matches!(
+                &*array.elem,
+                Type::Path(path) if is_primitive(path, "f64")
+            );
+            let is_vector = matches!(
+                &array.len,
+                Expr::Lit(ExprLit { lit: Lit::Int(len), .. })
+                    if matches!(len.base10_digits(), "2" | "3")
+            );
+
+            is_f64 && is_vector
+        }
+        _ => false,
+    }
+}
+
+/// Whether a path refers to the primitive type of the given name
+fn is_primitive(path: &TypePath, name: &str) -> bool {
+    is_path(path, &[name])
+        || is_path(path, &["std", "primitive", name])
+        || is_path(path, &["core", "primitive", name])
+}
+
+/// Whether a path consists of exactly the given segments
+///
+/// A leading `::` is allowed, but no generic arguments.
+fn is_path(path: &TypePath, segments: &[&str]) -> bool {
+    path.qself.is_none()
+        && path.path.segments.len() == segments.len()
+        && path
+            .path
+            .segments
+            .iter()
+            .zip(segments)
+            .all(|(segment, name)| {
+                segment.arguments.is_empty() && segment.ident == name
+            })
 }
 
 impl Parse for Argument {
@@ -303,12 +641,17 @@ mod tests {
 
         let Initializer {
             model: Model { metadata, geometry },
-        } = parse(&function).unwrap();
+            units,
+        } = parse(&function, ModelAttribute::default()).unwrap();
+
+        assert!(units.is_none());
 
         // Note: we can't #[derive(PartialEq)] on our parsed structs because
         // proc_macro2::Ident and friends don't implement PartialEq, so let's
         // manually check everything parsed correctly.
-        let Metadata { name, arguments } = metadata;
+        let Metadata {
+            name, arguments, ..
+        } = metadata;
         assert_eq!(name, "spacer");
         let expected_meta = &[
             ("outer".to_string(), Some("1.0".to_string())),
@@ -382,8 +725,163 @@ mod tests {
         };
         let function: ItemFn = syn::parse2(tokens).unwrap();
 
-        let init = parse(&function).unwrap();
+        let init = parse(&function, ModelAttribute::default()).unwrap();
 
         assert!(init.model.geometry.fallible);
     }
+
+    #[test]
+    fn parse_units() {
+        let function: ItemFn = syn::parse2(quote! {
+            pub fn spacer() -> fj::Shape {
+                todo!()
+            }
+        })
+        .unwrap();
+
+        let attribute: ModelAttribute =
+            syn::parse2(quote! { units = Inch }).unwrap();
+        let init = parse(&function, attribute).unwrap();
+
+        assert_eq!(init.units.unwrap().to_string(), "Inch");
+
+        assert!(syn::parse2::<ModelAttribute>(quote! { unit = Inch }).is_err());
+        assert!(syn::parse2::<ModelAttribute>(quote! { units = 1 }).is_err());
+    }
+
+    #[test]
+    fn parse_argument_types() {
+        let function: ItemFn = syn::parse2(quote! {
+            pub fn model(
+                count: u32,
+                offset: [f64; 3],
+                #[param(choices = ["round", "flat"])] style: Style,
+                color: Color,
+            ) -> fj::Shape {
+                todo!()
+            }
+        })
+        .unwrap();
+
+        let init = parse(&function, ModelAttribute::default()).unwrap();
+        let types: Vec<_> = init
+            .model
+            .metadata
+            .arguments
+            .iter()
+            .map(|arg| match &arg.argument_type {
+                ArgumentType::Parameter(ty) => ty.to_token_stream().to_string(),
+                ArgumentType::Choice(choices) => choices
+                    .iter()
+                    .map(LitStr::value)
+                    .collect::<Vec<_>>()
+                    .join("|"),
+                ArgumentType::Text => "text".to_string(),
+            })
+            .collect();
+        assert_eq!(types, ["u32", "[f64 ; 3]", "round|flat", "text"]);
+
+        let function: ItemFn = syn::parse2(quote! {
+            pub fn model(
+                #[param(choices = [1, 2])] style: Style,
+            ) -> fj::Shape {
+                todo!()
+            }
+        })
+        .unwrap();
+        assert!(parse(&function, ModelAttribute::default()).is_err());
+    }
+
+    #[test]
+    fn recognize_parameter_types_by_path() {
+        let parameter_types = [
+            quote! { u64 },
+            quote! { std::primitive::u64 },
+            quote! { ::core::primitive::f32 },
+            quote! { std::string::String },
+            quote! { [std::primitive::f64; 2] },
+        ];
+        let other_types = [
+            quote! { units::f64 },
+            quote! { my::String },
+            quote! { String<Utf8> },
+            quote! { std::primitive::Length },
+            quote! { [Length; 3] },
+        ];
+
+        for ty in parameter_types {
+            let ty: Type = syn::parse2(ty).unwrap();
+            assert!(is_parameter_type(&ty), "{}", ty.to_token_stream());
+        }
+        for ty in other_types {
+            let ty: Type = syn::parse2(ty).unwrap();
+            assert!(!is_parameter_type(&ty), "{}", ty.to_token_stream());
+        }
+    }
+
+    #[test]
+    fn parse_presets() {
+        let function: ItemFn = syn::parse2(quote! {
+            pub fn model(
+                width: f64,
+                offset: [f64; 2],
+                style: String,
+            ) -> fj::Shape {
+                todo!()
+            }
+        })
+        .unwrap();
+
+        let attribute: ModelAttribute = syn::parse2(quote! {
+            units = Inch,
+            presets(
+                small(width = 1, offset = [-1.5, 2.0], style = "flat"),
+                large(width = 10.0),
+            )
+        })
+        .unwrap();
+        let init = parse(&function, attribute).unwrap();
+
+        assert_eq!(init.units.unwrap().to_string(), "Inch");
+        let presets: Vec<_> = init
+            .model
+            .metadata
+            .presets
+            .iter()
+            .map(|preset| {
+                let values: Vec<_> = preset
+                    .values
+                    .iter()
+                    .map(|(argument, value)| format!("{argument}={value}"))
+                    .collect();
+                (preset.name.to_string(), values)
+            })
+            .collect();
+        assert_eq!(
+            presets,
+            [
+                (
+                    "small".to_string(),
+                    vec![
+                        "width=1".to_string(),
+                        "offset=[-1.5, 2.0]".to_string(),
+                        "style=flat".to_string(),
+                    ]
+                ),
+                ("large".to_string(), vec!["width=10.0".to_string()]),
+            ]
+        );
+
+        let parse_with = |attribute| -> syn::Result<Initializer> {
+            parse(&function, syn::parse2(attribute)?)
+        };
+        assert!(parse_with(quote! { presets(small(height = 1.0)) }).is_err());
+        assert!(parse_with(quote! {
+            presets(small(width = 1.0), small(width = 2.0))
+        })
+        .is_err());
+        assert!(
+            parse_with(quote! { presets(small(width = 2.0 * 3.0)) }).is_err()
+        );
+    }
 }
@@ -74,17 +74,22 @@ impl Vertices {
 
 impl From<&Mesh<fj_math::Point<3>>> for Vertices {
     fn from(mesh: &Mesh<fj_math::Point<3>>) -> Self {
+        // Edges where triangles meet at a larger angle than this are shaded
+        // as sharp edges.
+        const CREASE_ANGLE: f64 = 30.;
+
         let mut m = Mesh::new();
 
-        for triangle in mesh.triangles() {
-            let [a, b, c] = triangle.inner.points();
+        let normals = mesh.smooth_normals(CREASE_ANGLE.to_radians());
 
-            let normal = (b - a).cross(&(c - a)).normalize();
+        for (triangle, normals) in mesh.triangles().zip(normals) {
             let color = triangle.color;
 
-            m.push_vertex((a, normal, color));
-            m.push_vertex((b, normal, color));
-            m.push_vertex((c, normal, color));
+            for (point, normal) in
+                triangle.inner.points().into_iter().zip(normals)
+            {
+                m.push_vertex((point, normal, color));
+            }
         }
 
         let vertices = m
@@ -128,6 +133,15 @@ impl From<&DebugInfo> for Vertices {
             }
         }
 
+        for validation_issue in &debug_info.validation_issues {
+            let normal = [0.; 3];
+            let magenta = [1., 0., 1., 1.];
+
+            for &point in &validation_issue.points {
+                self_.push_cross(point, normal, magenta);
+            }
+        }
+
         self_
     }
 }
@@ -79,6 +79,11 @@ pub fn run(
 
                         match shape_processor.process(&evaluation.shape) {
                             Ok(shape) => {
+                                for issue in &shape.debug_info.validation_issues
+                                {
+                                    status.update_status(&issue.message);
+                                }
+
                                 viewer.handle_shape_update(shape);
                             }
                             Err(err) => {
@@ -101,6 +106,18 @@ pub fn run(
                         status.update_status("Model processed.");
                     }
 
+                    ModelEvent::Cancelled => {
+                        status.update_status(
+                            "Model changed again. Cancelled evaluation.",
+                        );
+                    }
+
+                    ModelEvent::TimedOut(timeout) => {
+                        status.update_status(&format!(
+                            "Model evaluation timed out after {timeout:?}."
+                        ));
+                    }
+
                     ModelEvent::Error(err) => {
                         status.update_status(&err.to_string());
                     }
@@ -7,7 +7,7 @@ use std::{
     ptr::NonNull,
 };
 
-use crate::models::Error;
+use crate::models::{Error, ModelPanic};
 
 /// A FFI-safe version of `Vec<T>`.
 #[repr(C)]
@@ -206,6 +206,29 @@ impl Deref for String {
     }
 }
 
+#[cfg(feature = "serde")]
+impl serde::ser::Serialize for String {
+    fn serialize<S>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::ser::Serializer,
+    {
+        self.deref().serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::de::Deserialize<'de> for String {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        Ok(std::string::String::deserialize(deserializer)?.into())
+    }
+}
+
 /// A version of `Result` that is `#[repr(C)]`.
 #[must_use]
 #[repr(C)]
@@ -333,6 +356,35 @@ impl Deref for StringSlice {
 #[repr(C)]
 pub struct BoxedError {
     msg: String,
+    panic: Option<PanicDetails>,
+}
+
+/// The details of a [`ModelPanic`], which are not part of its message.
+#[derive(Debug)]
+#[repr(C)]
+struct PanicDetails {
+    location: Option<String>,
+    backtrace: String,
+}
+
+impl BoxedError {
+    /// Convert the FFI-safe error back into an error, restoring panics.
+    ///
+    /// Errors that were created from a [`ModelPanic`] are converted back into
+    /// one, so the host can tell them apart from other errors.
+    pub fn into_error(self) -> Error {
+        match self.panic {
+            Option::Some(PanicDetails {
+                location,
+                backtrace,
+            }) => Box::new(ModelPanic {
+                message: self.msg.into(),
+                location: location.map(Into::into).into(),
+                backtrace: backtrace.into(),
+            }),
+            Option::None => Box::new(self),
+        }
+    }
 }
 
 impl Display for BoxedError {
@@ -345,6 +397,11 @@ impl std::error::Error for BoxedError {}
 
 impl From<Error> for BoxedError {
     fn from(err: Error) -> Self {
+        let err = match err.downcast::<ModelPanic>() {
+            Ok(panic) => return (*panic).into(),
+            Err(err) => err,
+        };
+
         // Open question: is it worth capturing the message from each source
         // error, too? We could have some sort of `sources: Vec<Source>` field
         // where `Source` is a private wrapper around String that implements
@@ -352,11 +409,24 @@ impl From<Error> for BoxedError {
         // particular error type, but they won't be able to downcast to it.
         BoxedError {
             msg: err.to_string().into(),
+            panic: Option::None,
         }
     }
 }
 
-#[derive(Debug, Clone)]
+impl From<ModelPanic> for BoxedError {
+    fn from(panic: ModelPanic) -> Self {
+        BoxedError {
+            msg: panic.message.into(),
+            panic: Option::Some(PanicDetails {
+                location: panic.location.into(),
+                backtrace: panic.backtrace.into(),
+            }),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 #[repr(C)]
 pub enum Option<T> {
     Some(T),
@@ -364,6 +434,13 @@ pub enum Option<T> {
 }
 
 impl<T> Option<T> {
+    pub fn as_ref(&self) -> Option<&T> {
+        match self {
+            Option::Some(value) => Option::Some(value),
+            Option::None => Option::None,
+        }
+    }
+
     pub fn map<T2>(self, func: impl FnOnce(T) -> T2) -> Option<T2> {
         match self {
             Option::Some(value) => Option::Some(func(value)),
@@ -392,3 +469,32 @@ impl<T> From<Option<T>> for std::option::Option<T> {
         }
     }
 }
+
+#[cfg(feature = "serde")]
+impl<T> serde::ser::Serialize for Option<T>
+where
+    T: serde::ser::Serialize,
+{
+    fn serialize<S>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::ser::Serializer,
+    {
+        std::option::Option::<&T>::from(self.as_ref()).serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T> serde::de::Deserialize<'de> for Option<T>
+where
+    T: serde::de::Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        Ok(std::option::Option::<T>::deserialize(deserializer)?.into())
+    }
+}
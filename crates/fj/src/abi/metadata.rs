@@ -6,6 +6,7 @@ pub struct ModelMetadata {
     name: ffi_safe::String,
     description: ffi_safe::Option<ffi_safe::String>,
     arguments: ffi_safe::Vec<ArgumentMetadata>,
+    presets: ffi_safe::Vec<PresetMetadata>,
 }
 
 impl From<ModelMetadata> for crate::models::ModelMetadata {
@@ -14,12 +15,14 @@ impl From<ModelMetadata> for crate::models::ModelMetadata {
             name,
             description,
             arguments,
+            presets,
         } = m;
 
         crate::models::ModelMetadata {
             name: name.into(),
             description: description.map(Into::into).into(),
             arguments: arguments.iter().cloned().map(|a| a.into()).collect(),
+            presets: presets.iter().cloned().map(|p| p.into()).collect(),
         }
     }
 }
@@ -30,12 +33,14 @@ impl From<crate::models::ModelMetadata> for ModelMetadata {
             name,
             description,
             arguments,
+            presets,
         } = m;
 
         ModelMetadata {
             name: name.into(),
             description: description.into(),
             arguments: arguments.into_iter().map(Into::into).collect(),
+            presets: presets.into_iter().map(Into::into).collect(),
         }
     }
 }
@@ -50,6 +55,7 @@ pub struct Metadata {
     homepage: ffi_safe::Option<ffi_safe::String>,
     repository: ffi_safe::Option<ffi_safe::String>,
     license: ffi_safe::Option<ffi_safe::String>,
+    units: crate::models::LengthUnit,
 }
 
 impl From<Metadata> for crate::models::Metadata {
@@ -62,6 +68,7 @@ impl From<Metadata> for crate::models::Metadata {
             homepage,
             repository,
             license,
+            units,
         } = m;
 
         crate::models::Metadata {
@@ -72,6 +79,7 @@ impl From<Metadata> for crate::models::Metadata {
             homepage: homepage.map(Into::into).into(),
             repository: repository.map(Into::into).into(),
             license: license.map(Into::into).into(),
+            units,
         }
     }
 }
@@ -86,6 +94,7 @@ impl From<crate::models::Metadata> for Metadata {
             homepage,
             repository,
             license,
+            units,
         } = m;
 
         Metadata {
@@ -96,6 +105,7 @@ impl From<crate::models::Metadata> for Metadata {
             homepage: homepage.into(),
             repository: repository.into(),
             license: license.into(),
+            units,
         }
     }
 }
@@ -106,6 +116,7 @@ pub struct ArgumentMetadata {
     name: ffi_safe::String,
     description: ffi_safe::Option<ffi_safe::String>,
     default_value: ffi_safe::Option<ffi_safe::String>,
+    parameter_type: ParameterType,
 }
 
 impl From<crate::models::ArgumentMetadata> for ArgumentMetadata {
@@ -114,12 +125,14 @@ impl From<crate::models::ArgumentMetadata> for ArgumentMetadata {
             name,
             description,
             default_value,
+            parameter_type,
         } = meta;
 
         ArgumentMetadata {
             name: name.into(),
             description: description.into(),
             default_value: default_value.into(),
+            parameter_type: parameter_type.into(),
         }
     }
 }
@@ -130,12 +143,110 @@ impl From<ArgumentMetadata> for crate::models::ArgumentMetadata {
             name,
             description,
             default_value,
+            parameter_type,
         } = meta;
 
         crate::models::ArgumentMetadata {
             name: name.into(),
             description: description.map(Into::into).into(),
             default_value: default_value.map(Into::into).into(),
+            parameter_type: parameter_type.into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+#[repr(C)]
+pub struct PresetMetadata {
+    name: ffi_safe::String,
+    values: ffi_safe::Vec<PresetValue>,
+}
+
+impl From<crate::models::PresetMetadata> for PresetMetadata {
+    fn from(meta: crate::models::PresetMetadata) -> Self {
+        let crate::models::PresetMetadata { name, values } = meta;
+
+        PresetMetadata {
+            name: name.into(),
+            values: values
+                .into_iter()
+                .map(|(argument, value)| PresetValue {
+                    argument: argument.into(),
+                    value: value.into(),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<PresetMetadata> for crate::models::PresetMetadata {
+    fn from(meta: PresetMetadata) -> Self {
+        let PresetMetadata { name, values } = meta;
+
+        crate::models::PresetMetadata {
+            name: name.into(),
+            values: values
+                .iter()
+                .cloned()
+                .map(|PresetValue { argument, value }| {
+                    (argument.into(), value.into())
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+#[repr(C)]
+pub(crate) struct PresetValue {
+    argument: ffi_safe::String,
+    value: ffi_safe::String,
+}
+
+#[derive(Debug, Clone)]
+#[repr(C)]
+pub(crate) enum ParameterType {
+    Integer,
+    Float,
+    Bool,
+    String,
+    Choice(ffi_safe::Vec<ffi_safe::String>),
+    Vector2,
+    Vector3,
+}
+
+impl From<crate::models::ParameterType> for ParameterType {
+    fn from(ty: crate::models::ParameterType) -> Self {
+        use crate::models::ParameterType as T;
+
+        match ty {
+            T::Integer => ParameterType::Integer,
+            T::Float => ParameterType::Float,
+            T::Bool => ParameterType::Bool,
+            T::String => ParameterType::String,
+            T::Choice(choices) => ParameterType::Choice(
+                choices.into_iter().map(Into::into).collect(),
+            ),
+            T::Vector2 => ParameterType::Vector2,
+            T::Vector3 => ParameterType::Vector3,
+        }
+    }
+}
+
+impl From<ParameterType> for crate::models::ParameterType {
+    fn from(ty: ParameterType) -> Self {
+        use crate::models::ParameterType as T;
+
+        match ty {
+            ParameterType::Integer => T::Integer,
+            ParameterType::Float => T::Float,
+            ParameterType::Bool => T::Bool,
+            ParameterType::String => T::String,
+            ParameterType::Choice(choices) => {
+                T::Choice(choices.iter().cloned().map(Into::into).collect())
+            }
+            ParameterType::Vector2 => T::Vector2,
+            ParameterType::Vector3 => T::Vector3,
         }
     }
 }
@@ -43,7 +43,15 @@ mod host;
 mod metadata;
 mod model;
 
-use std::any::Any;
+use std::{
+    any::Any,
+    backtrace::Backtrace,
+    cell::{Cell, RefCell},
+    panic::{self, AssertUnwindSafe},
+    sync::Once,
+};
+
+use crate::models::ModelPanic;
 
 pub use self::{
     context::Context,
@@ -93,9 +101,10 @@ macro_rules! register_model {
                 $crate::models::Error,
             > = $init;
 
-            match init(&mut *host) {
-                Ok(meta) => $crate::abi::InitResult::Ok(meta.into()),
-                Err(e) => $crate::abi::InitResult::Err(e.into()),
+            match $crate::abi::catch_panic(|| init(&mut *host)) {
+                Ok(Ok(meta)) => $crate::abi::InitResult::Ok(meta.into()),
+                Ok(Err(e)) => $crate::abi::InitResult::Err(e.into()),
+                Err(panic) => $crate::abi::InitResult::Err(panic.into()),
             }
         }
     };
@@ -116,17 +125,92 @@ pub type ShapeResult = ffi_safe::Result<crate::Shape, ffi_safe::BoxedError>;
 ///
 pub const INIT_FUNCTION_NAME: &str = "fj_model_init";
 
+thread_local! {
+    /// Whether panics on this thread are currently caught by [`catch_panic`]
+    static CATCHING: Cell<bool> = const { Cell::new(false) };
+
+    /// Location and backtrace of the last panic caught on this thread
+    static CAUGHT: RefCell<Option<(Option<String>, String)>> =
+        const { RefCell::new(None) };
+}
+
+/// Run model code, turning a panic into a [`ModelPanic`].
+///
+/// Panics must not unwind across the FFI boundary. The panic hook captures the
+/// location and backtrace of panics that are caught here, and leaves all other
+/// panics to the hook that was set before.
+pub fn catch_panic<T>(f: impl FnOnce() -> T) -> Result<T, ModelPanic> {
+    static INSTALL_HOOK: Once = Once::new();
+    INSTALL_HOOK.call_once(|| {
+        let previous_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !CATCHING.with(Cell::get) {
+                previous_hook(info);
+                return;
+            }
+
+            let location = info.location().map(ToString::to_string);
+            let backtrace = Backtrace::force_capture().to_string();
+            CAUGHT.with(|caught| {
+                *caught.borrow_mut() = Some((location, backtrace));
+            });
+        }));
+    });
+
+    let was_catching = CATCHING.with(|catching| catching.replace(true));
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    CATCHING.with(|catching| catching.set(was_catching));
+
+    result.map_err(|payload| {
+        let (location, backtrace) = CAUGHT
+            .with(|caught| caught.borrow_mut().take())
+            .unwrap_or_default();
+
+        ModelPanic {
+            message: panic_message(&*payload).to_owned(),
+            location,
+            backtrace,
+        }
+    })
+}
+
 fn on_panic(payload: Box<dyn Any + Send>) -> ! {
-    let msg: &str =
-        if let Some(s) = payload.downcast_ref::<std::string::String>() {
-            s.as_str()
-        } else if let Some(s) = payload.downcast_ref::<&str>() {
-            s
-        } else {
-            "A panic occurred"
-        };
-
-    eprintln!("{msg}");
+    eprintln!("{}", panic_message(&*payload));
     // It's not ideal, but panicking across the FFI boundary is UB.
     std::process::abort();
 }
+
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    if let Some(s) = payload.downcast_ref::<String>() {
+        s.as_str()
+    } else if let Some(s) = payload.downcast_ref::<&str>() {
+        s
+    } else {
+        "A panic occurred"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::models::{Error, ModelPanic};
+
+    use super::{catch_panic, ffi_safe::BoxedError};
+
+    #[test]
+    fn panic_becomes_error() {
+        assert_eq!(catch_panic(|| 1), Ok(1));
+
+        let panic = catch_panic(|| panic!("model is broken")).unwrap_err();
+        assert_eq!(panic.message, "model is broken");
+        assert!(panic.location.as_deref().unwrap().contains("mod.rs"));
+        assert!(panic.backtrace.contains("panic_becomes_error"));
+
+        // The panic survives the trip across the FFI boundary.
+        let err = BoxedError::from(panic.clone()).into_error();
+        assert_eq!(err.downcast_ref::<ModelPanic>(), Some(&panic));
+
+        let err: Error = "not a panic".into();
+        let err = BoxedError::from(err).into_error();
+        assert!(err.downcast_ref::<ModelPanic>().is_none());
+    }
+}
@@ -26,7 +26,7 @@ impl crate::models::Model for Model {
 
         match result {
             super::ffi_safe::Result::Ok(shape) => Ok(shape),
-            super::ffi_safe::Result::Err(err) => Err(err.into()),
+            super::ffi_safe::Result::Err(err) => Err(err.into_error()),
         }
     }
 
@@ -56,12 +56,10 @@ impl From<Box<dyn crate::models::Model>> for Model {
         ) -> ShapeResult {
             let model = &*(user_data as *mut Box<dyn crate::models::Model>);
 
-            match std::panic::catch_unwind(AssertUnwindSafe(|| {
-                model.shape(&ctx)
-            })) {
+            match crate::abi::catch_panic(|| model.shape(&ctx)) {
                 Ok(Ok(shape)) => ShapeResult::Ok(shape),
                 Ok(Err(err)) => ShapeResult::Err(err.into()),
-                Err(payload) => crate::abi::on_panic(payload),
+                Err(panic) => ShapeResult::Err(panic.into()),
             }
         }
 
@@ -0,0 +1,832 @@
+//! Import of 2-dimensional shapes from DXF files
+//!
+//! Reads `LINE`, `ARC`, `CIRCLE`, and `LWPOLYLINE` entities from the
+//! `ENTITIES` section, and converts them into [`Shape2d`]s. Lines and arcs
+//! don't need to be in any particular order. Entities whose end points meet are
+//! joined, until they form closed outlines.
+//!
+//! Outlines that are contained in another outline become holes, which are
+//! subtracted from the outline around them, using [`Difference2d`]. Outlines
+//! within those holes are imported as separate shapes.
+//!
+//! # Examples
+//!
+//! ``` rust
+//! use fj::syntax::*;
+//!
+//! # let dxf = "0\nSECTION\n2\nENTITIES\n0\nCIRCLE\n10\n0\n20\n0\n40\n5\n\
+//! #     0\nENDSEC\n0\nEOF\n";
+//! let shapes = fj::dxf::import(dxf, &fj::dxf::ImportOptions::default())?;
+//! let plate = shapes[0].sweep([0., 0., 2.]);
+//! # Ok::<(), fj::dxf::DxfError>(())
+//! ```
+//!
+//! # Limitations
+//!
+//! Arcs are approximated with line segments, except for full circles, which
+//! become [`Circle`](crate::Circle)s.
+//!
+//! Blocks and their references (`INSERT` entities) are ignored, as are all
+//! other entity types. Entities must be located in the xy-plane.
+
+use std::{error::Error, f64::consts::PI, fmt, fs, io, path::Path};
+
+use fj_math::{nest_polygons, signed_area};
+
+use crate::{Circle, Difference2d, Shape2d, Sketch};
+
+/// Options for importing a DXF file
+///
+/// See [`import`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct ImportOptions {
+    /// The maximum distance between an arc and its approximation
+    ///
+    /// End points of entities that are closer to each other than this are
+    /// considered to be connected.
+    ///
+    /// This is measured in millimeters, after conversion from the unit of the
+    /// file.
+    pub tolerance: f64,
+}
+
+impl Default for ImportOptions {
+    fn default() -> Self {
+        Self { tolerance: 0.01 }
+    }
+}
+
+/// Import all shapes from a DXF document
+///
+/// Coordinates are converted to millimeters, according to the `$INSUNITS`
+/// header variable. If the document doesn't specify a unit, millimeters are
+/// assumed.
+pub fn import(
+    dxf: &str,
+    options: &ImportOptions,
+) -> Result<Vec<Shape2d>, DxfError> {
+    let pairs = group_pairs(dxf)?;
+
+    let mut scale = 1.;
+    let mut builder = ContourBuilder::new(options.tolerance);
+
+    for section in sections(&pairs) {
+        match section.name {
+            "HEADER" => {
+                if let Some(units) = header_variable(section.pairs, "$INSUNITS")
+                {
+                    scale = millimeters_per_unit(units)?;
+                }
+            }
+            "ENTITIES" => {
+                for entity in entities(section.pairs) {
+                    builder.entity(&entity)?;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut shapes = Vec::new();
+
+    for (exterior, holes) in nest(builder.finish()?) {
+        let mut shape: Shape2d = exterior.to_sketch(scale).into();
+
+        for hole in holes {
+            shape = Difference2d::from_shapes([
+                shape,
+                hole.to_sketch(scale).into(),
+            ])
+            .into();
+        }
+
+        shapes.push(shape);
+    }
+
+    Ok(shapes)
+}
+
+/// Import all shapes from a DXF file
+///
+/// See [`import`].
+pub fn import_file(
+    path: impl AsRef<Path>,
+    options: &ImportOptions,
+) -> Result<Vec<Shape2d>, DxfError> {
+    let dxf = fs::read_to_string(path)?;
+    import(&dxf, options)
+}
+
+/// An error that can occur while importing a DXF file
+#[derive(Debug)]
+pub enum DxfError {
+    /// The file could not be read
+    Io(io::Error),
+
+    /// A group code or value is invalid
+    InvalidValue {
+        /// The line of the invalid group code or value
+        line: usize,
+
+        /// The invalid group code or value
+        value: String,
+    },
+
+    /// An entity is missing a required group code
+    MissingValue {
+        /// The type of the entity
+        entity: String,
+
+        /// The missing group code
+        code: i32,
+    },
+
+    /// An entity is not located in the xy-plane
+    NotPlanar {
+        /// The type of the entity
+        entity: String,
+    },
+
+    /// Entities form an outline that is not closed
+    OpenOutline {
+        /// The end point of the outline that doesn't connect to anything
+        point: [f64; 2],
+    },
+}
+
+impl fmt::Display for DxfError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "Error reading DXF file: {err}"),
+            Self::InvalidValue { line, value } => {
+                write!(f, "Invalid value in DXF file, line {line}: {value}")
+            }
+            Self::MissingValue { entity, code } => {
+                write!(f, "DXF entity `{entity}` is missing group code {code}")
+            }
+            Self::NotPlanar { entity } => {
+                write!(f, "DXF entity `{entity}` is not in the xy-plane")
+            }
+            Self::OpenOutline { point: [x, y] } => {
+                write!(f, "Outline in DXF file is not closed at ({x}, {y})")
+            }
+        }
+    }
+}
+
+impl Error for DxfError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for DxfError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+/// A pair of group code and value, the basic building block of a DXF file
+struct GroupPair<'r> {
+    code: i32,
+    value: &'r str,
+    line: usize,
+}
+
+impl GroupPair<'_> {
+    fn number(&self) -> Result<f64, DxfError> {
+        self.value.parse().map_err(|_| DxfError::InvalidValue {
+            line: self.line,
+            value: self.value.to_owned(),
+        })
+    }
+
+    fn integer(&self) -> Result<i64, DxfError> {
+        self.value.parse().map_err(|_| DxfError::InvalidValue {
+            line: self.line,
+            value: self.value.to_owned(),
+        })
+    }
+}
+
+fn group_pairs(dxf: &str) -> Result<Vec<GroupPair<'_>>, DxfError> {
+    let mut lines = dxf.lines().enumerate();
+    let mut pairs = Vec::new();
+
+    while let Some((i, code)) = lines.next() {
+        let code = code.trim();
+        if code.is_empty() {
+            continue;
+        }
+
+        let code = code.parse().map_err(|_| DxfError::InvalidValue {
+            line: i + 1,
+            value: code.to_owned(),
+        })?;
+        let (j, value) = lines.next().unwrap_or((i + 1, ""));
+
+        pairs.push(GroupPair {
+            code,
+            value: value.trim(),
+            line: j + 1,
+        });
+    }
+
+    Ok(pairs)
+}
+
+struct Section<'a, 'r> {
+    name: &'r str,
+    pairs: &'a [GroupPair<'r>],
+}
+
+fn sections<'a, 'r>(
+    pairs: &'a [GroupPair<'r>],
+) -> impl Iterator<Item = Section<'a, 'r>> {
+    let mut rest = pairs;
+
+    std::iter::from_fn(move || {
+        let start = rest.windows(2).position(|pairs| {
+            pairs[0].code == 0
+                && pairs[0].value == "SECTION"
+                && pairs[1].code == 2
+        })?;
+        let name = rest[start + 1].value;
+        let body = &rest[start + 2..];
+
+        let end = body
+            .iter()
+            .position(|pair| pair.code == 0 && pair.value == "ENDSEC")
+            .unwrap_or(body.len());
+
+        rest = &body[end..];
+        Some(Section {
+            name,
+            pairs: &body[..end],
+        })
+    })
+}
+
+fn header_variable<'a, 'r>(
+    pairs: &'a [GroupPair<'r>],
+    name: &str,
+) -> Option<&'a GroupPair<'r>> {
+    let position = pairs
+        .iter()
+        .position(|pair| pair.code == 9 && pair.value == name)?;
+    pairs.get(position + 1)
+}
+
+fn millimeters_per_unit(units: &GroupPair) -> Result<f64, DxfError> {
+    let scale = match units.integer()? {
+        // Unitless. Millimeters are the best guess.
+        0 => 1.,
+        1 => 25.4,
+        2 => 304.8,
+        3 => 1_609_344.,
+        4 => 1.,
+        5 => 10.,
+        6 => 1000.,
+        7 => 1_000_000.,
+        8 => 25.4e-6,
+        9 => 25.4e-3,
+        10 => 914.4,
+        13 => 1e-3,
+        14 => 100.,
+        _ => {
+            return Err(DxfError::InvalidValue {
+                line: units.line,
+                value: units.value.to_owned(),
+            })
+        }
+    };
+
+    Ok(scale)
+}
+
+/// An entity, made up of its type and the group pairs that follow it
+struct Entity<'a, 'r> {
+    kind: &'r str,
+    pairs: &'a [GroupPair<'r>],
+}
+
+impl Entity<'_, '_> {
+    fn number(&self, code: i32) -> Result<f64, DxfError> {
+        self.optional_number(code)?
+            .ok_or_else(|| DxfError::MissingValue {
+                entity: self.kind.to_owned(),
+                code,
+            })
+    }
+
+    fn optional_number(&self, code: i32) -> Result<Option<f64>, DxfError> {
+        self.pairs
+            .iter()
+            .find(|pair| pair.code == code)
+            .map(GroupPair::number)
+            .transpose()
+    }
+
+    /// The sign of the x-axis of the entity's coordinate system
+    ///
+    /// Entities that are drawn in the xy-plane, but viewed from below, have
+    /// an extrusion direction of `-z`, which mirrors their x-axis.
+    fn x_sign(&self) -> Result<f64, DxfError> {
+        let direction = [
+            self.optional_number(210)?.unwrap_or(0.),
+            self.optional_number(220)?.unwrap_or(0.),
+            self.optional_number(230)?.unwrap_or(1.),
+        ];
+
+        match direction {
+            [x, y, z] if x.abs() < 1e-9 && y.abs() < 1e-9 && z > 0. => Ok(1.),
+            [x, y, z] if x.abs() < 1e-9 && y.abs() < 1e-9 && z < 0. => Ok(-1.),
+            _ => Err(DxfError::NotPlanar {
+                entity: self.kind.to_owned(),
+            }),
+        }
+    }
+}
+
+fn entities<'a, 'r>(
+    pairs: &'a [GroupPair<'r>],
+) -> impl Iterator<Item = Entity<'a, 'r>> {
+    let mut rest = pairs;
+
+    std::iter::from_fn(move || {
+        let start = rest.iter().position(|pair| pair.code == 0)?;
+        let body = &rest[start + 1..];
+        let end = body
+            .iter()
+            .position(|pair| pair.code == 0)
+            .unwrap_or(body.len());
+
+        let entity = Entity {
+            kind: rest[start].value,
+            pairs: &body[..end],
+        };
+        rest = &body[end..];
+
+        Some(entity)
+    })
+}
+
+/// A closed outline
+#[derive(Clone)]
+struct Contour {
+    points: Vec<[f64; 2]>,
+
+    /// The center and radius, if the outline is a circle
+    circle: Option<([f64; 2], f64)>,
+}
+
+impl Contour {
+    fn to_sketch(&self, scale: f64) -> Sketch {
+        match self.circle {
+            Some((center, radius)) => Sketch::from_circle(
+                Circle::from_radius(radius * scale)
+                    .with_center(center.map(|c| c * scale)),
+            ),
+            None => Sketch::from_points(
+                self.points
+                    .iter()
+                    .map(|point| point.map(|c| c * scale))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+/// Collects entities, and joins them into closed outlines
+struct ContourBuilder {
+    tolerance: f64,
+    contours: Vec<Contour>,
+    chains: Vec<Vec<[f64; 2]>>,
+}
+
+impl ContourBuilder {
+    fn new(tolerance: f64) -> Self {
+        Self {
+            tolerance,
+            contours: Vec::new(),
+            chains: Vec::new(),
+        }
+    }
+
+    fn entity(&mut self, entity: &Entity) -> Result<(), DxfError> {
+        match entity.kind {
+            "LINE" => {
+                let a = [entity.number(10)?, entity.number(20)?];
+                let b = [entity.number(11)?, entity.number(21)?];
+
+                self.chains.push(vec![a, b]);
+            }
+            "ARC" => {
+                let x_sign = entity.x_sign()?;
+                let center = [entity.number(10)? * x_sign, entity.number(20)?];
+                let radius = entity.number(40)?;
+                let [mut start, mut end] = [
+                    entity.number(50)?.to_radians(),
+                    entity.number(51)?.to_radians(),
+                ];
+
+                if end <= start {
+                    end += 2. * PI;
+                }
+                if x_sign < 0. {
+                    // Mirroring the x-axis reverses the direction of the arc.
+                    [start, end] = [PI - end, PI - start];
+                }
+
+                let mut chain = vec![point_on_circle(center, radius, start)];
+                self.arc(&mut chain, center, radius, [start, end]);
+                self.chains.push(chain);
+            }
+            "CIRCLE" => {
+                let x_sign = entity.x_sign()?;
+                let center = [entity.number(10)? * x_sign, entity.number(20)?];
+                let radius = entity.number(40)?;
+
+                let mut points = vec![point_on_circle(center, radius, 0.)];
+                self.arc(&mut points, center, radius, [0., 2. * PI]);
+                points.pop();
+
+                self.contours.push(Contour {
+                    points,
+                    circle: Some((center, radius)),
+                });
+            }
+            "LWPOLYLINE" => self.polyline(entity)?,
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    fn polyline(&mut self, entity: &Entity) -> Result<(), DxfError> {
+        let x_sign = entity.x_sign()?;
+
+        let mut closed = false;
+        let mut vertices: Vec<([f64; 2], f64)> = Vec::new();
+
+        for pair in entity.pairs {
+            match pair.code {
+                70 => closed = pair.integer()? & 1 == 1,
+                10 => vertices.push(([pair.number()? * x_sign, 0.], 0.)),
+                20 | 42 => {
+                    let (point, bulge) =
+                        vertices.last_mut().ok_or_else(|| {
+                            DxfError::InvalidValue {
+                                line: pair.line,
+                                value: pair.value.to_owned(),
+                            }
+                        })?;
+
+                    if pair.code == 20 {
+                        point[1] = pair.number()?;
+                    } else {
+                        // Mirroring the x-axis reverses the direction of arcs.
+                        *bulge = pair.number()? * x_sign;
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let first = match vertices.first() {
+            Some(&(first, _)) => first,
+            None => return Ok(()),
+        };
+
+        let mut points = vec![first];
+        let num_segments = if closed {
+            vertices.len()
+        } else {
+            vertices.len() - 1
+        };
+
+        for i in 0..num_segments {
+            let (a, bulge) = vertices[i];
+            let (b, _) = vertices[(i + 1) % vertices.len()];
+
+            if bulge == 0. {
+                points.push(b);
+            } else {
+                self.bulge(&mut points, [a, b], bulge);
+            }
+        }
+
+        if closed {
+            points.pop();
+            self.contours.push(Contour {
+                points,
+                circle: None,
+            });
+        } else {
+            self.chains.push(points);
+        }
+
+        Ok(())
+    }
+
+    /// Add the arc between two points, as defined by a bulge
+    ///
+    /// The bulge is the tangent of a quarter of the arc's angle. Positive
+    /// values mean the arc goes counter-clockwise.
+    fn bulge(
+        &self,
+        points: &mut Vec<[f64; 2]>,
+        [a, b]: [[f64; 2]; 2],
+        bulge: f64,
+    ) {
+        let angle = 4. * bulge.atan();
+
+        let chord = distance(a, b);
+        let radius = chord / (2. * (angle / 2.).sin()).abs();
+
+        // The center is on the perpendicular bisector of the chord, at the
+        // distance that makes the arc's angle come out right.
+        let middle = [(a[0] + b[0]) / 2., (a[1] + b[1]) / 2.];
+        let normal = [-(b[1] - a[1]) / chord, (b[0] - a[0]) / chord];
+        let offset = chord / 2. / (angle / 2.).tan();
+        let center = [
+            middle[0] + normal[0] * offset,
+            middle[1] + normal[1] * offset,
+        ];
+
+        let start = (a[1] - center[1]).atan2(a[0] - center[0]);
+        self.arc(points, center, radius, [start, start + angle]);
+
+        // Make sure the polyline vertex is hit exactly, so it can be joined
+        // with other entities.
+        if let Some(last) = points.last_mut() {
+            *last = b;
+        }
+    }
+
+    /// Add the points of an arc, excluding its start point
+    fn arc(
+        &self,
+        points: &mut Vec<[f64; 2]>,
+        center: [f64; 2],
+        radius: f64,
+        [start, end]: [f64; 2],
+    ) {
+        let angle = end - start;
+
+        let max_angle = if self.tolerance < radius {
+            2. * (1. - self.tolerance / radius).acos()
+        } else {
+            PI / 2.
+        };
+        let num_segments = (angle.abs() / max_angle).ceil().max(1.) as usize;
+
+        for i in 1..=num_segments {
+            let t = start + angle * i as f64 / num_segments as f64;
+            points.push(point_on_circle(center, radius, t));
+        }
+    }
+
+    fn finish(mut self) -> Result<Vec<Contour>, DxfError> {
+        while let Some(mut points) = self.chains.pop() {
+            loop {
+                let start = points[0];
+                let end = points[points.len() - 1];
+
+                if points.len() > 2 && distance(start, end) <= self.tolerance {
+                    points.pop();
+                    break;
+                }
+
+                let next = self.chains.iter().enumerate().find_map(|(i, c)| {
+                    if distance(c[0], end) <= self.tolerance {
+                        Some((i, false))
+                    } else if distance(c[c.len() - 1], end) <= self.tolerance {
+                        Some((i, true))
+                    } else {
+                        None
+                    }
+                });
+
+                let (i, reversed) = match next {
+                    Some(next) => next,
+                    None => return Err(DxfError::OpenOutline { point: end }),
+                };
+
+                let mut next = self.chains.swap_remove(i);
+                if reversed {
+                    next.reverse();
+                }
+                points.extend(next.into_iter().skip(1));
+            }
+
+            self.contours.push(Contour {
+                points,
+                circle: None,
+            });
+        }
+
+        Ok(self.contours)
+    }
+}
+
+fn point_on_circle([x, y]: [f64; 2], radius: f64, angle: f64) -> [f64; 2] {
+    let (sin, cos) = angle.sin_cos();
+    [x + radius * cos, y + radius * sin]
+}
+
+/// Sort outlines into exteriors and the holes within them
+///
+/// See [`nest_polygons`]. All returned outlines are counter-clockwise.
+fn nest(contours: Vec<Contour>) -> Vec<(Contour, Vec<Contour>)> {
+    let points = contours
+        .iter()
+        .map(|contour| contour.points.as_slice())
+        .collect::<Vec<_>>();
+    let nested = nest_polygons(&points);
+
+    nested
+        .into_iter()
+        .map(|(exterior, holes)| {
+            let holes = holes
+                .into_iter()
+                .map(|hole| ccw(contours[hole].clone()))
+                .collect();
+            (ccw(contours[exterior].clone()), holes)
+        })
+        .collect()
+}
+
+fn ccw(mut contour: Contour) -> Contour {
+    if signed_area(&contour.points).is_negative() {
+        contour.points.reverse();
+    }
+    contour
+}
+
+fn distance([ax, ay]: [f64; 2], [bx, by]: [f64; 2]) -> f64 {
+    ((bx - ax).powi(2) + (by - ay).powi(2)).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Chain, Shape2d};
+
+    use super::{import, DxfError, ImportOptions};
+
+    fn dxf(entities: &[(i32, &str)]) -> String {
+        let mut dxf = String::from("0\nSECTION\n2\nENTITIES\n");
+        for (code, value) in entities {
+            dxf.push_str(&format!("{code}\n{value}\n"));
+        }
+        dxf.push_str("0\nENDSEC\n0\nEOF\n");
+        dxf
+    }
+
+    fn line(
+        a: [&'static str; 2],
+        b: [&'static str; 2],
+    ) -> Vec<(i32, &'static str)> {
+        vec![(0, "LINE"), (10, a[0]), (20, a[1]), (11, b[0]), (21, b[1])]
+    }
+
+    #[test]
+    fn import_lines_in_any_order() -> Result<(), DxfError> {
+        let entities = [
+            line(["0", "0"], ["10", "0"]),
+            line(["0", "10"], ["10", "10"]),
+            line(["0", "10"], ["0", "0"]),
+            line(["10", "0"], ["10", "10"]),
+        ]
+        .concat();
+
+        let shapes = import(&dxf(&entities), &ImportOptions::default())?;
+
+        assert_eq!(shapes.len(), 1);
+        let points = match &shapes[0] {
+            Shape2d::Sketch(sketch) => match sketch.chain() {
+                Chain::PolyChain(chain) => chain.to_points(),
+                Chain::Circle(_) => unreachable!(),
+            },
+            shape => panic!("Expected sketch, got {shape:?}"),
+        };
+        assert_eq!(points.len(), 4);
+
+        Ok(())
+    }
+
+    #[test]
+    fn import_holes() -> Result<(), DxfError> {
+        let entities = [
+            (0, "LWPOLYLINE"),
+            (90, "4"),
+            (70, "1"),
+            (10, "-10"),
+            (20, "-10"),
+            (10, "10"),
+            (20, "-10"),
+            (10, "10"),
+            (20, "10"),
+            (10, "-10"),
+            (20, "10"),
+            (0, "CIRCLE"),
+            (10, "0"),
+            (20, "0"),
+            (40, "5"),
+            (0, "CIRCLE"),
+            (10, "0"),
+            (20, "0"),
+            (40, "2"),
+        ];
+
+        let shapes = import(&dxf(&entities), &ImportOptions::default())?;
+
+        // The small circle is within the hole made by the large one, so it
+        // becomes a separate shape.
+        assert_eq!(shapes.len(), 2);
+        assert!(shapes
+            .iter()
+            .any(|shape| matches!(shape, Shape2d::Difference(_))));
+        assert!(shapes.iter().any(|shape| matches!(
+            shape,
+            Shape2d::Sketch(sketch)
+                if matches!(sketch.chain(), Chain::Circle(_))
+        )));
+
+        Ok(())
+    }
+
+    #[test]
+    fn import_bulges() -> Result<(), DxfError> {
+        // A slot, made from two half circles with a radius of 5.
+        let entities = [
+            (0, "LWPOLYLINE"),
+            (70, "1"),
+            (10, "0"),
+            (20, "0"),
+            (10, "20"),
+            (20, "0"),
+            (42, "1"),
+            (10, "20"),
+            (20, "10"),
+            (10, "0"),
+            (20, "10"),
+            (42, "1"),
+        ];
+
+        let options = ImportOptions { tolerance: 0.001 };
+        let shapes = import(&dxf(&entities), &options)?;
+
+        let points = match &shapes[0] {
+            Shape2d::Sketch(sketch) => match sketch.chain() {
+                Chain::PolyChain(chain) => chain.to_points(),
+                Chain::Circle(_) => unreachable!(),
+            },
+            shape => panic!("Expected sketch, got {shape:?}"),
+        };
+
+        let max_x = points.iter().map(|[x, _]| *x).fold(f64::MIN, f64::max);
+        let min_x = points.iter().map(|[x, _]| *x).fold(f64::MAX, f64::min);
+        assert!((max_x - 25.).abs() < 0.001);
+        assert!((min_x + 5.).abs() < 0.001);
+
+        Ok(())
+    }
+
+    #[test]
+    fn import_open_outline() {
+        let entities = [
+            line(["0", "0"], ["10", "0"]),
+            line(["10", "0"], ["10", "10"]),
+        ]
+        .concat();
+
+        let result = import(&dxf(&entities), &ImportOptions::default());
+        assert!(matches!(result, Err(DxfError::OpenOutline { .. })));
+    }
+
+    #[test]
+    fn import_inches() -> Result<(), DxfError> {
+        let dxf = "0\nSECTION\n2\nHEADER\n9\n$INSUNITS\n70\n1\n0\nENDSEC\n\
+            0\nSECTION\n2\nENTITIES\n0\nCIRCLE\n10\n1\n20\n-2\n40\n1\n\
+            0\nENDSEC\n0\nEOF\n";
+
+        let shapes = import(dxf, &ImportOptions::default())?;
+
+        match &shapes[0] {
+            Shape2d::Sketch(sketch) => match sketch.chain() {
+                Chain::Circle(circle) => {
+                    assert_eq!(circle.radius(), 25.4);
+                    assert_eq!(circle.center(), [25.4, -50.8]);
+                }
+                Chain::PolyChain(_) => panic!("Expected circle"),
+            },
+            shape => panic!("Expected sketch, got {shape:?}"),
+        }
+
+        Ok(())
+    }
+}
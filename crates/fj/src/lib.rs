@@ -23,15 +23,26 @@ pub mod syntax;
 #[doc(hidden)]
 pub mod abi;
 mod angle;
+pub mod dxf;
 mod group;
 pub mod models;
+mod pattern;
 mod shape_2d;
+#[cfg(feature = "svg")]
+pub mod svg;
 mod sweep;
+mod text;
 mod transform;
 pub mod version;
 
 pub use self::{
-    angle::*, group::Group, shape_2d::*, sweep::Sweep, transform::Transform,
+    angle::*,
+    group::Group,
+    pattern::{CircularPattern, LinearPattern},
+    shape_2d::*,
+    sweep::Sweep,
+    text::{Alignment, Font, FontData, Text},
+    transform::{Mirror, Transform},
 };
 pub use fj_proc::*;
 
@@ -51,4 +62,10 @@ pub enum Shape {
 
     /// A transformed 3-dimensional shape
     Transform(Box<Transform>),
+
+    /// A circular pattern of a 3-dimensional shape
+    CircularPattern(Box<CircularPattern>),
+
+    /// A linear pattern of a 3-dimensional shape
+    LinearPattern(Box<LinearPattern>),
 }
@@ -1,16 +1,95 @@
+use crate::models::{
+    Error, Parameter, ParameterError, ParameterType, ParameterValue,
+};
+
 /// Contextual information passed to a [`Model`][crate::models::Model] when it
 /// is being initialized.
 pub trait Context {
     /// Get an argument that was passed to this model.
     fn get_argument(&self, name: &str) -> Option<&str>;
+
+    /// Get an argument that was passed to this model, parsed as the given type.
+    fn get_value(
+        &self,
+        name: &str,
+        parameter_type: &ParameterType,
+    ) -> Result<Option<ParameterValue>, ParameterError> {
+        self.get_argument(name)
+            .map(|value| parameter_type.parse(value))
+            .transpose()
+    }
+
+    /// Get an integer argument that was passed to this model.
+    fn get_integer(&self, name: &str) -> Result<Option<i64>, Error> {
+        get(self, name)
+    }
+
+    /// Get a number argument that was passed to this model.
+    fn get_float(&self, name: &str) -> Result<Option<f64>, Error> {
+        get(self, name)
+    }
+
+    /// Get a boolean argument that was passed to this model.
+    fn get_bool(&self, name: &str) -> Result<Option<bool>, Error> {
+        get(self, name)
+    }
+
+    /// Get a 2D vector argument that was passed to this model.
+    fn get_vector2(&self, name: &str) -> Result<Option<[f64; 2]>, Error> {
+        get(self, name)
+    }
+
+    /// Get a 3D vector argument that was passed to this model.
+    fn get_vector3(&self, name: &str) -> Result<Option<[f64; 3]>, Error> {
+        get(self, name)
+    }
+}
+
+fn get<T: Parameter>(
+    ctx: &(impl Context + ?Sized),
+    name: &str,
+) -> Result<Option<T>, Error> {
+    ctx.get_value(name, &T::parameter_type())?
+        .map(T::from_value)
+        .transpose()
 }
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashMap;
+
     use super::*;
 
+    struct Arguments(HashMap<&'static str, &'static str>);
+
+    impl Context for Arguments {
+        fn get_argument(&self, name: &str) -> Option<&str> {
+            self.0.get(name).copied()
+        }
+    }
+
     #[test]
     fn context_is_object_safe() {
         let _: &dyn Context;
     }
+
+    #[test]
+    fn typed_arguments() {
+        let ctx = Arguments(HashMap::from([
+            ("count", "3"),
+            ("scale", "0.5"),
+            ("hollow", "true"),
+            ("offset", "[1, 2, 3]"),
+        ]));
+        let ctx: &dyn Context = &ctx;
+
+        assert_eq!(ctx.get_integer("count").unwrap(), Some(3));
+        assert_eq!(ctx.get_float("scale").unwrap(), Some(0.5));
+        assert_eq!(ctx.get_bool("hollow").unwrap(), Some(true));
+        assert_eq!(ctx.get_vector3("offset").unwrap(), Some([1., 2., 3.]));
+        assert_eq!(ctx.get_vector2("missing").unwrap(), None);
+
+        assert!(ctx.get_integer("scale").is_err());
+        assert!(ctx.get_vector2("offset").is_err());
+    }
 }
@@ -1,6 +1,9 @@
+use super::{LengthUnit, ParameterType};
+
 /// Information about a particular module that can be used by the host for
 /// things like introspection and search.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Metadata {
     /// A short, human-friendly name used to identify this module.
     pub name: String,
@@ -27,6 +30,9 @@ pub struct Metadata {
     ///
     /// [spdx]: https://spdx.dev/spdx-specification-21-web-version/#h.jxpfx0ykyb60
     pub license: Option<String>,
+
+    /// The unit of length that the coordinates of the model are measured in.
+    pub units: LengthUnit,
 }
 
 impl Metadata {
@@ -49,6 +55,7 @@ impl Metadata {
             homepage: None,
             repository: None,
             license: None,
+            units: LengthUnit::default(),
         }
     }
 
@@ -119,10 +126,16 @@ impl Metadata {
             ..self
         }
     }
+
+    /// Set the [`Metadata::units`] field.
+    pub fn with_units(self, units: LengthUnit) -> Self {
+        Metadata { units, ..self }
+    }
 }
 
 /// Metadata about a [`crate::models::Model`].
 #[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ModelMetadata {
     /// A short, human-friendly name used to identify this model.
     pub name: String,
@@ -132,6 +145,9 @@ pub struct ModelMetadata {
 
     /// Arguments that the model uses when calculating its geometry.
     pub arguments: Vec<ArgumentMetadata>,
+
+    /// Named sets of values for the model's arguments.
+    pub presets: Vec<PresetMetadata>,
 }
 
 impl ModelMetadata {
@@ -148,6 +164,7 @@ impl ModelMetadata {
             name,
             description: None,
             arguments: Vec::new(),
+            presets: Vec::new(),
         }
     }
 
@@ -172,10 +189,17 @@ impl ModelMetadata {
         self.arguments.push(arg.into());
         self
     }
+
+    /// Add a preset to the [`ModelMetadata::presets`] list.
+    pub fn with_preset(mut self, preset: PresetMetadata) -> Self {
+        self.presets.push(preset);
+        self
+    }
 }
 
 /// Metadata describing a model's argument.
 #[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ArgumentMetadata {
     /// The name used to refer to this argument.
     pub name: String,
@@ -186,6 +210,9 @@ pub struct ArgumentMetadata {
 
     /// Something that could be used as a default if no value was provided.
     pub default_value: Option<String>,
+
+    /// The type of this argument, which determines how its value is parsed.
+    pub parameter_type: ParameterType,
 }
 
 impl ArgumentMetadata {
@@ -201,6 +228,7 @@ impl ArgumentMetadata {
             name,
             description: None,
             default_value: None,
+            parameter_type: ParameterType::default(),
         }
     }
 
@@ -223,6 +251,12 @@ impl ArgumentMetadata {
         self.default_value = Some(default_value.into());
         self
     }
+
+    /// Set the [`ArgumentMetadata::parameter_type`].
+    pub fn with_type(mut self, parameter_type: ParameterType) -> Self {
+        self.parameter_type = parameter_type;
+        self
+    }
 }
 
 impl From<&str> for ArgumentMetadata {
@@ -230,3 +264,42 @@ impl From<&str> for ArgumentMetadata {
         ArgumentMetadata::new(name)
     }
 }
+
+/// A named set of values for a model's arguments, like `small` or `large`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PresetMetadata {
+    /// The name used to refer to this preset.
+    pub name: String,
+
+    /// The values of the model's arguments, by argument name.
+    ///
+    /// Arguments that are not listed here keep their default value.
+    pub values: Vec<(String, String)>,
+}
+
+impl PresetMetadata {
+    /// Create a new [`PresetMetadata`].
+    ///
+    /// # Panics
+    ///
+    /// The `name` must not be empty.
+    pub fn new(name: impl Into<String>) -> Self {
+        let name = name.into();
+        assert!(!name.is_empty());
+        PresetMetadata {
+            name,
+            values: Vec::new(),
+        }
+    }
+
+    /// Add a value to the [`PresetMetadata::values`] list.
+    pub fn with_value(
+        mut self,
+        argument: impl Into<String>,
+        value: impl Into<String>,
+    ) -> Self {
+        self.values.push((argument.into(), value.into()));
+        self
+    }
+}
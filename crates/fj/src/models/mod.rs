@@ -4,12 +4,18 @@ mod context;
 mod host;
 mod metadata;
 mod model;
+mod panic;
+mod parameter;
+mod units;
 
 pub use self::{
     context::Context,
     host::{Host, HostExt},
-    metadata::{ArgumentMetadata, Metadata, ModelMetadata},
+    metadata::{ArgumentMetadata, Metadata, ModelMetadata, PresetMetadata},
     model::Model,
+    panic::ModelPanic,
+    parameter::{Parameter, ParameterError, ParameterType, ParameterValue},
+    units::{LengthUnit, ParseLengthUnitError},
 };
 
 /// A generic error used when defining a model.
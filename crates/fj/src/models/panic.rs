@@ -0,0 +1,31 @@
+use std::fmt::{self, Display, Formatter};
+
+/// A panic in model code
+///
+/// Panics must not unwind across the boundary between model and host. They are
+/// caught before that, and reported to the host as an error of this type.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ModelPanic {
+    /// The message that the model panicked with
+    pub message: String,
+
+    /// The location in the source code where the model panicked, if known
+    pub location: Option<String>,
+
+    /// The backtrace at the point where the model panicked
+    pub backtrace: String,
+}
+
+impl Display for ModelPanic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match &self.location {
+            Some(location) => {
+                write!(f, "Model panicked at {location}: {}", self.message)
+            }
+            None => write!(f, "Model panicked: {}", self.message),
+        }
+    }
+}
+
+impl std::error::Error for ModelPanic {}
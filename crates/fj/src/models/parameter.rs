@@ -0,0 +1,307 @@
+use std::fmt::{self, Display, Formatter};
+
+use super::Error;
+
+/// The type of a model parameter
+///
+/// Hosts pass the arguments of a model as text. The type of a parameter
+/// determines how that text is parsed. See [`ParameterType::parse`].
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ParameterType {
+    /// An integer, like `3` or `-1`
+    Integer,
+
+    /// A number, like `2.5`
+    Float,
+
+    /// A boolean, `true` or `false`
+    Bool,
+
+    /// Any text
+    #[default]
+    String,
+
+    /// One of a fixed set of names
+    Choice(Vec<String>),
+
+    /// A 2D vector, two numbers like `1, 2` or `[1, 2]`
+    Vector2,
+
+    /// A 3D vector, three numbers like `1, 2, 3` or `[1, 2, 3]`
+    Vector3,
+}
+
+impl ParameterType {
+    /// Parse a value of this type from text
+    pub fn parse(&self, input: &str) -> Result<ParameterValue, ParameterError> {
+        let error = || ParameterError {
+            input: input.to_owned(),
+            expected: self.clone(),
+        };
+        let text = input.trim();
+
+        let value = match self {
+            Self::Integer => {
+                ParameterValue::Integer(text.parse().map_err(|_| error())?)
+            }
+            Self::Float => {
+                ParameterValue::Float(text.parse().map_err(|_| error())?)
+            }
+            Self::Bool => {
+                ParameterValue::Bool(text.parse().map_err(|_| error())?)
+            }
+            Self::String => ParameterValue::String(input.to_owned()),
+            Self::Choice(choices) => {
+                if !choices.iter().any(|choice| choice == text) {
+                    return Err(error());
+                }
+                ParameterValue::String(text.to_owned())
+            }
+            Self::Vector2 => {
+                let [x, y] = parse_components(text).ok_or_else(error)?;
+                ParameterValue::Vector2([x, y])
+            }
+            Self::Vector3 => {
+                let [x, y, z] = parse_components(text).ok_or_else(error)?;
+                ParameterValue::Vector3([x, y, z])
+            }
+        };
+
+        Ok(value)
+    }
+}
+
+impl Display for ParameterType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Integer => write!(f, "an integer"),
+            Self::Float => write!(f, "a number"),
+            Self::Bool => write!(f, "`true` or `false`"),
+            Self::String => write!(f, "text"),
+            Self::Choice(choices) => {
+                write!(f, "one of ")?;
+                for (i, choice) in choices.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "`{choice}`")?;
+                }
+                Ok(())
+            }
+            Self::Vector2 => write!(f, "two numbers, like `1, 2`"),
+            Self::Vector3 => write!(f, "three numbers, like `1, 2, 3`"),
+        }
+    }
+}
+
+/// Parse the components of a vector, separated by commas or whitespace
+fn parse_components<const D: usize>(text: &str) -> Option<[f64; D]> {
+    let text = text
+        .strip_prefix('[')
+        .and_then(|text| text.strip_suffix(']'))
+        .unwrap_or(text);
+
+    let mut components = [0.; D];
+    let mut parts = text
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|part| !part.is_empty());
+
+    for component in &mut components {
+        *component = parts.next()?.parse().ok()?;
+    }
+    if parts.next().is_some() {
+        return None;
+    }
+
+    Some(components)
+}
+
+/// The value of a model parameter
+///
+/// Values are displayed as text that [`ParameterType::parse`] accepts.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ParameterValue {
+    /// An integer
+    ///
+    /// Wide enough to hold the values of all integer types that implement
+    /// [`Parameter`], from `i64::MIN` to `u64::MAX`.
+    Integer(i128),
+
+    /// A number
+    Float(f64),
+
+    /// A boolean
+    Bool(bool),
+
+    /// Text, or one of a fixed set of names
+    String(String),
+
+    /// A 2D vector
+    Vector2([f64; 2]),
+
+    /// A 3D vector
+    Vector3([f64; 3]),
+}
+
+impl Display for ParameterValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Integer(value) => write!(f, "{value}"),
+            Self::Float(value) => write!(f, "{value}"),
+            Self::Bool(value) => write!(f, "{value}"),
+            Self::String(value) => write!(f, "{value}"),
+            Self::Vector2([x, y]) => write!(f, "{x}, {y}"),
+            Self::Vector3([x, y, z]) => write!(f, "{x}, {y}, {z}"),
+        }
+    }
+}
+
+/// Text could not be parsed as the value of a model parameter
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParameterError {
+    /// The text that could not be parsed
+    pub input: String,
+
+    /// The type that was expected
+    pub expected: ParameterType,
+}
+
+impl Display for ParameterError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "Expected {}, found `{}`", self.expected, self.input)
+    }
+}
+
+impl std::error::Error for ParameterError {}
+
+/// A type that the arguments of a model can have
+///
+/// The [`crate::model`] macro uses this for arguments of these types, to tell
+/// the host about their type.
+pub trait Parameter: Sized {
+    /// The type of parameter that arguments of this type are passed as
+    fn parameter_type() -> ParameterType;
+
+    /// Convert a value that was parsed as [`Parameter::parameter_type`]
+    fn from_value(value: ParameterValue) -> Result<Self, Error>;
+}
+
+macro_rules! impl_parameter {
+    ($variant:ident, $convert:expr => $($ty:ty),*) => {
+        $(
+            impl Parameter for $ty {
+                fn parameter_type() -> ParameterType {
+                    ParameterType::$variant
+                }
+
+                fn from_value(value: ParameterValue) -> Result<Self, Error> {
+                    match value {
+                        ParameterValue::$variant(value) => $convert(value),
+                        value => Err(unexpected(value, Self::parameter_type())),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_parameter!(
+    Integer,
+    |value: i128| Self::try_from(value)
+        .map_err(|_| format!("Value `{value}` is out of range").into())
+    => i8, i16, i32, i64, isize, u8, u16, u32, u64, usize
+);
+impl_parameter!(Float, |value: f64| Ok(value as Self) => f32);
+impl_parameter!(Float, Ok => f64);
+impl_parameter!(Bool, Ok => bool);
+impl_parameter!(String, Ok => String);
+impl_parameter!(Vector2, Ok => [f64; 2]);
+impl_parameter!(Vector3, Ok => [f64; 3]);
+
+fn unexpected(value: ParameterValue, expected: ParameterType) -> Error {
+    ParameterError {
+        input: value.to_string(),
+        expected,
+    }
+    .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Parameter, ParameterType, ParameterValue};
+
+    #[test]
+    fn parse_values() {
+        let parse = |ty: ParameterType, input| ty.parse(input).ok();
+
+        assert_eq!(
+            parse(ParameterType::Integer, " -3"),
+            Some(ParameterValue::Integer(-3))
+        );
+        assert_eq!(parse(ParameterType::Integer, "1.5"), None);
+        assert_eq!(
+            parse(ParameterType::Float, "1.5"),
+            Some(ParameterValue::Float(1.5))
+        );
+        assert_eq!(
+            parse(ParameterType::Bool, "true"),
+            Some(ParameterValue::Bool(true))
+        );
+        assert_eq!(parse(ParameterType::Bool, "yes"), None);
+
+        let choice = ParameterType::Choice(vec!["round".into(), "flat".into()]);
+        assert_eq!(
+            parse(choice.clone(), "flat"),
+            Some(ParameterValue::String("flat".into()))
+        );
+        assert_eq!(parse(choice, "pointy"), None);
+
+        assert_eq!(
+            parse(ParameterType::Vector2, "[1, 2.5]"),
+            Some(ParameterValue::Vector2([1., 2.5]))
+        );
+        assert_eq!(
+            parse(ParameterType::Vector3, "1 2 3"),
+            Some(ParameterValue::Vector3([1., 2., 3.]))
+        );
+        assert_eq!(parse(ParameterType::Vector3, "1, 2"), None);
+        assert_eq!(parse(ParameterType::Vector2, "1, 2, 3"), None);
+
+        // Values are displayed in a way that can be parsed again.
+        let value = ParameterValue::Vector3([1., -2., 0.5]);
+        assert_eq!(
+            parse(ParameterType::Vector3, &value.to_string()),
+            Some(value)
+        );
+    }
+
+    #[test]
+    fn convert_values() {
+        assert_eq!(u8::from_value(ParameterValue::Integer(255)).unwrap(), 255);
+        assert_eq!(
+            u64::from_value(
+                ParameterType::Integer
+                    .parse("18446744073709551615")
+                    .unwrap()
+            )
+            .unwrap(),
+            u64::MAX
+        );
+        assert_eq!(
+            i64::from_value(
+                ParameterType::Integer
+                    .parse("-9223372036854775808")
+                    .unwrap()
+            )
+            .unwrap(),
+            i64::MIN
+        );
+        assert!(u64::from_value(ParameterValue::Integer(-1)).is_err());
+        assert!(i64::from_value(ParameterValue::Integer(1 << 63)).is_err());
+        assert!(u8::from_value(ParameterValue::Integer(256)).is_err());
+        assert!(u8::from_value(ParameterValue::Float(1.)).is_err());
+        assert_eq!(f32::from_value(ParameterValue::Float(0.5)).unwrap(), 0.5);
+    }
+}
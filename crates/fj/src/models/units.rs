@@ -0,0 +1,104 @@
+use std::{fmt, str::FromStr};
+
+/// A unit of length
+///
+/// All coordinates of a model are bare numbers. The unit that they are
+/// measured in is declared in [`crate::models::Metadata::units`], and is used
+/// to scale the model, when it is exported to a file format that has units.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+pub enum LengthUnit {
+    /// Millimeters
+    #[default]
+    Millimeter,
+
+    /// Centimeters
+    Centimeter,
+
+    /// Meters
+    Meter,
+
+    /// Inches
+    Inch,
+}
+
+impl LengthUnit {
+    /// The length of the unit, in millimeters
+    pub fn in_millimeters(&self) -> f64 {
+        match self {
+            Self::Millimeter => 1.,
+            Self::Centimeter => 10.,
+            Self::Meter => 1000.,
+            Self::Inch => 25.4,
+        }
+    }
+
+    /// Convert a length from this unit to another one
+    pub fn convert(&self, value: f64, to: LengthUnit) -> f64 {
+        value * self.in_millimeters() / to.in_millimeters()
+    }
+
+    /// The symbol of the unit, like `mm` for millimeters
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Self::Millimeter => "mm",
+            Self::Centimeter => "cm",
+            Self::Meter => "m",
+            Self::Inch => "in",
+        }
+    }
+}
+
+impl fmt::Display for LengthUnit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.symbol())
+    }
+}
+
+impl FromStr for LengthUnit {
+    type Err = ParseLengthUnitError;
+
+    /// Parse a unit from its symbol or its name, ignoring case
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "mm" | "millimeter" | "millimeters" => Ok(Self::Millimeter),
+            "cm" | "centimeter" | "centimeters" => Ok(Self::Centimeter),
+            "m" | "meter" | "meters" => Ok(Self::Meter),
+            "in" | "inch" | "inches" => Ok(Self::Inch),
+            _ => Err(ParseLengthUnitError(s.to_owned())),
+        }
+    }
+}
+
+/// An error that occurs when parsing an unknown [`LengthUnit`]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParseLengthUnitError(pub String);
+
+impl fmt::Display for ParseLengthUnitError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "unknown unit of length `{}`, expected `mm`, `cm`, `m`, or `in`",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for ParseLengthUnitError {}
+
+#[cfg(test)]
+mod tests {
+    use super::LengthUnit;
+
+    #[test]
+    fn parse_and_convert() {
+        let inch: LengthUnit = "Inches".parse().unwrap();
+        assert_eq!(inch, LengthUnit::Inch);
+        assert_eq!(inch.to_string(), "in");
+        assert!("ft".parse::<LengthUnit>().is_err());
+
+        assert_eq!(inch.convert(2., LengthUnit::Millimeter), 50.8);
+        assert_eq!(LengthUnit::Meter.convert(1., LengthUnit::Centimeter), 100.);
+    }
+}
@@ -0,0 +1,174 @@
+use crate::{abi::ffi_safe, Angle, Shape};
+
+/// A linear pattern of a 3-dimensional shape
+///
+/// Creates `count` instances of a shape, each one offset by `spacing` from the
+/// previous one. The first instance is the original shape.
+///
+/// # Examples
+///
+/// Convenient syntax for this operation is available through [`crate::syntax`].
+///
+/// ``` rust
+/// # let shape = fj::Sketch::from_points(vec![[0., 0.], [1., 0.], [0., 1.]]);
+/// use fj::syntax::*;
+///
+/// // `shape` can be anything that converts to `fj::Shape`
+/// let row = shape.linear_pattern(5, [2., 0., 0.]);
+///
+/// // Leave out the instance in the middle
+/// let row_with_gap = row.skip([2]);
+/// ```
+///
+/// # Limitations
+///
+/// Like in a [`crate::Group`], the instances are not allowed to touch or
+/// overlap. This is not currently checked, except for instances that end up
+/// at the same position, which are rejected.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+pub struct LinearPattern {
+    shape: Shape,
+    count: u32,
+    spacing: [f64; 3],
+    skipped: ffi_safe::Vec<u32>,
+}
+
+impl LinearPattern {
+    /// Create a `LinearPattern` with `count` instances, offset by `spacing`
+    pub fn from_spacing(shape: Shape, count: u32, spacing: [f64; 3]) -> Self {
+        Self {
+            shape,
+            count,
+            spacing,
+            skipped: Vec::new().into(),
+        }
+    }
+
+    /// Leave out the instances with the given indices
+    ///
+    /// The instance with index `0` is the original shape.
+    pub fn skip(self, indices: impl IntoIterator<Item = u32>) -> Self {
+        let skipped = self.skipped.iter().copied().chain(indices).collect();
+        Self { skipped, ..self }
+    }
+
+    /// Access the shape being repeated
+    pub fn shape(&self) -> &Shape {
+        &self.shape
+    }
+
+    /// Access the number of instances, including skipped ones
+    pub fn count(&self) -> u32 {
+        self.count
+    }
+
+    /// Access the offset between two neighboring instances
+    pub fn spacing(&self) -> [f64; 3] {
+        self.spacing
+    }
+
+    /// Access the indices of the instances that are left out
+    pub fn skipped(&self) -> &[u32] {
+        &self.skipped
+    }
+}
+
+impl From<LinearPattern> for Shape {
+    fn from(shape: LinearPattern) -> Self {
+        Self::LinearPattern(Box::new(shape))
+    }
+}
+
+/// A circular pattern of a 3-dimensional shape
+///
+/// Creates `count` instances of a shape, each one rotated by `angle` around
+/// `axis` relative to the previous one. The axis goes through the origin. The
+/// first instance is the original shape.
+///
+/// # Examples
+///
+/// Convenient syntax for this operation is available through [`crate::syntax`].
+///
+/// ``` rust
+/// # let shape = fj::Sketch::from_points(vec![[2., 0.], [3., 0.], [2., 1.]]);
+/// use fj::syntax::*;
+///
+/// // `shape` can be anything that converts to `fj::Shape`
+/// let bolt_circle =
+///     shape.circular_pattern(6, [0., 0., 1.], fj::Angle::from_rev(1. / 6.));
+/// ```
+///
+/// # Limitations
+///
+/// Like in a [`crate::Group`], the instances are not allowed to touch or
+/// overlap. This is not currently checked, except for instances that end up
+/// at the same position, which are rejected.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+pub struct CircularPattern {
+    shape: Shape,
+    count: u32,
+    axis: [f64; 3],
+    angle: Angle,
+    skipped: ffi_safe::Vec<u32>,
+}
+
+impl CircularPattern {
+    /// Create a `CircularPattern` with `count` instances, `angle` apart
+    pub fn from_angle(
+        shape: Shape,
+        count: u32,
+        axis: [f64; 3],
+        angle: Angle,
+    ) -> Self {
+        Self {
+            shape,
+            count,
+            axis,
+            angle,
+            skipped: Vec::new().into(),
+        }
+    }
+
+    /// Leave out the instances with the given indices
+    ///
+    /// The instance with index `0` is the original shape.
+    pub fn skip(self, indices: impl IntoIterator<Item = u32>) -> Self {
+        let skipped = self.skipped.iter().copied().chain(indices).collect();
+        Self { skipped, ..self }
+    }
+
+    /// Access the shape being repeated
+    pub fn shape(&self) -> &Shape {
+        &self.shape
+    }
+
+    /// Access the number of instances, including skipped ones
+    pub fn count(&self) -> u32 {
+        self.count
+    }
+
+    /// Access the axis of the rotation
+    pub fn axis(&self) -> [f64; 3] {
+        self.axis
+    }
+
+    /// Access the angle between two neighboring instances
+    pub fn angle(&self) -> Angle {
+        self.angle
+    }
+
+    /// Access the indices of the instances that are left out
+    pub fn skipped(&self) -> &[u32] {
+        &self.skipped
+    }
+}
+
+impl From<CircularPattern> for Shape {
+    fn from(shape: CircularPattern) -> Self {
+        Self::CircularPattern(Box::new(shape))
+    }
+}
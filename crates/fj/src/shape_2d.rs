@@ -1,4 +1,4 @@
-use crate::{abi::ffi_safe, Shape};
+use crate::{abi::ffi_safe, Shape, Text};
 
 /// A 2-dimensional shape
 #[derive(Clone, Debug, PartialEq)]
@@ -10,6 +10,9 @@ pub enum Shape2d {
 
     /// A sketch
     Sketch(Sketch),
+
+    /// Text
+    Text(Box<Text>),
 }
 
 impl Shape2d {
@@ -18,6 +21,7 @@ impl Shape2d {
         match &self {
             Shape2d::Sketch(s) => s.color(),
             Shape2d::Difference(d) => d.color(),
+            Shape2d::Text(t) => t.color(),
         }
     }
 }
@@ -78,9 +82,8 @@ impl From<Difference2d> for Shape2d {
 /// represented by a number of points. For example, if the points a, b, and c
 /// are provided, the edges ab, bc, and ca are assumed.
 ///
-/// Nothing about these edges is checked right now, but algorithms might assume
-/// that the edges are non-overlapping. If you create a `Sketch` with
-/// overlapping edges, you're on your own.
+/// The edges must not intersect or overlap each other. If they do, processing
+/// the sketch results in a validation error.
 ///
 /// # Examples
 ///
@@ -165,18 +168,39 @@ pub enum Chain {
 pub struct Circle {
     /// The radius of the circle
     radius: f64,
+
+    /// The center of the circle
+    #[cfg_attr(feature = "serde", serde(default))]
+    center: [f64; 2],
 }
 
 impl Circle {
     /// Construct a new circle with a specific radius
+    ///
+    /// The circle is centered on the origin. Use [`Circle::with_center`] to
+    /// move it elsewhere.
     pub fn from_radius(radius: f64) -> Self {
-        Self { radius }
+        Self {
+            radius,
+            center: [0., 0.],
+        }
+    }
+
+    /// Move the circle, so it is centered on the given point
+    pub fn with_center(mut self, center: [f64; 2]) -> Self {
+        self.center = center;
+        self
     }
 
     /// Access the circle's radius
     pub fn radius(&self) -> f64 {
         self.radius
     }
+
+    /// Access the circle's center
+    pub fn center(&self) -> [f64; 2] {
+        self.center
+    }
 }
 
 /// A polygonal chain that is part of a [`Sketch`]
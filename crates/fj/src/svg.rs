@@ -0,0 +1,1049 @@
+//! Import of 2-dimensional shapes from SVG files
+//!
+//! Reads the outlines of `<path>`, `<rect>`, `<circle>`, `<ellipse>`, and
+//! `<polygon>` elements, and converts them into [`Shape2d`]s. Curves are
+//! approximated with line segments.
+//!
+//! Each element is filled according to its `fill-rule` (`nonzero`, unless
+//! specified otherwise). Outlines that bound the filled area from the inside
+//! become holes, which are subtracted from the outline around them, using
+//! [`Difference2d`].
+//!
+//! # Examples
+//!
+//! ``` rust
+//! use fj::syntax::*;
+//!
+//! let svg = r#"
+//!     <svg xmlns="http://www.w3.org/2000/svg" width="20mm" height="20mm"
+//!         viewBox="0 0 20 20">
+//!         <path d="M 0 0 H 20 V 20 H 0 Z M 5 5 V 15 H 15 V 5 Z" />
+//!     </svg>
+//! "#;
+//!
+//! let shapes = fj::svg::import(svg, &fj::svg::ImportOptions::default())?;
+//! let plate = shapes[0].sweep([0., 0., 2.]);
+//! # Ok::<(), fj::svg::SvgError>(())
+//! ```
+//!
+//! # Limitations
+//!
+//! Styling from CSS (like `style` attributes, or `<style>` elements) is
+//! ignored. Separate elements are imported as separate shapes, even if they
+//! overlap.
+
+use std::{
+    error::Error, f64::consts::PI, fmt, fs, io, path::Path, str::FromStr,
+};
+
+use fj_math::{assign_interiors, signed_area, winding_number};
+
+use crate::{Difference2d, Shape2d, Sketch};
+
+/// Options for importing an SVG file
+///
+/// See [`import`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct ImportOptions {
+    /// The unit that the imported coordinates are converted to
+    pub unit: Unit,
+
+    /// A factor that is applied to all coordinates, after converting them
+    pub scale: f64,
+
+    /// The maximum distance between a curve and its approximation
+    ///
+    /// This is measured in the final coordinates, after conversion and
+    /// scaling.
+    pub tolerance: f64,
+}
+
+impl Default for ImportOptions {
+    fn default() -> Self {
+        Self {
+            unit: Unit::Millimeter,
+            scale: 1.,
+            tolerance: 0.01,
+        }
+    }
+}
+
+/// The unit that imported coordinates are converted to
+///
+/// See [`ImportOptions`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Unit {
+    /// Keep the SVG's user units, without any conversion
+    UserUnit,
+
+    /// Convert to millimeters
+    ///
+    /// The conversion is based on the `width`, `height`, and `viewBox`
+    /// attributes of the document. If those don't specify a physical size,
+    /// user units are assumed to be CSS pixels (96 per inch).
+    Millimeter,
+}
+
+/// Import all shapes from an SVG document
+///
+/// Returns one shape per filled area. The SVG's y-axis points down, so it is
+/// flipped, to keep the imported shapes the right way up.
+pub fn import(
+    svg: &str,
+    options: &ImportOptions,
+) -> Result<Vec<Shape2d>, SvgError> {
+    let document = roxmltree::Document::parse(svg)?;
+    let root = document.root_element();
+
+    let unit_scale = match options.unit {
+        Unit::UserUnit => 1.,
+        Unit::Millimeter => millimeters_per_user_unit(root)?,
+    };
+    let scale = unit_scale * options.scale;
+    let document_transform = Affine([scale, 0., 0., -scale, 0., 0.]);
+
+    let mut shapes = Vec::new();
+
+    for node in root.descendants().filter(|node| node.is_element()) {
+        if is_definition(node) {
+            continue;
+        }
+
+        let transform = document_transform.then(&node_transform(node)?);
+        let mut builder = ContourBuilder::new(transform, options.tolerance);
+
+        match node.tag_name().name() {
+            "path" => builder.path(attribute(node, "d")?)?,
+            "polygon" => builder.polygon(attribute(node, "points")?)?,
+            "rect" => builder.rect(
+                [
+                    number(node, "x", Some(0.))?,
+                    number(node, "y", Some(0.))?,
+                    number(node, "width", None)?,
+                    number(node, "height", None)?,
+                ],
+                corner_radii(node)?,
+            ),
+            "circle" => {
+                let r = number(node, "r", None)?;
+                builder.ellipse([
+                    number(node, "cx", Some(0.))?,
+                    number(node, "cy", Some(0.))?,
+                    r,
+                    r,
+                ]);
+            }
+            "ellipse" => builder.ellipse([
+                number(node, "cx", Some(0.))?,
+                number(node, "cy", Some(0.))?,
+                number(node, "rx", None)?,
+                number(node, "ry", None)?,
+            ]),
+            _ => continue,
+        }
+
+        let fill_rule = match inherited(node, "fill-rule") {
+            Some("evenodd") => FillRule::EvenOdd,
+            _ => FillRule::NonZero,
+        };
+        let color = match inherited(node, "fill") {
+            Some(fill) => svgtypes::Color::from_str(fill)
+                .ok()
+                .map(|c| [c.red, c.green, c.blue, c.alpha]),
+            None => None,
+        };
+
+        for (exterior, holes) in classify(builder.finish(), fill_rule) {
+            let mut shape: Shape2d = sketch(exterior, color).into();
+
+            for hole in holes {
+                shape = Difference2d::from_shapes([
+                    shape,
+                    sketch(hole, color).into(),
+                ])
+                .into();
+            }
+
+            shapes.push(shape);
+        }
+    }
+
+    Ok(shapes)
+}
+
+/// Import all shapes from an SVG file
+///
+/// See [`import`].
+pub fn import_file(
+    path: impl AsRef<Path>,
+    options: &ImportOptions,
+) -> Result<Vec<Shape2d>, SvgError> {
+    let svg = fs::read_to_string(path)?;
+    import(&svg, options)
+}
+
+/// An error that can occur while importing an SVG file
+#[derive(Debug)]
+pub enum SvgError {
+    /// The file could not be read
+    Io(io::Error),
+
+    /// The document is not valid XML
+    Xml(roxmltree::Error),
+
+    /// An attribute has an invalid value
+    InvalidAttribute {
+        /// The name of the attribute
+        name: String,
+
+        /// The invalid value, if the attribute was present
+        value: Option<String>,
+    },
+}
+
+impl fmt::Display for SvgError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "Error reading SVG file: {err}"),
+            Self::Xml(err) => write!(f, "Error parsing SVG document: {err}"),
+            Self::InvalidAttribute {
+                name,
+                value: Some(value),
+            } => {
+                write!(f, "Invalid value for SVG attribute `{name}`: {value}")
+            }
+            Self::InvalidAttribute { name, value: None } => {
+                write!(f, "Missing SVG attribute `{name}`")
+            }
+        }
+    }
+}
+
+impl Error for SvgError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            Self::Xml(err) => Some(err),
+            Self::InvalidAttribute { .. } => None,
+        }
+    }
+}
+
+impl From<io::Error> for SvgError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<roxmltree::Error> for SvgError {
+    fn from(err: roxmltree::Error) -> Self {
+        Self::Xml(err)
+    }
+}
+
+type Contour = Vec<[f64; 2]>;
+
+fn sketch(contour: Contour, color: Option<[u8; 4]>) -> Sketch {
+    let sketch = Sketch::from_points(contour);
+
+    match color {
+        Some(color) => sketch.with_color(color),
+        None => sketch,
+    }
+}
+
+fn attribute<'a>(
+    node: roxmltree::Node<'a, '_>,
+    name: &str,
+) -> Result<&'a str, SvgError> {
+    node.attribute(name)
+        .ok_or_else(|| SvgError::InvalidAttribute {
+            name: name.to_owned(),
+            value: None,
+        })
+}
+
+fn invalid(name: &str, value: &str) -> SvgError {
+    SvgError::InvalidAttribute {
+        name: name.to_owned(),
+        value: Some(value.to_owned()),
+    }
+}
+
+/// Read a numeric attribute, in user units
+fn number(
+    node: roxmltree::Node,
+    name: &str,
+    default: Option<f64>,
+) -> Result<f64, SvgError> {
+    let value = match (node.attribute(name), default) {
+        (Some(value), _) => value,
+        (None, Some(default)) => return Ok(default),
+        (None, None) => attribute(node, name)?,
+    };
+
+    use svgtypes::LengthUnit;
+    match svgtypes::Length::from_str(value) {
+        Ok(length)
+            if matches!(length.unit, LengthUnit::None | LengthUnit::Px) =>
+        {
+            Ok(length.number)
+        }
+        _ => Err(invalid(name, value)),
+    }
+}
+
+/// Read the radii of a rectangle's rounded corners, in user units
+///
+/// If only one of `rx` and `ry` is specified, it's used for both.
+fn corner_radii(node: roxmltree::Node) -> Result<[f64; 2], SvgError> {
+    let radius = |name| match node.attribute(name) {
+        None | Some("auto") => Ok(None),
+        Some(value) => match number(node, name, None)? {
+            radius if radius >= 0. => Ok(Some(radius)),
+            _ => Err(invalid(name, value)),
+        },
+    };
+
+    let radii = match (radius("rx")?, radius("ry")?) {
+        (Some(rx), Some(ry)) => [rx, ry],
+        (Some(r), None) | (None, Some(r)) => [r, r],
+        (None, None) => [0., 0.],
+    };
+    Ok(radii)
+}
+
+/// Indicate whether a node is only a definition, instead of being rendered
+fn is_definition(node: roxmltree::Node) -> bool {
+    node.ancestors().any(|node| {
+        matches!(
+            node.tag_name().name(),
+            "defs" | "clipPath" | "mask" | "marker" | "pattern" | "symbol"
+        )
+    })
+}
+
+/// Find an attribute on a node, or the closest ancestor that has it
+fn inherited<'a>(node: roxmltree::Node<'a, '_>, name: &str) -> Option<&'a str> {
+    node.ancestors().find_map(|node| node.attribute(name))
+}
+
+/// Compute the combined `transform` of a node and its ancestors
+fn node_transform(node: roxmltree::Node) -> Result<Affine, SvgError> {
+    let mut transform = Affine::IDENTITY;
+
+    for node in node.ancestors() {
+        if let Some(value) = node.attribute("transform") {
+            let t = svgtypes::Transform::from_str(value)
+                .map_err(|_| invalid("transform", value))?;
+            transform = Affine([t.a, t.b, t.c, t.d, t.e, t.f]).then(&transform);
+        }
+    }
+
+    Ok(transform)
+}
+
+fn millimeters_per_user_unit(root: roxmltree::Node) -> Result<f64, SvgError> {
+    const MM_PER_PX: f64 = 25.4 / 96.;
+
+    let width = match root.attribute("width") {
+        Some(value) => Some(
+            svgtypes::Length::from_str(value)
+                .map_err(|_| invalid("width", value))?,
+        ),
+        None => None,
+    };
+    let view_box = match root.attribute("viewBox") {
+        Some(value) => Some(
+            svgtypes::ViewBox::from_str(value)
+                .map_err(|_| invalid("viewBox", value))?,
+        ),
+        None => None,
+    };
+
+    let (width, view_box) = match (width, view_box) {
+        (Some(width), Some(view_box)) => (width, view_box),
+        _ => return Ok(MM_PER_PX),
+    };
+
+    use svgtypes::LengthUnit;
+    let width_mm = match width.unit {
+        LengthUnit::Mm => width.number,
+        LengthUnit::Cm => width.number * 10.,
+        LengthUnit::In => width.number * 25.4,
+        LengthUnit::Pt => width.number * 25.4 / 72.,
+        LengthUnit::Pc => width.number * 25.4 / 6.,
+        LengthUnit::None | LengthUnit::Px => width.number * MM_PER_PX,
+        _ => return Ok(MM_PER_PX),
+    };
+
+    Ok(width_mm / view_box.w)
+}
+
+/// A 2D affine transform, in the same layout as the SVG `matrix` transform
+#[derive(Clone, Copy, Debug)]
+struct Affine([f64; 6]);
+
+impl Affine {
+    const IDENTITY: Self = Self([1., 0., 0., 1., 0., 0.]);
+
+    /// Return the transform that applies `other` first, then `self`
+    fn then(&self, other: &Self) -> Self {
+        let [a, b, c, d, e, f] = self.0;
+        let [oa, ob, oc, od, oe, of] = other.0;
+
+        Self([
+            a * oa + c * ob,
+            b * oa + d * ob,
+            a * oc + c * od,
+            b * oc + d * od,
+            a * oe + c * of + e,
+            b * oe + d * of + f,
+        ])
+    }
+
+    fn apply(&self, [x, y]: [f64; 2]) -> [f64; 2] {
+        let [a, b, c, d, e, f] = self.0;
+        [a * x + c * y + e, b * x + d * y + f]
+    }
+
+    /// Estimate the largest factor by which the transform scales a distance
+    fn max_scale(&self) -> f64 {
+        let [a, b, c, d, _, _] = self.0;
+        f64::hypot(a, b).max(f64::hypot(c, d))
+    }
+}
+
+/// Builds contours from SVG outlines, approximating curves
+///
+/// Points are transformed as they are added. Bézier curves are transformed
+/// before they are approximated, which is exact for affine transforms.
+struct ContourBuilder {
+    transform: Affine,
+    tolerance: f64,
+    contours: Vec<Contour>,
+    current: Contour,
+}
+
+impl ContourBuilder {
+    fn new(transform: Affine, tolerance: f64) -> Self {
+        Self {
+            transform,
+            tolerance,
+            contours: Vec::new(),
+            current: Vec::new(),
+        }
+    }
+
+    fn path(&mut self, data: &str) -> Result<(), SvgError> {
+        use svgtypes::PathSegment::*;
+
+        // Positions in user units, untransformed.
+        let mut start = [0., 0.];
+        let mut position = [0., 0.];
+
+        // Smooth curves reflect the last control point of the previous
+        // segment, but only if that is a curve of the same kind.
+        let mut last_cubic_control = None;
+        let mut last_quadratic_control = None;
+
+        for segment in svgtypes::PathParser::from(data) {
+            let segment = segment.map_err(|_| invalid("d", data))?;
+
+            let offset = |abs: bool, [x, y]: [f64; 2]| {
+                if abs {
+                    [x, y]
+                } else {
+                    [position[0] + x, position[1] + y]
+                }
+            };
+            let reflected = |control: Option<[f64; 2]>| match control {
+                Some([x, y]) => [2. * position[0] - x, 2. * position[1] - y],
+                None => position,
+            };
+
+            let mut cubic_control = None;
+            let mut quadratic_control = None;
+            let target = match segment {
+                MoveTo { abs, x, y } => {
+                    let target = offset(abs, [x, y]);
+                    self.close();
+                    self.line(target);
+                    start = target;
+                    target
+                }
+                LineTo { abs, x, y } => {
+                    let target = offset(abs, [x, y]);
+                    self.line(target);
+                    target
+                }
+                HorizontalLineTo { abs, x } => {
+                    let x = if abs { x } else { position[0] + x };
+                    let target = [x, position[1]];
+                    self.line(target);
+                    target
+                }
+                VerticalLineTo { abs, y } => {
+                    let y = if abs { y } else { position[1] + y };
+                    let target = [position[0], y];
+                    self.line(target);
+                    target
+                }
+                CurveTo {
+                    abs,
+                    x1,
+                    y1,
+                    x2,
+                    y2,
+                    x,
+                    y,
+                } => {
+                    let c1 = offset(abs, [x1, y1]);
+                    let c2 = offset(abs, [x2, y2]);
+                    let target = offset(abs, [x, y]);
+                    self.bezier(&[position, c1, c2, target]);
+                    cubic_control = Some(c2);
+                    target
+                }
+                SmoothCurveTo { abs, x2, y2, x, y } => {
+                    let c1 = reflected(last_cubic_control);
+                    let c2 = offset(abs, [x2, y2]);
+                    let target = offset(abs, [x, y]);
+                    self.bezier(&[position, c1, c2, target]);
+                    cubic_control = Some(c2);
+                    target
+                }
+                Quadratic { abs, x1, y1, x, y } => {
+                    let c = offset(abs, [x1, y1]);
+                    let target = offset(abs, [x, y]);
+                    self.bezier(&[position, c, target]);
+                    quadratic_control = Some(c);
+                    target
+                }
+                SmoothQuadratic { abs, x, y } => {
+                    let c = reflected(last_quadratic_control);
+                    let target = offset(abs, [x, y]);
+                    self.bezier(&[position, c, target]);
+                    quadratic_control = Some(c);
+                    target
+                }
+                EllipticalArc {
+                    abs,
+                    rx,
+                    ry,
+                    x_axis_rotation,
+                    large_arc,
+                    sweep,
+                    x,
+                    y,
+                } => {
+                    let target = offset(abs, [x, y]);
+                    self.arc(
+                        position,
+                        target,
+                        [rx, ry],
+                        x_axis_rotation.to_radians(),
+                        large_arc,
+                        sweep,
+                    );
+                    target
+                }
+                ClosePath { .. } => {
+                    self.close();
+
+                    // If the path continues without a move, the next subpath
+                    // starts where this one started.
+                    self.line(start);
+                    start
+                }
+            };
+
+            last_cubic_control = cubic_control;
+            last_quadratic_control = quadratic_control;
+            position = target;
+        }
+
+        self.close();
+        Ok(())
+    }
+
+    fn polygon(&mut self, points: &str) -> Result<(), SvgError> {
+        let numbers = points
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|s| !s.is_empty())
+            .map(|s| s.parse::<f64>().map_err(|_| invalid("points", points)))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for point in numbers.chunks_exact(2) {
+            self.line([point[0], point[1]]);
+        }
+
+        self.close();
+        Ok(())
+    }
+
+    /// Add a rectangle, with corners rounded by elliptical arcs
+    ///
+    /// Radii that are larger than half of the rectangle are reduced, as the
+    /// SVG specification requires.
+    fn rect(&mut self, [x, y, width, height]: [f64; 4], [rx, ry]: [f64; 2]) {
+        let rx = rx.min(width / 2.);
+        let ry = ry.min(height / 2.);
+
+        if rx <= 0. || ry <= 0. {
+            for point in [
+                [x, y],
+                [x + width, y],
+                [x + width, y + height],
+                [x, y + height],
+            ] {
+                self.line(point);
+            }
+
+            self.close();
+            return;
+        }
+
+        let corners = [
+            ([x + width - rx, y], [x + width, y + ry]),
+            ([x + width, y + height - ry], [x + width - rx, y + height]),
+            ([x + rx, y + height], [x, y + height - ry]),
+            ([x, y + ry], [x + rx, y]),
+        ];
+        for (from, to) in corners {
+            self.line(from);
+            self.arc(from, to, [rx, ry], 0., false, true);
+        }
+
+        self.close();
+    }
+
+    fn ellipse(&mut self, [cx, cy, rx, ry]: [f64; 4]) {
+        let n = self.arc_segments(rx.max(ry), 2. * PI);
+
+        for i in 0..n {
+            let angle = 2. * PI * i as f64 / n as f64;
+            self.line([cx + rx * angle.cos(), cy + ry * angle.sin()]);
+        }
+
+        self.close();
+    }
+
+    fn line(&mut self, point: [f64; 2]) {
+        let point = self.transform.apply(point);
+
+        if self.current.last() != Some(&point) {
+            self.current.push(point);
+        }
+    }
+
+    /// Approximate a Bézier curve, starting at the current position
+    ///
+    /// The number of segments is chosen using Wang's formula, which bounds
+    /// the distance between the curve and its approximation.
+    fn bezier(&mut self, control_points: &[[f64; 2]]) {
+        let points = control_points
+            .iter()
+            .map(|&point| self.transform.apply(point))
+            .collect::<Vec<_>>();
+        let degree = (points.len() - 1) as f64;
+
+        let max_second_difference = points
+            .windows(3)
+            .map(|w| {
+                let x = w[0][0] - 2. * w[1][0] + w[2][0];
+                let y = w[0][1] - 2. * w[1][1] + w[2][1];
+                f64::hypot(x, y)
+            })
+            .fold(0., f64::max);
+        let n = (degree * (degree - 1.) / 8. * max_second_difference
+            / self.tolerance)
+            .sqrt()
+            .ceil()
+            .max(1.) as usize;
+
+        for i in 1..=n {
+            let point = de_casteljau(&points, i as f64 / n as f64);
+
+            if self.current.last() != Some(&point) {
+                self.current.push(point);
+            }
+        }
+    }
+
+    /// Approximate an elliptical arc, as defined by an SVG path
+    ///
+    /// Uses the conversion from endpoint to center parameterization from the
+    /// SVG specification.
+    fn arc(
+        &mut self,
+        from: [f64; 2],
+        to: [f64; 2],
+        [rx, ry]: [f64; 2],
+        rotation: f64,
+        large_arc: bool,
+        sweep: bool,
+    ) {
+        let (mut rx, mut ry) = (rx.abs(), ry.abs());
+        if rx == 0. || ry == 0. || from == to {
+            self.line(to);
+            return;
+        }
+
+        let (sin, cos) = rotation.sin_cos();
+        let dx = (from[0] - to[0]) / 2.;
+        let dy = (from[1] - to[1]) / 2.;
+        let x1 = cos * dx + sin * dy;
+        let y1 = -sin * dx + cos * dy;
+
+        // Radii that are too small are scaled up, until the arc fits.
+        let lambda = (x1 * x1) / (rx * rx) + (y1 * y1) / (ry * ry);
+        if lambda > 1. {
+            rx *= lambda.sqrt();
+            ry *= lambda.sqrt();
+        }
+
+        let numerator =
+            rx * rx * ry * ry - rx * rx * y1 * y1 - ry * ry * x1 * x1;
+        let denominator = rx * rx * y1 * y1 + ry * ry * x1 * x1;
+        let mut factor = (numerator / denominator).max(0.).sqrt();
+        if large_arc == sweep {
+            factor = -factor;
+        }
+        let cx1 = factor * rx * y1 / ry;
+        let cy1 = -factor * ry * x1 / rx;
+
+        let cx = cos * cx1 - sin * cy1 + (from[0] + to[0]) / 2.;
+        let cy = sin * cx1 + cos * cy1 + (from[1] + to[1]) / 2.;
+
+        let angle = |ux: f64, uy: f64| uy.atan2(ux);
+        let start = angle((x1 - cx1) / rx, (y1 - cy1) / ry);
+        let end = angle((-x1 - cx1) / rx, (-y1 - cy1) / ry);
+
+        let mut delta = end - start;
+        if sweep && delta < 0. {
+            delta += 2. * PI;
+        }
+        if !sweep && delta > 0. {
+            delta -= 2. * PI;
+        }
+
+        let n = self.arc_segments(rx.max(ry), delta.abs());
+        for i in 1..=n {
+            let theta = start + delta * i as f64 / n as f64;
+            let (x, y) = (rx * theta.cos(), ry * theta.sin());
+
+            self.line([cos * x - sin * y + cx, sin * x + cos * y + cy]);
+        }
+    }
+
+    /// Compute the number of segments that approximate an arc
+    ///
+    /// `radius` is in user units. The tolerance is converted accordingly.
+    fn arc_segments(&self, radius: f64, angle: f64) -> usize {
+        let radius = radius * self.transform.max_scale();
+        if radius <= self.tolerance {
+            return 1;
+        }
+
+        let max_angle = 2. * (1. - self.tolerance / radius).acos();
+        (angle / max_angle).ceil().max(1.) as usize
+    }
+
+    fn close(&mut self) {
+        let mut contour = std::mem::take(&mut self.current);
+
+        if contour.len() > 1 && contour.first() == contour.last() {
+            contour.pop();
+        }
+        if contour.len() >= 3 {
+            self.contours.push(contour);
+        }
+    }
+
+    fn finish(mut self) -> Vec<Contour> {
+        self.close();
+        self.contours
+    }
+}
+
+fn de_casteljau(points: &[[f64; 2]], t: f64) -> [f64; 2] {
+    let mut points = points.to_vec();
+
+    while points.len() > 1 {
+        points = points
+            .windows(2)
+            .map(|w| {
+                [
+                    w[0][0] + (w[1][0] - w[0][0]) * t,
+                    w[0][1] + (w[1][1] - w[0][1]) * t,
+                ]
+            })
+            .collect();
+    }
+
+    points[0]
+}
+
+#[derive(Clone, Copy)]
+enum FillRule {
+    NonZero,
+    EvenOdd,
+}
+
+impl FillRule {
+    fn is_filled(&self, winding_number: i32) -> bool {
+        match self {
+            Self::NonZero => winding_number != 0,
+            Self::EvenOdd => winding_number % 2 != 0,
+        }
+    }
+}
+
+/// Sort contours into exteriors and the holes within them
+///
+/// A contour is an exterior, if the area just inside of it is filled, and the
+/// area just outside isn't. For a hole, it's the other way around. Contours
+/// that have filled area (or no filled area) on both sides don't bound the
+/// filled area, and are dropped.
+///
+/// All returned contours are wound counter-clockwise.
+fn classify(
+    contours: Vec<Contour>,
+    fill_rule: FillRule,
+) -> Vec<(Contour, Vec<Contour>)> {
+    let is_filled = |point: [f64; 2]| {
+        let winding_number = contours
+            .iter()
+            .map(|contour| winding_number(contour, point))
+            .sum();
+        fill_rule.is_filled(winding_number)
+    };
+
+    let mut exteriors = Vec::new();
+    let mut interiors = Vec::new();
+
+    for (i, contour) in contours.iter().enumerate() {
+        let [inside, outside] = match probe_points(contour) {
+            Some(points) => points,
+            None => continue,
+        };
+
+        match (is_filled(inside), is_filled(outside)) {
+            (true, false) => exteriors.push(i),
+            (false, true) => interiors.push(i),
+            _ => {}
+        }
+    }
+
+    let polygons = contours.iter().map(Vec::as_slice).collect::<Vec<_>>();
+
+    assign_interiors(&polygons, exteriors, interiors)
+        .into_iter()
+        .map(|(exterior, holes)| {
+            let holes = holes
+                .into_iter()
+                .map(|hole| ccw(contours[hole].clone()))
+                .collect();
+            (ccw(contours[exterior].clone()), holes)
+        })
+        .collect()
+}
+
+/// Find a point just inside and a point just outside of a contour
+///
+/// The points are placed on either side of the midpoint of the contour's
+/// longest edge. Returns `None` for degenerate contours.
+fn probe_points(contour: &Contour) -> Option<[[f64; 2]; 2]> {
+    let edges = contour
+        .iter()
+        .zip(contour.iter().cycle().skip(1))
+        .map(|(&a, &b)| (a, b));
+    let (a, b) = edges.max_by(|(a, b), (c, d)| {
+        distance(*a, *b).total_cmp(&distance(*c, *d))
+    })?;
+
+    let length = distance(a, b);
+    if length == 0. {
+        return None;
+    }
+
+    let mid = [(a[0] + b[0]) / 2., (a[1] + b[1]) / 2.];
+    let normal = [-(b[1] - a[1]) / length, (b[0] - a[0]) / length];
+
+    // Far enough from the edge to not be affected by numerical noise, but
+    // close enough to not reach another edge of a reasonable contour.
+    let offset = length * 1e-6;
+    let left = [mid[0] + normal[0] * offset, mid[1] + normal[1] * offset];
+    let right = [mid[0] - normal[0] * offset, mid[1] - normal[1] * offset];
+
+    if winding_number(contour, left) != 0 {
+        Some([left, right])
+    } else {
+        Some([right, left])
+    }
+}
+
+fn ccw(mut contour: Contour) -> Contour {
+    if signed_area(&contour).is_negative() {
+        contour.reverse();
+    }
+    contour
+}
+
+fn distance(a: [f64; 2], b: [f64; 2]) -> f64 {
+    f64::hypot(b[0] - a[0], b[1] - a[1])
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Shape2d, Sketch};
+
+    use super::{import, ImportOptions, Unit};
+
+    fn import_user_units(svg: &str) -> Vec<Shape2d> {
+        let options = ImportOptions {
+            unit: Unit::UserUnit,
+            ..ImportOptions::default()
+        };
+        import(svg, &options).unwrap()
+    }
+
+    fn points(shape: &Shape2d) -> Vec<[f64; 2]> {
+        match shape {
+            Shape2d::Sketch(sketch) => match sketch.chain() {
+                crate::Chain::PolyChain(chain) => chain.to_points(),
+                crate::Chain::Circle(_) => unreachable!(),
+            },
+            _ => panic!("Expected sketch, got {shape:?}"),
+        }
+    }
+
+    #[test]
+    fn import_hole() {
+        let shapes = import_user_units(
+            r#"<svg xmlns="http://www.w3.org/2000/svg">
+                <path d="M 0 0 H 10 V 10 H 0 Z M 2 2 V 8 H 8 V 2 Z" />
+            </svg>"#,
+        );
+
+        assert_eq!(shapes.len(), 1);
+        let difference = match &shapes[0] {
+            Shape2d::Difference(difference) => difference,
+            shape => panic!("Expected difference, got {shape:?}"),
+        };
+
+        let [exterior, hole] = difference.shapes();
+        assert_eq!(
+            points(exterior),
+            [[0., -10.], [10., -10.], [10., 0.], [0., 0.]]
+        );
+        assert_eq!(points(hole).len(), 4);
+    }
+
+    #[test]
+    fn import_same_winding_with_fill_rules() {
+        // Both contours are wound the same way. With the `nonzero` fill rule,
+        // the inner one doesn't bound the filled area. With `evenodd`, it's a
+        // hole.
+        let path = "M 0 0 H 10 V 10 H 0 Z M 2 2 H 8 V 8 H 2 Z";
+        let nonzero = import_user_units(&format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg">
+                <path d="{path}" />
+            </svg>"#
+        ));
+        let evenodd = import_user_units(&format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg">
+                <path d="{path}" fill-rule="evenodd" />
+            </svg>"#
+        ));
+
+        assert!(matches!(nonzero[..], [Shape2d::Sketch(_)]));
+        assert!(matches!(evenodd[..], [Shape2d::Difference(_)]));
+    }
+
+    #[test]
+    fn import_curves_within_tolerance() {
+        let options = ImportOptions {
+            unit: Unit::UserUnit,
+            tolerance: 0.001,
+            ..ImportOptions::default()
+        };
+        let shapes = import(
+            r#"<svg xmlns="http://www.w3.org/2000/svg">
+                <path d="M 10 0 A 10 10 0 0 1 -10 0 A 10 10 0 0 1 10 0 Z" />
+            </svg>"#,
+            &options,
+        )
+        .unwrap();
+
+        for [x, y] in points(&shapes[0]) {
+            let distance = (x * x + y * y).sqrt();
+            assert!((distance - 10.).abs() < 0.001);
+        }
+    }
+
+    #[test]
+    fn import_millimeters() {
+        let shapes = import(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="20mm"
+                height="10mm" viewBox="0 0 200 100">
+                <rect x="0" y="0" width="200" height="100"
+                    transform="scale(0.5)" />
+            </svg>"#,
+            &ImportOptions::default(),
+        )
+        .unwrap();
+
+        let max_x = points(&shapes[0])
+            .into_iter()
+            .map(|[x, _]| x)
+            .fold(f64::MIN, f64::max);
+        assert!((max_x - 10.).abs() < 1e-9);
+    }
+
+    #[test]
+    fn import_rounded_rect() {
+        let options = ImportOptions {
+            unit: Unit::UserUnit,
+            tolerance: 0.001,
+            ..ImportOptions::default()
+        };
+        let shapes = import(
+            r#"<svg xmlns="http://www.w3.org/2000/svg">
+                <rect width="10" height="10" rx="2" />
+            </svg>"#,
+            &options,
+        )
+        .unwrap();
+
+        let points = points(&shapes[0]);
+        assert!(points.len() > 8);
+
+        // The y-axis is flipped, so the corner at the origin is at the top.
+        for [x, y] in points {
+            assert!((0. ..=10.).contains(&x));
+            assert!((-10. ..=0.).contains(&y));
+
+            if x < 2. && y > -2. {
+                let distance = f64::hypot(x - 2., y + 2.);
+                assert!((distance - 2.).abs() < 0.001);
+            }
+        }
+    }
+
+    #[test]
+    fn import_keeps_fill_color() {
+        let shapes = import_user_units(
+            r##"<svg xmlns="http://www.w3.org/2000/svg">
+                <g fill="#00ff00"><circle r="5" /></g>
+            </svg>"##,
+        );
+
+        let sketch: &Sketch = match &shapes[0] {
+            Shape2d::Sketch(sketch) => sketch,
+            shape => panic!("Expected sketch, got {shape:?}"),
+        };
+        assert_eq!(sketch.color(), [0, 255, 0, 255]);
+    }
+}
@@ -91,6 +91,58 @@ where
     }
 }
 
+/// Convenient syntax to create an [`fj::LinearPattern`] or
+/// [`fj::CircularPattern`]
+///
+/// [`fj::LinearPattern`]: crate::LinearPattern
+/// [`fj::CircularPattern`]: crate::CircularPattern
+pub trait Pattern {
+    /// Create a linear pattern
+    ///
+    /// Create `count` instances of `shape`, each offset by `spacing` from the
+    /// previous one.
+    fn linear_pattern(
+        &self,
+        count: u32,
+        spacing: [f64; 3],
+    ) -> crate::LinearPattern;
+
+    /// Create a circular pattern
+    ///
+    /// Create `count` instances of `shape`, each rotated by `angle` around
+    /// `axis` relative to the previous one.
+    fn circular_pattern(
+        &self,
+        count: u32,
+        axis: [f64; 3],
+        angle: crate::Angle,
+    ) -> crate::CircularPattern;
+}
+
+impl<T> Pattern for T
+where
+    T: Clone + Into<crate::Shape>,
+{
+    fn linear_pattern(
+        &self,
+        count: u32,
+        spacing: [f64; 3],
+    ) -> crate::LinearPattern {
+        let shape = self.clone().into();
+        crate::LinearPattern::from_spacing(shape, count, spacing)
+    }
+
+    fn circular_pattern(
+        &self,
+        count: u32,
+        axis: [f64; 3],
+        angle: crate::Angle,
+    ) -> crate::CircularPattern {
+        let shape = self.clone().into();
+        crate::CircularPattern::from_angle(shape, count, axis, angle)
+    }
+}
+
 /// Convenient syntax to create an [`fj::Transform`]
 ///
 /// [`fj::Transform`]: crate::Transform
@@ -105,6 +157,23 @@ pub trait Transform {
     ///
     /// Create a translation that translates `shape` by `offset`.
     fn translate(&self, offset: [f64; 3]) -> crate::Transform;
+
+    /// Create a uniform scaling
+    ///
+    /// Create a scaling that scales `shape` by `factor` along all axes.
+    fn scale(&self, factor: f64) -> crate::Transform;
+
+    /// Create a non-uniform scaling
+    ///
+    /// Create a scaling that scales `shape` by the respective component of
+    /// `factors` along each axis.
+    fn scale_non_uniform(&self, factors: [f64; 3]) -> crate::Transform;
+
+    /// Create a mirroring
+    ///
+    /// Create a mirroring that mirrors `shape` across the plane that goes
+    /// through `origin` and is perpendicular to `normal`.
+    fn mirror(&self, origin: [f64; 3], normal: [f64; 3]) -> crate::Transform;
 }
 
 impl<T> Transform for T
@@ -112,22 +181,22 @@ where
     T: Clone + Into<crate::Shape>,
 {
     fn rotate(&self, axis: [f64; 3], angle: crate::Angle) -> crate::Transform {
-        let shape = self.clone().into();
-        crate::Transform {
-            shape,
-            axis,
-            angle,
-            offset: [0.; 3],
-        }
+        crate::Transform::new(self.clone()).with_rotation(axis, angle)
     }
 
     fn translate(&self, offset: [f64; 3]) -> crate::Transform {
-        let shape = self.clone().into();
-        crate::Transform {
-            shape,
-            axis: [1., 0., 0.],
-            angle: crate::Angle::from_rad(0.),
-            offset,
-        }
+        crate::Transform::new(self.clone()).with_offset(offset)
+    }
+
+    fn scale(&self, factor: f64) -> crate::Transform {
+        self.scale_non_uniform([factor; 3])
+    }
+
+    fn scale_non_uniform(&self, factors: [f64; 3]) -> crate::Transform {
+        crate::Transform::new(self.clone()).with_scale(factors)
+    }
+
+    fn mirror(&self, origin: [f64; 3], normal: [f64; 3]) -> crate::Transform {
+        crate::Transform::new(self.clone()).with_mirror(origin, normal)
     }
 }
@@ -0,0 +1,169 @@
+use std::{fs, io, path::Path};
+
+use crate::{abi::ffi_safe, Shape, Shape2d};
+
+/// A line of text, made from the outlines of a font's glyphs
+///
+/// Each glyph becomes one or more faces. Holes in a glyph (like in "o" or "B")
+/// become holes in the respective face.
+///
+/// The baseline of the first line is at `y = 0`. Additional lines, separated
+/// by `\n`, go below it. Where the text is placed horizontally depends on its
+/// [`Alignment`].
+///
+/// # Examples
+///
+/// ``` rust
+/// use fj::syntax::*;
+///
+/// let label = fj::Text::from_str("Part 42", 5.)
+///     .with_alignment(fj::Alignment::Center)
+///     .sweep([0., 0., 1.]);
+/// ```
+///
+/// Curves in the glyph outlines are approximated with line segments, within the
+/// tolerance that the shape is processed with.
+///
+/// # Limitations
+///
+/// Characters that the font has no glyph for are left out. Glyphs are placed
+/// next to each other according to their advance width, without kerning.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+pub struct Text {
+    text: ffi_safe::String,
+    size: f64,
+    font: Font,
+    alignment: Alignment,
+    color: [u8; 4],
+}
+
+impl Text {
+    /// Create text with the given font size, using the default font
+    ///
+    /// The font size is the height of the font's em square.
+    pub fn from_str(text: &str, size: f64) -> Self {
+        Self {
+            text: text.to_owned().into(),
+            size,
+            font: Font::Default,
+            alignment: Alignment::Left,
+            color: [255, 0, 0, 255],
+        }
+    }
+
+    /// Set the font of the text
+    pub fn with_font(mut self, font: Font) -> Self {
+        self.font = font;
+        self
+    }
+
+    /// Set the horizontal alignment of the text
+    pub fn with_alignment(mut self, alignment: Alignment) -> Self {
+        self.alignment = alignment;
+        self
+    }
+
+    /// Set the rendering color of the text in RGBA
+    pub fn with_color(mut self, color: [u8; 4]) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Access the text
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Access the font size
+    pub fn size(&self) -> f64 {
+        self.size
+    }
+
+    /// Access the font
+    pub fn font(&self) -> &Font {
+        &self.font
+    }
+
+    /// Access the horizontal alignment
+    pub fn alignment(&self) -> Alignment {
+        self.alignment
+    }
+
+    /// Get the rendering color of the text in RGBA
+    pub fn color(&self) -> [u8; 4] {
+        self.color
+    }
+}
+
+impl From<Text> for Shape {
+    fn from(shape: Text) -> Self {
+        Self::Shape2d(shape.into())
+    }
+}
+
+impl From<Text> for Shape2d {
+    fn from(shape: Text) -> Self {
+        Shape2d::Text(Box::new(shape))
+    }
+}
+
+/// The font used by [`Text`]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+pub enum Font {
+    /// A simple block font that is built into Fornjot
+    ///
+    /// Covers printable ASCII characters. Other characters are left out.
+    Default,
+
+    /// The contents of a TrueType or OpenType font file
+    Data(FontData),
+}
+
+impl Font {
+    /// Use the TrueType or OpenType font with the given contents
+    ///
+    /// The data is not checked here. If it isn't a valid font, processing the
+    /// text will fail.
+    pub fn from_bytes(data: Vec<u8>) -> Self {
+        Self::Data(FontData(data.into()))
+    }
+
+    /// Load a TrueType or OpenType font file
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        Ok(Self::from_bytes(fs::read(path)?))
+    }
+}
+
+/// The contents of a font file
+///
+/// See [`Font::Data`].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+pub struct FontData(ffi_safe::Vec<u8>);
+
+impl FontData {
+    /// Access the contents of the font file
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+/// The horizontal alignment of [`Text`]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+pub enum Alignment {
+    /// Lines start at `x = 0`
+    Left,
+
+    /// Lines are centered around `x = 0`
+    Center,
+
+    /// Lines end at `x = 0`
+    Right,
+}
@@ -1,4 +1,4 @@
-use crate::{Angle, Shape};
+use crate::{abi::ffi_safe, Angle, Shape};
 
 /// A transformed 3-dimensional shape
 ///
@@ -13,17 +13,32 @@ use crate::{Angle, Shape};
 /// // `shape` can be anything that converts to `fj::Shape`
 /// let rotated = shape.rotate([0., 0., 1.], fj::Angle::from_rev(0.5));
 /// let translated = shape.translate([1., 2., 3.]);
+/// let scaled = shape.scale(2.);
+/// let mirrored = shape.mirror([0., 0., 0.], [1., 0., 0.]);
 /// ```
 ///
+/// # Order of Operations
+///
+/// The components of a transform are applied in the following order: scaling,
+/// mirroring, rotation, translation.
+///
 /// # Limitations
 ///
-/// Transformations are currently limited to a rotation, followed by a
-/// translation.
+/// Transformations are currently limited to a scaling, followed by a
+/// mirroring, a rotation, and a translation.
+///
+/// Scale factors must be finite and not zero. A negative scale factor flips
+/// the shape along its axis. The normal of the mirror plane, and the axis of
+/// the rotation (unless the angle is zero), must not be zero.
+///
+/// Non-uniform scaling of shapes that contain circles is not supported, as
+/// the result can't be represented by the kernel.
 ///
 /// See issue:
 /// <https://github.com/hannobraun/Fornjot/issues/101>
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
 #[repr(C)]
 pub struct Transform {
     /// The shape being transformed
@@ -37,6 +52,71 @@ pub struct Transform {
 
     /// The offset of the translation
     pub offset: [f64; 3],
+
+    /// The scaling factors along the x, y, and z axes
+    ///
+    /// Use `[1., 1., 1.]` to not scale.
+    pub scale: [f64; 3],
+
+    /// The plane to mirror across, if any
+    pub mirror: ffi_safe::Option<Mirror>,
+}
+
+impl Transform {
+    /// Create a transform that leaves `shape` as it is
+    ///
+    /// Use the `with_*` methods to set the components of the transform.
+    pub fn new(shape: impl Into<Shape>) -> Self {
+        Self {
+            shape: shape.into(),
+            axis: [1., 0., 0.],
+            angle: Angle::from_rad(0.),
+            offset: [0.; 3],
+            scale: [1.; 3],
+            mirror: ffi_safe::Option::None,
+        }
+    }
+
+    /// Set the rotation, by `angle` around `axis`
+    pub fn with_rotation(mut self, axis: [f64; 3], angle: Angle) -> Self {
+        self.axis = axis;
+        self.angle = angle;
+        self
+    }
+
+    /// Set the offset of the translation
+    pub fn with_offset(mut self, offset: [f64; 3]) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Set the scaling factors along the x, y, and z axes
+    pub fn with_scale(mut self, scale: [f64; 3]) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// Set the plane to mirror across
+    pub fn with_mirror(mut self, origin: [f64; 3], normal: [f64; 3]) -> Self {
+        self.mirror = Some(Mirror { origin, normal }).into();
+        self
+    }
+}
+
+/// A plane to mirror a shape across
+///
+/// See [`Transform`].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+pub struct Mirror {
+    /// A point on the plane
+    pub origin: [f64; 3],
+
+    /// The normal of the plane
+    ///
+    /// Doesn't need to be normalized.
+    pub normal: [f64; 3],
 }
 
 impl From<Transform> for Shape {
@@ -1,11 +1,14 @@
 mod ffi;
 
-use std::{ffi::CString, fs, process::Command, ptr};
+use std::{env, ffi::CString, fs, process::Command, ptr};
 
 use anyhow::{anyhow, bail, Context as _};
 use tempfile::tempdir;
 
 fn main() -> anyhow::Result<()> {
+    // Checking the meshes is opt-in, until all models are known to pass.
+    let check_mesh = env::args().skip(1).any(|arg| arg == "--check-mesh");
+
     for model in fs::read_dir("models")? {
         let model = model?;
         let model = model.file_name().into_string().map_err(|err| {
@@ -17,12 +20,17 @@ fn main() -> anyhow::Result<()> {
         let export_file_path = dir.path().join(file_name);
         let export_file_path_str = export_file_path.to_str().unwrap();
 
-        let exit_status = Command::new("cargo")
+        let mut command = Command::new("cargo");
+        command
             .arg("run")
             .arg("--")
             .arg(&model)
-            .args(["--export", export_file_path_str])
-            .status()?;
+            .args(["--export", export_file_path_str]);
+        if check_mesh {
+            command.arg("--check-mesh");
+        }
+
+        let exit_status = command.status()?;
 
         if !exit_status.success() {
             bail!(